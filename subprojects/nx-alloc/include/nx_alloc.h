@@ -52,6 +52,22 @@ void* __nx_alloc_realloc(void* ptr, size_t new_size);
  */
 void __nx_alloc_free(void* p);
 
+/**
+ * @brief Heap usage snapshot.
+ */
+typedef struct {
+    size_t size; ///< Total heap size in bytes.
+    size_t used; ///< Bytes currently allocated.
+    size_t free; ///< Bytes currently free (possibly fragmented).
+} NxAllocHeapStats;
+
+/**
+ * @brief Returns the current heap usage.
+ * @note All fields are zero if the heap has not been initialized yet.
+ * @param[out] out Heap usage snapshot.
+ */
+void __nx_alloc__heap_stats(NxAllocHeapStats* out);
+
 #ifdef __cplusplus
 }
 #endif
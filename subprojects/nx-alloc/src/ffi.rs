@@ -119,6 +119,18 @@ pub unsafe extern "C" fn __nx_alloc__realloc(ptr: *mut c_void, new_size: usize)
     let new_alloc_ptr = {
         let mut alloc = global_allocator::lock();
 
+        // Resize the block in place if possible, keeping the data where it is
+        // SAFETY: The block was allocated by this allocator with `old_size` and `align`,
+        // as recorded in its metadata, and has not been freed.
+        if unsafe { alloc.realloc_in_place(allocation.as_ptr(), old_size, align, layout.size()) } {
+            // SAFETY: The allocation start is non-null, as it precedes a valid data pointer.
+            let base = unsafe { ptr::NonNull::new_unchecked(allocation.as_ptr()) };
+            // SAFETY: The block now spans `layout.size()` bytes with the same alignment, so
+            // rewriting the metadata for `layout` keeps the data at the same offset.
+            let new_allocation = unsafe { Allocation::new_with_metadata(base, layout) };
+            return new_allocation.data_ptr();
+        }
+
         // Allocate new block
         let raw_alloc_ptr = unsafe { alloc.malloc(layout.size(), layout.align()) };
         let Some(new_alloc_ptr) = ptr::NonNull::new(raw_alloc_ptr) else {
@@ -142,6 +154,36 @@ pub unsafe extern "C" fn __nx_alloc__realloc(ptr: *mut c_void, new_size: usize)
     new_allocation.data_ptr() as *mut c_void
 }
 
+/// Heap usage snapshot, as returned by [`__nx_alloc__heap_stats`].
+#[repr(C)]
+pub struct HeapStats {
+    /// Total heap size in bytes.
+    pub size: usize,
+    /// Bytes currently allocated.
+    pub used: usize,
+    /// Bytes currently free (possibly fragmented).
+    pub free: usize,
+}
+
+/// Writes the current heap usage to `out`.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_alloc__heap_stats(out: *mut HeapStats) {
+    let Some(mut out) = ptr::NonNull::new(out) else {
+        return; // If the pointer is null, no-op
+    };
+
+    let stats = global_allocator::heap_stats();
+    unsafe {
+        out.as_mut().size = stats.size;
+        out.as_mut().used = stats.used;
+        out.as_mut().free = stats.free;
+    }
+}
+
 mod newlib {
     use core::ffi::c_void;
 
@@ -6,11 +6,13 @@
 use core::{
     alloc::{GlobalAlloc, Layout},
     ffi::c_void,
-    ptr::NonNull,
+    mem,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
 };
 
 use crate::{
-    llffalloc,
+    llffalloc::{self, HeapStats},
     sync::{Mutex, MutexGuard},
 };
 
@@ -18,6 +20,23 @@ use crate::{
 #[cfg_attr(feature = "global-allocator", global_allocator)]
 pub static ALLOC: NxAllocator = NxAllocator::new_uninit();
 
+/// Out-of-memory hook, stored as a type-erased `fn(Layout)` (null = unset).
+static OOM_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets a hook called whenever the global allocator fails to satisfy a request.
+///
+/// The hook receives the failed layout and runs before the null pointer is
+/// returned (and Rust aborts). The allocator lock is not held, so the hook may
+/// call [`heap_stats`], but it must not allocate.
+pub fn set_oom_hook(hook: fn(Layout)) {
+    OOM_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Returns the current heap usage.
+pub fn heap_stats() -> HeapStats {
+    ALLOC.0.lock().stats()
+}
+
 /// Initialize the linked-list allocator heap via SVC.
 ///
 /// This function is idempotent - subsequent calls after initialization are no-ops.
@@ -64,12 +83,62 @@ impl NxAllocator {
 
 unsafe impl GlobalAlloc for NxAllocator {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        let mut alloc = self.0.lock();
-        unsafe { alloc.malloc(layout.size(), layout.align()) }
+        let ptr = {
+            let mut alloc = self.0.lock();
+            unsafe { alloc.malloc(layout.size(), layout.align()) }
+        };
+
+        if ptr.is_null() {
+            call_oom_hook(layout);
+        }
+
+        ptr
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
         let mut alloc = self.0.lock();
         unsafe { alloc.free(ptr, layout.size(), layout.align()) }
     }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = {
+            let mut alloc = self.0.lock();
+
+            // Resize without copying if the neighbouring memory allows it
+            // SAFETY: The caller guarantees `ptr` was allocated by this allocator with
+            // `layout`, and frees it with `new_size` from now on.
+            if unsafe { alloc.realloc_in_place(ptr, layout.size(), layout.align(), new_size) } {
+                return ptr;
+            }
+
+            unsafe { alloc.malloc(new_size, layout.align()) }
+        };
+
+        if new_ptr.is_null() {
+            // SAFETY: The caller guarantees `new_size` forms a valid layout with the alignment.
+            call_oom_hook(unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) });
+            return new_ptr;
+        }
+
+        // Copy outside of the critical section; both blocks are owned by the caller
+        unsafe { ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size)) };
+
+        let mut alloc = self.0.lock();
+        unsafe { alloc.free(ptr, layout.size(), layout.align()) };
+
+        new_ptr
+    }
+}
+
+/// Calls the out-of-memory hook, if set.
+#[cold]
+fn call_oom_hook(layout: Layout) {
+    let hook = OOM_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+
+    // SAFETY: Non-null values are only ever stored by `set_oom_hook` from a `fn(Layout)`.
+    let hook = unsafe { mem::transmute::<*mut (), fn(Layout)>(hook) };
+    hook(layout);
 }
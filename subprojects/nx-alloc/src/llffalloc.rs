@@ -3,21 +3,26 @@
 //! This module provides a linked list first fit allocator.
 //! It is used to allocate memory for the entire program.
 //!
-//! It is based on the [linked_list_allocator](https://github.com/rust-osdev/linked_list_allocator) crate.
+//! It is based on the [linked_list_allocator](https://github.com/rust-osdev/linked_list_allocator) crate,
+//! extended so that a block can be resized in place (see [`Heap::realloc_in_place`]).
 use core::{
     alloc::Layout,
-    ffi::{c_char, c_void},
+    ffi::c_void,
     ptr::{self, NonNull},
 };
 
 use nx_svc::{
-    mem::set_heap_size,
+    mem::{HEAP_SIZE_ALIGN, set_heap_size},
     misc::{get_total_memory_size, get_used_memory_size},
 };
 
+use self::hole::HoleList;
+
+mod hole;
+
 /// A wrapper around the linked list allocator that provides
 /// a lazy initialization mechanism for the heap.
-pub struct Heap(Option<linked_list_allocator::Heap>);
+pub struct Heap(Option<HoleList>);
 
 impl Heap {
     /// Create a new allocator with an uninitialized heap.
@@ -43,7 +48,21 @@ impl Heap {
     /// - `addr` points to a valid, owned memory region of at least `size` bytes
     /// - The memory region will remain valid for the lifetime of the allocator
     pub unsafe fn init_with_heap_override(&mut self, addr: NonNull<c_void>, size: usize) {
-        self.0 = Some(unsafe { linked_list_allocator::Heap::new(addr.as_ptr() as *mut u8, size) });
+        self.0 = Some(unsafe { HoleList::new(addr.as_ptr() as *mut u8, size) });
+    }
+
+    /// Returns the current heap usage.
+    ///
+    /// All fields are zero if the heap has not been initialized yet.
+    pub fn stats(&self) -> HeapStats {
+        match &self.0 {
+            Some(heap) => HeapStats {
+                size: heap.size(),
+                used: heap.used(),
+                free: heap.free(),
+            },
+            None => HeapStats::default(),
+        }
     }
 
     /// Allocate memory from the heap.
@@ -61,8 +80,8 @@ impl Heap {
 
         let heap = self.0.get_or_insert_with(init_inner_heap);
         match heap.allocate_first_fit(layout) {
-            Ok(nn) => nn.as_ptr(),
-            Err(_) => ptr::null_mut(),
+            Some(nn) => nn.as_ptr(),
+            None => ptr::null_mut(),
         }
     }
 
@@ -74,25 +93,65 @@ impl Heap {
     /// - `ptr` was previously allocated by this allocator with the same size and alignment
     /// - `ptr` has not been freed already
     /// - The memory region is no longer in use
-    pub unsafe fn free(&mut self, ptr: *mut u8, size: usize, align: usize) {
+    pub unsafe fn free(&mut self, ptr: *mut u8, size: usize, _align: usize) {
         let Some(ptr) = ptr::NonNull::new(ptr) else {
             return;
         };
 
         let heap = self.0.get_or_insert_with(init_inner_heap);
-        let layout = unsafe { Layout::from_size_align_unchecked(size, align) };
-        unsafe { heap.deallocate(ptr, layout) };
+        unsafe { heap.deallocate(ptr, size) };
     }
+
+    /// Resize an allocation without moving it.
+    ///
+    /// Shrinking always succeeds. Growing succeeds if the free block right
+    /// after the allocation can absorb the growth. Returns `false`, leaving
+    /// the allocation untouched, if the allocation has to be moved instead.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` was previously allocated by this allocator with
+    /// `old_size` and `align`, and has not been freed already. On success, the allocation
+    /// must be freed with `new_size` and `align`.
+    pub unsafe fn realloc_in_place(
+        &mut self,
+        ptr: *mut u8,
+        old_size: usize,
+        align: usize,
+        new_size: usize,
+    ) -> bool {
+        let Some(ptr) = ptr::NonNull::new(ptr) else {
+            return false;
+        };
+        if Layout::from_size_align(new_size, align).is_err() {
+            return false;
+        }
+
+        let heap = self.0.get_or_insert_with(init_inner_heap);
+        // SAFETY: The caller guarantees `ptr` is a live block of `old_size` bytes from this
+        // heap.
+        unsafe { heap.try_resize_in_place(ptr, old_size, new_size) }
+    }
+}
+
+/// Heap usage snapshot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Total heap size in bytes.
+    pub size: usize,
+    /// Bytes currently allocated.
+    pub used: usize,
+    /// Bytes currently free (possibly fragmented).
+    pub free: usize,
 }
 
 /// Initialize the heap via SVC memory allocation.
 ///
 /// This function allocates heap memory using the kernel's SetHeapSize SVC.
 /// It is either called by the `init` function or when the heap is first used.
-fn init_inner_heap() -> linked_list_allocator::Heap {
+fn init_inner_heap() -> HoleList {
     // Default heap size if not specified (0x2000000 * 16)
     const DEFAULT_HEAP_SIZE: usize = 0x2_000_000 * 16;
-    const HEAP_SIZE_ALIGN: usize = 0x200_000;
 
     // Try to get total and used memory to determine heap size
     let mem_available = get_total_memory_size().unwrap_or(0);
@@ -109,12 +168,12 @@ fn init_inner_heap() -> linked_list_allocator::Heap {
 
     // Actually allocate the heap
     let heap_bottom = match set_heap_size(heap_size) {
-        Ok(heap_addr) => heap_addr as *mut c_char,
+        Ok(heap_addr) => heap_addr.as_ptr(),
         Err(_) => {
             panic!("Failed to allocate heap memory: HEAP_ALLOCATION_FAILED");
         }
     };
 
     // SAFETY: The kernel guarantees this region is valid and owned by us.
-    unsafe { linked_list_allocator::Heap::new(heap_bottom, heap_size) }
+    unsafe { HoleList::new(heap_bottom, heap_size) }
 }
@@ -0,0 +1,273 @@
+//! # Hole list
+//!
+//! The free memory of the heap is tracked as an address-sorted, singly linked
+//! list of holes. Each hole stores its node in its first bytes, so the list
+//! needs no memory of its own.
+//!
+//! Every hole and every allocated block starts at a [`BLOCK_ALIGN`]-aligned
+//! address and spans a multiple of [`BLOCK_ALIGN`] bytes. This keeps every
+//! leftover region large enough to hold a hole node, and lets a block grow
+//! into the hole right after it without copying.
+
+use core::{
+    alloc::Layout,
+    ptr::{self, NonNull},
+};
+
+/// Alignment and size granularity of holes and allocated blocks.
+pub const BLOCK_ALIGN: usize = size_of::<Hole>();
+
+const _: () = assert!(BLOCK_ALIGN.is_power_of_two() && BLOCK_ALIGN >= align_of::<Hole>());
+
+/// A free memory region, stored at the start of the region itself.
+struct Hole {
+    size: usize,
+    next: Option<NonNull<Hole>>,
+}
+
+/// An address-sorted list of free memory regions.
+pub struct HoleList {
+    /// Dummy node whose `next` is the lowest hole.
+    first: Hole,
+    size: usize,
+    used: usize,
+}
+
+// SAFETY: The list exclusively owns the memory its nodes point into.
+unsafe impl Send for HoleList {}
+
+impl HoleList {
+    /// Creates a hole list covering `[heap_bottom, heap_bottom + heap_size)`.
+    ///
+    /// The region is trimmed to [`BLOCK_ALIGN`] on both ends.
+    ///
+    /// # Safety
+    ///
+    /// The memory region must be valid, owned by the hole list, and remain
+    /// valid for the lifetime of the hole list.
+    pub unsafe fn new(heap_bottom: *mut u8, heap_size: usize) -> Self {
+        let start = heap_bottom.align_offset(BLOCK_ALIGN);
+        let size = heap_size.saturating_sub(start) & !(BLOCK_ALIGN - 1);
+        let bottom = heap_bottom.wrapping_add(start);
+
+        let mut list = Self {
+            first: Hole {
+                size: 0,
+                next: None,
+            },
+            size,
+            used: 0,
+        };
+
+        if size > 0 {
+            // SAFETY: The region is valid, owned, and large enough for a hole.
+            list.first.next = Some(unsafe { write_hole(bottom, size, None) });
+        }
+
+        list
+    }
+
+    /// Returns the size of the heap region in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of bytes currently allocated.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the number of bytes currently free.
+    pub fn free(&self) -> usize {
+        self.size - self.used
+    }
+
+    /// Allocates a block for `layout` from the first hole that fits it.
+    pub fn allocate_first_fit(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+        let size = block_size(layout.size())?;
+        let align = layout.align().max(BLOCK_ALIGN);
+
+        let mut prev = NonNull::from(&mut self.first);
+        // SAFETY: Every node in the list is a valid hole owned by the list.
+        unsafe {
+            while let Some(mut hole) = prev.as_ref().next {
+                let hole_addr = hole.as_ptr().cast::<u8>();
+                let hole_size = hole.as_ref().size;
+
+                let front = hole_addr.align_offset(align);
+                if front.checked_add(size).is_some_and(|end| end <= hole_size) {
+                    let block = hole_addr.add(front);
+                    let back = hole_size - front - size;
+                    let next = hole.as_ref().next;
+
+                    // Keep the leftovers on both sides as holes, in order
+                    let after = if back > 0 {
+                        Some(write_hole(block.add(size), back, next))
+                    } else {
+                        next
+                    };
+                    if front > 0 {
+                        hole.as_mut().size = front;
+                        hole.as_mut().next = after;
+                    } else {
+                        prev.as_mut().next = after;
+                    }
+
+                    self.used += size;
+                    return Some(NonNull::new_unchecked(block));
+                }
+
+                prev = hole;
+            }
+        }
+
+        None
+    }
+
+    /// Returns the block at `ptr` to the list, merging it with its neighbours.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this list for a block of `size` bytes
+    /// (as last set by [`try_resize_in_place`](Self::try_resize_in_place)),
+    /// and must not be freed already.
+    pub unsafe fn deallocate(&mut self, ptr: NonNull<u8>, size: usize) {
+        // A valid block size never overflows
+        let size = block_size(size).unwrap_or(0);
+        self.used -= size;
+
+        // SAFETY: The block is owned by the caller and about to become a hole.
+        unsafe { self.insert_hole(ptr.as_ptr(), size) };
+    }
+
+    /// Resizes the block at `ptr` from `old_size` to `new_size` bytes without
+    /// moving it.
+    ///
+    /// Shrinking always succeeds and returns the tail to the list. Growing
+    /// succeeds if the hole right after the block can absorb the growth.
+    /// Returns `false`, leaving the block untouched, otherwise.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been returned by this list for a block of `old_size`
+    /// bytes, and must not be freed already.
+    pub unsafe fn try_resize_in_place(
+        &mut self,
+        ptr: NonNull<u8>,
+        old_size: usize,
+        new_size: usize,
+    ) -> bool {
+        let Some(new_size) = block_size(new_size) else {
+            return false;
+        };
+        let old_size = block_size(old_size).unwrap_or(0);
+        let block = ptr.as_ptr();
+
+        if new_size <= old_size {
+            let tail = old_size - new_size;
+            if tail > 0 {
+                self.used -= tail;
+                // SAFETY: The tail is owned by the caller and no longer used.
+                unsafe { self.insert_hole(block.add(new_size), tail) };
+            }
+            return true;
+        }
+
+        let growth = new_size - old_size;
+        let block_end = block.wrapping_add(old_size);
+
+        let mut prev = NonNull::from(&mut self.first);
+        // SAFETY: Every node in the list is a valid hole owned by the list.
+        unsafe {
+            while let Some(hole) = prev.as_ref().next {
+                let hole_addr = hole.as_ptr().cast::<u8>();
+                if hole_addr < block_end {
+                    prev = hole;
+                    continue;
+                }
+
+                if hole_addr > block_end || hole.as_ref().size < growth {
+                    return false;
+                }
+
+                // Take the front of the adjacent hole
+                let rest = hole.as_ref().size - growth;
+                let next = hole.as_ref().next;
+                prev.as_mut().next = if rest > 0 {
+                    Some(write_hole(hole_addr.add(growth), rest, next))
+                } else {
+                    next
+                };
+
+                self.used += growth;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Inserts a free region into the list, merging it with adjacent holes.
+    ///
+    /// # Safety
+    ///
+    /// The region must be [`BLOCK_ALIGN`]-aligned, a non-zero multiple of
+    /// [`BLOCK_ALIGN`] in size, within the heap, and not overlap any hole.
+    unsafe fn insert_hole(&mut self, addr: *mut u8, size: usize) {
+        let first = NonNull::from(&mut self.first);
+
+        // SAFETY: Every node in the list is a valid hole owned by the list, and
+        // the caller guarantees the region can hold a new one.
+        unsafe {
+            // Find the last hole below the region
+            let mut prev = first;
+            while let Some(hole) = prev.as_ref().next {
+                if hole.as_ptr().cast::<u8>() > addr {
+                    break;
+                }
+                prev = hole;
+            }
+
+            let next = prev.as_ref().next;
+            let prev_end = prev.as_ptr().cast::<u8>().wrapping_add(prev.as_ref().size);
+
+            let mut node = if prev != first && prev_end == addr {
+                prev.as_mut().size += size;
+                prev
+            } else {
+                let node = write_hole(addr, size, next);
+                prev.as_mut().next = Some(node);
+                node
+            };
+
+            if let Some(next) = next {
+                let node_end = node.as_ptr().cast::<u8>().add(node.as_ref().size);
+                if node_end == next.as_ptr().cast::<u8>() {
+                    node.as_mut().size += next.as_ref().size;
+                    node.as_mut().next = next.as_ref().next;
+                }
+            }
+        }
+    }
+}
+
+/// Rounds a requested size up to the size of the block holding it.
+///
+/// Returns `None` on overflow.
+fn block_size(size: usize) -> Option<usize> {
+    size.max(1).checked_next_multiple_of(BLOCK_ALIGN)
+}
+
+/// Writes a hole node at `addr`.
+///
+/// # Safety
+///
+/// `addr` must be [`BLOCK_ALIGN`]-aligned and valid for writing a hole node.
+unsafe fn write_hole(addr: *mut u8, size: usize, next: Option<NonNull<Hole>>) -> NonNull<Hole> {
+    let hole = addr.cast::<Hole>();
+    // SAFETY: The caller guarantees `addr` is aligned and writable.
+    unsafe {
+        ptr::write(hole, Hole { size, next });
+        NonNull::new_unchecked(hole)
+    }
+}
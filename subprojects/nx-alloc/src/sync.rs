@@ -2,14 +2,28 @@
 //!
 //! This module provides a simple mutex (and mutex guard) implementation used to
 //! protect the allocator from concurrent access.
+//!
+//! With the `single-thread` feature, the underlying lock is replaced at
+//! compile time by a no-op: locking and unlocking compile to nothing.
+//!
+//! # Safety
+//!
+//! The `single-thread` feature removes all synchronization from the
+//! allocator. It is only sound if **no two threads ever use the allocator
+//! concurrently**, i.e. if the program never allocates, reallocates or frees
+//! from any thread other than the main thread (including threads created by
+//! libraries, and threads that only free memory allocated elsewhere). Any
+//! concurrent use is a data race on the heap, which is undefined behavior and
+//! typically corrupts the heap.
 
 use core::cell::UnsafeCell;
 
-use nx_sys_sync as sys;
+#[cfg(not(feature = "single-thread"))]
+use nx_sys_sync::Mutex as RawMutex;
 
 /// A mutual exclusion primitive useful for protecting shared data.
 pub struct Mutex<T: ?Sized> {
-    inner: sys::Mutex,
+    inner: RawMutex,
     data: UnsafeCell<T>,
 }
 
@@ -21,7 +35,7 @@ impl<T> Mutex<T> {
     #[inline]
     pub const fn new(data: T) -> Mutex<T> {
         Mutex {
-            inner: sys::Mutex::new(),
+            inner: RawMutex::new(),
             data: UnsafeCell::new(data),
         }
     }
@@ -75,3 +89,23 @@ impl<T: ?Sized> Drop for MutexGuard<'_, T> {
         self.lock.inner.unlock();
     }
 }
+
+/// Lock that does nothing, used by the `single-thread` feature.
+///
+/// See the [module documentation](self) for the invariant this relies on.
+#[cfg(feature = "single-thread")]
+struct RawMutex;
+
+#[cfg(feature = "single-thread")]
+impl RawMutex {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    fn lock(&self) {}
+
+    #[inline(always)]
+    fn unlock(&self) {}
+}
@@ -2,6 +2,17 @@
 // Licensed under: MIT OR Apache-2.0
 
 //! Barrier functions.
+//!
+//! [`dmb`], [`dsb`] and [`isb`] take the barrier domain as a type argument.
+//! [`dmb_ish`], [`dmb_ishld`], [`dsb_sy`] and [`isb_sy`] are shorthands for
+//! the variants needed when sharing memory with other cores and services.
+//!
+//! Rust atomics with `Acquire`/`Release` ordering emit the required barriers
+//! for the atomic access itself, but do not order the surrounding volatile
+//! accesses used to copy data out of shared memory. Readers that copy a
+//! payload with volatile loads need an explicit barrier;
+//! [`core::sync::atomic::compiler_fence`] only constrains the compiler, not
+//! the CPU.
 
 mod sealed {
     pub trait Dmb {
@@ -93,3 +104,46 @@ where
 {
     arg.__isb()
 }
+
+/// Data Memory Barrier, inner shareable domain (`DMB ISH`).
+///
+/// Orders all memory accesses before the barrier against all memory accesses
+/// after it, as observed by the other cores. Use it to publish or consume data
+/// in normal memory shared with other threads or with the kernel and system
+/// services, such as service shared memory.
+#[inline(always)]
+pub fn dmb_ish() {
+    dmb(ISH)
+}
+
+/// Data Memory Barrier for loads, inner shareable domain (`DMB ISHLD`).
+///
+/// Orders loads before the barrier against all memory accesses after it. This
+/// gives acquire semantics to plain or volatile loads, and is the barrier a
+/// seqlock reader wants between reading the sequence counter and the payload,
+/// and between the payload and the counter re-check.
+#[inline(always)]
+pub fn dmb_ishld() {
+    dmb(ISHLD)
+}
+
+/// Data Synchronization Barrier, full system (`DSB SY`).
+///
+/// Blocks until every memory access before the barrier has completed,
+/// including accesses to device memory. Needed after writing to device memory
+/// or cache maintenance when the effect must be visible before continuing;
+/// a DMB is enough for ordering accesses to normal memory.
+#[inline(always)]
+pub fn dsb_sy() {
+    dsb(SY)
+}
+
+/// Instruction Synchronization Barrier (`ISB SY`).
+///
+/// Flushes the pipeline so that instructions after the barrier observe the
+/// effects of earlier system register writes and cache maintenance, e.g. after
+/// modifying code or a control register.
+#[inline(always)]
+pub fn isb_sy() {
+    isb(SY)
+}
@@ -1,6 +1,18 @@
 //! Functions to read and write control registers
 //!
 //! This module provides functions for interacting with the CPU control registers.
+//!
+//! # EL0 access
+//!
+//! Applications run at EL0, where only a subset of system registers is reachable:
+//!
+//! | Register      | EL0 access   | Accessor                              |
+//! |---------------|--------------|---------------------------------------|
+//! | `cntpct_el0`  | Read-only    | [`cntpct_el0`]                        |
+//! | `cntfrq_el0`  | Read-only    | [`cntfrq_el0`]                        |
+//! | `tpidrro_el0` | Read-only    | [`tpidrro_el0`]                       |
+//! | `tpidr_el0`   | Read/write   | [`tpidr_el0`], [`set_tpidr_el0`]      |
+//! | `midr_el1`    | None (traps) | [`midr_el1`] (EL1 only)               |
 
 use core::arch::naked_asm;
 
@@ -94,3 +106,83 @@ pub unsafe extern "C" fn tpidrro_el0() -> usize {
         "ret",
     );
 }
+
+/// Read the `tpidr_el0` system register.
+///
+/// This function reads the `tpidr_el0` system register, which holds the user-writable thread
+/// pointer for the current thread.
+///
+/// Returns the current thread pointer value.
+///
+/// # References
+///
+/// - [ARM TPIDR_EL0 Register](https://developer.arm.com/documentation/ddi0601/2024-12/AArch64-Registers/TPIDR-EL0--EL0-Read-Write-Software-Thread-ID-Register)
+/// - [rust-embedded/aarch64-cpu: tpidr_el0.rs](https://github.com/rust-embedded/aarch64-cpu/blob/main/src/registers/tpidr_el0.rs)
+///
+/// # SAFETY
+///
+/// This function is `naked`, and its body is written in assembly.
+/// The assembly code reads the `tpidr_el0` system register and returns
+/// its value in `x0`, according to the AArch64 procedure call standard.
+/// The `noreturn` option is used to prevent the compiler from generating
+/// a function prologue and epilogue.
+#[unsafe(naked)]
+pub unsafe extern "C" fn tpidr_el0() -> usize {
+    naked_asm!(
+        "mrs x0, tpidr_el0", // Move the value of `tpidr_el0` into the return register `x0`
+        "ret",
+    );
+}
+
+/// Write the `tpidr_el0` system register.
+///
+/// This function sets the user-writable thread pointer for the current thread.
+///
+/// # References
+///
+/// - [ARM TPIDR_EL0 Register](https://developer.arm.com/documentation/ddi0601/2024-12/AArch64-Registers/TPIDR-EL0--EL0-Read-Write-Software-Thread-ID-Register)
+///
+/// # Safety
+///
+/// In this runtime, thread-local storage is owned by `nx_sys_thread_tls`: the thread pointer
+/// returned by `__aarch64_read_tp()` is read from the `ThreadVars` block of the TLS region
+/// found through `tpidrro_el0`, which `nx_sys_thread_tls::init_thread_vars` sets up for every
+/// thread. `tpidr_el0` is not part of that setup, so writing it does not move the thread's
+/// `#[thread_local]` variables, and must not be used to do so.
+///
+/// The register is shared by all the code running on the current thread. The caller must
+/// ensure no other code on the thread relies on its value, such as C code built with a
+/// hardware thread pointer (`-mtp=el0`) instead of the runtime's `-mtp=soft`.
+#[unsafe(naked)]
+pub unsafe extern "C" fn set_tpidr_el0(value: usize) {
+    naked_asm!(
+        "msr tpidr_el0, x0", // Move the first argument `x0` into `tpidr_el0`
+        "ret",
+    );
+}
+
+/// Read the `midr_el1` system register.
+///
+/// This function reads the `midr_el1` system register, which identifies the implementer, part
+/// number, variant and revision of the CPU core (e.g. `0x411FD071` for the Switch's Cortex-A57
+/// r1p1).
+///
+/// Returns the raw register value.
+///
+/// # References
+///
+/// - [ARM MIDR-EL1 Register](https://developer.arm.com/documentation/ddi0601/2024-12/AArch64-Registers/MIDR-EL1--Main-ID-Register)
+/// - [rust-embedded/aarch64-cpu: midr_el1.rs](https://github.com/rust-embedded/aarch64-cpu/blob/main/src/registers/midr_el1.rs)
+///
+/// # Safety
+///
+/// `midr_el1` is not accessible from EL0, and Horizon does not emulate the access: calling this
+/// from an application raises an undefined-instruction exception. The caller must be running
+/// at EL1 or above.
+#[unsafe(naked)]
+pub unsafe extern "C" fn midr_el1() -> u64 {
+    naked_asm!(
+        "mrs x0, midr_el1", // Move the value of `midr_el1` into the return register `x0`
+        "ret",
+    );
+}
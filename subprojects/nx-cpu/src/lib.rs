@@ -11,3 +11,6 @@ extern crate nx_panic_handler as _; // provides #[panic_handler]
 
 pub mod barrier;
 pub mod control_regs;
+pub mod spin;
+
+pub use spin::spin_loop_hint;
@@ -0,0 +1,88 @@
+//! Spin-wait hints and backoff.
+//!
+//! Busy-wait loops, such as seqlock readers retrying a torn read, should not
+//! hammer the bus at full speed. [`spin_loop_hint`] tells the core it is
+//! spinning, and [`ExponentialBackoff`] spaces out retries.
+//!
+//! Nothing here ever leaves the core: yielding needs a supervisor call, which
+//! this crate does not depend on. Waits that should eventually yield the core
+//! to other threads use `nx_sys_sync::Backoff` instead.
+
+/// Number of [`ExponentialBackoff`] steps that only spin.
+///
+/// Step `n` spins `2^n` hints, so the last spinning step spins 64 times.
+const SPIN_LIMIT: u32 = 6;
+
+/// Number of [`ExponentialBackoff::snooze`] steps.
+///
+/// The last snoozing step spins 1024 hints.
+const SNOOZE_LIMIT: u32 = 10;
+
+/// Hints the core that the caller is in a spin-wait loop (`YIELD`).
+///
+/// Lets the core lower its power draw and give priority to the other
+/// hardware thread, if any, while waiting.
+#[inline(always)]
+pub fn spin_loop_hint() {
+    unsafe { core::arch::asm!("yield", options(nomem, nostack, preserves_flags)) }
+}
+
+/// Exponential backoff for spin-wait loops.
+///
+/// Each call to [`spin`](Self::spin) or [`snooze`](Self::snooze) waits twice
+/// as long as the previous one, up to a limit. Create a new backoff (or
+/// [`reset`](Self::reset) it) for every wait.
+#[derive(Debug, Default)]
+pub struct ExponentialBackoff {
+    step: u32,
+}
+
+impl ExponentialBackoff {
+    /// Creates a backoff at its first step.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { step: 0 }
+    }
+
+    /// Resets the backoff to its first step.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.step = 0;
+    }
+
+    /// Spins with an increasing number of hints.
+    ///
+    /// Never leaves the core; use it when the wait is expected to be short,
+    /// e.g. for a retry after a lost race.
+    pub fn spin(&mut self) {
+        for _ in 0..1u32 << self.step.min(SPIN_LIMIT) {
+            spin_loop_hint();
+        }
+        if self.step <= SPIN_LIMIT {
+            self.step += 1;
+        }
+    }
+
+    /// Spins like [`spin`](Self::spin), but keeps doubling the number of hints
+    /// past the spin limit, up to 1024.
+    ///
+    /// Never leaves the core either; use it when waiting on another thread to
+    /// make progress, and the wait cannot block. Once
+    /// [`is_completed`](Self::is_completed) returns `true`, callers that can
+    /// afford it should yield the core instead.
+    pub fn snooze(&mut self) {
+        for _ in 0..1u32 << self.step.min(SNOOZE_LIMIT) {
+            spin_loop_hint();
+        }
+        if self.step <= SNOOZE_LIMIT {
+            self.step += 1;
+        }
+    }
+
+    /// Returns `true` once the backoff has gone past the spin limit, i.e. when
+    /// the wait is no longer expected to be short.
+    #[inline]
+    pub fn is_completed(&self) -> bool {
+        self.step > SPIN_LIMIT
+    }
+}
@@ -113,7 +113,9 @@ impl<'a> core::fmt::Write for Cursor<'a> {
 /// - [`break_event()`] function to trigger the break with a message buffer
 ///
 /// This is intentionally minimal to avoid pulling in the full `nx-svc` crate,
-/// allowing the panic handler to remain lightweight and dependency-free.
+/// allowing the panic handler to remain lightweight and dependency-free. It
+/// cannot use `nx-svc` anyway: `nx-svc` links this crate for its panic
+/// handler. Application code should use `nx_svc::debug::break_with` instead.
 mod svc {
     /// Result code returned from supervisor calls.
     type ResultCode = u32;
@@ -124,7 +126,8 @@ mod svc {
     /// Reasons for triggering a debug break event.
     ///
     /// These values are passed to the `svcBreak` system call to indicate
-    /// the reason for breaking into the debugger.
+    /// the reason for breaking into the debugger. They mirror
+    /// `nx_svc::raw::BreakReason`; both follow the kernel ABI.
     #[repr(u32)]
     #[allow(dead_code)]
     pub(super) enum BreakReason {
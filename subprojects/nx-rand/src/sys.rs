@@ -64,6 +64,29 @@ pub fn next_u64() -> u64 {
     get_rng().next_u64()
 }
 
+/// Initializes the global RNG, mixing `extra` into the TRNG entropy.
+///
+/// The extra seed is XORed into the first 128 bits of the kernel entropy, the
+/// same way libnx mixes in the homebrew loader's random seed. It can only add
+/// entropy, never remove it, so a predictable `extra` does not weaken the RNG.
+///
+/// This must be called before the first use of the RNG to have any effect.
+///
+/// # Returns
+///
+/// * `true` if the RNG was initialized with the extra seed
+/// * `false` if the RNG was already initialized (or being initialized)
+pub fn init_with_seed(extra: [u64; 2]) -> bool {
+    if RNG_STATE.try_claim_initialization().is_err() {
+        return false;
+    }
+
+    init_rng(extra);
+    RNG_STATE.mark_as_initialized();
+
+    true
+}
+
 /// Returns a reference to the global RNG instance, initializing it if necessary.
 ///
 /// This function ensures that the RNG is initialized only once, even in the presence
@@ -85,7 +108,7 @@ fn get_rng() -> &'static mut ChaCha20Rng {
                 }
 
                 // We've claimed initialization, so initialize the RNG
-                init_rng();
+                init_rng([0; 2]);
                 RNG_STATE.mark_as_initialized();
 
                 break;
@@ -108,13 +131,14 @@ fn get_rng() -> &'static mut ChaCha20Rng {
 ///
 /// This function:
 /// 1. Collects 256 bits of entropy from the system TRNG
-/// 2. Uses this entropy to seed a ChaCha20 RNG
-/// 3. Stores the RNG in the global static variable
+/// 2. XORs the `extra` seed into the first 128 bits
+/// 3. Uses this entropy to seed a ChaCha20 RNG
+/// 4. Stores the RNG in the global static variable
 ///
 /// # Panics
 ///
 /// This function will panic if it fails to obtain entropy from the system TRNG.
-fn init_rng() {
+fn init_rng(extra: [u64; 2]) {
     let mut seed = [0u64; 4];
     for (i, item) in seed.iter_mut().enumerate() {
         // Get process TRNG seeds from kernel using the new helper
@@ -124,6 +148,9 @@ fn init_rng() {
         }
     }
 
+    seed[0] ^= extra[0];
+    seed[1] ^= extra[1];
+
     unsafe {
         RNG.write(ChaCha20Rng::from_seed(core::mem::transmute::<
             [u64; 4],
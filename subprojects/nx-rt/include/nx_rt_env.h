@@ -0,0 +1,21 @@
+/**
+ * @file nx_rt_env.h
+ * @brief Environment helpers exposed by the nx-rt crate, with no libnx counterpart.
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <switch/runtime/env.h>
+
+/// Number of entries after which a ConfigEntry array with no EndOfList entry is truncated.
+#define NX_RT_ENV_MAX_CONFIG_ENTRIES 48
+
+/**
+ * @brief Counts the entries of a ConfigEntry array, as parsed by envSetup().
+ * @note The EndOfList entry is counted. Without one, counting stops after @ref NX_RT_ENV_MAX_CONFIG_ENTRIES entries.
+ * @param[in] entries ConfigEntry array, terminated by EndOfList or with at least @ref NX_RT_ENV_MAX_CONFIG_ENTRIES entries.
+ * @param[out] truncated Set to true if counting stopped before an EndOfList entry. May be NULL.
+ * @return Number of entries counted, or 0 if @p entries is NULL.
+ */
+size_t __nx_rt__env_count_config_entries(const ConfigEntry* entries, bool* truncated);
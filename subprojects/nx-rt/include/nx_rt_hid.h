@@ -0,0 +1,20 @@
+/**
+ * @file nx_rt_hid.h
+ * @brief HID helpers exposed by the nx-rt crate, with no libnx counterpart.
+ */
+#pragma once
+
+#include <stdint.h>
+#include <switch/services/hid.h>
+
+/**
+ * @brief Applies radial deadzones to a raw stick position, as the Rust Gamepad does.
+ * @note Deadzones are fractions of the full stick range. The Gamepad defaults are 0.1 and 0.1.
+ * @param inner_deadzone Fraction of the range around the center that reads as centered.
+ * @param outer_deadzone Fraction of the range at the edge that reads as fully pushed.
+ * @param stick Raw stick position, each axis in -0x7FFF to 0x7FFF.
+ * @param[out] out_x Horizontal position, in -1.0 to 1.0.
+ * @param[out] out_y Vertical position, in -1.0 to 1.0.
+ * @return 0 on success, or an error code if an output pointer is NULL.
+ */
+uint32_t __nx_rt__hid_stick_apply_deadzones(float inner_deadzone, float outer_deadzone, HidAnalogStickState stick, float* out_x, float* out_y);
@@ -0,0 +1,84 @@
+/**
+ * @file nx_rt_time.h
+ * @brief Time helpers exposed by the nx-rt crate, with no libnx counterpart.
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <time.h>
+#include <switch/services/time.h>
+
+/// Out-of-range field reported by __nx_rt__time_tm_to_calendar_time.
+typedef enum {
+    NxRtTmField_Year   = 1, ///< tm_year + 1900 is negative or does not fit in 16 bits.
+    NxRtTmField_Month  = 2, ///< tm_mon is not in 0-11.
+    NxRtTmField_Day    = 3, ///< tm_mday is not in 1-31.
+    NxRtTmField_Hour   = 4, ///< tm_hour is not in 0-23.
+    NxRtTmField_Minute = 5, ///< tm_min is not in 0-59.
+    NxRtTmField_Second = 6, ///< tm_sec is not in 0-59.
+} NxRtTmField;
+
+/**
+ * @brief Converts a calendar time to a struct tm.
+ * @note The day of the week and day of the year are computed from the date, and tm_isdst is set to -1.
+ * @param[in] caltime Calendar time.
+ * @param[out] tm Converted time.
+ * @return 0 on success, or an error code if a pointer is NULL.
+ */
+uint32_t __nx_rt__time_calendar_time_to_tm(const TimeCalendarTime* caltime, struct tm* tm);
+
+/**
+ * @brief Converts a struct tm back to a calendar time.
+ * @note tm_wday, tm_yday and tm_isdst are ignored. Fields are not normalized.
+ * @param[in] tm Time to convert.
+ * @param[out] caltime Calendar time, left untouched on failure.
+ * @return 0 on success, the out-of-range @ref NxRtTmField, or an error code if a pointer is NULL.
+ */
+uint32_t __nx_rt__time_tm_to_calendar_time(const struct tm* tm, TimeCalendarTime* caltime);
+
+/// Periodic timer storage, initialized by __nx_rt__time_periodic_timer_init.
+typedef struct {
+    uint64_t opaque[4];
+} NxRtPeriodicTimer;
+
+/**
+ * @brief Starts a periodic timer at a system tick sampled by the caller.
+ * @note Periods are counted from @p start_tick, so rounding errors do not accumulate across polls.
+ * @param[out] timer Timer to initialize.
+ * @param period_ns Period in nanoseconds, non-zero.
+ * @param start_tick System tick the timer starts at.
+ * @param max_steps Cap on the periods reported by a single poll (0 is raised to 1).
+ * @return 0 on success, or an error code if @p timer is NULL or @p period_ns is zero.
+ */
+uint32_t __nx_rt__time_periodic_timer_init(NxRtPeriodicTimer* timer, uint64_t period_ns, uint64_t start_tick, uint32_t max_steps);
+
+/**
+ * @brief Returns the number of whole periods elapsed since the previous poll.
+ * @note Periods past max_steps are dropped, not carried over to later polls.
+ * @param timer Initialized timer.
+ * @param now_tick Current system tick, which must not go backwards between polls.
+ * @return Number of periods elapsed, capped at max_steps.
+ */
+uint32_t __nx_rt__time_periodic_timer_poll(NxRtPeriodicTimer* timer, uint64_t now_tick);
+
+/**
+ * @brief Gets the current steady clock time point.
+ * @note Reads shared memory when available, and falls back to IPC otherwise.
+ * @param[out] out Time point, in seconds, and its clock source ID.
+ * @return Result code.
+ */
+uint32_t __nx_rt__time_get_steady_clock_time_point(TimeSteadyClockTimePoint* out);
+
+/**
+ * @brief Checks whether time reads go through shared memory.
+ * @return true if shared memory is mapped and in use, false otherwise or if the service is not initialized.
+ */
+bool __nx_rt__time_has_shared_memory(void);
+
+/**
+ * @brief Stops using shared memory, forcing every time read through IPC.
+ * @note Shared memory is used again after timeExit() and timeInitialize().
+ * @return 0 on success, or an error code if the service is not initialized.
+ */
+uint32_t __nx_rt__time_disable_shared_memory(void);
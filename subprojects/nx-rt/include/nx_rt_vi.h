@@ -0,0 +1,47 @@
+/**
+ * @file nx_rt_vi.h
+ * @brief VI helpers exposed by the nx-rt crate, with no libnx counterpart.
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <switch/display/buffer_producer.h>
+
+/**
+ * @brief Encodes the request parcel payload of a DEQUEUE_BUFFER transaction.
+ * @param is_async Whether the dequeue is asynchronous.
+ * @param width Buffer width, or 0 for the queue default.
+ * @param height Buffer height, or 0 for the queue default.
+ * @param format Pixel format, or 0 for the queue default.
+ * @param usage Gralloc usage flags.
+ * @param[out] out Payload buffer.
+ * @param out_capacity Size of @p out, in bytes.
+ * @param[out] out_size Size of the payload, in bytes.
+ * @return 0 on success, or an error code if a pointer is NULL or the payload does not fit.
+ */
+uint32_t __nx_rt__vi_bq_dequeue_buffer_request(bool is_async, uint32_t width, uint32_t height, int32_t format, uint32_t usage, void* out, size_t out_capacity, size_t* out_size);
+
+/**
+ * @brief Encodes the request parcel payload of a QUEUE_BUFFER transaction.
+ * @note The unk field of @p input is not sent.
+ * @param slot Slot of the buffer to queue.
+ * @param[in] input Queue parameters.
+ * @param[out] out Payload buffer.
+ * @param out_capacity Size of @p out, in bytes.
+ * @param[out] out_size Size of the payload, in bytes.
+ * @return 0 on success, or an error code if a pointer is NULL or the payload does not fit.
+ */
+uint32_t __nx_rt__vi_bq_queue_buffer_request(int32_t slot, const BqQueueBufferInput* input, void* out, size_t out_capacity, size_t* out_size);
+
+/**
+ * @brief Parses the reply parcel payload of a DEQUEUE_BUFFER transaction.
+ * @param[in] payload Reply payload.
+ * @param size Size of @p payload, in bytes.
+ * @param[out] slot Dequeued slot.
+ * @param[out] fence Fence to wait on before writing to the buffer.
+ * @param[out] needs_reallocation Whether the buffer in the slot must be requested again.
+ * @return 0 on success, or an error code if the reply is malformed or carries an error status.
+ */
+uint32_t __nx_rt__vi_bq_parse_dequeue_buffer_reply(const void* payload, size_t size, int32_t* slot, NvMultiFence* fence, bool* needs_reallocation);
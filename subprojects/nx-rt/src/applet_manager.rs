@@ -5,14 +5,14 @@
 //! they are stored in a dedicated structure rather than the generic service registry.
 
 use nx_service_applet::{
-    AppletFocusHandlingMode, AppletFocusState, AppletMessage, AppletProxyService, AppletService,
-    AppletType, ApplicationFunctions, CommonStateGetter, SelfController, WindowController,
+    AppletFocusHandlingMode, AppletFocusState, AppletMessage, AppletProxyService, AppletSession,
+    AppletType, CommonStateGetter, ReceiveMessageError, SelfController, WindowController,
     aruid::Aruid,
 };
 use nx_std_sync::{once_lock::OnceLock, rwlock::RwLock};
 use nx_svc::process::Handle as ProcessHandle;
 
-use crate::service_manager;
+use crate::{service_manager, vi_manager};
 
 /// Global applet state, lazily initialized.
 static APPLET_STATE: OnceLock<RwLock<Option<AppletState>>> = OnceLock::new();
@@ -39,58 +39,20 @@ pub fn init(applet_type: AppletType, process_handle: ProcessHandle) -> Result<()
     let sm_guard = service_manager::sm_session();
     let sm = sm_guard.as_ref().expect("SM not initialized");
 
-    // Connect to appletOE or appletAE
-    let service =
-        match nx_service_applet::connect(sm, applet_type).map_err(ConnectError::Connect)? {
-            Some(service) => service,
+    // Connect to appletOE or appletAE, and get the sub-interfaces. Dropping the
+    // session on a later error closes all of them.
+    let session =
+        match AppletSession::open(sm, applet_type, process_handle).map_err(ConnectError::Open)? {
+            Some(session) => session,
             None => return Ok(()), // AppletType::None
         };
+    let common_state_getter = session.common_state_getter();
+    let self_controller = session.self_controller();
+    let window_controller = session.window_controller();
 
-    // Open proxy session
-    let proxy = service
-        .open_proxy(applet_type, process_handle)
-        .map_err(ConnectError::OpenProxy)?;
-
-    // Get sub-interfaces
-    let common_state_getter = proxy
-        .get_common_state_getter()
-        .map_err(ConnectError::GetCommonStateGetter)?;
-
-    let self_controller = proxy
-        .get_self_controller()
-        .map_err(ConnectError::GetSelfController)?;
-
-    // WindowController and ApplicationFunctions handling depends on applet type
-    let is_application = matches!(applet_type, AppletType::Application);
-
-    // WindowController is mandatory for Application type, optional for others
-    let window_controller = if is_application {
-        Some(
-            proxy
-                .get_window_controller()
-                .map_err(ConnectError::GetWindowController)?,
-        )
-    } else {
-        proxy.get_window_controller().ok()
-    };
-
-    // ApplicationFunctions is only available for Application type
-    let application_functions = if is_application {
-        Some(
-            proxy
-                .get_application_functions()
-                .map_err(ConnectError::GetApplicationFunctions)?,
-        )
-    } else {
-        None
-    };
-
-    // Application-specific initialization handshake
-    if is_application {
-        // SAFETY: For Application type, we ensured window_controller and application_functions are Some
-        let wc = window_controller.as_ref().unwrap();
-        let app_funcs = application_functions.as_ref().unwrap();
-
+    // Application-specific initialization handshake (IApplicationFunctions is
+    // only opened for Application type)
+    if let (Some(wc), Some(app_funcs)) = (window_controller, session.application_functions()) {
         // 1. Get message event handle
         let event_handle = common_state_getter
             .get_event_handle()
@@ -119,12 +81,15 @@ pub fn init(applet_type: AppletType, process_handle: ProcessHandle) -> Result<()
             }
 
             // Receive and process message
-            if let Ok(Some(msg)) = common_state_getter.receive_message()
-                && matches!(msg, AppletMessage::FocusStateChanged)
-            {
-                focus_state = common_state_getter
-                    .get_current_focus_state()
-                    .map_err(ConnectError::GetFocusState)?;
+            match common_state_getter.receive_message() {
+                Ok(Some(AppletMessage::FocusStateChanged)) => {
+                    focus_state = common_state_getter
+                        .get_current_focus_state()
+                        .map_err(ConnectError::GetFocusState)?;
+                }
+                // Other messages, and those from newer firmware, are dequeued and skipped
+                Ok(_) | Err(ReceiveMessageError::UnknownMessage(_)) => {}
+                Err(err) => return Err(ConnectError::ReceiveMessage(err)),
             }
         }
 
@@ -153,20 +118,10 @@ pub fn init(applet_type: AppletType, process_handle: ProcessHandle) -> Result<()
     }
 
     // Fetch and cache the applet resource user ID
-    let aruid = window_controller
-        .as_ref()
-        .and_then(|wc| wc.get_applet_resource_user_id().unwrap_or(None));
+    let aruid = window_controller.and_then(|wc| wc.get_applet_resource_user_id().unwrap_or(None));
 
     // Store in registry
-    let applet_state = AppletState {
-        service,
-        proxy,
-        common_state_getter,
-        self_controller,
-        window_controller,
-        application_functions,
-        aruid,
-    };
+    let applet_state = AppletState { session, aruid };
 
     let mut guard = state().write();
     *guard = Some(applet_state);
@@ -211,7 +166,7 @@ pub fn get_self_controller() -> Option<impl core::ops::Deref<Target = SelfContro
 pub fn get_window_controller() -> Option<impl core::ops::Deref<Target = WindowController> + 'static>
 {
     let guard = state().read();
-    if guard.as_ref()?.window_controller.is_some() {
+    if guard.as_ref()?.session.window_controller().is_some() {
         Some(AppletWindowControllerRef(guard))
     } else {
         None
@@ -226,38 +181,169 @@ pub fn get_applet_resource_user_id() -> Option<Aruid> {
     state().read().as_ref().and_then(|s| s.aruid)
 }
 
-/// Exits the applet service session.
-pub fn exit() {
-    let mut guard = state().write();
-    if let Some(applet_state) = guard.take() {
-        // Close sub-interfaces and sessions in reverse order
-        if let Some(application_functions) = applet_state.application_functions {
-            application_functions.close();
+/// Drains the applet message queue, passing each message accepted by `filter`
+/// to `handler`.
+///
+/// Messages rejected by the filter are still dequeued, and so acknowledged,
+/// just not delivered; this keeps the message event cleared once the queue is
+/// empty. Use [`MessageFilter::all`] to receive every message.
+///
+/// Returns once the queue is empty. For [`AppletMessage::RequestToDisplay`], a
+/// handler returning [`MessageAction::ApproveToDisplay`] releases the foreground
+/// rights and approves the request (see [`approve_to_display`]); the action is
+/// ignored for every other message. Returns `NotInitialized` if the applet
+/// service is not initialized.
+///
+/// A filtered out `RequestToDisplay` is not approved.
+///
+/// [`AppletMessage::OperationModeChanged`] also invalidates the VI display
+/// resolution cache, since docking changes the resolution, whether or not the
+/// filter accepts it.
+///
+/// The state lock is not held while `handler` runs, so it may freely call the
+/// other functions in this module. Call it once per frame from the main loop,
+/// e.g. with [`MessageFilter::lifecycle`] to stop on
+/// [`AppletMessage::ExitRequest`].
+pub fn pump_messages(
+    filter: MessageFilter,
+    mut handler: impl FnMut(AppletMessage) -> MessageAction,
+) -> Result<(), PumpMessagesError> {
+    loop {
+        let msg = {
+            let guard = state().read();
+            let applet_state = guard.as_ref().ok_or(PumpMessagesError::NotInitialized)?;
+            match applet_state.session.common_state_getter().receive_message() {
+                Ok(msg) => msg,
+                // Messages from newer firmware are dequeued; skip them
+                Err(ReceiveMessageError::UnknownMessage(_)) => continue,
+                Err(err) => return Err(PumpMessagesError::ReceiveMessage(err)),
+            }
+        };
+
+        let Some(msg) = msg else {
+            return Ok(());
+        };
+
+        if msg == AppletMessage::OperationModeChanged
+            && let Some(vi) = vi_manager::get_service()
+        {
+            vi.invalidate_display_resolution_cache();
+        }
+
+        if !filter.contains(msg) {
+            continue;
         }
-        if let Some(window_controller) = applet_state.window_controller {
-            window_controller.close();
+
+        let action = handler(msg);
+        if msg == AppletMessage::RequestToDisplay && action == MessageAction::ApproveToDisplay {
+            approve_to_display().map_err(PumpMessagesError::ApproveToDisplay)?;
         }
-        applet_state.self_controller.close();
-        applet_state.common_state_getter.close();
-        applet_state.proxy.close();
-        applet_state.service.close();
+    }
+}
+
+/// Hands the display over in response to [`AppletMessage::RequestToDisplay`].
+///
+/// Releases the foreground rights, then approves the request. Once the applet
+/// is back `InFocus` (signalled by `FocusStateChanged`), the rights must be
+/// re-acquired via [`WindowController::acquire_foreground_rights`].
+pub fn approve_to_display() -> Result<(), ApproveToDisplayError> {
+    let guard = state().read();
+    let applet_state = guard
+        .as_ref()
+        .ok_or(ApproveToDisplayError::NotInitialized)?;
+
+    let wc = applet_state
+        .session
+        .window_controller()
+        .ok_or(ApproveToDisplayError::WindowControllerUnavailable)?;
+
+    wc.release_foreground_rights()
+        .map_err(ApproveToDisplayError::ReleaseForegroundRights)?;
+
+    applet_state
+        .session
+        .self_controller()
+        .approve_to_display()
+        .map_err(ApproveToDisplayError::ApproveToDisplay)?;
+
+    Ok(())
+}
+
+/// Exits the applet service session.
+pub fn exit() {
+    // Dropping the session closes the sub-interfaces and sessions in reverse order
+    let _ = state().write().take();
+}
+
+/// Handler response for a message delivered by [`pump_messages`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageAction {
+    /// No further action.
+    #[default]
+    Continue,
+    /// Hand the display over to the requesting applet.
+    ///
+    /// Only meaningful for [`AppletMessage::RequestToDisplay`].
+    ApproveToDisplay,
+}
+
+/// Set of applet messages delivered by [`pump_messages`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageFilter(u128);
+
+impl MessageFilter {
+    /// Accepts every message.
+    pub const fn all() -> Self {
+        Self(u128::MAX)
+    }
+
+    /// Accepts no message; every message is drained without being delivered.
+    pub const fn none() -> Self {
+        Self(0)
+    }
+
+    /// Accepts the messages that drive the application lifecycle:
+    /// [`ExitRequest`](AppletMessage::ExitRequest),
+    /// [`FocusStateChanged`](AppletMessage::FocusStateChanged) and
+    /// [`Resume`](AppletMessage::Resume).
+    pub const fn lifecycle() -> Self {
+        Self::none()
+            .with(AppletMessage::ExitRequest)
+            .with(AppletMessage::FocusStateChanged)
+            .with(AppletMessage::Resume)
+    }
+
+    /// Returns the filter with `msg` accepted.
+    pub const fn with(self, msg: AppletMessage) -> Self {
+        Self(self.0 | Self::bit(msg))
+    }
+
+    /// Returns the filter with `msg` rejected.
+    pub const fn without(self, msg: AppletMessage) -> Self {
+        Self(self.0 & !Self::bit(msg))
+    }
+
+    /// Returns `true` if the filter accepts `msg`.
+    pub const fn contains(self, msg: AppletMessage) -> bool {
+        self.0 & Self::bit(msg) != 0
+    }
+
+    /// Bit of `msg` in the set; every message value is below 128.
+    const fn bit(msg: AppletMessage) -> u128 {
+        1 << (msg as u32)
+    }
+}
+
+impl Default for MessageFilter {
+    fn default() -> Self {
+        Self::all()
     }
 }
 
 /// Internal storage for applet service sessions.
 struct AppletState {
-    /// Main service session (appletOE or appletAE)
-    service: AppletService,
-    /// Proxy session
-    proxy: AppletProxyService,
-    /// ICommonStateGetter sub-interface
-    common_state_getter: CommonStateGetter,
-    /// ISelfController sub-interface
-    self_controller: SelfController,
-    /// IWindowController sub-interface (mandatory for Application, optional for others)
-    window_controller: Option<WindowController>,
-    /// IApplicationFunctions sub-interface (Application type only)
-    application_functions: Option<ApplicationFunctions>,
+    /// Service, proxy, and sub-interface sessions
+    session: AppletSession,
     /// Cached applet resource user ID (fetched once during init)
     aruid: Option<Aruid>,
 }
@@ -270,7 +356,7 @@ impl core::ops::Deref for AppletProxyRef {
 
     fn deref(&self) -> &Self::Target {
         // SAFETY: We only create AppletProxyRef when the option is Some
-        &self.0.as_ref().unwrap().proxy
+        self.0.as_ref().unwrap().session.proxy()
     }
 }
 
@@ -284,7 +370,7 @@ impl core::ops::Deref for AppletCommonStateGetterRef {
 
     fn deref(&self) -> &Self::Target {
         // SAFETY: We only create this ref when the option is Some
-        &self.0.as_ref().unwrap().common_state_getter
+        self.0.as_ref().unwrap().session.common_state_getter()
     }
 }
 
@@ -296,7 +382,7 @@ impl core::ops::Deref for AppletSelfControllerRef {
 
     fn deref(&self) -> &Self::Target {
         // SAFETY: We only create this ref when the option is Some
-        &self.0.as_ref().unwrap().self_controller
+        self.0.as_ref().unwrap().session.self_controller()
     }
 }
 
@@ -310,31 +396,21 @@ impl core::ops::Deref for AppletWindowControllerRef {
 
     fn deref(&self) -> &Self::Target {
         // SAFETY: We only create this ref when both AppletState and window_controller are Some
-        self.0.as_ref().unwrap().window_controller.as_ref().unwrap()
+        self.0
+            .as_ref()
+            .unwrap()
+            .session
+            .window_controller()
+            .unwrap()
     }
 }
 
 /// Error returned by [`init`].
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectError {
-    /// Failed to connect to applet service.
-    #[error("failed to connect to applet service")]
-    Connect(#[source] nx_service_applet::ConnectError),
-    /// Failed to open proxy session.
-    #[error("failed to open applet proxy")]
-    OpenProxy(#[source] nx_service_applet::OpenProxyError),
-    /// Failed to get ICommonStateGetter.
-    #[error("failed to get ICommonStateGetter")]
-    GetCommonStateGetter(#[source] nx_service_applet::GetCommonStateGetterError),
-    /// Failed to get ISelfController.
-    #[error("failed to get ISelfController")]
-    GetSelfController(#[source] nx_service_applet::GetSelfControllerError),
-    /// Failed to get IWindowController.
-    #[error("failed to get IWindowController")]
-    GetWindowController(#[source] nx_service_applet::GetWindowControllerError),
-    /// Failed to get IApplicationFunctions.
-    #[error("failed to get IApplicationFunctions")]
-    GetApplicationFunctions(#[source] nx_service_applet::GetApplicationFunctionsError),
+    /// Failed to open the applet session and its sub-interfaces.
+    #[error("failed to open applet session")]
+    Open(#[source] nx_service_applet::OpenSessionError),
     /// Failed to get message event handle.
     #[error("failed to get message event handle")]
     GetEventHandle(#[source] nx_service_applet::GetEventHandleError),
@@ -344,6 +420,9 @@ pub enum ConnectError {
     /// Failed to wait for synchronization.
     #[error("failed to wait for synchronization")]
     WaitSynchronization(#[source] nx_svc::sync::WaitSyncError),
+    /// Failed to receive a message while waiting for focus.
+    #[error("failed to receive message")]
+    ReceiveMessage(#[source] nx_service_applet::ReceiveMessageError),
     /// Failed to acquire foreground rights.
     #[error("failed to acquire foreground rights")]
     AcquireForegroundRights(#[source] nx_service_applet::AcquireForegroundRightsError),
@@ -364,3 +443,34 @@ pub enum ConnectError {
         #[source] nx_service_applet::SetPerformanceModeChangedNotificationError,
     ),
 }
+
+/// Error returned by [`pump_messages`].
+#[derive(Debug, thiserror::Error)]
+pub enum PumpMessagesError {
+    /// The applet service is not initialized.
+    #[error("applet service not initialized")]
+    NotInitialized,
+    /// Failed to receive a message.
+    #[error("failed to receive applet message")]
+    ReceiveMessage(#[source] nx_service_applet::ReceiveMessageError),
+    /// Failed to approve a display request.
+    #[error("failed to approve display request")]
+    ApproveToDisplay(#[source] ApproveToDisplayError),
+}
+
+/// Error returned by [`approve_to_display`].
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveToDisplayError {
+    /// The applet service is not initialized.
+    #[error("applet service not initialized")]
+    NotInitialized,
+    /// IWindowController was not obtained during init.
+    #[error("IWindowController not available")]
+    WindowControllerUnavailable,
+    /// Failed to release foreground rights.
+    #[error("failed to release foreground rights")]
+    ReleaseForegroundRights(#[source] nx_service_applet::ReleaseForegroundRightsError),
+    /// Failed to approve the display request.
+    #[error("failed to approve display request")]
+    ApproveToDisplay(#[source] nx_service_applet::ApproveToDisplayError),
+}
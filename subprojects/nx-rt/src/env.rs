@@ -103,9 +103,9 @@ unsafe fn env_init_nro(state: &mut EnvState, ctx: NonNull<ConfigEntry>, saved_lr
     state.is_nso = false;
 
     // SAFETY: Caller guarantees ctx points to valid ConfigEntry array terminated by EndOfList
-    let entries = unsafe { ConfigEntries::from_ptr(ctx) };
+    let mut entries = unsafe { ConfigEntries::from_ptr(ctx) };
 
-    for entry in entries {
+    for entry in entries.by_ref() {
         match entry {
             Entry::HosVersion {
                 version,
@@ -183,6 +183,19 @@ unsafe fn env_init_nro(state: &mut EnvState, ctx: NonNull<ConfigEntry>, saved_lr
             }
         }
     }
+
+    state.parse_truncated = entries.is_truncated();
+}
+
+/// Returns true if the loader's ConfigEntry array was not terminated
+///
+/// Parsing stops after a fixed number of entries when no `EndOfList` entry is
+/// found, so a malformed environment cannot make it read out of bounds. The
+/// entries seen up to that point are still applied.
+pub fn parse_truncated() -> bool {
+    // SAFETY: ENV_STATE is initialized once via setup() and is read-only after that.
+    let state = unsafe { ENV_STATE.get_ref() };
+    state.parse_truncated
 }
 
 /// Get loader info string pointer and size
@@ -284,6 +297,25 @@ pub fn random_seed() -> Option<[u64; 2]> {
     state.random_seed
 }
 
+/// Seed the `nx-rand` global RNG with the loader-provided random seed
+///
+/// The seed is mixed into the kernel TRNG entropy when the RNG is first
+/// initialized. When the loader did not provide a seed (e.g. NSO mode), the
+/// system tick is mixed in instead. The tick is predictable and adds no real
+/// entropy, so in that case the RNG quality rests on the kernel TRNG alone.
+///
+/// Must be called after [`setup`] and before the RNG is first used. Returns
+/// `false` if the RNG was already initialized.
+#[cfg(feature = "rand")]
+pub fn seed_rng() -> bool {
+    let seed = random_seed().unwrap_or_else(|| {
+        let tick = nx_svc::misc::get_system_tick();
+        [tick, tick.rotate_left(32)]
+    });
+
+    nx_rand::sys::init_with_seed(seed)
+}
+
 /// Get user ID storage pointer if present
 pub fn user_id_storage() -> Option<NonNull<AccountUid>> {
     // SAFETY: ENV_STATE is initialized once via setup() and is read-only after that.
@@ -439,6 +471,9 @@ struct EnvState {
 
     /// APT workaround flag (true if APT is broken and should not be used)
     applet_workaround: bool,
+
+    /// ConfigEntry array hit the entry cap without an EndOfList terminator
+    parse_truncated: bool,
 }
 
 impl EnvState {
@@ -459,6 +494,7 @@ impl EnvState {
             service_override_count: 0,
             applet_type: AppletType::Default,
             applet_workaround: false,
+            parse_truncated: false,
         }
     }
 }
@@ -5,6 +5,7 @@
 
 use core::{
     ffi::{c_char, c_void},
+    marker::PhantomData,
     ptr::NonNull,
 };
 
@@ -152,11 +153,18 @@ impl Entry {
 /// Iterator over ConfigEntry array with compile-time bound.
 ///
 /// Stops at `EndOfList` or after `MAX_CONFIG_ENTRIES`, whichever comes first.
-/// Yields parsed [`Entry`] values directly.
+/// Yields parsed [`Entry`] values directly. Entries are read one at a time, so
+/// nothing past the terminator is ever touched.
+///
+/// A well-formed array always ends with `EndOfList` within the bound. If the
+/// bound is reached first, iteration stops there and
+/// [`is_truncated`](Self::is_truncated) reports it.
 pub struct ConfigEntries<'a> {
-    entries: &'a [ConfigEntry],
+    ptr: NonNull<ConfigEntry>,
     index: usize,
     done: bool,
+    truncated: bool,
+    _marker: PhantomData<&'a ConfigEntry>,
 }
 
 impl<'a> ConfigEntries<'a> {
@@ -167,28 +175,43 @@ impl<'a> ConfigEntries<'a> {
     /// `ptr` must point to a valid ConfigEntry array terminated by `EndOfList`,
     /// with at least `MAX_CONFIG_ENTRIES` readable elements OR an `EndOfList` before that.
     pub unsafe fn from_ptr(ptr: NonNull<ConfigEntry>) -> Self {
-        // SAFETY: Create slice with max bound. Safe because:
-        // 1. Loader guarantees EndOfList terminator
-        // 2. We stop iteration at EndOfList anyway
-        // 3. MAX_CONFIG_ENTRIES is the theoretical maximum
-        let entries = unsafe { core::slice::from_raw_parts(ptr.as_ptr(), MAX_CONFIG_ENTRIES) };
         Self {
-            entries,
+            ptr,
             index: 0,
             done: false,
+            truncated: false,
+            _marker: PhantomData,
         }
     }
+
+    /// Returns true if iteration stopped at `MAX_CONFIG_ENTRIES` without
+    /// finding `EndOfList`.
+    ///
+    /// Only meaningful once the iterator has been exhausted.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
 }
 
 impl Iterator for ConfigEntries<'_> {
     type Item = Entry;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.done || self.index >= self.entries.len() {
+        if self.done {
+            return None;
+        }
+
+        if self.index >= MAX_CONFIG_ENTRIES {
+            // The loader never terminated the array; stop rather than read
+            // out of bounds.
+            self.done = true;
+            self.truncated = true;
             return None;
         }
 
-        let entry = Entry::from_config(&self.entries[self.index]);
+        // SAFETY: The caller of `from_ptr` guarantees every entry up to
+        // `EndOfList` or `MAX_CONFIG_ENTRIES` is readable, and we stop at both.
+        let entry = Entry::from_config(unsafe { self.ptr.add(self.index).as_ref() });
         self.index += 1;
 
         if matches!(entry, Entry::LoaderInfo { .. }) {
@@ -227,7 +227,14 @@ pub unsafe extern "C" fn __nx_rt__applet_receive_message(msg: *mut u32) -> u32 {
             // This matches libnx behavior where the queue may be empty
             0
         }
+        Err(nx_service_applet::ReceiveMessageError::UnknownMessage(raw)) => {
+            // Pass messages unknown to the crate through, as libnx does
+            // SAFETY: Caller guarantees msg points to valid memory.
+            unsafe { *msg = raw };
+            0
+        }
         Err(nx_service_applet::ReceiveMessageError::Dispatch(e)) => dispatch_error_to_rc(e),
+        Err(nx_service_applet::ReceiveMessageError::ServiceError(code)) => code,
         Err(nx_service_applet::ReceiveMessageError::InvalidResponse) => GENERIC_ERROR,
     }
 }
@@ -344,37 +351,49 @@ fn applet_connect_error_to_rc(err: applet_manager::ConnectError) -> u32 {
     use nx_svc::error::ToRawResultCode;
 
     match err {
-        applet_manager::ConnectError::Connect(e) => match e {
-            nx_service_applet::ConnectError::GetService(e) => match e {
-                nx_service_sm::GetServiceCmifError::SendRequest(e) => e.to_rc(),
-                nx_service_sm::GetServiceCmifError::ParseResponse(e) => match e {
-                    cmif::ParseResponseError::InvalidMagic => GENERIC_ERROR,
-                    cmif::ParseResponseError::ServiceError(code) => code,
+        applet_manager::ConnectError::Open(nx_service_applet::OpenSessionError::Connect(e)) => {
+            match e {
+                nx_service_applet::ConnectError::GetService(e) => match e {
+                    nx_service_sm::GetServiceCmifError::SendRequest(e) => e.to_rc(),
+                    nx_service_sm::GetServiceCmifError::ParseResponse(e) => match e {
+                        cmif::ParseResponseError::InvalidMagic => GENERIC_ERROR,
+                        cmif::ParseResponseError::ServiceError(code) => code,
+                    },
+                    nx_service_sm::GetServiceCmifError::MissingHandle => GENERIC_ERROR,
                 },
-                nx_service_sm::GetServiceCmifError::MissingHandle => GENERIC_ERROR,
-            },
-            nx_service_applet::ConnectError::ConvertToDomain(e) => {
-                convert_to_domain_error_to_rc(e.0)
+                nx_service_applet::ConnectError::ConvertToDomain(e) => {
+                    convert_to_domain_error_to_rc(e.0)
+                }
             }
-        },
-        applet_manager::ConnectError::OpenProxy(e) => match e {
-            nx_service_applet::OpenProxyError::InvalidAppletType => GENERIC_ERROR,
-            nx_service_applet::OpenProxyError::Dispatch(e) => dispatch_error_to_rc(e),
-            nx_service_applet::OpenProxyError::MissingObject => GENERIC_ERROR,
-        },
-        applet_manager::ConnectError::GetCommonStateGetter(e) => match e {
+        }
+        applet_manager::ConnectError::Open(nx_service_applet::OpenSessionError::OpenProxy(e)) => {
+            match e {
+                nx_service_applet::OpenProxyError::InvalidAppletType => GENERIC_ERROR,
+                nx_service_applet::OpenProxyError::Dispatch(e) => dispatch_error_to_rc(e),
+                nx_service_applet::OpenProxyError::MissingObject => GENERIC_ERROR,
+            }
+        }
+        applet_manager::ConnectError::Open(
+            nx_service_applet::OpenSessionError::GetCommonStateGetter(e),
+        ) => match e {
             nx_service_applet::GetCommonStateGetterError::Dispatch(e) => dispatch_error_to_rc(e),
             nx_service_applet::GetCommonStateGetterError::MissingObject => GENERIC_ERROR,
         },
-        applet_manager::ConnectError::GetSelfController(e) => match e {
+        applet_manager::ConnectError::Open(
+            nx_service_applet::OpenSessionError::GetSelfController(e),
+        ) => match e {
             nx_service_applet::GetSelfControllerError::Dispatch(e) => dispatch_error_to_rc(e),
             nx_service_applet::GetSelfControllerError::MissingObject => GENERIC_ERROR,
         },
-        applet_manager::ConnectError::GetWindowController(e) => match e {
+        applet_manager::ConnectError::Open(
+            nx_service_applet::OpenSessionError::GetWindowController(e),
+        ) => match e {
             nx_service_applet::GetWindowControllerError::Dispatch(e) => dispatch_error_to_rc(e),
             nx_service_applet::GetWindowControllerError::MissingObject => GENERIC_ERROR,
         },
-        applet_manager::ConnectError::GetApplicationFunctions(e) => match e {
+        applet_manager::ConnectError::Open(
+            nx_service_applet::OpenSessionError::GetApplicationFunctions(e),
+        ) => match e {
             nx_service_applet::GetApplicationFunctionsError::Dispatch(e) => dispatch_error_to_rc(e),
             nx_service_applet::GetApplicationFunctionsError::MissingObject => GENERIC_ERROR,
         },
@@ -388,6 +407,12 @@ fn applet_connect_error_to_rc(err: applet_manager::ConnectError) -> u32 {
             nx_service_applet::GetCurrentFocusStateError::InvalidValue(_) => GENERIC_ERROR,
         },
         applet_manager::ConnectError::WaitSynchronization(e) => e.to_rc(),
+        applet_manager::ConnectError::ReceiveMessage(e) => match e {
+            nx_service_applet::ReceiveMessageError::Dispatch(e) => dispatch_error_to_rc(e),
+            nx_service_applet::ReceiveMessageError::ServiceError(code) => code,
+            nx_service_applet::ReceiveMessageError::UnknownMessage(_) => GENERIC_ERROR,
+            nx_service_applet::ReceiveMessageError::InvalidResponse => GENERIC_ERROR,
+        },
         applet_manager::ConnectError::AcquireForegroundRights(e) => match e {
             nx_service_applet::AcquireForegroundRightsError::Dispatch(e) => dispatch_error_to_rc(e),
         },
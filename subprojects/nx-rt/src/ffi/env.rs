@@ -1,11 +1,14 @@
 //! Environment/loader config FFI
 
-use core::ffi::{c_char, c_uint, c_void};
+use core::{
+    ffi::{c_char, c_uint, c_void},
+    ptr::NonNull,
+};
 
 use nx_svc::{raw::INVALID_HANDLE, thread::Handle as ThreadHandle};
 
 use crate::{
-    env::{self, AccountUid, ConfigEntry, LoaderReturnFn},
+    env::{self, AccountUid, ConfigEntries, ConfigEntry, LoaderReturnFn},
     init, service_manager,
 };
 
@@ -79,6 +82,10 @@ pub unsafe extern "C" fn __nx_rt__env_setup(
 
     // Set global applet type from env config
     set_applet_type(env::applet_type().as_raw());
+
+    // Seed the global RNG before anything else can use it
+    #[cfg(feature = "rand")]
+    env::seed_rng();
 }
 
 /// Initialize main thread TLS (ThreadVars and .tdata copy).
@@ -286,6 +293,38 @@ pub unsafe extern "C" fn __nx_rt__env_get_user_id_storage() -> *mut AccountUid {
     }
 }
 
+/// Count the entries of a ConfigEntry array, as parsed by `envSetup()`.
+///
+/// Counting includes the `EndOfList` entry and stops after a fixed number of
+/// entries if there is none, in which case `truncated` is set. Returns 0 if
+/// `entries` is null. libnx has no counterpart.
+///
+/// # Safety
+///
+/// `entries` must be null or point to a ConfigEntry array terminated by
+/// `EndOfList`, or with at least as many readable entries as the bound.
+/// `truncated` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__env_count_config_entries(
+    entries: *const ConfigEntry,
+    truncated: *mut bool,
+) -> usize {
+    let Some(ptr) = NonNull::new(entries.cast_mut()) else {
+        return 0;
+    };
+
+    // SAFETY: The caller guarantees the array is terminated or bounded.
+    let mut iter = unsafe { ConfigEntries::from_ptr(ptr) };
+    let count = iter.by_ref().count();
+
+    if !truncated.is_null() {
+        // SAFETY: The caller guarantees `truncated` is valid for writes.
+        unsafe { truncated.write(iter.is_truncated()) };
+    }
+
+    count
+}
+
 /// Get the current HOS version (without Atmosphere bit).
 ///
 /// Corresponds to `hosversionGet()` in `hosversion.h`.
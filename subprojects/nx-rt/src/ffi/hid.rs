@@ -166,3 +166,32 @@ pub unsafe extern "C" fn __nx_rt__hid_initialize_gesture() {
         let _ = service.activate_gesture();
     }
 }
+
+/// Applies the `inner_deadzone` and `outer_deadzone` radial deadzones to a
+/// raw stick position, as [`nx_service_hid::Gamepad::stick_left`] does,
+/// writing each axis normalized to `-1.0..=1.0`.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__hid_stick_apply_deadzones(
+    inner_deadzone: f32,
+    outer_deadzone: f32,
+    stick: nx_service_hid::shmem::AnalogStickState,
+    out_x: *mut f32,
+    out_y: *mut f32,
+) -> u32 {
+    if out_x.is_null() || out_y.is_null() {
+        return GENERIC_ERROR;
+    }
+
+    let config = nx_service_hid::StickConfig {
+        inner_deadzone,
+        outer_deadzone,
+    };
+    let (x, y) = config.apply(stick);
+    unsafe {
+        *out_x = x;
+        *out_y = y;
+    }
+    0
+}
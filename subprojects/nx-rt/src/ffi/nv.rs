@@ -287,6 +287,7 @@ fn nv_connect_error_to_rc(err: crate::nv_manager::ConnectError) -> u32 {
 
     match err {
         crate::nv_manager::ConnectError::Connect(e) => match e {
+            nx_service_nv::ConnectError::InvalidConfig(_) => GENERIC_ERROR,
             nx_service_nv::ConnectError::GetService(sm_err) => match sm_err {
                 nx_service_sm::GetServiceCmifError::SendRequest(e) => e.to_rc(),
                 nx_service_sm::GetServiceCmifError::ParseResponse(e) => match e {
@@ -92,7 +92,7 @@ pub unsafe extern "C" fn __nx_rt__time_get_current_time(
                     cmif::ParseResponseError::ServiceError(code) => code,
                 },
                 nx_service_time::GetCurrentTimeError::NetworkClockUnavailable => GENERIC_ERROR,
-                nx_service_time::GetCurrentTimeError::LocalClockNotSupported => GENERIC_ERROR,
+                nx_service_time::GetCurrentTimeError::LocalClockUnavailable => GENERIC_ERROR,
                 nx_service_time::GetCurrentTimeError::SourceIdMismatch => GENERIC_ERROR,
             },
         },
@@ -133,3 +133,153 @@ pub unsafe extern "C" fn __nx_rt__time_to_calendar_time_with_my_rule(
         None => GENERIC_ERROR,
     }
 }
+
+/// Gets the current steady clock time point.
+///
+/// Reads shared memory when available, and falls back to IPC otherwise.
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_get_steady_clock_time_point(
+    out: *mut nx_service_time::TimeSteadyClockTimePoint,
+) -> u32 {
+    if out.is_null() {
+        return GENERIC_ERROR;
+    }
+
+    match crate::time_manager::get_service() {
+        Some(service) => match service.get_current_time_point() {
+            Ok(point) => {
+                unsafe { *out = point };
+                0
+            }
+            Err(err) => match err {
+                nx_service_time::GetCurrentTimePointError::SendRequest(e) => e.to_rc(),
+                nx_service_time::GetCurrentTimePointError::ParseResponse(e) => match e {
+                    cmif::ParseResponseError::InvalidMagic => GENERIC_ERROR,
+                    cmif::ParseResponseError::ServiceError(code) => code,
+                },
+            },
+        },
+        None => GENERIC_ERROR,
+    }
+}
+
+/// Returns whether time reads go through shared memory.
+///
+/// Returns `false` if the Time service is not initialized. libnx has no
+/// counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_has_shared_memory() -> bool {
+    crate::time_manager::get_service().is_some_and(|service| service.has_shared_memory())
+}
+
+/// Stops using shared memory, forcing every time read through IPC until the
+/// service is reinitialized.
+///
+/// Returns an error if the Time service is not initialized. libnx has no
+/// counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_disable_shared_memory() -> u32 {
+    if crate::time_manager::disable_shared_memory() {
+        0
+    } else {
+        GENERIC_ERROR
+    }
+}
+
+/// Converts a calendar time to a C `struct tm`.
+///
+/// The day of the week and day of the year are computed from the date, and
+/// `tm_isdst` is set to -1 (unknown). libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_calendar_time_to_tm(
+    caltime: *const nx_service_time::TimeCalendarTime,
+    tm: *mut nx_service_time::CTm,
+) -> u32 {
+    if caltime.is_null() || tm.is_null() {
+        return GENERIC_ERROR;
+    }
+
+    unsafe { *tm = nx_service_time::CTm::from(*caltime) };
+    0
+}
+
+/// Converts a C `struct tm` back to a calendar time.
+///
+/// `tm_wday`, `tm_yday` and `tm_isdst` are ignored. Returns 0 on success, or
+/// the out-of-range field (1-based, in `CTmRangeError` order: year, month,
+/// day, hour, minute, second) without writing `caltime`. libnx has no
+/// counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_tm_to_calendar_time(
+    tm: *const nx_service_time::CTm,
+    caltime: *mut nx_service_time::TimeCalendarTime,
+) -> u32 {
+    if tm.is_null() || caltime.is_null() {
+        return GENERIC_ERROR;
+    }
+
+    match nx_service_time::TimeCalendarTime::try_from(unsafe { *tm }) {
+        Ok(cal) => {
+            unsafe { *caltime = cal };
+            0
+        }
+        Err(err) => match err {
+            nx_service_time::CTmRangeError::Year => 1,
+            nx_service_time::CTmRangeError::Month => 2,
+            nx_service_time::CTmRangeError::Day => 3,
+            nx_service_time::CTmRangeError::Hour => 4,
+            nx_service_time::CTmRangeError::Minute => 5,
+            nx_service_time::CTmRangeError::Second => 6,
+        },
+    }
+}
+
+/// C storage for a [`nx_service_time::PeriodicTimer`].
+///
+/// Matches `NxRtPeriodicTimer` in `nx_rt_time.h`.
+#[repr(C, align(8))]
+pub struct PeriodicTimerStorage([u64; 4]);
+
+const _: () = {
+    assert!(size_of::<nx_service_time::PeriodicTimer>() <= size_of::<PeriodicTimerStorage>());
+    assert!(align_of::<nx_service_time::PeriodicTimer>() <= align_of::<PeriodicTimerStorage>());
+};
+
+/// Starts a periodic timer at `start_tick`, a system tick sampled by the
+/// caller, reporting at most `max_steps` periods per poll.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_periodic_timer_init(
+    timer: *mut PeriodicTimerStorage,
+    period_ns: u64,
+    start_tick: u64,
+    max_steps: u32,
+) -> u32 {
+    if timer.is_null() || period_ns == 0 {
+        return GENERIC_ERROR;
+    }
+
+    let value =
+        nx_service_time::PeriodicTimer::new_at(period_ns, start_tick).with_max_steps(max_steps);
+    unsafe { timer.cast::<nx_service_time::PeriodicTimer>().write(value) };
+    0
+}
+
+/// Returns the number of whole periods elapsed since the previous poll, up
+/// to `now_tick`, a system tick sampled by the caller.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__time_periodic_timer_poll(
+    timer: *mut PeriodicTimerStorage,
+    now_tick: u64,
+) -> u32 {
+    if timer.is_null() {
+        return 0;
+    }
+
+    let timer = unsafe { &mut *timer.cast::<nx_service_time::PeriodicTimer>() };
+    timer.poll_at(now_tick)
+}
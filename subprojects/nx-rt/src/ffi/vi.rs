@@ -956,6 +956,128 @@ pub unsafe extern "C" fn __nx_rt__vi_manager_draw_fatal_text32(
     }
 }
 
+/// C-compatible queue buffer input matching libnx `BqQueueBufferInput`.
+#[repr(C)]
+pub struct BqQueueBufferInput {
+    /// Presentation timestamp, in nanoseconds.
+    pub timestamp: i64,
+    /// Whether the timestamp was generated automatically.
+    pub is_auto_timestamp: i32,
+    /// Crop rectangle.
+    pub crop: nx_service_vi::buffer_producer::Rect,
+    /// Scaling mode.
+    pub scaling_mode: i32,
+    /// Transform to apply.
+    pub transform: u32,
+    /// Transform that persists across queued buffers.
+    pub sticky_transform: u32,
+    /// Unused.
+    pub unk: u32,
+    /// Swap interval, in vertical syncs.
+    pub swap_interval: u32,
+    /// Fence the compositor waits on before reading the buffer.
+    pub fence: nx_service_vi::buffer_producer::MultiFence,
+}
+
+/// Encodes the request parcel of a `DEQUEUE_BUFFER` transaction into `out`,
+/// writing the payload size to `out_size`.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__vi_bq_dequeue_buffer_request(
+    is_async: bool,
+    width: u32,
+    height: u32,
+    format: i32,
+    usage: u32,
+    out: *mut c_void,
+    out_capacity: usize,
+    out_size: *mut usize,
+) -> u32 {
+    let Ok(request) = nx_service_vi::buffer_producer::dequeue_buffer_request(
+        is_async, width, height, format, usage,
+    ) else {
+        return GENERIC_ERROR;
+    };
+
+    unsafe { write_parcel_payload(&request, out, out_capacity, out_size) }
+}
+
+/// Encodes the request parcel of a `QUEUE_BUFFER` transaction into `out`,
+/// writing the payload size to `out_size`.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__vi_bq_queue_buffer_request(
+    slot: i32,
+    input: *const BqQueueBufferInput,
+    out: *mut c_void,
+    out_capacity: usize,
+    out_size: *mut usize,
+) -> u32 {
+    if input.is_null() {
+        return GENERIC_ERROR;
+    }
+
+    let input = unsafe { &*input };
+    let input = nx_service_vi::buffer_producer::QueueBufferInput {
+        timestamp: input.timestamp,
+        is_auto_timestamp: input.is_auto_timestamp != 0,
+        crop: input.crop,
+        scaling_mode: input.scaling_mode,
+        transform: input.transform,
+        sticky_transform: input.sticky_transform,
+        swap_interval: input.swap_interval,
+        fence: input.fence,
+    };
+
+    let Ok(request) = nx_service_vi::buffer_producer::queue_buffer_request(slot, &input) else {
+        return GENERIC_ERROR;
+    };
+
+    unsafe { write_parcel_payload(&request, out, out_capacity, out_size) }
+}
+
+/// Parses the reply parcel of a `DEQUEUE_BUFFER` transaction from its
+/// `size`-byte payload.
+///
+/// libnx has no counterpart.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_rt__vi_bq_parse_dequeue_buffer_reply(
+    payload: *const c_void,
+    size: usize,
+    slot: *mut i32,
+    fence: *mut nx_service_vi::buffer_producer::MultiFence,
+    needs_reallocation: *mut bool,
+) -> u32 {
+    if payload.is_null()
+        || slot.is_null()
+        || fence.is_null()
+        || needs_reallocation.is_null()
+        || size > nx_service_vi::parcel::PARCEL_MAX_PAYLOAD
+    {
+        return GENERIC_ERROR;
+    }
+
+    let mut reply = nx_service_vi::parcel::Parcel::new();
+    // SAFETY: The caller guarantees payload points to size readable bytes.
+    let data = unsafe { core::slice::from_raw_parts(payload.cast::<u8>(), size) };
+    reply.payload_mut()[..size].copy_from_slice(data);
+    reply.set_payload_size(size);
+
+    match nx_service_vi::buffer_producer::parse_dequeue_buffer_reply(&mut reply) {
+        Ok(dequeued) => {
+            unsafe {
+                *slot = dequeued.slot;
+                *fence = dequeued.fence;
+                *needs_reallocation = dequeued.needs_reallocation;
+            }
+            0
+        }
+        Err(_) => GENERIC_ERROR,
+    }
+}
+
 /// Sets VI FFI session buffers from the active service.
 fn set_vi_ffi_sessions() {
     let Some(service_ref) = vi_manager::get_service() else {
@@ -1058,50 +1180,32 @@ fn clear_vi_ffi_sessions() {
     }
 }
 
-/// Parses native window data to extract binder object ID.
-fn parse_native_window_binder_id(
-    native_window: &[u8; nx_service_vi::NATIVE_WINDOW_SIZE],
-) -> Option<u32> {
-    // Parcel header structure
-    #[repr(C)]
-    struct ParcelHeader {
-        payload_off: u32,
-        payload_size: u32,
-        objects_off: u32,
-        objects_size: u32,
-    }
-
-    if native_window.len() < core::mem::size_of::<ParcelHeader>() {
-        return None;
-    }
-
-    let header =
-        unsafe { core::ptr::read_unaligned(native_window.as_ptr().cast::<ParcelHeader>()) };
-
-    let payload_off = header.payload_off as usize;
-    let payload_size = header.payload_size as usize;
-
-    if payload_off > native_window.len() {
-        return None;
-    }
-    if payload_off + payload_size > native_window.len() {
-        return None;
-    }
-    if payload_size < 3 * 4 {
-        return None;
+/// Copies the payload of `parcel` to `out`, a C buffer of `out_capacity`
+/// bytes.
+unsafe fn write_parcel_payload(
+    parcel: &nx_service_vi::parcel::Parcel,
+    out: *mut c_void,
+    out_capacity: usize,
+    out_size: *mut usize,
+) -> u32 {
+    let payload = parcel.payload();
+    if out.is_null() || out_size.is_null() || payload.len() > out_capacity {
+        return GENERIC_ERROR;
     }
 
-    // Binder object ID is at offset 2 (third u32) in the payload
-    let binder_id_offset = payload_off + 2 * 4;
-    if binder_id_offset + 4 > native_window.len() {
-        return None;
+    unsafe {
+        core::ptr::copy_nonoverlapping(payload.as_ptr(), out.cast::<u8>(), payload.len());
+        *out_size = payload.len();
     }
+    0
+}
 
-    let binder_id = unsafe {
-        core::ptr::read_unaligned(native_window.as_ptr().add(binder_id_offset).cast::<u32>())
-    };
-
-    Some(binder_id)
+/// Parses native window data to extract binder object ID.
+fn parse_native_window_binder_id(
+    native_window: &[u8; nx_service_vi::NATIVE_WINDOW_SIZE],
+) -> Option<u32> {
+    nx_service_vi::Binder::from_native_window(native_window)
+        .map(|binder| binder.id().to_raw() as u32)
 }
 
 fn vi_connect_error_to_rc(err: vi_manager::ConnectError) -> u32 {
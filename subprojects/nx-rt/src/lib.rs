@@ -18,9 +18,12 @@ pub mod argv;
 pub mod env;
 pub mod hid_manager;
 pub mod init;
+pub mod mem;
 pub mod nv_manager;
+pub mod resource;
 pub mod service_manager;
 pub mod service_registry;
+pub mod sync;
 pub mod thread_registry;
 pub mod time_manager;
 pub mod vi_manager;
@@ -0,0 +1,81 @@
+//! # Physical Memory Mapping
+//!
+//! Checked wrappers around `svcMapPhysicalMemory` and
+//! `svcUnmapPhysicalMemory` (3.0.0+). These SVCs are only usable when the
+//! loader hints them as available, so the wrappers consult the
+//! [syscall hints](crate::env::syscall_hints) before issuing them.
+//!
+//! See [`nx_svc::mem::map_physical_memory`] for how this relates to
+//! `set_heap_size`.
+
+use core::{ffi::c_void, ptr::NonNull};
+
+use nx_svc::{code, mem as svc_mem};
+
+use crate::env;
+
+/// Backs `[addr, addr + size)` of the alias region with new physical memory.
+///
+/// Returns [`MapPhysicalMemoryError::Unsupported`] if the SVC is not hinted
+/// as available. `addr` and `size` must be non-zero multiples of
+/// [`svc_mem::PHYSICAL_MEMORY_ALIGN`].
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+pub fn map_physical_memory(
+    addr: NonNull<c_void>,
+    size: usize,
+) -> Result<(), MapPhysicalMemoryError> {
+    if !env::syscall_hints().is_available(code::MAP_PHYSICAL_MEMORY as u32) {
+        return Err(MapPhysicalMemoryError::Unsupported);
+    }
+
+    svc_mem::map_physical_memory(addr, size).map_err(MapPhysicalMemoryError::Svc)
+}
+
+/// Releases physical memory mapped with [`map_physical_memory`].
+///
+/// Returns [`UnmapPhysicalMemoryError::Unsupported`] if the SVC is not hinted
+/// as available.
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+///
+/// # Safety
+///
+/// The range must not be in use anymore; its contents are discarded.
+pub unsafe fn unmap_physical_memory(
+    addr: NonNull<c_void>,
+    size: usize,
+) -> Result<(), UnmapPhysicalMemoryError> {
+    if !env::syscall_hints().is_available(code::UNMAP_PHYSICAL_MEMORY as u32) {
+        return Err(UnmapPhysicalMemoryError::Unsupported);
+    }
+
+    // SAFETY: The caller guarantees the range is no longer in use.
+    unsafe { svc_mem::unmap_physical_memory(addr, size) }.map_err(UnmapPhysicalMemoryError::Svc)
+}
+
+/// Error returned by [`map_physical_memory`].
+#[derive(Debug, thiserror::Error)]
+pub enum MapPhysicalMemoryError {
+    /// The SVC is not available to this process.
+    #[error("svcMapPhysicalMemory is not available")]
+    Unsupported,
+    /// The SVC failed.
+    #[error("failed to map physical memory")]
+    Svc(#[source] svc_mem::MapPhysicalMemoryError),
+}
+
+/// Error returned by [`unmap_physical_memory`].
+#[derive(Debug, thiserror::Error)]
+pub enum UnmapPhysicalMemoryError {
+    /// The SVC is not available to this process.
+    #[error("svcUnmapPhysicalMemory is not available")]
+    Unsupported,
+    /// The SVC failed.
+    #[error("failed to unmap physical memory")]
+    Svc(#[source] svc_mem::UnmapPhysicalMemoryError),
+}
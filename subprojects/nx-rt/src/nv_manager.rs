@@ -3,10 +3,17 @@
 //! This module manages the NV service session and provides a singleton interface
 //! for accessing NVIDIA driver functionality throughout the application lifecycle.
 
-use nx_service_nv::{NvConfig, NvService, NvServiceType};
+use nx_service_nv::{NV_DEFAULT_TRANSFER_MEM_SIZE, NvConfig, NvService, NvServiceType};
 use nx_std_sync::{once_lock::OnceLock, rwlock::RwLock};
 
-use crate::{applet_manager, env, service_manager};
+use crate::{
+    applet_manager,
+    env::{
+        self,
+        hos_version::{self, HosVersion},
+    },
+    service_manager,
+};
 
 /// Global NV state, lazily initialized.
 static NV_STATE: OnceLock<RwLock<Option<NvState>>> = OnceLock::new();
@@ -93,6 +100,15 @@ pub fn is_initialized() -> bool {
     state().read().is_some()
 }
 
+/// Returns whether the NV ioctl2 and ioctl3 commands are available.
+///
+/// They were added in HOS 3.0.0. Pass this to
+/// [`NvGpuChannel::open`](nx_service_nv::NvGpuChannel::open).
+#[inline]
+pub fn ioctl2_supported() -> bool {
+    hos_version::get() >= HosVersion::new(3, 0, 0)
+}
+
 /// Internal storage for NV service.
 struct NvState {
     /// NV service session
@@ -137,7 +153,7 @@ impl Default for NvConfigState {
     fn default() -> Self {
         Self {
             service_type: NvServiceType::Auto,
-            transfer_mem_size: 0x80_0000, // 8 MB
+            transfer_mem_size: NV_DEFAULT_TRANSFER_MEM_SIZE,
         }
     }
 }
@@ -183,5 +199,6 @@ pub fn make_config() -> NvConfig {
     NvConfig {
         service_type: get_service_type(),
         transfer_mem_size: get_transfer_mem_size(),
+        ..NvConfig::default()
     }
 }
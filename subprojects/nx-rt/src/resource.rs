@@ -0,0 +1,43 @@
+//! # Resource Limits
+//!
+//! Checked wrapper around `svcGetResourceLimitPeakValue` (11.0.0+). The SVC
+//! does not exist on older firmware, so the wrapper consults the
+//! [syscall hints](crate::env::syscall_hints) before issuing it.
+//!
+//! The limit and current values are always available, see
+//! [`nx_svc::resource::ResourceLimit`].
+
+use nx_svc::{
+    code,
+    raw::LimitableResource,
+    resource::{ResourceLimit, ResourceLimitValueError},
+};
+
+use crate::env;
+
+/// Returns the highest amount of `which` that was in use at once.
+///
+/// Returns [`PeakError::Unsupported`] if the SVC is not hinted as available,
+/// which is always the case before 11.0.0.
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+pub fn peak(limit: &ResourceLimit, which: LimitableResource) -> Result<i64, PeakError> {
+    if !env::syscall_hints().is_available(code::GET_RESOURCE_LIMIT_PEAK_VALUE as u32) {
+        return Err(PeakError::Unsupported);
+    }
+
+    limit.peak(which).map_err(PeakError::Svc)
+}
+
+/// Error returned by [`peak`].
+#[derive(Debug, thiserror::Error)]
+pub enum PeakError {
+    /// The SVC is not available to this process.
+    #[error("svcGetResourceLimitPeakValue is not available")]
+    Unsupported,
+    /// The SVC failed.
+    #[error("failed to get resource limit peak value")]
+    Svc(#[source] ResourceLimitValueError),
+}
@@ -0,0 +1,110 @@
+//! # Address Arbitration
+//!
+//! Checked wrappers around `svcWaitForAddress` and `svcSignalToAddress`
+//! (4.0.0+), the futex-like building blocks for custom synchronization
+//! primitives. These SVCs do not exist on older firmware, so the wrappers
+//! consult the [syscall hints](crate::env::syscall_hints) before issuing them.
+//!
+//! The 64-bit [`wait_for_address_64`] additionally requires 19.0.0+.
+//!
+//! See [`nx_svc::sync::wait_for_address`] for the arbitration semantics.
+
+use core::sync::atomic::{AtomicU32, AtomicU64};
+
+use nx_svc::{
+    code,
+    raw::{ArbitrationType, SignalType},
+    sync as svc_sync,
+};
+
+use crate::env::{
+    self,
+    hos_version::{self, HosVersion},
+};
+
+/// Waits on `addr` as described by `arb_type` and `value`.
+///
+/// Returns [`WaitForAddressError::Unsupported`] if the SVC is not hinted as
+/// available. `timeout_ns` is negative for an infinite wait.
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+pub fn wait_for_address(
+    addr: &AtomicU32,
+    arb_type: ArbitrationType,
+    value: i64,
+    timeout_ns: i64,
+) -> Result<(), WaitForAddressError> {
+    if !env::syscall_hints().is_available(code::WAIT_FOR_ADDRESS as u32) {
+        return Err(WaitForAddressError::Unsupported);
+    }
+
+    svc_sync::wait_for_address(addr, arb_type, value, timeout_ns).map_err(WaitForAddressError::Svc)
+}
+
+/// Waits on the 64-bit `addr` while it equals `value`.
+///
+/// Returns [`WaitForAddressError::Unsupported`] if the SVC is not hinted as
+/// available, or before 19.0.0.
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+pub fn wait_for_address_64(
+    addr: &AtomicU64,
+    value: i64,
+    timeout_ns: i64,
+) -> Result<(), WaitForAddressError> {
+    if !env::syscall_hints().is_available(code::WAIT_FOR_ADDRESS as u32)
+        || hos_version::get() < HosVersion::new(19, 0, 0)
+    {
+        return Err(WaitForAddressError::Unsupported);
+    }
+
+    svc_sync::wait_for_address_64(addr, value, timeout_ns).map_err(WaitForAddressError::Svc)
+}
+
+/// Wakes up to `count` threads waiting on `addr` (all of them if `count` is
+/// less than or equal to 0).
+///
+/// Returns [`SignalToAddressError::Unsupported`] if the SVC is not hinted as
+/// available.
+///
+/// # Panics
+///
+/// Panics if called before the environment is initialized.
+pub fn signal_to_address(
+    addr: &AtomicU32,
+    signal_type: SignalType,
+    value: i32,
+    count: i32,
+) -> Result<(), SignalToAddressError> {
+    if !env::syscall_hints().is_available(code::SIGNAL_TO_ADDRESS as u32) {
+        return Err(SignalToAddressError::Unsupported);
+    }
+
+    svc_sync::signal_to_address(addr, signal_type, value, count).map_err(SignalToAddressError::Svc)
+}
+
+/// Error returned by [`wait_for_address`] and [`wait_for_address_64`].
+#[derive(Debug, thiserror::Error)]
+pub enum WaitForAddressError {
+    /// The SVC is not available to this process.
+    #[error("svcWaitForAddress is not available")]
+    Unsupported,
+    /// The SVC failed.
+    #[error("failed to wait for address")]
+    Svc(#[source] svc_sync::WaitForAddressError),
+}
+
+/// Error returned by [`signal_to_address`].
+#[derive(Debug, thiserror::Error)]
+pub enum SignalToAddressError {
+    /// The SVC is not available to this process.
+    #[error("svcSignalToAddress is not available")]
+    Unsupported,
+    /// The SVC failed.
+    #[error("failed to signal to address")]
+    Svc(#[source] svc_sync::SignalToAddressError),
+}
@@ -48,6 +48,21 @@ pub fn get_service() -> Option<impl core::ops::Deref<Target = TimeService> + 'st
     }
 }
 
+/// Stops the Time service from using shared memory, forcing every read
+/// through IPC until the service is reinitialized.
+///
+/// Returns `false` if the Time service is not initialized.
+pub fn disable_shared_memory() -> bool {
+    let mut guard = state().write();
+    match guard.as_mut() {
+        Some(time_state) => {
+            time_state.service.disable_shared_memory();
+            true
+        }
+        None => false,
+    }
+}
+
 /// Exits the Time service.
 pub fn exit() {
     let mut guard = state().write();
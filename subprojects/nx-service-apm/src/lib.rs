@@ -54,12 +54,7 @@ impl ApmService {
     #[inline]
     pub fn open_session(&self) -> Result<ApmSession, OpenSessionError> {
         let session_handle = cmif::open_session(self.0.session)?;
-        let service = Service {
-            session: session_handle,
-            own_handle: 1,
-            object_id: 0,
-            pointer_buffer_size: 0,
-        };
+        let service = Service::new_subservice(&self.0, session_handle);
         Ok(ApmSession(service))
     }
 
@@ -139,12 +134,7 @@ pub fn connect(sm: &SmService) -> Result<ApmService, ConnectError> {
         .get_service_handle_cmif(SERVICE_NAME)
         .map_err(ConnectError::GetService)?;
 
-    let service = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let service = Service::new(handle);
 
     Ok(ApmService(service))
 }
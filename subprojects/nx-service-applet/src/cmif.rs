@@ -4,22 +4,38 @@
 
 use core::mem::size_of;
 
-use nx_sf::service::{BufferAttr, DispatchError, Service, ServiceConvertToDomainError};
-use nx_svc::process::Handle as ProcessHandle;
+use nx_sf::{
+    cmif::ParseResponseError,
+    service::{BufferAttr, DispatchError, OutHandleAttr, Service, ServiceConvertToDomainError},
+};
+use nx_svc::{mem::tmem::Handle as TmemHandle, process::Handle as ProcessHandle};
 
 use crate::{
-    AppletProxyService, ApplicationFunctions, CommonStateGetter, SelfController, WindowController,
+    AppletProxyService, ApplicationFunctions, AudioController, CommonStateGetter,
+    DisplayController, LibraryAppletCreator, SelfController, Storage, WindowController,
     aruid::Aruid,
     proto::{
-        AppletAttribute, AppletFocusHandlingMode, AppletType, CMD_AF_NOTIFY_RUNNING,
-        CMD_GET_APPLICATION_FUNCTIONS, CMD_GET_COMMON_STATE_GETTER, CMD_GET_SELF_CONTROLLER,
-        CMD_GET_WINDOW_CONTROLLER, CMD_OPEN_APPLICATION_PROXY, CMD_OPEN_LIBRARY_APPLET_PROXY,
-        CMD_OPEN_LIBRARY_APPLET_PROXY_OLD, CMD_OPEN_OVERLAY_APPLET_PROXY,
-        CMD_OPEN_SYSTEM_APPLET_PROXY, CMD_OPEN_SYSTEM_APPLICATION_PROXY,
-        CMD_SC_CREATE_MANAGED_DISPLAY_LAYER, CMD_SC_SET_FOCUS_HANDLING_MODE,
-        CMD_SC_SET_OPERATION_MODE_CHANGED_NOTIFICATION, CMD_SC_SET_OUT_OF_FOCUS_SUSPENDING_ENABLED,
-        CMD_SC_SET_PERFORMANCE_MODE_CHANGED_NOTIFICATION, CMD_WC_ACQUIRE_FOREGROUND_RIGHTS,
-        CMD_WC_GET_APPLET_RESOURCE_USER_ID,
+        AppletAttribute, AppletFocusHandlingMode, AppletType, CAPTURE_IMAGE_SIZE,
+        CMD_AC_GET_MAIN_APPLET_EXPECTED_MASTER_VOLUME, CMD_AC_SET_EXPECTED_MASTER_VOLUME,
+        CMD_AF_NOTIFY_RUNNING, CMD_AF_POP_LAUNCH_PARAMETER, CMD_AF_SET_TERMINATE_RESULT,
+        CMD_DC_ACQUIRE_LAST_APPLICATION_CAPTURE_BUFFER,
+        CMD_DC_GET_LAST_FOREGROUND_CAPTURE_IMAGE_EX,
+        CMD_DC_RELEASE_LAST_APPLICATION_CAPTURE_BUFFER, CMD_GET_APPLICATION_FUNCTIONS,
+        CMD_GET_AUDIO_CONTROLLER, CMD_GET_COMMON_STATE_GETTER, CMD_GET_DISPLAY_CONTROLLER,
+        CMD_GET_LIBRARY_APPLET_CREATOR, CMD_GET_SELF_CONTROLLER, CMD_GET_WINDOW_CONTROLLER,
+        CMD_LAC_CREATE_STORAGE, CMD_LAC_TERMINATE_ALL_LIBRARY_APPLETS, CMD_OPEN_APPLICATION_PROXY,
+        CMD_OPEN_LIBRARY_APPLET_PROXY, CMD_OPEN_LIBRARY_APPLET_PROXY_OLD,
+        CMD_OPEN_OVERLAY_APPLET_PROXY, CMD_OPEN_SYSTEM_APPLET_PROXY,
+        CMD_OPEN_SYSTEM_APPLICATION_PROXY, CMD_SC_APPROVE_TO_DISPLAY,
+        CMD_SC_CREATE_MANAGED_DISPLAY_LAYER, CMD_SC_EXIT, CMD_SC_SET_AUTO_SLEEP_DISABLED,
+        CMD_SC_SET_FOCUS_HANDLING_MODE, CMD_SC_SET_HANDLES_REQUEST_TO_DISPLAY,
+        CMD_SC_SET_IDLE_TIME_DETECTION_EXTENSION, CMD_SC_SET_OPERATION_MODE_CHANGED_NOTIFICATION,
+        CMD_SC_SET_OUT_OF_FOCUS_SUSPENDING_ENABLED,
+        CMD_SC_SET_PERFORMANCE_MODE_CHANGED_NOTIFICATION, CMD_STORAGE_ACCESSOR_GET_SIZE,
+        CMD_STORAGE_ACCESSOR_READ, CMD_STORAGE_ACCESSOR_WRITE, CMD_STORAGE_OPEN,
+        CMD_WC_ACQUIRE_FOREGROUND_RIGHTS, CMD_WC_GET_APPLET_RESOURCE_USER_ID,
+        CMD_WC_RELEASE_FOREGROUND_RIGHTS, IdleTimeExtension, LaunchParameterKind,
+        RESULT_NO_DATA_IN_CHANNEL, RESULT_UNKNOWN_COMMAND_ID,
     },
 };
 
@@ -244,6 +260,26 @@ pub enum AcquireForegroundRightsError {
     Dispatch(#[source] DispatchError),
 }
 
+/// Releases foreground rights via IWindowController (cmd 11).
+pub fn release_foreground_rights(
+    window_controller: &Service,
+) -> Result<(), ReleaseForegroundRightsError> {
+    window_controller
+        .dispatch(CMD_WC_RELEASE_FOREGROUND_RIGHTS)
+        .send()
+        .map_err(ReleaseForegroundRightsError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`release_foreground_rights`].
+#[derive(Debug, thiserror::Error)]
+pub enum ReleaseForegroundRightsError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
 /// Sets the focus handling mode on ISelfController.
 ///
 /// This translates the high-level mode into the three boolean parameters
@@ -422,6 +458,96 @@ pub enum GetAppletResourceUserIdError {
     InvalidResponse,
 }
 
+/// Gets the ILibraryAppletCreator sub-interface from the proxy.
+pub fn get_library_applet_creator(
+    proxy: &Service,
+) -> Result<LibraryAppletCreator, GetLibraryAppletCreatorError> {
+    let result = proxy
+        .dispatch(CMD_GET_LIBRARY_APPLET_CREATOR)
+        .out_objects(1)
+        .send()
+        .map_err(GetLibraryAppletCreatorError::Dispatch)?;
+
+    if result.objects.is_empty() {
+        return Err(GetLibraryAppletCreatorError::MissingObject);
+    }
+
+    let object_id = result.objects[0];
+
+    // Create sub-interface as domain subservice
+    let service = Service {
+        session: proxy.session,
+        own_handle: 0,
+        object_id,
+        pointer_buffer_size: proxy.pointer_buffer_size,
+    };
+
+    Ok(LibraryAppletCreator(service))
+}
+
+/// Error returned by [`get_library_applet_creator`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetLibraryAppletCreatorError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
+/// Terminates all library applets created by this creator (ILibraryAppletCreator, cmd 1).
+pub fn terminate_all_library_applets(
+    creator: &Service,
+) -> Result<(), TerminateAllLibraryAppletsError> {
+    creator
+        .dispatch(CMD_LAC_TERMINATE_ALL_LIBRARY_APPLETS)
+        .send()
+        .map_err(TerminateAllLibraryAppletsError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`terminate_all_library_applets`].
+#[derive(Debug, thiserror::Error)]
+pub enum TerminateAllLibraryAppletsError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Creates a storage of `size` bytes (ILibraryAppletCreator, cmd 10).
+pub fn create_storage(creator: &Service, size: i64) -> Result<Storage, CreateStorageError> {
+    let dispatch = creator.dispatch(CMD_LAC_CREATE_STORAGE).out_objects(1);
+
+    // SAFETY: size is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw((&size as *const i64).cast::<u8>(), size_of::<i64>()) };
+
+    let result = dispatch.send().map_err(CreateStorageError::Dispatch)?;
+
+    if result.objects.is_empty() {
+        return Err(CreateStorageError::MissingObject);
+    }
+
+    Ok(Storage(Service {
+        session: creator.session,
+        own_handle: 0,
+        object_id: result.objects[0],
+        pointer_buffer_size: creator.pointer_buffer_size,
+    }))
+}
+
+/// Error returned by [`create_storage`].
+#[derive(Debug, thiserror::Error)]
+pub enum CreateStorageError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
 /// Gets the IApplicationFunctions sub-interface from the proxy (Application type only).
 pub fn get_application_functions(
     proxy: &Service,
@@ -460,6 +586,29 @@ pub enum GetApplicationFunctionsError {
     MissingObject,
 }
 
+/// Sets the result reported when the application exits (IApplicationFunctions, cmd 22).
+pub fn set_terminate_result(
+    app_funcs: &Service,
+    result: u32,
+) -> Result<(), SetTerminateResultError> {
+    let dispatch = app_funcs.dispatch(CMD_AF_SET_TERMINATE_RESULT);
+
+    // SAFETY: result is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw((&raw const result).cast::<u8>(), size_of::<u32>()) };
+
+    dispatch.send().map_err(SetTerminateResultError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`set_terminate_result`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetTerminateResultError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
 /// Notifies the system that the application has completed initialization (IApplicationFunctions).
 ///
 /// This should be called after waiting for InFocus state, acquiring foreground rights,
@@ -491,6 +640,196 @@ pub enum NotifyRunningError {
     InvalidResponse,
 }
 
+/// Pops the next launch parameter of the given kind (IApplicationFunctions, cmd 1).
+///
+/// Returns `Ok(None)` if no parameter of that kind is available.
+pub fn pop_launch_parameter(
+    app_funcs: &Service,
+    kind: LaunchParameterKind,
+) -> Result<Option<Storage>, PopLaunchParameterError> {
+    let input = kind as u32;
+
+    let dispatch = app_funcs
+        .dispatch(CMD_AF_POP_LAUNCH_PARAMETER)
+        .out_objects(1);
+
+    // SAFETY: input is valid and lives until send() completes.
+    let dispatch =
+        unsafe { dispatch.in_raw((&input as *const u32).cast::<u8>(), size_of::<u32>()) };
+
+    let result = match dispatch.send() {
+        Ok(result) => result,
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_NO_DATA_IN_CHANNEL,
+        ))) => return Ok(None),
+        Err(err) => return Err(PopLaunchParameterError::Dispatch(err)),
+    };
+
+    if result.objects.is_empty() {
+        return Err(PopLaunchParameterError::MissingObject);
+    }
+
+    let service = Service {
+        session: app_funcs.session,
+        own_handle: 0,
+        object_id: result.objects[0],
+        pointer_buffer_size: app_funcs.pointer_buffer_size,
+    };
+
+    Ok(Some(Storage(service)))
+}
+
+/// Error returned by [`pop_launch_parameter`].
+#[derive(Debug, thiserror::Error)]
+pub enum PopLaunchParameterError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
+/// Opens an IStorageAccessor for the storage contents (IStorage, cmd 0).
+fn open_storage_accessor(storage: &Service) -> Result<Service, OpenStorageAccessorError> {
+    let result = storage
+        .dispatch(CMD_STORAGE_OPEN)
+        .out_objects(1)
+        .send()
+        .map_err(OpenStorageAccessorError::Dispatch)?;
+
+    if result.objects.is_empty() {
+        return Err(OpenStorageAccessorError::MissingObject);
+    }
+
+    Ok(Service {
+        session: storage.session,
+        own_handle: 0,
+        object_id: result.objects[0],
+        pointer_buffer_size: storage.pointer_buffer_size,
+    })
+}
+
+/// Error returned when opening an IStorageAccessor.
+#[derive(Debug, thiserror::Error)]
+pub enum OpenStorageAccessorError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
+/// Gets the size of the storage contents in bytes (IStorageAccessor, cmd 0).
+pub fn storage_get_size(storage: &Service) -> Result<u64, StorageGetSizeError> {
+    let accessor = open_storage_accessor(storage).map_err(StorageGetSizeError::Open)?;
+
+    let result = accessor
+        .dispatch(CMD_STORAGE_ACCESSOR_GET_SIZE)
+        .out_size(size_of::<u64>())
+        .send()
+        .map_err(StorageGetSizeError::Dispatch);
+
+    let size = result.and_then(|result| {
+        if result.data.len() < size_of::<u64>() {
+            return Err(StorageGetSizeError::InvalidResponse);
+        }
+
+        // SAFETY: Response data contains the i64 size.
+        Ok(unsafe { core::ptr::read_unaligned(result.data.as_ptr().cast::<u64>()) })
+    });
+
+    accessor.close();
+    size
+}
+
+/// Error returned by [`storage_get_size`].
+#[derive(Debug, thiserror::Error)]
+pub enum StorageGetSizeError {
+    /// Failed to open the storage accessor.
+    #[error("failed to open storage accessor")]
+    Open(#[source] OpenStorageAccessorError),
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response data was invalid.
+    #[error("invalid response data")]
+    InvalidResponse,
+}
+
+/// Reads storage contents starting at `offset` into `buf` (IStorageAccessor, cmd 11).
+pub fn storage_read(
+    storage: &Service,
+    offset: u64,
+    buf: &mut [u8],
+) -> Result<(), StorageReadError> {
+    let accessor = open_storage_accessor(storage).map_err(StorageReadError::Open)?;
+
+    let dispatch = accessor.dispatch(CMD_STORAGE_ACCESSOR_READ).buffer(
+        buf.as_mut_ptr(),
+        buf.len(),
+        BufferAttr::OUT.or(BufferAttr::HIPC_AUTO_SELECT),
+    );
+
+    // SAFETY: offset is valid and lives until send() completes.
+    let dispatch =
+        unsafe { dispatch.in_raw((&offset as *const u64).cast::<u8>(), size_of::<u64>()) };
+
+    let result = dispatch
+        .send()
+        .map(|_| ())
+        .map_err(StorageReadError::Dispatch);
+
+    accessor.close();
+    result
+}
+
+/// Error returned by [`storage_read`].
+#[derive(Debug, thiserror::Error)]
+pub enum StorageReadError {
+    /// Failed to open the storage accessor.
+    #[error("failed to open storage accessor")]
+    Open(#[source] OpenStorageAccessorError),
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Writes `data` into the storage starting at `offset` (IStorageAccessor, cmd 10).
+pub fn storage_write(storage: &Service, offset: u64, data: &[u8]) -> Result<(), StorageWriteError> {
+    let accessor = open_storage_accessor(storage).map_err(StorageWriteError::Open)?;
+
+    let dispatch = accessor.dispatch(CMD_STORAGE_ACCESSOR_WRITE).buffer(
+        data.as_ptr(),
+        data.len(),
+        BufferAttr::IN.or(BufferAttr::HIPC_AUTO_SELECT),
+    );
+
+    // SAFETY: offset is valid and lives until send() completes.
+    let dispatch =
+        unsafe { dispatch.in_raw((&offset as *const u64).cast::<u8>(), size_of::<u64>()) };
+
+    let result = dispatch
+        .send()
+        .map(|_| ())
+        .map_err(StorageWriteError::Dispatch);
+
+    accessor.close();
+    result
+}
+
+/// Error returned by [`storage_write`].
+#[derive(Debug, thiserror::Error)]
+pub enum StorageWriteError {
+    /// Failed to open the storage accessor.
+    #[error("failed to open storage accessor")]
+    Open(#[source] OpenStorageAccessorError),
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
 /// Creates a managed display layer (ISelfController, cmd 40).
 pub fn create_managed_display_layer(
     self_controller: &Service,
@@ -521,3 +860,461 @@ pub enum CreateManagedDisplayLayerError {
     #[error("invalid response data")]
     InvalidResponse,
 }
+
+/// Exits the applet (ISelfController, cmd 0).
+pub fn exit(self_controller: &Service) -> Result<(), ExitError> {
+    self_controller
+        .dispatch(CMD_SC_EXIT)
+        .send()
+        .map_err(ExitError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`exit`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExitError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Restores the `NoSuspend` focus handling mode and exits the applet.
+///
+/// If `terminate_result` is set, it is first reported with
+/// [`set_terminate_result`] through a temporary IApplicationFunctions, so
+/// this requires an application proxy. Opens a temporary ISelfController from
+/// the proxy and closes it before returning.
+pub fn exit_gracefully(
+    proxy: &Service,
+    terminate_result: Option<u32>,
+) -> Result<(), ExitGracefullyError> {
+    if let Some(terminate_result) = terminate_result {
+        let app_funcs = get_application_functions(proxy)
+            .map_err(ExitGracefullyError::GetApplicationFunctions)?;
+        let result = set_terminate_result(&app_funcs.0, terminate_result)
+            .map_err(ExitGracefullyError::SetTerminateResult);
+        app_funcs.close();
+        result?;
+    }
+
+    let self_controller =
+        get_self_controller(proxy).map_err(ExitGracefullyError::GetSelfController)?;
+
+    let result = set_focus_handling_mode(&self_controller.0, AppletFocusHandlingMode::NoSuspend)
+        .map_err(ExitGracefullyError::SetFocusHandlingMode)
+        .and_then(|()| exit(&self_controller.0).map_err(ExitGracefullyError::Exit));
+
+    self_controller.close();
+
+    result
+}
+
+/// Error returned by [`exit_gracefully`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExitGracefullyError {
+    /// Failed to get the IApplicationFunctions for the terminate result.
+    #[error("failed to get application functions")]
+    GetApplicationFunctions(#[source] GetApplicationFunctionsError),
+    /// Failed to set the terminate result.
+    #[error("failed to set terminate result")]
+    SetTerminateResult(#[source] SetTerminateResultError),
+    /// Failed to get the ISelfController.
+    #[error("failed to get self controller")]
+    GetSelfController(#[source] GetSelfControllerError),
+    /// Failed to restore the focus handling mode.
+    #[error("failed to set focus handling mode")]
+    SetFocusHandlingMode(#[source] SetFocusHandlingModeError),
+    /// Failed to exit.
+    #[error("failed to exit")]
+    Exit(#[source] ExitError),
+}
+
+/// Sets whether the applet handles display requests itself (ISelfController,
+/// cmd 50).
+pub fn set_handles_request_to_display(
+    self_controller: &Service,
+    enabled: bool,
+) -> Result<(), SetHandlesRequestToDisplayError> {
+    let input: u8 = enabled as u8;
+
+    let dispatch = self_controller.dispatch(CMD_SC_SET_HANDLES_REQUEST_TO_DISPLAY);
+
+    // SAFETY: input is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw((&raw const input).cast::<u8>(), size_of::<u8>()) };
+
+    match dispatch.send() {
+        Ok(_) => Ok(()),
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => Err(SetHandlesRequestToDisplayError::Unsupported),
+        Err(err) => Err(SetHandlesRequestToDisplayError::Dispatch(err)),
+    }
+}
+
+/// Error returned by [`set_handles_request_to_display`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetHandlesRequestToDisplayError {
+    /// The firmware's self controller does not implement the command.
+    #[error("handling display requests not supported")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Approves a pending display request (ISelfController, cmd 51).
+///
+/// Response to an `AppletMessage::RequestToDisplay` message.
+pub fn approve_to_display(self_controller: &Service) -> Result<(), ApproveToDisplayError> {
+    self_controller
+        .dispatch(CMD_SC_APPROVE_TO_DISPLAY)
+        .send()
+        .map_err(ApproveToDisplayError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`approve_to_display`].
+#[derive(Debug, thiserror::Error)]
+pub enum ApproveToDisplayError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Sets the idle time detection extension (ISelfController, cmd 62).
+pub fn set_idle_time_detection_extension(
+    self_controller: &Service,
+    ext: IdleTimeExtension,
+) -> Result<(), SetIdleTimeDetectionExtensionError> {
+    let input: u32 = ext as u32;
+
+    let dispatch = self_controller.dispatch(CMD_SC_SET_IDLE_TIME_DETECTION_EXTENSION);
+
+    // SAFETY: input is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw((&raw const input).cast::<u8>(), size_of::<u32>()) };
+
+    dispatch
+        .send()
+        .map_err(SetIdleTimeDetectionExtensionError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`set_idle_time_detection_extension`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetIdleTimeDetectionExtensionError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Sets whether auto-sleep is disabled (ISelfController, cmd 68, 2.0.0+).
+pub fn set_auto_sleep_disabled(
+    self_controller: &Service,
+    disabled: bool,
+) -> Result<(), SetAutoSleepDisabledError> {
+    let input: u8 = disabled as u8;
+
+    let dispatch = self_controller.dispatch(CMD_SC_SET_AUTO_SLEEP_DISABLED);
+
+    // SAFETY: input is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw((&raw const input).cast::<u8>(), size_of::<u8>()) };
+
+    dispatch
+        .send()
+        .map_err(SetAutoSleepDisabledError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`set_auto_sleep_disabled`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetAutoSleepDisabledError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Gets the IAudioController sub-interface from the proxy.
+pub fn get_audio_controller(proxy: &Service) -> Result<AudioController, GetAudioControllerError> {
+    let result = match proxy
+        .dispatch(CMD_GET_AUDIO_CONTROLLER)
+        .out_objects(1)
+        .send()
+    {
+        Ok(result) => result,
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => return Err(GetAudioControllerError::Unsupported),
+        Err(err) => return Err(GetAudioControllerError::Dispatch(err)),
+    };
+
+    if result.objects.is_empty() {
+        return Err(GetAudioControllerError::MissingObject);
+    }
+
+    let object_id = result.objects[0];
+
+    // Create sub-interface as domain subservice
+    let service = Service {
+        session: proxy.session,
+        own_handle: 0,
+        object_id,
+        pointer_buffer_size: proxy.pointer_buffer_size,
+    };
+
+    Ok(AudioController(service))
+}
+
+/// Error returned by [`get_audio_controller`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetAudioControllerError {
+    /// The proxy for this applet type does not provide an IAudioController.
+    #[error("audio controller not supported by this applet proxy")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
+/// Sets the expected master volumes (IAudioController, cmd 0).
+///
+/// Both volumes must be in `0.0..=1.0`.
+pub fn set_expected_master_volume(
+    audio_controller: &Service,
+    main_applet_volume: f32,
+    library_applet_volume: f32,
+) -> Result<(), SetExpectedMasterVolumeError> {
+    for volume in [main_applet_volume, library_applet_volume] {
+        if !(0.0..=1.0).contains(&volume) {
+            return Err(SetExpectedMasterVolumeError::InvalidVolume(volume));
+        }
+    }
+
+    let input: [f32; 2] = [main_applet_volume, library_applet_volume];
+
+    let dispatch = audio_controller.dispatch(CMD_AC_SET_EXPECTED_MASTER_VOLUME);
+
+    // SAFETY: input is valid and lives until send() completes.
+    let dispatch = unsafe { dispatch.in_raw(input.as_ptr().cast::<u8>(), size_of_val(&input)) };
+
+    match dispatch.send() {
+        Ok(_) => Ok(()),
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => Err(SetExpectedMasterVolumeError::Unsupported),
+        Err(err) => Err(SetExpectedMasterVolumeError::Dispatch(err)),
+    }
+}
+
+/// Error returned by [`set_expected_master_volume`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetExpectedMasterVolumeError {
+    /// A volume was outside `0.0..=1.0`.
+    #[error("volume out of range: {0}")]
+    InvalidVolume(f32),
+    /// The audio controller does not implement the command.
+    #[error("expected master volume not supported")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
+
+/// Gets the main applet's expected master volume (IAudioController, cmd 1).
+pub fn get_main_applet_expected_master_volume(
+    audio_controller: &Service,
+) -> Result<f32, GetMainAppletExpectedMasterVolumeError> {
+    let result = match audio_controller
+        .dispatch(CMD_AC_GET_MAIN_APPLET_EXPECTED_MASTER_VOLUME)
+        .out_size(size_of::<f32>())
+        .send()
+    {
+        Ok(result) => result,
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => return Err(GetMainAppletExpectedMasterVolumeError::Unsupported),
+        Err(err) => return Err(GetMainAppletExpectedMasterVolumeError::Dispatch(err)),
+    };
+
+    if result.data.len() < size_of::<f32>() {
+        return Err(GetMainAppletExpectedMasterVolumeError::InvalidResponse);
+    }
+
+    // SAFETY: Response data contains the f32 volume.
+    let volume = unsafe { core::ptr::read_unaligned(result.data.as_ptr().cast::<f32>()) };
+
+    Ok(volume)
+}
+
+/// Error returned by [`get_main_applet_expected_master_volume`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetMainAppletExpectedMasterVolumeError {
+    /// The audio controller does not implement the command.
+    #[error("expected master volume not supported")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response data was invalid.
+    #[error("invalid response data")]
+    InvalidResponse,
+}
+
+/// Gets the IDisplayController sub-interface from the proxy (cmd 4).
+pub fn get_display_controller(
+    proxy: &Service,
+) -> Result<DisplayController, GetDisplayControllerError> {
+    let result = proxy
+        .dispatch(CMD_GET_DISPLAY_CONTROLLER)
+        .out_objects(1)
+        .send()
+        .map_err(GetDisplayControllerError::Dispatch)?;
+
+    if result.objects.is_empty() {
+        return Err(GetDisplayControllerError::MissingObject);
+    }
+
+    let object_id = result.objects[0];
+
+    // Create sub-interface as domain subservice
+    let service = Service {
+        session: proxy.session,
+        own_handle: 0,
+        object_id,
+        pointer_buffer_size: proxy.pointer_buffer_size,
+    };
+
+    Ok(DisplayController(service))
+}
+
+/// Error returned by [`get_display_controller`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetDisplayControllerError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected domain object.
+    #[error("missing domain object in response")]
+    MissingObject,
+}
+
+/// Copies the last foreground capture image into `buf` (IDisplayController, cmd 5, 2.0.0+).
+///
+/// `buf` must be exactly [`CAPTURE_IMAGE_SIZE`] bytes. Returns the flag
+/// output by the service.
+pub fn get_last_foreground_capture_image_ex(
+    display_controller: &Service,
+    buf: &mut [u8],
+) -> Result<bool, GetCaptureImageError> {
+    if buf.len() != CAPTURE_IMAGE_SIZE {
+        return Err(GetCaptureImageError::InvalidBufferSize(buf.len()));
+    }
+
+    let result = display_controller
+        .dispatch(CMD_DC_GET_LAST_FOREGROUND_CAPTURE_IMAGE_EX)
+        .buffer(
+            buf.as_mut_ptr(),
+            buf.len(),
+            BufferAttr::OUT.or(BufferAttr::HIPC_MAP_ALIAS),
+        )
+        .out_size(size_of::<u8>())
+        .send();
+
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => return Err(GetCaptureImageError::Unsupported),
+        Err(err) => return Err(GetCaptureImageError::Dispatch(err)),
+    };
+
+    let Some(&flag) = resp.data.first() else {
+        return Err(GetCaptureImageError::InvalidResponse);
+    };
+
+    Ok(flag != 0)
+}
+
+/// Error returned by [`get_last_foreground_capture_image_ex`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetCaptureImageError {
+    /// The buffer is not [`CAPTURE_IMAGE_SIZE`] bytes.
+    #[error("invalid capture buffer size: {0:#x}")]
+    InvalidBufferSize(usize),
+    /// The command is not available on this firmware.
+    #[error("capture image not supported")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response data was invalid.
+    #[error("invalid response data")]
+    InvalidResponse,
+}
+
+/// Acquires the last application capture buffer (IDisplayController, cmd 10).
+///
+/// Returns the transfer memory handle of the buffer.
+pub fn acquire_last_application_capture_buffer(
+    display_controller: &Service,
+) -> Result<TmemHandle, AcquireCaptureBufferError> {
+    let result = display_controller
+        .dispatch(CMD_DC_ACQUIRE_LAST_APPLICATION_CAPTURE_BUFFER)
+        .out_handle(0, OutHandleAttr::Copy)
+        .send();
+
+    let resp = match result {
+        Ok(resp) => resp,
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(
+            RESULT_UNKNOWN_COMMAND_ID,
+        ))) => return Err(AcquireCaptureBufferError::Unsupported),
+        Err(err) => return Err(AcquireCaptureBufferError::Dispatch(err)),
+    };
+
+    if resp.copy_handles.is_empty() {
+        return Err(AcquireCaptureBufferError::MissingHandle);
+    }
+
+    // SAFETY: Kernel returned a valid transfer memory handle in the response.
+    Ok(unsafe { TmemHandle::from_raw(resp.copy_handles[0]) })
+}
+
+/// Error returned by [`acquire_last_application_capture_buffer`].
+#[derive(Debug, thiserror::Error)]
+pub enum AcquireCaptureBufferError {
+    /// The command is not available on this firmware.
+    #[error("capture buffer not supported")]
+    Unsupported,
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+    /// Response did not contain the expected handle.
+    #[error("missing handle in response")]
+    MissingHandle,
+}
+
+/// Releases the last application capture buffer (IDisplayController, cmd 11).
+pub fn release_last_application_capture_buffer(
+    display_controller: &Service,
+) -> Result<(), ReleaseCaptureBufferError> {
+    display_controller
+        .dispatch(CMD_DC_RELEASE_LAST_APPLICATION_CAPTURE_BUFFER)
+        .send()
+        .map_err(ReleaseCaptureBufferError::Dispatch)?;
+
+    Ok(())
+}
+
+/// Error returned by [`release_last_application_capture_buffer`].
+#[derive(Debug, thiserror::Error)]
+pub enum ReleaseCaptureBufferError {
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
+}
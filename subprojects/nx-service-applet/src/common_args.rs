@@ -0,0 +1,106 @@
+//! Library applet CommonArguments header.
+//!
+//! Every library applet expects its first input storage to hold a
+//! [`CommonArguments`] header. Applets that receive a malformed header
+//! usually exit immediately without reporting an error.
+
+use core::mem::offset_of;
+
+use static_assertions::const_assert_eq;
+
+use crate::{LibraryAppletCreator, Storage, StorageWriteError, cmif::CreateStorageError};
+
+/// Version of the CommonArguments header layout.
+const COMMON_ARGUMENTS_VERSION: u32 = 1;
+
+/// CommonArguments header passed first to every library applet (0x20 bytes).
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct CommonArguments {
+    /// Header layout version (always 1).
+    pub common_args_version: u32,
+    /// Header size in bytes (always 0x20).
+    pub common_args_size: u32,
+    /// Applet-specific API version expected by the library applet.
+    pub la_version: u32,
+    /// Theme color the applet should use (0 = system default).
+    pub theme_color: i32,
+    /// Whether the applet plays its startup sound.
+    pub play_startup_sound: u8,
+    _padding: [u8; 7],
+    /// System tick at the time the header was built.
+    pub system_tick: u64,
+}
+
+const_assert_eq!(size_of::<CommonArguments>(), 0x20);
+const_assert_eq!(offset_of!(CommonArguments, la_version), 0x8);
+const_assert_eq!(offset_of!(CommonArguments, theme_color), 0xC);
+const_assert_eq!(offset_of!(CommonArguments, play_startup_sound), 0x10);
+const_assert_eq!(offset_of!(CommonArguments, system_tick), 0x18);
+
+impl CommonArguments {
+    /// Creates a header for a library applet expecting API `version`.
+    ///
+    /// Fills in the layout version, size, and the current system tick.
+    pub fn new(version: u32) -> Self {
+        Self {
+            common_args_version: COMMON_ARGUMENTS_VERSION,
+            common_args_size: size_of::<Self>() as u32,
+            la_version: version,
+            theme_color: 0,
+            play_startup_sound: 0,
+            _padding: [0; 7],
+            system_tick: nx_svc::misc::get_system_tick(),
+        }
+    }
+
+    /// Sets the theme color the applet should use.
+    #[inline]
+    pub fn with_theme_color(mut self, color: i32) -> Self {
+        self.theme_color = color;
+        self
+    }
+
+    /// Sets whether the applet plays its startup sound.
+    #[inline]
+    pub fn with_startup_sound(mut self, play: bool) -> Self {
+        self.play_startup_sound = play as u8;
+        self
+    }
+
+    /// Returns the raw header bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: CommonArguments is repr(C) plain data with explicit padding.
+        unsafe {
+            core::slice::from_raw_parts((self as *const Self).cast::<u8>(), size_of::<Self>())
+        }
+    }
+
+    /// Creates a storage holding this header.
+    ///
+    /// The storage must be the first one pushed to the library applet.
+    pub fn into_storage(self, creator: &LibraryAppletCreator) -> Result<Storage, IntoStorageError> {
+        let storage = creator
+            .create_storage(size_of::<Self>() as i64)
+            .map_err(IntoStorageError::Create)?;
+
+        if let Err(err) = storage.write(0, self.as_bytes()) {
+            storage.close();
+            return Err(IntoStorageError::Write(err));
+        }
+
+        Ok(storage)
+    }
+}
+
+/// Error returned by [`CommonArguments::into_storage`].
+#[derive(Debug, thiserror::Error)]
+pub enum IntoStorageError {
+    /// Failed to create the storage.
+    #[error("failed to create storage")]
+    Create(#[source] CreateStorageError),
+    /// Failed to write the header to the storage.
+    #[error("failed to write storage")]
+    Write(#[source] StorageWriteError),
+}
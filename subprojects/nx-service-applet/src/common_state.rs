@@ -6,13 +6,16 @@
 
 use core::{mem::size_of, ptr};
 
-use nx_sf::service::{DispatchError, OutHandleAttr, Service};
-use nx_svc::sync::EventHandle;
+use nx_sf::{
+    cmif::ParseResponseError,
+    service::{DispatchError, OutHandleAttr, Service},
+};
+use nx_svc::sync::{EventHandle, WaitSyncError};
 
 use crate::proto::{
-    AppletFocusState, AppletMessage, AppletOperationMode, CMD_CSG_GET_CURRENT_FOCUS_STATE,
-    CMD_CSG_GET_EVENT_HANDLE, CMD_CSG_GET_OPERATION_MODE, CMD_CSG_GET_PERFORMANCE_MODE,
-    CMD_CSG_RECEIVE_MESSAGE,
+    AppletFocusState, AppletMessage, AppletOperationMode, AppletPerformanceMode,
+    CMD_CSG_GET_CURRENT_FOCUS_STATE, CMD_CSG_GET_EVENT_HANDLE, CMD_CSG_GET_OPERATION_MODE,
+    CMD_CSG_GET_PERFORMANCE_MODE, CMD_CSG_RECEIVE_MESSAGE, RESULT_NO_MESSAGE,
 };
 
 /// Gets the message event handle from ICommonStateGetter.
@@ -46,7 +49,8 @@ pub enum GetEventHandleError {
 
 /// Receives a pending message from ICommonStateGetter.
 ///
-/// Returns `Ok(None)` if no message is pending (error 0x680).
+/// Returns `Ok(None)` if no message is pending, which the service reports
+/// with [`RESULT_NO_MESSAGE`] (0x680). Any other result code is an error.
 pub fn receive_message(csg: &Service) -> Result<Option<AppletMessage>, ReceiveMessageError> {
     let result = csg
         .dispatch(CMD_CSG_RECEIVE_MESSAGE)
@@ -62,29 +66,45 @@ pub fn receive_message(csg: &Service) -> Result<Option<AppletMessage>, ReceiveMe
             // SAFETY: Response data contains u32 message type.
             let raw = unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<u32>()) };
 
-            Ok(AppletMessage::from_raw(raw))
+            AppletMessage::from_raw(raw)
+                .map(Some)
+                .ok_or(ReceiveMessageError::UnknownMessage(raw))
         }
-        Err(DispatchError::ParseResponse(err)) => {
-            // Check for "no message available" error (0x680)
-            if let nx_sf::cmif::ParseResponseError::ServiceError(code) = err
-                && code == 0x680
-            {
-                return Ok(None);
-            }
-            Err(ReceiveMessageError::Dispatch(DispatchError::ParseResponse(
-                err,
-            )))
+        Err(DispatchError::ParseResponse(ParseResponseError::ServiceError(code))) => {
+            service_error(code)
         }
         Err(err) => Err(ReceiveMessageError::Dispatch(err)),
     }
 }
 
+/// Maps a result code returned by ReceiveMessage.
+///
+/// Only [`RESULT_NO_MESSAGE`] means the queue is empty; anything else is a
+/// real failure and must not be mistaken for it.
+fn service_error(code: u32) -> Result<Option<AppletMessage>, ReceiveMessageError> {
+    if code == RESULT_NO_MESSAGE {
+        Ok(None)
+    } else {
+        Err(ReceiveMessageError::ServiceError(code))
+    }
+}
+
 /// Error returned by [`receive_message`].
 #[derive(Debug, thiserror::Error)]
 pub enum ReceiveMessageError {
     /// Failed to dispatch the request.
     #[error("failed to dispatch request")]
     Dispatch(#[source] DispatchError),
+    /// The service failed with a result code other than
+    /// [`RESULT_NO_MESSAGE`].
+    #[error("service error: {0:#x}")]
+    ServiceError(u32),
+    /// A message was dequeued, but its type is not a known
+    /// [`AppletMessage`].
+    ///
+    /// The message is consumed; further messages may still be pending.
+    #[error("unknown applet message: {0}")]
+    UnknownMessage(u32),
     /// Response data was invalid.
     #[error("invalid response data")]
     InvalidResponse,
@@ -137,7 +157,15 @@ pub fn get_performance_mode(csg: &Service) -> Result<u32, GetPerformanceModeErro
     Ok(mode)
 }
 
-/// Error returned by [`get_performance_mode`].
+/// Gets the current performance mode as an [`AppletPerformanceMode`].
+pub fn get_performance_mode_typed(
+    csg: &Service,
+) -> Result<AppletPerformanceMode, GetPerformanceModeError> {
+    let raw = get_performance_mode(csg)?;
+    AppletPerformanceMode::from_raw(raw).ok_or(GetPerformanceModeError::InvalidValue(raw))
+}
+
+/// Error returned by [`get_performance_mode`] and [`get_performance_mode_typed`].
 #[derive(Debug, thiserror::Error)]
 pub enum GetPerformanceModeError {
     /// Failed to dispatch the request.
@@ -146,6 +174,106 @@ pub enum GetPerformanceModeError {
     /// Response data was invalid.
     #[error("invalid response data")]
     InvalidResponse,
+    /// Performance mode value was unknown.
+    #[error("unknown performance mode value: {0}")]
+    InvalidValue(u32),
+}
+
+/// Waits for a `PerformanceModeChanged` message, then returns the new
+/// performance mode.
+///
+/// Messages are received until a `PerformanceModeChanged` arrives; any other
+/// message received while waiting is discarded, except `ExitRequest`, which
+/// stops the wait with [`WaitPerformanceModeError::ExitRequested`].
+/// `timeout_ns` bounds the whole wait (`u64::MAX` waits forever).
+pub fn wait_performance_mode(
+    csg: &Service,
+    timeout_ns: u64,
+) -> Result<AppletPerformanceMode, WaitPerformanceModeError> {
+    let event = get_event_handle(csg).map_err(WaitPerformanceModeError::GetEventHandle)?;
+
+    let result = wait_for_message(
+        csg,
+        &event,
+        AppletMessage::PerformanceModeChanged,
+        timeout_ns,
+    );
+
+    // GetEventHandle hands out a new copy of the handle on every call
+    // SAFETY: The handle was obtained above and is not used after this.
+    let _ = unsafe { nx_svc::raw::close_handle(event.to_raw()) };
+
+    result?;
+    get_performance_mode_typed(csg).map_err(WaitPerformanceModeError::GetPerformanceMode)
+}
+
+/// Error returned by [`wait_performance_mode`].
+#[derive(Debug, thiserror::Error)]
+pub enum WaitPerformanceModeError {
+    /// Failed to get the message event handle.
+    #[error("failed to get message event handle")]
+    GetEventHandle(#[source] GetEventHandleError),
+    /// Failed to receive a message.
+    #[error("failed to receive message")]
+    ReceiveMessage(#[source] ReceiveMessageError),
+    /// Waiting on the message event failed.
+    #[error("failed to wait on message event")]
+    Wait(#[source] WaitSyncError),
+    /// No `PerformanceModeChanged` message arrived within the timeout.
+    #[error("timed out waiting for performance mode change")]
+    Timeout,
+    /// An `ExitRequest` message arrived while waiting.
+    ///
+    /// The message has been consumed, so the caller must handle the exit
+    /// request itself.
+    #[error("exit requested while waiting for performance mode change")]
+    ExitRequested,
+    /// Failed to get the new performance mode.
+    #[error("failed to get performance mode")]
+    GetPerformanceMode(#[source] GetPerformanceModeError),
+}
+
+/// Receives messages until `expected` arrives or `timeout_ns` elapses.
+///
+/// Stops early if an `ExitRequest` arrives, so that it is not lost.
+fn wait_for_message(
+    csg: &Service,
+    event: &EventHandle,
+    expected: AppletMessage,
+    timeout_ns: u64,
+) -> Result<(), WaitPerformanceModeError> {
+    let start = nx_svc::misc::get_system_tick();
+
+    loop {
+        match receive_message(csg) {
+            Ok(Some(msg)) if msg == expected => return Ok(()),
+            Ok(Some(AppletMessage::ExitRequest)) => {
+                return Err(WaitPerformanceModeError::ExitRequested);
+            }
+            // Keep draining the queue before blocking again. Messages from
+            // newer firmware are dequeued too, so skip them the same way.
+            Ok(Some(_)) | Err(ReceiveMessageError::UnknownMessage(_)) => continue,
+            Ok(None) => {}
+            Err(err) => return Err(WaitPerformanceModeError::ReceiveMessage(err)),
+        }
+
+        let elapsed = nx_svc::misc::ticks_to_nanos(nx_svc::misc::get_system_tick() - start);
+        let remaining = timeout_ns.saturating_sub(elapsed);
+        if remaining == 0 {
+            return Err(WaitPerformanceModeError::Timeout);
+        }
+
+        // SAFETY: The handle is a valid readable event.
+        match unsafe { nx_svc::sync::wait_synchronization_single(event, remaining) } {
+            Ok(()) => {
+                // The message event has autoclear=false
+                // SAFETY: The handle is a valid readable event.
+                let _ = unsafe { nx_svc::sync::reset_signal(event) };
+            }
+            Err(WaitSyncError::TimedOut) => return Err(WaitPerformanceModeError::Timeout),
+            Err(err) => return Err(WaitPerformanceModeError::Wait(err)),
+        }
+    }
 }
 
 /// Gets the current focus state from ICommonStateGetter.
@@ -95,7 +95,7 @@
 //! | 0 | `GetEventHandle` | Event signaled when messages are available |
 //! | 1 | `ReceiveMessage` | Dequeue an [`AppletMessage`] (error 0x680 if empty) |
 //! | 5 | `GetOperationMode` | Handheld vs docked ([`AppletOperationMode`]) |
-//! | 6 | `GetPerformanceMode` | Normal vs boost CPU/GPU clocks |
+//! | 6 | `GetPerformanceMode` | Normal vs boost CPU/GPU clocks ([`AppletPerformanceMode`]) |
 //! | 9 | `GetCurrentFocusState` | Current focus state ([`AppletFocusState`]) |
 //!
 //! ## [`SelfController`] — "Control my own applet"
@@ -104,7 +104,7 @@
 //!
 //! | Command | Name | Status | Purpose |
 //! |---------|------|--------|---------|
-//! | 0 | `Exit` | | Clean exit from the applet |
+//! | 0 | `Exit` | ✅ | Clean exit from the applet |
 //! | 1-2 | `LockExit`/`UnlockExit` | | Prevent forced closure |
 //! | 10 | `SetScreenShotPermission` | | Control screenshot capability |
 //! | 11 | `SetOperationModeChangedNotification` | ✅ | Enable handheld/docked notifications |
@@ -112,6 +112,10 @@
 //! | 13 | `SetFocusHandlingMode` | ✅ | Configure suspension behavior |
 //! | 16 | `SetOutOfFocusSuspendingEnabled` | ✅ | Enable/disable out-of-focus suspension |
 //! | 40 | `CreateManagedDisplayLayer` | | Create a display layer |
+//! | 50 | `SetHandlesRequestToDisplay` | ✅ | Opt in to `RequestToDisplay` messages |
+//! | 51 | `ApproveToDisplay` | ✅ | Respond to a `RequestToDisplay` message |
+//! | 62 | `SetIdleTimeDetectionExtension` | ✅ | Extend the idle time before dimming and sleep |
+//! | 68 | `SetAutoSleepDisabled` | ✅ | Disable auto-sleep (2.0.0+, see [`AutoSleepInhibitor`]) |
 //!
 //! ## [`WindowController`] — "Manage my display"
 //!
@@ -121,17 +125,48 @@
 //! |---------|------|--------|---------|
 //! | 1 | `GetAppletResourceUserId` | ✅ | Get the applet resource user ID |
 //! | 10 | `AcquireForegroundRights` | ✅ | Claim the foreground display |
+//! | 11 | `ReleaseForegroundRights` | ✅ | Give up the foreground display |
 //!
-//! ## ILibraryAppletCreator — "Launch system dialogs"
+//! ## [`AudioController`] — "How loud should I be?"
+//!
+//! Expected master volumes, used to follow the system volume and to duck
+//! audio while an overlay is shown:
+//!
+//! | Command | Name | Status | Purpose |
+//! |---------|------|--------|---------|
+//! | 0 | `SetExpectedMasterVolume` | ✅ | Set the main and library applet volumes |
+//! | 1 | `GetMainAppletExpectedMasterVolume` | ✅ | Get the main applet volume |
+//! | 2 | `GetLibraryAppletExpectedMasterVolume` | | Get the library applet volume |
+//!
+//! ## [`DisplayController`] — "What is on screen?"
+//!
+//! Capture images of the screen, as 1280x720 RGBA8:
+//!
+//! | Command | Name | Status | Purpose |
+//! |---------|------|--------|---------|
+//! | 5 | `GetLastForegroundCaptureImageEx` | ✅ | Copy the last foreground image (2.0.0+) |
+//! | 10 | `AcquireLastApplicationCaptureBuffer` | ✅ | Get the last application image as transfer memory |
+//! | 11 | `ReleaseLastApplicationCaptureBuffer` | ✅ | Release the acquired buffer |
+//!
+//! ## [`ApplicationFunctions`] — "Application-only services"
+//!
+//! Available only to `AppletType::Application` via appletOE:
+//!
+//! | Command | Name | Status | Purpose |
+//! |---------|------|--------|---------|
+//! | 1 | `PopLaunchParameter` | ✅ | Pop a launch parameter [`Storage`] ([`LaunchParameterKind`]) |
+//! | 40 | `NotifyRunning` | ✅ | Signal that initialization is complete |
+//!
+//! ## [`LibraryAppletCreator`] — "Launch system dialogs"
 //!
 //! Create and manage library applets:
 //!
-//! | Command | Name | Purpose |
-//! |---------|------|---------|
-//! | 0 | `CreateLibraryApplet` | Launch a library applet by ID |
-//! | 1 | `TerminateAllLibraryApplets` | Terminate all created applets |
-//! | 10 | `CreateStorage` | Allocate storage for data transfer |
-//! | 11 | `CreateTransferMemoryStorage` | Create storage from TransferMemory |
+//! | Command | Name | Status | Purpose |
+//! |---------|------|--------|---------|
+//! | 0 | `CreateLibraryApplet` | | Launch a library applet by ID ([`LibraryAppletMode`]) |
+//! | 1 | `TerminateAllLibraryApplets` | ✅ | Terminate all created applets |
+//! | 10 | `CreateStorage` | ✅ | Allocate storage for data transfer |
+//! | 11 | `CreateTransferMemoryStorage` | | Create storage from TransferMemory |
 //!
 //! # Message System
 //!
@@ -171,6 +206,27 @@
 //! | 90 | `CaptureButtonShortPressed` | Screenshot button pressed |
 //! | 92 | `AlbumScreenShotTaken` | Screenshot was captured |
 //!
+//! ## Display Requests
+//!
+//! `RequestToDisplay` is posted when another applet (typically the HOME menu
+//! overlay) wants the screen, but only to applets that opted in with
+//! `ISelfController::SetHandlesRequestToDisplay` (cmd 50). Otherwise the system
+//! handles the request on the applet's behalf. Once opted in, ignoring it
+//! causes the system to force the applet out of the foreground. The expected
+//! response is:
+//!
+//! ```text
+//! RequestToDisplay(51)
+//!     │
+//!     ├─ IWindowController::ReleaseForegroundRights (cmd 11)
+//!     ├─ ISelfController::ApproveToDisplay (cmd 51)
+//!     │
+//!     │      [other applet owns the display]
+//!     │
+//!     └─ FocusStateChanged(15) → InFocus
+//!            └─ IWindowController::AcquireForegroundRights (cmd 10)
+//! ```
+//!
 //! # Focus States and Suspension
 //!
 //! The [`AppletFocusState`] indicates the applet's visibility and activity:
@@ -259,6 +315,8 @@
 //!
 //! Every library applet receives a **CommonArguments** header (0x20 bytes) containing
 //! version info and system tick, followed by applet-specific configuration data.
+//! Build it with [`CommonArguments::new`] and push [`CommonArguments::into_storage`]
+//! before any other storage.
 //!
 //! # Application Lifecycle
 //!
@@ -278,6 +336,9 @@
 //!     └─ NotifyRunning()
 //! ```
 //!
+//! [`AppletSession::open`] covers the first three steps and owns the resulting
+//! sessions, closing them all when dropped.
+//!
 //! ## 2. Main Loop
 //!
 //! ```text
@@ -301,8 +362,12 @@
 //! exit requested → cleanup → service cleanup
 //!     │
 //!     ├─ User cleanup code
-//!     ├─ SetFocusHandlingMode(NoSuspend)
+//!     ├─ TerminateAllLibraryApplets (if any were created)
 //!     ├─ Reset CPU boost if used
+//!     ├─ SetTerminateResult (optional)    ┐
+//!     ├─ SetFocusHandlingMode(NoSuspend)  │
+//!     ├─ ISelfController::Exit            ├─ AppletProxyService::exit_gracefully
+//!     ├─ Close applet proxy               ┘
 //!     └─ Close applet service
 //! ```
 //!
@@ -317,31 +382,48 @@ extern crate nx_panic_handler; // Provide #![panic_handler]
 
 use nx_service_sm::SmService;
 use nx_sf::service::Service;
-use nx_svc::{ipc::Handle as SessionHandle, process::Handle as ProcessHandle, sync::EventHandle};
+use nx_svc::{
+    ipc::Handle as SessionHandle, misc::GetInfoError, process::Handle as ProcessHandle,
+    sync::EventHandle,
+};
 
 use crate::aruid::Aruid;
 
 pub mod aruid;
 mod cmif;
+mod common_args;
 mod common_state;
 mod proto;
+mod session;
 
 pub use self::{
     cmif::{
-        AcquireForegroundRightsError, ConnectError, CreateManagedDisplayLayerError,
-        GetAppletResourceUserIdError, GetApplicationFunctionsError, GetCommonStateGetterError,
-        GetSelfControllerError, GetWindowControllerError, NotifyRunningError, OpenProxyError,
-        SetFocusHandlingModeError, SetOperationModeChangedNotificationError,
+        AcquireCaptureBufferError, AcquireForegroundRightsError, ApproveToDisplayError,
+        ConnectError, CreateManagedDisplayLayerError, CreateStorageError, ExitError,
+        ExitGracefullyError, GetAppletResourceUserIdError, GetApplicationFunctionsError,
+        GetAudioControllerError, GetCaptureImageError, GetCommonStateGetterError,
+        GetDisplayControllerError, GetLibraryAppletCreatorError,
+        GetMainAppletExpectedMasterVolumeError, GetSelfControllerError, GetWindowControllerError,
+        NotifyRunningError, OpenProxyError, OpenStorageAccessorError, PopLaunchParameterError,
+        ReleaseCaptureBufferError, ReleaseForegroundRightsError, SetAutoSleepDisabledError,
+        SetExpectedMasterVolumeError, SetFocusHandlingModeError, SetHandlesRequestToDisplayError,
+        SetIdleTimeDetectionExtensionError, SetOperationModeChangedNotificationError,
         SetOutOfFocusSuspendingEnabledError, SetPerformanceModeChangedNotificationError,
+        SetTerminateResultError, StorageGetSizeError, StorageReadError, StorageWriteError,
+        TerminateAllLibraryAppletsError,
     },
+    common_args::{CommonArguments, IntoStorageError},
     common_state::{
         GetCurrentFocusStateError, GetEventHandleError, GetOperationModeError,
-        GetPerformanceModeError, ReceiveMessageError,
+        GetPerformanceModeError, ReceiveMessageError, WaitPerformanceModeError,
     },
     proto::{
         AppletAttribute, AppletFocusHandlingMode, AppletFocusState, AppletMessage,
-        AppletOperationMode, AppletType, SERVICE_NAME_AE, SERVICE_NAME_OE,
+        AppletOperationMode, AppletPerformanceMode, AppletType, CAPTURE_IMAGE_HEIGHT,
+        CAPTURE_IMAGE_SIZE, CAPTURE_IMAGE_WIDTH, IdleTimeExtension, LaunchParameterKind,
+        LibraryAppletMode, RESULT_NO_MESSAGE, SERVICE_NAME_AE, SERVICE_NAME_OE,
     },
+    session::{AppletSession, OpenSessionError},
 };
 
 /// Applet main service session (appletOE or appletAE).
@@ -429,6 +511,27 @@ impl AppletProxyService {
         self.0.close();
     }
 
+    /// Restores the `NoSuspend` focus handling mode, exits, and closes the proxy.
+    ///
+    /// This is the canonical shutdown path for [`AppletType::Application`]:
+    /// returning from `main` without it can leave the system in a stale
+    /// foreground state. The proxy is closed even if a step fails.
+    ///
+    /// Library applets created by this application must be terminated first,
+    /// see [`LibraryAppletCreator::terminate_all_library_applets`].
+    ///
+    /// If `terminate_result` is set, it is reported first, as with
+    /// [`ApplicationFunctions::set_terminate_result`].
+    ///
+    /// Other applet types must not use this. Library applets return to their
+    /// caller through `ILibraryAppletSelfAccessor::ExitProcessAndReturn`, and
+    /// system and overlay applets are not expected to exit.
+    pub fn exit_gracefully(self, terminate_result: Option<u32>) -> Result<(), ExitGracefullyError> {
+        let result = cmif::exit_gracefully(&self.0, terminate_result);
+        self.close();
+        result
+    }
+
     /// Gets the ICommonStateGetter sub-interface.
     ///
     /// Provides access to focus state, operation mode, and message events.
@@ -453,6 +556,25 @@ impl AppletProxyService {
         cmif::get_window_controller(&self.0)
     }
 
+    /// Gets the IAudioController sub-interface.
+    ///
+    /// Provides the expected master volumes used to follow the system volume
+    /// and to duck audio while another applet is shown. Returns
+    /// [`GetAudioControllerError::Unsupported`] if the proxy does not provide
+    /// one.
+    #[inline]
+    pub fn get_audio_controller(&self) -> Result<AudioController, GetAudioControllerError> {
+        cmif::get_audio_controller(&self.0)
+    }
+
+    /// Gets the IDisplayController sub-interface.
+    ///
+    /// Provides access to the capture images of the screen.
+    #[inline]
+    pub fn get_display_controller(&self) -> Result<DisplayController, GetDisplayControllerError> {
+        cmif::get_display_controller(&self.0)
+    }
+
     /// Gets the IApplicationFunctions sub-interface (Application type only).
     ///
     /// Provides application-specific functionality like NotifyRunning.
@@ -463,6 +585,60 @@ impl AppletProxyService {
     ) -> Result<ApplicationFunctions, GetApplicationFunctionsError> {
         cmif::get_application_functions(&self.0)
     }
+
+    /// Gets the ILibraryAppletCreator sub-interface.
+    ///
+    /// Provides library applet creation and storage allocation.
+    #[inline]
+    pub fn get_library_applet_creator(
+        &self,
+    ) -> Result<LibraryAppletCreator, GetLibraryAppletCreatorError> {
+        cmif::get_library_applet_creator(&self.0)
+    }
+}
+
+/// ILibraryAppletCreator sub-interface.
+///
+/// Creates library applets and the storages used to pass them data.
+#[repr(transparent)]
+pub struct LibraryAppletCreator(Service);
+
+impl LibraryAppletCreator {
+    /// Returns the underlying session handle.
+    #[inline]
+    pub fn session(&self) -> SessionHandle {
+        self.0.session
+    }
+
+    /// Returns the domain object ID (0 if non-domain).
+    #[inline]
+    pub fn object_id(&self) -> u32 {
+        self.0.object_id
+    }
+
+    /// Consumes and closes the interface.
+    #[inline]
+    pub fn close(self) {
+        self.0.close();
+    }
+
+    /// Terminates every library applet created through this creator.
+    ///
+    /// Succeeds if there is none left. Call it before
+    /// [`AppletProxyService::exit_gracefully`] when the application exits
+    /// while a library applet may still be running: exiting with a live
+    /// child applet leaves the system in a bad state, and the creator (a
+    /// sub-interface of the proxy) cannot be used once the proxy is closed.
+    #[inline]
+    pub fn terminate_all_library_applets(&self) -> Result<(), TerminateAllLibraryAppletsError> {
+        cmif::terminate_all_library_applets(&self.0)
+    }
+
+    /// Creates a storage of `size` bytes.
+    #[inline]
+    pub fn create_storage(&self, size: i64) -> Result<Storage, CreateStorageError> {
+        cmif::create_storage(&self.0, size)
+    }
 }
 
 /// ICommonStateGetter sub-interface.
@@ -505,7 +681,9 @@ impl CommonStateGetter {
 
     /// Receives a pending message.
     ///
-    /// Returns `Ok(None)` if no message is pending.
+    /// Returns `Ok(None)` if no message is pending. A message of a type this
+    /// crate does not know is consumed and reported as
+    /// [`ReceiveMessageError::UnknownMessage`].
     #[inline]
     pub fn receive_message(&self) -> Result<Option<AppletMessage>, ReceiveMessageError> {
         common_state::receive_message(&self.0)
@@ -517,12 +695,41 @@ impl CommonStateGetter {
         common_state::get_operation_mode(&self.0)
     }
 
-    /// Gets the current performance mode.
+    /// Gets the current performance mode as a raw value.
+    ///
+    /// See [`get_performance_mode_typed`](Self::get_performance_mode_typed)
+    /// for the typed value.
     #[inline]
     pub fn get_performance_mode(&self) -> Result<u32, GetPerformanceModeError> {
         common_state::get_performance_mode(&self.0)
     }
 
+    /// Gets the current performance mode (normal/boost).
+    #[inline]
+    pub fn get_performance_mode_typed(
+        &self,
+    ) -> Result<AppletPerformanceMode, GetPerformanceModeError> {
+        common_state::get_performance_mode_typed(&self.0)
+    }
+
+    /// Waits until the performance mode changes and returns the new mode.
+    ///
+    /// Requires `PerformanceModeChanged` notifications to be enabled with
+    /// [`SelfController::set_performance_mode_changed_notification`].
+    /// Messages other than `PerformanceModeChanged` received while waiting
+    /// are discarded, so don't call this concurrently with a message loop.
+    /// An `ExitRequest` stops the wait with
+    /// [`WaitPerformanceModeError::ExitRequested`] instead, so it is never lost.
+    ///
+    /// `timeout_ns` bounds the whole wait (`u64::MAX` waits forever).
+    #[inline]
+    pub fn wait_performance_mode(
+        &self,
+        timeout_ns: u64,
+    ) -> Result<AppletPerformanceMode, WaitPerformanceModeError> {
+        common_state::wait_performance_mode(&self.0, timeout_ns)
+    }
+
     /// Gets the current focus state.
     #[inline]
     pub fn get_current_focus_state(&self) -> Result<AppletFocusState, GetCurrentFocusStateError> {
@@ -617,6 +824,90 @@ impl SelfController {
     pub fn create_managed_display_layer(&self) -> Result<u64, CreateManagedDisplayLayerError> {
         cmif::create_managed_display_layer(&self.0)
     }
+
+    /// Exits the applet.
+    ///
+    /// Prefer [`AppletProxyService::exit_gracefully`], which restores the
+    /// focus handling mode first.
+    #[inline]
+    pub fn exit(&self) -> Result<(), ExitError> {
+        cmif::exit(&self.0)
+    }
+
+    /// Sets whether the applet handles display requests itself.
+    ///
+    /// When enabled, the applet receives [`AppletMessage::RequestToDisplay`]
+    /// whenever another applet (e.g. the HOME menu overlay) wants the screen,
+    /// and must respond: release the foreground rights and call
+    /// [`approve_to_display`](Self::approve_to_display). An applet that
+    /// ignores the message is forced out of the foreground. When disabled (the
+    /// default), no such message is posted and the system hands the display
+    /// over by itself.
+    ///
+    /// Fails with [`SetHandlesRequestToDisplayError::Unsupported`] if the
+    /// firmware does not implement the command.
+    #[inline]
+    pub fn set_handles_request_to_display(
+        &self,
+        enabled: bool,
+    ) -> Result<(), SetHandlesRequestToDisplayError> {
+        cmif::set_handles_request_to_display(&self.0, enabled)
+    }
+
+    /// Approves a pending display request.
+    ///
+    /// Response to an [`AppletMessage::RequestToDisplay`] message. Release the
+    /// foreground rights via [`WindowController::release_foreground_rights`]
+    /// first, and re-acquire them once focus returns.
+    #[inline]
+    pub fn approve_to_display(&self) -> Result<(), ApproveToDisplayError> {
+        cmif::approve_to_display(&self.0)
+    }
+
+    /// Sets the idle time detection extension.
+    ///
+    /// Extends the idle time after which the screen dims and the console
+    /// auto-sleeps. Reset to [`IdleTimeExtension::None`] once the
+    /// non-interactive content ends.
+    #[inline]
+    pub fn set_idle_time_detection_extension(
+        &self,
+        ext: IdleTimeExtension,
+    ) -> Result<(), SetIdleTimeDetectionExtensionError> {
+        cmif::set_idle_time_detection_extension(&self.0, ext)
+    }
+
+    /// Sets whether auto-sleep is disabled (2.0.0+).
+    ///
+    /// While disabled, the console neither dims the screen nor goes to sleep
+    /// on its own. Prefer [`AutoSleepInhibitor`], which re-enables auto-sleep
+    /// when dropped.
+    #[inline]
+    pub fn set_auto_sleep_disabled(&self, disabled: bool) -> Result<(), SetAutoSleepDisabledError> {
+        cmif::set_auto_sleep_disabled(&self.0, disabled)
+    }
+}
+
+/// Keeps the console from auto-sleeping while alive.
+///
+/// Disables auto-sleep on creation and re-enables it when dropped, e.g. for
+/// the duration of a video playback. Requires 2.0.0+.
+pub struct AutoSleepInhibitor<'a> {
+    controller: &'a SelfController,
+}
+
+impl<'a> AutoSleepInhibitor<'a> {
+    /// Disables auto-sleep until the returned inhibitor is dropped.
+    pub fn new(controller: &'a SelfController) -> Result<Self, SetAutoSleepDisabledError> {
+        controller.set_auto_sleep_disabled(true)?;
+        Ok(Self { controller })
+    }
+}
+
+impl Drop for AutoSleepInhibitor<'_> {
+    fn drop(&mut self) {
+        let _ = self.controller.set_auto_sleep_disabled(false);
+    }
 }
 
 /// IWindowController sub-interface.
@@ -665,6 +956,127 @@ impl WindowController {
     pub fn acquire_foreground_rights(&self) -> Result<(), AcquireForegroundRightsError> {
         cmif::acquire_foreground_rights(&self.0)
     }
+
+    /// Releases foreground display rights.
+    ///
+    /// Part of the [`AppletMessage::RequestToDisplay`] response sequence; the
+    /// rights must be re-acquired once the applet is back in focus.
+    #[inline]
+    pub fn release_foreground_rights(&self) -> Result<(), ReleaseForegroundRightsError> {
+        cmif::release_foreground_rights(&self.0)
+    }
+}
+
+/// IDisplayController sub-interface.
+///
+/// Provides the capture images AM keeps of the screen, as
+/// [`CAPTURE_IMAGE_WIDTH`]x[`CAPTURE_IMAGE_HEIGHT`] RGBA8 pixels. The capture
+/// commands are not available to [`AppletType::Application`].
+#[repr(transparent)]
+pub struct DisplayController(Service);
+
+impl DisplayController {
+    /// Returns the underlying session handle.
+    #[inline]
+    pub fn session(&self) -> SessionHandle {
+        self.0.session
+    }
+
+    /// Returns the domain object ID (0 if non-domain).
+    #[inline]
+    pub fn object_id(&self) -> u32 {
+        self.0.object_id
+    }
+
+    /// Consumes and closes the interface.
+    #[inline]
+    pub fn close(self) {
+        self.0.close();
+    }
+
+    /// Copies the last foreground capture image into `buf` (2.0.0+).
+    ///
+    /// `buf` must be exactly [`CAPTURE_IMAGE_SIZE`] bytes; it is filled with
+    /// the RGBA8 image of what was last shown in the foreground. The image is
+    /// passed as an out buffer, so no transfer memory is involved. Returns
+    /// the flag output by the service.
+    #[inline]
+    pub fn get_last_foreground_capture_image_ex(
+        &self,
+        buf: &mut [u8],
+    ) -> Result<bool, GetCaptureImageError> {
+        cmif::get_last_foreground_capture_image_ex(&self.0, buf)
+    }
+
+    /// Acquires the capture buffer holding the last application image.
+    ///
+    /// Returns a transfer memory handle of [`CAPTURE_IMAGE_SIZE`] bytes, to
+    /// be mapped with no permissions through `nx-sys-mem`'s transfer memory
+    /// helpers. Unmap it and close the handle before calling
+    /// [`release_last_application_capture_buffer`](Self::release_last_application_capture_buffer).
+    ///
+    /// Returns [`AcquireCaptureBufferError::Unsupported`] on firmware that
+    /// no longer provides the command.
+    #[inline]
+    pub fn acquire_last_application_capture_buffer(
+        &self,
+    ) -> Result<nx_svc::mem::tmem::Handle, AcquireCaptureBufferError> {
+        cmif::acquire_last_application_capture_buffer(&self.0)
+    }
+
+    /// Releases the capture buffer acquired with
+    /// [`acquire_last_application_capture_buffer`](Self::acquire_last_application_capture_buffer).
+    #[inline]
+    pub fn release_last_application_capture_buffer(&self) -> Result<(), ReleaseCaptureBufferError> {
+        cmif::release_last_application_capture_buffer(&self.0)
+    }
+}
+
+/// IAudioController sub-interface.
+///
+/// Provides the expected master volumes of the main applet and library
+/// applets, in `0.0..=1.0`.
+#[repr(transparent)]
+pub struct AudioController(Service);
+
+impl AudioController {
+    /// Returns the underlying session handle.
+    #[inline]
+    pub fn session(&self) -> SessionHandle {
+        self.0.session
+    }
+
+    /// Returns the domain object ID (0 if non-domain).
+    #[inline]
+    pub fn object_id(&self) -> u32 {
+        self.0.object_id
+    }
+
+    /// Consumes and closes the interface.
+    #[inline]
+    pub fn close(self) {
+        self.0.close();
+    }
+
+    /// Sets the expected master volumes of the main applet and library applets.
+    ///
+    /// Both volumes must be in `0.0..=1.0`.
+    #[inline]
+    pub fn set_expected_master_volume(
+        &self,
+        main: f32,
+        applet: f32,
+    ) -> Result<(), SetExpectedMasterVolumeError> {
+        cmif::set_expected_master_volume(&self.0, main, applet)
+    }
+
+    /// Gets the expected master volume of the main applet.
+    #[inline]
+    pub fn get_main_applet_expected_master_volume(
+        &self,
+    ) -> Result<f32, GetMainAppletExpectedMasterVolumeError> {
+        cmif::get_main_applet_expected_master_volume(&self.0)
+    }
 }
 
 /// IApplicationFunctions interface (Application type only).
@@ -702,6 +1114,78 @@ impl ApplicationFunctions {
     pub fn notify_running(&self) -> Result<bool, NotifyRunningError> {
         cmif::notify_running(&self.0)
     }
+
+    /// Returns the application ID of the running application.
+    ///
+    /// IApplicationFunctions has no command for this; for applications the
+    /// process program ID reported by the kernel is the application ID.
+    #[inline]
+    pub fn get_current_application_id(&self) -> Result<u64, GetInfoError> {
+        nx_svc::misc::get_program_id()
+    }
+
+    /// Sets the result code reported to the system when the application
+    /// exits, e.g. to signal an abnormal termination.
+    ///
+    /// `result` is a Horizon result code (module in bits 0-8, description in
+    /// bits 9-21); `0` means success. Available on all firmware versions.
+    ///
+    /// A homebrew loader chain-loading through hbloader cannot read this
+    /// back; the next program only sees whether the previous load succeeded,
+    /// through `nx_rt::env::last_load_result`.
+    #[inline]
+    pub fn set_terminate_result(&self, result: u32) -> Result<(), SetTerminateResultError> {
+        cmif::set_terminate_result(&self.0, result)
+    }
+
+    /// Pops the next launch parameter of the given kind.
+    ///
+    /// Returns `Ok(None)` if the application was launched without one.
+    #[inline]
+    pub fn pop_launch_parameter(
+        &self,
+        kind: LaunchParameterKind,
+    ) -> Result<Option<Storage>, PopLaunchParameterError> {
+        cmif::pop_launch_parameter(&self.0, kind)
+    }
+}
+
+/// IStorage interface.
+///
+/// A block of data exchanged with the applet manager, e.g. a launch parameter.
+#[repr(transparent)]
+pub struct Storage(Service);
+
+impl Storage {
+    /// Returns the domain object ID (0 if non-domain).
+    #[inline]
+    pub fn object_id(&self) -> u32 {
+        self.0.object_id
+    }
+
+    /// Consumes and closes the interface.
+    #[inline]
+    pub fn close(self) {
+        self.0.close();
+    }
+
+    /// Returns the size of the storage contents in bytes.
+    #[inline]
+    pub fn size(&self) -> Result<u64, StorageGetSizeError> {
+        cmif::storage_get_size(&self.0)
+    }
+
+    /// Reads the storage contents starting at `offset` into `buf`.
+    #[inline]
+    pub fn read(&self, offset: u64, buf: &mut [u8]) -> Result<(), StorageReadError> {
+        cmif::storage_read(&self.0, offset, buf)
+    }
+
+    /// Writes `data` into the storage starting at `offset`.
+    #[inline]
+    pub fn write(&self, offset: u64, data: &[u8]) -> Result<(), StorageWriteError> {
+        cmif::storage_write(&self.0, offset, data)
+    }
 }
 
 /// Connects to the applet service (appletOE or appletAE) based on applet type.
@@ -738,12 +1222,7 @@ pub fn connect(
         .map_err(ConnectError::GetService)?;
 
     // Create service and convert to domain
-    let mut service = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let mut service = Service::new(handle);
 
     service
         .convert_to_domain()
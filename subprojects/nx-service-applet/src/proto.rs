@@ -50,11 +50,9 @@ pub const CMD_GET_WINDOW_CONTROLLER: u32 = 2;
 // They are defined here for documentation purposes.
 
 /// Command ID for GetAudioController
-#[allow(dead_code)]
 pub const CMD_GET_AUDIO_CONTROLLER: u32 = 3;
 
 /// Command ID for GetDisplayController
-#[allow(dead_code)]
 pub const CMD_GET_DISPLAY_CONTROLLER: u32 = 4;
 
 /// Command ID for GetProcessWindingController (LibraryApplet only)
@@ -62,7 +60,6 @@ pub const CMD_GET_DISPLAY_CONTROLLER: u32 = 4;
 pub const CMD_GET_PROCESS_WINDING_CONTROLLER: u32 = 10;
 
 /// Command ID for GetLibraryAppletCreator
-#[allow(dead_code)]
 pub const CMD_GET_LIBRARY_APPLET_CREATOR: u32 = 11;
 
 /// Command ID for GetLibraryAppletSelfAccessor or IFunctions (type-dependent)
@@ -108,6 +105,9 @@ pub const CMD_CSG_GET_BOOT_MODE: u32 = 8;
 /// Command ID for GetCurrentFocusState (ICommonStateGetter)
 pub const CMD_CSG_GET_CURRENT_FOCUS_STATE: u32 = 9;
 
+/// Command ID for Exit (ISelfController)
+pub const CMD_SC_EXIT: u32 = 0;
+
 /// Command ID for SetOperationModeChangedNotification (ISelfController)
 pub const CMD_SC_SET_OPERATION_MODE_CHANGED_NOTIFICATION: u32 = 11;
 
@@ -123,18 +123,71 @@ pub const CMD_SC_SET_OUT_OF_FOCUS_SUSPENDING_ENABLED: u32 = 16;
 /// Command ID for CreateManagedDisplayLayer (ISelfController)
 pub const CMD_SC_CREATE_MANAGED_DISPLAY_LAYER: u32 = 40;
 
+/// Command ID for SetHandlesRequestToDisplay (ISelfController)
+///
+/// Opts in to receiving [`AppletMessage::RequestToDisplay`] messages.
+pub const CMD_SC_SET_HANDLES_REQUEST_TO_DISPLAY: u32 = 50;
+
+/// Command ID for ApproveToDisplay (ISelfController)
+///
+/// Response to an [`AppletMessage::RequestToDisplay`] message.
+pub const CMD_SC_APPROVE_TO_DISPLAY: u32 = 51;
+
+/// Command ID for SetIdleTimeDetectionExtension (ISelfController)
+pub const CMD_SC_SET_IDLE_TIME_DETECTION_EXTENSION: u32 = 62;
+
+/// Command ID for SetAutoSleepDisabled (ISelfController, 2.0.0+)
+pub const CMD_SC_SET_AUTO_SLEEP_DISABLED: u32 = 68;
+
 /// Command ID for GetAppletResourceUserId (IWindowController)
 pub const CMD_WC_GET_APPLET_RESOURCE_USER_ID: u32 = 1;
 
 /// Command ID for AcquireForegroundRights (IWindowController)
 pub const CMD_WC_ACQUIRE_FOREGROUND_RIGHTS: u32 = 10;
 
+/// Command ID for ReleaseForegroundRights (IWindowController)
+pub const CMD_WC_RELEASE_FOREGROUND_RIGHTS: u32 = 11;
+
+/// Command ID for SetExpectedMasterVolume (IAudioController)
+pub const CMD_AC_SET_EXPECTED_MASTER_VOLUME: u32 = 0;
+
+/// Command ID for GetMainAppletExpectedMasterVolume (IAudioController)
+pub const CMD_AC_GET_MAIN_APPLET_EXPECTED_MASTER_VOLUME: u32 = 1;
+
+/// Command ID for GetLastForegroundCaptureImageEx (IDisplayController, 2.0.0+)
+pub const CMD_DC_GET_LAST_FOREGROUND_CAPTURE_IMAGE_EX: u32 = 5;
+
+/// Command ID for AcquireLastApplicationCaptureBuffer (IDisplayController)
+pub const CMD_DC_ACQUIRE_LAST_APPLICATION_CAPTURE_BUFFER: u32 = 10;
+
+/// Command ID for ReleaseLastApplicationCaptureBuffer (IDisplayController)
+pub const CMD_DC_RELEASE_LAST_APPLICATION_CAPTURE_BUFFER: u32 = 11;
+
+/// Width in pixels of the IDisplayController capture images.
+pub const CAPTURE_IMAGE_WIDTH: usize = 1280;
+
+/// Height in pixels of the IDisplayController capture images.
+pub const CAPTURE_IMAGE_HEIGHT: usize = 720;
+
+/// Size in bytes of an IDisplayController capture image (RGBA8, 0x384000).
+pub const CAPTURE_IMAGE_SIZE: usize = CAPTURE_IMAGE_WIDTH * CAPTURE_IMAGE_HEIGHT * 4;
+
 /// Command ID for GetApplicationFunctions (IApplicationProxy, AppletType::Application only)
 ///
 /// Returns IApplicationFunctions interface (cmd 20).
 /// Only available for Application type applets via appletOE.
 pub const CMD_GET_APPLICATION_FUNCTIONS: u32 = 20;
 
+/// Command ID for PopLaunchParameter (IApplicationFunctions)
+///
+/// Pops the next launch parameter storage of the requested kind.
+pub const CMD_AF_POP_LAUNCH_PARAMETER: u32 = 1;
+
+/// Command ID for SetTerminateResult (IApplicationFunctions)
+///
+/// Sets the result reported to the system when the application exits.
+pub const CMD_AF_SET_TERMINATE_RESULT: u32 = 22;
+
 /// Command ID for NotifyRunning (IApplicationFunctions)
 ///
 /// Notifies the system that the application has completed initialization
@@ -144,6 +197,42 @@ pub const CMD_GET_APPLICATION_FUNCTIONS: u32 = 20;
 /// - Setting up focus handling mode
 pub const CMD_AF_NOTIFY_RUNNING: u32 = 40;
 
+/// Command ID for TerminateAllLibraryApplets (ILibraryAppletCreator)
+///
+/// Terminates every library applet created through this creator.
+pub const CMD_LAC_TERMINATE_ALL_LIBRARY_APPLETS: u32 = 1;
+
+/// Command ID for CreateStorage (ILibraryAppletCreator)
+///
+/// Allocates an IStorage of the given size for passing data to library applets.
+pub const CMD_LAC_CREATE_STORAGE: u32 = 10;
+
+/// Command ID for Open (IStorage)
+///
+/// Returns an IStorageAccessor for the storage contents.
+pub const CMD_STORAGE_OPEN: u32 = 0;
+
+/// Command ID for GetSize (IStorageAccessor)
+pub const CMD_STORAGE_ACCESSOR_GET_SIZE: u32 = 0;
+
+/// Command ID for Write (IStorageAccessor)
+pub const CMD_STORAGE_ACCESSOR_WRITE: u32 = 10;
+
+/// Command ID for Read (IStorageAccessor)
+pub const CMD_STORAGE_ACCESSOR_READ: u32 = 11;
+
+/// AM result returned by ReceiveMessage when the message queue is empty
+/// (module 128, description 3).
+pub const RESULT_NO_MESSAGE: u32 = 0x680;
+
+/// AM result returned by PopLaunchParameter when no parameter of the
+/// requested kind is available (module 128, description 2).
+pub const RESULT_NO_DATA_IN_CHANNEL: u32 = 0x480;
+
+/// CMIF result returned when an interface does not implement the requested
+/// command (module 10, description 221).
+pub const RESULT_UNKNOWN_COMMAND_ID: u32 = 0x1BA0A;
+
 /// Applet type determining which service and proxy to use.
 ///
 /// This value controls whether the applet connects to `appletOE` or `appletAE`,
@@ -259,6 +348,29 @@ impl AppletOperationMode {
     }
 }
 
+/// Performance mode of the console (CPU/GPU clock configuration).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum AppletPerformanceMode {
+    /// Normal clocks (handheld).
+    #[default]
+    Normal = 0,
+    /// Boosted clocks (docked).
+    Boost = 1,
+}
+
+impl AppletPerformanceMode {
+    /// Creates an `AppletPerformanceMode` from a raw u32 value.
+    #[inline]
+    pub const fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Normal),
+            1 => Some(Self::Boost),
+            _ => None,
+        }
+    }
+}
+
 /// Messages received from the applet event.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
@@ -273,7 +385,7 @@ pub enum AppletMessage {
     OperationModeChanged = 30,
     /// Performance mode changed.
     PerformanceModeChanged = 31,
-    /// Display requested (see `appletApproveToDisplay`).
+    /// Another applet requested the display (see `SelfController::approve_to_display`).
     RequestToDisplay = 51,
     /// Capture button was short-pressed.
     CaptureButtonShortPressed = 90,
@@ -317,6 +429,51 @@ pub enum AppletFocusHandlingMode {
     AlwaysSuspend = 3,
 }
 
+/// Idle time detection extension for applications.
+///
+/// Extends the idle time after which the console dims the screen and goes to
+/// auto-sleep, as configured in System Settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum IdleTimeExtension {
+    /// Use the configured idle times (default).
+    #[default]
+    None = 0,
+    /// Extend the idle times; for non-interactive content such as video.
+    Extended = 1,
+    /// Extend the idle times further, even when they are set to the minimum.
+    ExtendedUnsafe = 2,
+}
+
+/// Kind of launch parameter passed to an application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LaunchParameterKind {
+    /// Application-specific user channel data.
+    UserChannel = 1,
+    /// Account preselected by the launcher (`AccountUid`, plus a magic header).
+    PreselectedUser = 2,
+}
+
+/// How a library applet is displayed when launched.
+///
+/// Passed to `CreateLibraryApplet` (ILibraryAppletCreator, cmd 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum LibraryAppletMode {
+    /// Applet takes over the whole screen (default).
+    #[default]
+    AllForeground = 0,
+    /// Applet is drawn over the caller, which stays visible.
+    PartialForeground = 1,
+    /// Applet runs without a UI.
+    NoUi = 2,
+    /// Partial foreground, rendering to an indirect layer (2.0.0+).
+    PartialForegroundWithIndirectDisplay = 3,
+    /// Full foreground, initially hidden (4.0.0+).
+    AllForegroundInitiallyHidden = 4,
+}
+
 /// Applet attribute for LibraryApplet proxy (3.0.0+).
 ///
 /// Used with `OpenLibraryAppletProxyOld` (cmd 201).
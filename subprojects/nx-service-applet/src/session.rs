@@ -0,0 +1,229 @@
+//! Applet session bundling the service, the proxy, and its sub-interfaces.
+//!
+//! [`AppletSession::open`] performs the whole connection sequence (service,
+//! proxy, and the sub-interfaces every applet needs) and owns the result, so
+//! a single drop closes everything in the right order. If any step fails, the
+//! sessions opened so far are closed before the error is returned.
+
+use nx_service_sm::SmService;
+use nx_sf::service::Service;
+use nx_svc::process::Handle as ProcessHandle;
+
+use crate::{
+    AppletProxyService, AppletService, ApplicationFunctions, CommonStateGetter, SelfController,
+    WindowController, cmif,
+    cmif::{
+        ConnectError, ExitGracefullyError, GetApplicationFunctionsError, GetCommonStateGetterError,
+        GetSelfControllerError, GetWindowControllerError, OpenProxyError,
+    },
+    proto::AppletType,
+};
+
+/// Owned applet service session with its proxy and sub-interfaces.
+///
+/// The references returned by the accessors borrow the session: dropping it
+/// closes every sub-interface, the proxy, and the service, in that order.
+pub struct AppletSession {
+    service: AppletService,
+    proxy: AppletProxyService,
+    common_state_getter: CommonStateGetter,
+    self_controller: SelfController,
+    window_controller: Option<WindowController>,
+    application_functions: Option<ApplicationFunctions>,
+}
+
+impl AppletSession {
+    /// Connects to the applet service and opens every standard sub-interface.
+    ///
+    /// `IWindowController` and `IApplicationFunctions` are required for
+    /// [`AppletType::Application`]. For the other applet types,
+    /// `IWindowController` is opened if the proxy provides it, and
+    /// `IApplicationFunctions` is not opened.
+    ///
+    /// Returns `Ok(None)` if `applet_type` is `AppletType::None`.
+    pub fn open(
+        sm: &SmService,
+        applet_type: AppletType,
+        process_handle: ProcessHandle,
+    ) -> Result<Option<Self>, OpenSessionError> {
+        let Some(service) = crate::connect(sm, applet_type).map_err(OpenSessionError::Connect)?
+        else {
+            return Ok(None);
+        };
+
+        let mut opened = OpenedSessions::new();
+        opened.push(&service.0);
+
+        let proxy = service
+            .open_proxy(applet_type, process_handle)
+            .map_err(OpenSessionError::OpenProxy)?;
+        opened.push(&proxy.0);
+
+        let common_state_getter = proxy
+            .get_common_state_getter()
+            .map_err(OpenSessionError::GetCommonStateGetter)?;
+        opened.push(&common_state_getter.0);
+
+        let self_controller = proxy
+            .get_self_controller()
+            .map_err(OpenSessionError::GetSelfController)?;
+        opened.push(&self_controller.0);
+
+        let is_application = matches!(applet_type, AppletType::Application);
+
+        let window_controller = if is_application {
+            Some(
+                proxy
+                    .get_window_controller()
+                    .map_err(OpenSessionError::GetWindowController)?,
+            )
+        } else {
+            proxy.get_window_controller().ok()
+        };
+        if let Some(window_controller) = &window_controller {
+            opened.push(&window_controller.0);
+        }
+
+        let application_functions = if is_application {
+            Some(
+                proxy
+                    .get_application_functions()
+                    .map_err(OpenSessionError::GetApplicationFunctions)?,
+            )
+        } else {
+            None
+        };
+
+        opened.disarm();
+
+        Ok(Some(Self {
+            service,
+            proxy,
+            common_state_getter,
+            self_controller,
+            window_controller,
+            application_functions,
+        }))
+    }
+
+    /// Returns the applet service session.
+    #[inline]
+    pub fn service(&self) -> &AppletService {
+        &self.service
+    }
+
+    /// Returns the applet proxy session.
+    #[inline]
+    pub fn proxy(&self) -> &AppletProxyService {
+        &self.proxy
+    }
+
+    /// Returns the ICommonStateGetter sub-interface.
+    #[inline]
+    pub fn common_state_getter(&self) -> &CommonStateGetter {
+        &self.common_state_getter
+    }
+
+    /// Returns the ISelfController sub-interface.
+    #[inline]
+    pub fn self_controller(&self) -> &SelfController {
+        &self.self_controller
+    }
+
+    /// Returns the IWindowController sub-interface, if the proxy provided one.
+    ///
+    /// Always available for [`AppletType::Application`].
+    #[inline]
+    pub fn window_controller(&self) -> Option<&WindowController> {
+        self.window_controller.as_ref()
+    }
+
+    /// Returns the IApplicationFunctions sub-interface.
+    ///
+    /// Only available for [`AppletType::Application`].
+    #[inline]
+    pub fn application_functions(&self) -> Option<&ApplicationFunctions> {
+        self.application_functions.as_ref()
+    }
+
+    /// Runs [`AppletProxyService::exit_gracefully`] and closes the session.
+    ///
+    /// The same applet type restrictions apply. The session is closed even if
+    /// a step fails.
+    pub fn exit_gracefully(self, terminate_result: Option<u32>) -> Result<(), ExitGracefullyError> {
+        cmif::exit_gracefully(&self.proxy.0, terminate_result)
+    }
+}
+
+impl Drop for AppletSession {
+    fn drop(&mut self) {
+        // Sub-interfaces first, then the proxy and the service they live on
+        if let Some(application_functions) = &self.application_functions {
+            application_functions.0.close();
+        }
+        if let Some(window_controller) = &self.window_controller {
+            window_controller.0.close();
+        }
+        self.self_controller.0.close();
+        self.common_state_getter.0.close();
+        self.proxy.0.close();
+        self.service.0.close();
+    }
+}
+
+/// Sessions opened so far by [`AppletSession::open`].
+///
+/// Closes them in reverse order when dropped, unless disarmed.
+struct OpenedSessions {
+    services: [Option<Service>; 5],
+    len: usize,
+}
+
+impl OpenedSessions {
+    fn new() -> Self {
+        Self {
+            services: [None; 5],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, service: &Service) {
+        self.services[self.len] = Some(*service);
+        self.len += 1;
+    }
+
+    fn disarm(mut self) {
+        self.len = 0;
+    }
+}
+
+impl Drop for OpenedSessions {
+    fn drop(&mut self) {
+        for service in self.services[..self.len].iter().rev().flatten() {
+            service.close();
+        }
+    }
+}
+
+/// Error returned by [`AppletSession::open`].
+#[derive(Debug, thiserror::Error)]
+pub enum OpenSessionError {
+    /// Failed to connect to the applet service.
+    #[error("failed to connect to applet service")]
+    Connect(#[source] ConnectError),
+    /// Failed to open the proxy session.
+    #[error("failed to open applet proxy")]
+    OpenProxy(#[source] OpenProxyError),
+    /// Failed to get ICommonStateGetter.
+    #[error("failed to get ICommonStateGetter")]
+    GetCommonStateGetter(#[source] GetCommonStateGetterError),
+    /// Failed to get ISelfController.
+    #[error("failed to get ISelfController")]
+    GetSelfController(#[source] GetSelfControllerError),
+    /// Failed to get IWindowController.
+    #[error("failed to get IWindowController")]
+    GetWindowController(#[source] GetWindowControllerError),
+    /// Failed to get IApplicationFunctions.
+    #[error("failed to get IApplicationFunctions")]
+    GetApplicationFunctions(#[source] GetApplicationFunctionsError),
+}
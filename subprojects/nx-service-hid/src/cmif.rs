@@ -10,9 +10,15 @@ use nx_sf::cmif;
 use nx_svc::{
     ipc::{self, Handle as SessionHandle},
     mem::shmem::Handle as ShmemHandle,
+    sync::EventHandle,
 };
 
-use crate::proto::{applet_resource_cmds, cmds};
+use crate::{
+    proto::{active_vibration_device_list_cmds, applet_resource_cmds, cmds},
+    shmem::NpadId,
+    types::{NpadHandheldActivationMode, NpadJoyDeviceType, NpadJoyHoldType},
+    vibration::{VibrationDeviceHandle, VibrationValue},
+};
 
 /// Creates an IAppletResource sub-interface.
 ///
@@ -228,6 +234,364 @@ pub fn set_supported_npad_id_type(
     Ok(())
 }
 
+/// Sets the Joy-Con hold orientation.
+///
+/// This is IHidServer command 120.
+pub fn set_npad_joy_hold_type(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    hold_type: NpadJoyHoldType,
+) -> Result<(), SetNpadJoyHoldTypeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::SET_NPAD_JOY_HOLD_TYPE)
+        .context(0x20)
+        .data_size(16) // u64 ARUID + u64 hold_type
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u64 ARUID, u64 hold_type
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        aruid: u64,
+        hold_type: u64,
+    }
+    let input = Input {
+        aruid,
+        hold_type: hold_type as u64,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(SetNpadJoyHoldTypeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SetNpadJoyHoldTypeError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Assigns a single Joy-Con of a pair to an npad.
+///
+/// This is IHidServer command 123 (SetNpadJoyAssignmentModeSingle, 3.0.0+).
+pub fn set_npad_joy_assignment_mode_single(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    npad_id: NpadId,
+    device_type: NpadJoyDeviceType,
+) -> Result<(), SetNpadJoyAssignmentModeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::SET_NPAD_JOY_ASSIGNMENT_MODE_SINGLE)
+        .context(0x20)
+        .data_size(24) // u32 npad_id + u32 pad + u64 ARUID + u64 device_type
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u32 npad_id, u32 pad, u64 ARUID, u64 device_type
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        npad_id: u32,
+        pad: u32,
+        aruid: u64,
+        device_type: u64,
+    }
+    let input = Input {
+        npad_id: npad_id.to_raw(),
+        pad: 0,
+        aruid,
+        device_type: device_type as u64,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(SetNpadJoyAssignmentModeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SetNpadJoyAssignmentModeError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Assigns both Joy-Cons of a pair to an npad.
+///
+/// This is IHidServer command 124.
+pub fn set_npad_joy_assignment_mode_dual(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    npad_id: NpadId,
+) -> Result<(), SetNpadJoyAssignmentModeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::SET_NPAD_JOY_ASSIGNMENT_MODE_DUAL)
+        .context(0x20)
+        .data_size(16) // u32 npad_id + u32 pad + u64 ARUID
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u32 npad_id, u32 pad, u64 ARUID
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        npad_id: u32,
+        pad: u32,
+        aruid: u64,
+    }
+    let input = Input {
+        npad_id: npad_id.to_raw(),
+        pad: 0,
+        aruid,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(SetNpadJoyAssignmentModeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SetNpadJoyAssignmentModeError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Sets how Joy-Cons attached to the console are activated.
+///
+/// This is IHidServer command 128.
+pub fn set_npad_handheld_activation_mode(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    mode: NpadHandheldActivationMode,
+) -> Result<(), SetNpadHandheldActivationModeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::SET_NPAD_HANDHELD_ACTIVATION_MODE)
+        .context(0x20)
+        .data_size(16) // u64 ARUID + u64 mode
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u64 ARUID, u64 mode
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        aruid: u64,
+        mode: u64,
+    }
+    let input = Input {
+        aruid,
+        mode: mode as u64,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(SetNpadHandheldActivationModeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SetNpadHandheldActivationModeError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Acquires the event signaled when the style set of an npad changes.
+///
+/// This is IHidServer command 106.
+pub fn acquire_npad_style_set_update_event_handle(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    npad_id: NpadId,
+) -> Result<EventHandle, AcquireNpadStyleSetUpdateEventHandleError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::ACQUIRE_NPAD_STYLE_SET_UPDATE_EVENT_HANDLE)
+        .context(0x20)
+        .data_size(24) // u32 npad_id + u32 pad + u64 ARUID + u64 event_ptr
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u32 npad_id, u32 pad, u64 ARUID, u64 event_ptr
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        npad_id: u32,
+        pad: u32,
+        aruid: u64,
+        // Official software passes a pointer here; the service ignores it
+        event_ptr: u64,
+    }
+    let input = Input {
+        npad_id: npad_id.to_raw(),
+        pad: 0,
+        aruid,
+        event_ptr: 0,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session)
+        .map_err(AcquireNpadStyleSetUpdateEventHandleError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(AcquireNpadStyleSetUpdateEventHandleError::ParseResponse)?;
+
+    // Extract the copy handle from response
+    let handle = resp
+        .copy_handles
+        .first()
+        .copied()
+        .ok_or(AcquireNpadStyleSetUpdateEventHandleError::MissingHandle)?;
+
+    // SAFETY: Handle is from a valid IPC response.
+    Ok(unsafe { EventHandle::from_raw(handle) })
+}
+
+/// Sends a vibration value to a vibration device.
+///
+/// This is IHidServer command 201.
+pub fn send_vibration_value(
+    session: SessionHandle,
+    aruid: Option<Aruid>,
+    device: VibrationDeviceHandle,
+    value: VibrationValue,
+) -> Result<(), SendVibrationValueError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::SEND_VIBRATION_VALUE)
+        .context(0x20)
+        .data_size(32) // u32 device + VibrationValue + u32 pad + u64 ARUID
+        .send_pid()
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write input data: u32 device, VibrationValue, u32 pad, u64 ARUID
+    // SAFETY: req.data points to valid payload area with space for the struct.
+    let aruid = aruid.map(|a| a.to_raw()).unwrap_or(NO_ARUID);
+
+    #[repr(C)]
+    struct Input {
+        device: u32,
+        value: VibrationValue,
+        pad: u32,
+        aruid: u64,
+    }
+    let input = Input {
+        device: device.to_raw(),
+        value,
+        pad: 0,
+        aruid,
+    };
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(SendVibrationValueError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SendVibrationValueError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Creates an IActiveVibrationDeviceList sub-interface.
+///
+/// This is IHidServer command 203.
+pub fn create_active_vibration_device_list(
+    session: SessionHandle,
+) -> Result<SessionHandle, CreateActiveVibrationDeviceListError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(cmds::CREATE_ACTIVE_VIBRATION_DEVICE_LIST).build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let _req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    ipc::send_sync_request(session).map_err(CreateActiveVibrationDeviceListError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(CreateActiveVibrationDeviceListError::ParseResponse)?;
+
+    // Extract the move handle from response
+    let handle = resp
+        .move_handles
+        .first()
+        .copied()
+        .ok_or(CreateActiveVibrationDeviceListError::MissingHandle)?;
+
+    // SAFETY: Handle is from a valid IPC response.
+    Ok(unsafe { SessionHandle::from_raw(handle) })
+}
+
+/// Activates a vibration device.
+///
+/// This is IActiveVibrationDeviceList command 0.
+pub fn activate_vibration_device(
+    session: SessionHandle,
+    device: VibrationDeviceHandle,
+) -> Result<(), ActivateVibrationDeviceError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(
+        active_vibration_device_list_cmds::ACTIVATE_VIBRATION_DEVICE,
+    )
+    .data_size(4) // u32 device
+    .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // SAFETY: req.data points to valid payload area with space for u32.
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<u32>().cast_mut(), device.to_raw());
+    }
+
+    ipc::send_sync_request(session).map_err(ActivateVibrationDeviceError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(ActivateVibrationDeviceError::ParseResponse)?;
+
+    Ok(())
+}
+
 /// Activates touch screen input.
 ///
 /// This is IHidServer command 11.
@@ -437,6 +801,90 @@ pub enum SetSupportedNpadIdTypeError {
     ParseResponse(#[source] cmif::ParseResponseError),
 }
 
+/// Error returned by [`set_npad_joy_hold_type`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetNpadJoyHoldTypeError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by [`set_npad_joy_assignment_mode_single`] and
+/// [`set_npad_joy_assignment_mode_dual`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetNpadJoyAssignmentModeError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by [`set_npad_handheld_activation_mode`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetNpadHandheldActivationModeError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by [`acquire_npad_style_set_update_event_handle`].
+#[derive(Debug, thiserror::Error)]
+pub enum AcquireNpadStyleSetUpdateEventHandleError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+    /// Missing event handle in response.
+    #[error("missing event handle in response")]
+    MissingHandle,
+}
+
+/// Error returned by [`send_vibration_value`].
+#[derive(Debug, thiserror::Error)]
+pub enum SendVibrationValueError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by [`create_active_vibration_device_list`].
+#[derive(Debug, thiserror::Error)]
+pub enum CreateActiveVibrationDeviceListError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+    /// Missing session handle in response.
+    #[error("missing session handle in response")]
+    MissingHandle,
+}
+
+/// Error returned by [`activate_vibration_device`].
+#[derive(Debug, thiserror::Error)]
+pub enum ActivateVibrationDeviceError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
 /// Error returned by [`activate_touch_screen`].
 #[derive(Debug, thiserror::Error)]
 pub enum ActivateTouchScreenError {
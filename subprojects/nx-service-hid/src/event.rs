@@ -0,0 +1,55 @@
+//! Owned HID service events.
+
+use nx_svc::sync::{self, EventHandle, WaitSyncError};
+
+/// Event handle acquired from the HID service.
+///
+/// The handle is a copy handed out by the service for this process and is
+/// closed when the `Event` is dropped.
+#[derive(Debug)]
+pub struct Event(EventHandle);
+
+impl Event {
+    /// Takes ownership of an event handle.
+    pub(crate) fn new(handle: EventHandle) -> Self {
+        Self(handle)
+    }
+
+    /// Returns the underlying event handle, e.g. to wait on it together with
+    /// other handles.
+    ///
+    /// The handle stays owned by this event; do not close it.
+    #[inline]
+    pub fn handle(&self) -> EventHandle {
+        self.0
+    }
+
+    /// Waits up to `timeout_ns` nanoseconds for the event to be signaled,
+    /// then clears it.
+    ///
+    /// Use `u64::MAX` for an infinite wait, `0` for an immediate check.
+    pub fn wait(&self, timeout_ns: u64) -> Result<(), WaitSyncError> {
+        // SAFETY: The handle is owned by this event and stays open until it
+        // is dropped.
+        unsafe { sync::wait_synchronization_single(&self.0, timeout_ns) }?;
+        self.clear();
+        Ok(())
+    }
+
+    /// Clears the signaled state of the event.
+    pub fn clear(&self) {
+        // Resetting an event that is not signaled fails with InvalidState,
+        // which leaves it cleared all the same
+        // SAFETY: The handle is owned by this event and refers to a readable
+        // event.
+        let _ = unsafe { sync::reset_signal(&self.0) };
+    }
+}
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        // SAFETY: The handle is owned by this event and is closed exactly
+        // once.
+        let _ = unsafe { nx_svc::raw::close_handle(self.0.to_raw()) };
+    }
+}
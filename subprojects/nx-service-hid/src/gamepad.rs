@@ -0,0 +1,280 @@
+//! High-level controller polling over the npad shared memory.
+//!
+//! [`Gamepad`] is the equivalent of libnx's `PadState`: call
+//! [`Gamepad::update`] once per frame, then query the held buttons, the
+//! buttons pressed or released since the previous update, and the stick
+//! positions.
+//!
+//! Stick positions are cleaned up with a radial deadzone, configured with
+//! [`StickConfig`]; the raw positions remain available for callers applying
+//! their own response curve.
+//!
+//! The npads must be configured beforehand with
+//! [`HidService::set_supported_npad_style_set`],
+//! [`HidService::set_supported_npad_id_type`], and
+//! [`HidService::activate_npad`].
+
+use crate::{
+    HidService,
+    shmem::{AnalogStickState, NpadAttributes, NpadButtons, NpadId, NpadStyleSet, npad},
+    six_axis::inv_sqrt,
+};
+
+/// Deadzones applied to the stick positions of a [`Gamepad`].
+///
+/// Both deadzones are fractions of the full stick range, measured on the
+/// distance from the center (radial), not on each axis separately, so the
+/// direction of the stick is preserved:
+///
+/// - Positions closer to the center than `inner_deadzone` read as centered,
+///   hiding the noise of a resting stick.
+/// - Positions within `outer_deadzone` of the edge read as fully pushed, so
+///   worn sticks that no longer reach the edge still get full deflection.
+///
+/// The range in between is rescaled to `0.0..=1.0`. If the deadzones overlap
+/// (`inner_deadzone + outer_deadzone >= 1.0`), the stick reads either
+/// centered or fully pushed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickConfig {
+    /// Fraction of the range around the center that reads as centered.
+    pub inner_deadzone: f32,
+    /// Fraction of the range at the edge that reads as fully pushed.
+    pub outer_deadzone: f32,
+}
+
+impl StickConfig {
+    /// Default deadzones: 10% around the center and 10% at the edge.
+    pub const DEFAULT: Self = Self {
+        inner_deadzone: 0.1,
+        outer_deadzone: 0.1,
+    };
+
+    /// No deadzones: the positions are only normalized.
+    pub const NONE: Self = Self {
+        inner_deadzone: 0.0,
+        outer_deadzone: 0.0,
+    };
+
+    /// Applies the deadzones to a raw stick position.
+    ///
+    /// Returns the position with each axis in `-1.0..=1.0` and a distance
+    /// from the center of at most `1.0`.
+    pub fn apply(&self, stick: AnalogStickState) -> (f32, f32) {
+        let max = npad::ANALOG_STICK_MAX as f32;
+        let (x, y) = (stick.x as f32 / max, stick.y as f32 / max);
+
+        let norm_sq = x * x + y * y;
+        let inner = self.inner_deadzone.max(0.0);
+        if norm_sq == 0.0 || norm_sq <= inner * inner {
+            return (0.0, 0.0);
+        }
+
+        let inv_norm = inv_sqrt(norm_sq);
+        let norm = norm_sq * inv_norm;
+
+        let range = 1.0 - inner - self.outer_deadzone.max(0.0);
+        let scaled = if range > 0.0 {
+            ((norm - inner) / range).min(1.0)
+        } else {
+            1.0
+        };
+
+        (x * inv_norm * scaled, y * inv_norm * scaled)
+    }
+}
+
+impl Default for StickConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Controller state merged from one or more npads.
+///
+/// Reading several npads lets a single `Gamepad` follow the player whether
+/// the Joy-Cons are attached to the console ([`NpadId::Handheld`]) or
+/// detached ([`NpadId::No1`]): buttons are combined and, for each stick, the
+/// one pushed furthest wins.
+pub struct Gamepad<'a> {
+    hid: &'a HidService,
+    /// Bitmask of npad entry indices to read.
+    id_mask: u16,
+    style_set: NpadStyleSet,
+    connection_changed: bool,
+    attributes: NpadAttributes,
+    buttons_cur: NpadButtons,
+    buttons_old: NpadButtons,
+    sticks: [AnalogStickState; 2],
+    stick_config: StickConfig,
+    stick_values: [(f32, f32); 2],
+}
+
+impl<'a> Gamepad<'a> {
+    /// Creates a gamepad reading the given npads.
+    pub fn new(hid: &'a HidService, ids: &[NpadId]) -> Self {
+        let id_mask = ids.iter().fold(0u16, |mask, id| mask | (1 << id.index()));
+
+        Self {
+            hid,
+            id_mask,
+            style_set: NpadStyleSet::empty(),
+            connection_changed: false,
+            attributes: NpadAttributes::empty(),
+            buttons_cur: NpadButtons::empty(),
+            buttons_old: NpadButtons::empty(),
+            sticks: [AnalogStickState::default(); 2],
+            stick_config: StickConfig::DEFAULT,
+            stick_values: [(0.0, 0.0); 2],
+        }
+    }
+
+    /// Creates a gamepad reading player 1 and the handheld controller.
+    ///
+    /// This matches libnx's `padInitializeDefault`.
+    #[inline]
+    pub fn new_default(hid: &'a HidService) -> Self {
+        Self::new(hid, &[NpadId::No1, NpadId::Handheld])
+    }
+
+    /// Reads the latest state of every configured npad.
+    ///
+    /// The previously held buttons are kept for edge detection, so this
+    /// should be called exactly once per frame.
+    pub fn update(&mut self) {
+        let shmem = self.hid.shared_memory();
+
+        let mut style_set = NpadStyleSet::empty();
+        let mut attributes = NpadAttributes::empty();
+        let mut buttons = NpadButtons::empty();
+        let mut sticks = [AnalogStickState::default(); 2];
+
+        for index in 0..npad::NPAD_ENTRY_COUNT {
+            if self.id_mask & (1 << index) == 0 {
+                continue;
+            }
+
+            let entry = &shmem.npad.entries[index];
+            let Some(state) = entry.latest() else {
+                continue;
+            };
+            if !state.is_connected() {
+                continue;
+            }
+
+            style_set |= entry.read_style_set();
+            attributes |= state.attributes;
+            buttons |= state.buttons;
+            merge_stick(&mut sticks[0], state.analog_stick_l);
+            merge_stick(&mut sticks[1], state.analog_stick_r);
+        }
+
+        self.connection_changed = self.style_set.is_empty() != style_set.is_empty();
+        self.style_set = style_set;
+        self.attributes = attributes;
+        self.buttons_old = self.buttons_cur;
+        self.buttons_cur = buttons;
+        self.sticks = sticks;
+        self.stick_values = sticks.map(|stick| self.stick_config.apply(stick));
+    }
+
+    /// Returns the deadzones applied to the stick positions.
+    #[inline]
+    pub fn stick_config(&self) -> StickConfig {
+        self.stick_config
+    }
+
+    /// Sets the deadzones applied to the stick positions.
+    ///
+    /// Takes effect on the next [`update`](Self::update).
+    #[inline]
+    pub fn set_stick_config(&mut self, config: StickConfig) {
+        self.stick_config = config;
+    }
+
+    /// Returns `true` if any of the configured npads is connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.attributes.contains(NpadAttributes::IS_CONNECTED)
+    }
+
+    /// Returns `true` if a controller appeared or vanished in the last update.
+    ///
+    /// Set when the combined style set went from empty to non-empty or back,
+    /// i.e. when the first controller connected or the last one disconnected.
+    /// To be notified without polling, wait on
+    /// [`HidService::get_npad_style_set_update_event`].
+    #[inline]
+    pub fn connection_changed(&self) -> bool {
+        self.connection_changed
+    }
+
+    /// Returns the combined style set of the connected npads.
+    #[inline]
+    pub fn style_set(&self) -> NpadStyleSet {
+        self.style_set
+    }
+
+    /// Returns the combined attributes of the connected npads.
+    #[inline]
+    pub fn attributes(&self) -> NpadAttributes {
+        self.attributes
+    }
+
+    /// Returns the buttons held down as of the last update.
+    #[inline]
+    pub fn buttons_held(&self) -> NpadButtons {
+        self.buttons_cur
+    }
+
+    /// Returns the buttons pressed since the previous update.
+    #[inline]
+    pub fn buttons_down(&self) -> NpadButtons {
+        self.buttons_cur & !self.buttons_old
+    }
+
+    /// Returns the buttons released since the previous update.
+    #[inline]
+    pub fn buttons_up(&self) -> NpadButtons {
+        !self.buttons_cur & self.buttons_old
+    }
+
+    /// Returns the raw left stick position.
+    #[inline]
+    pub fn raw_stick_left(&self) -> AnalogStickState {
+        self.sticks[0]
+    }
+
+    /// Returns the raw right stick position.
+    #[inline]
+    pub fn raw_stick_right(&self) -> AnalogStickState {
+        self.sticks[1]
+    }
+
+    /// Returns the left stick position, each axis normalized to `-1.0..=1.0`.
+    ///
+    /// The [`StickConfig`] deadzones are applied.
+    #[inline]
+    pub fn stick_left(&self) -> (f32, f32) {
+        self.stick_values[0]
+    }
+
+    /// Returns the right stick position, each axis normalized to `-1.0..=1.0`.
+    ///
+    /// The [`StickConfig`] deadzones are applied.
+    #[inline]
+    pub fn stick_right(&self) -> (f32, f32) {
+        self.stick_values[1]
+    }
+}
+
+/// Replaces `acc` with `stick` if `stick` is pushed further from the center.
+fn merge_stick(acc: &mut AnalogStickState, stick: AnalogStickState) {
+    let magnitude = |s: AnalogStickState| {
+        let (x, y) = (s.x as i64, s.y as i64);
+        x * x + y * y
+    };
+
+    if magnitude(stick) > magnitude(*acc) {
+        *acc = stick;
+    }
+}
@@ -10,32 +10,63 @@
 //! - Gesture recognition
 //!
 //! The HID service uses shared memory (0x40000 bytes) with lock-free LIFO ring
-//! buffers for reading input state.
+//! buffers for reading input state. For controllers, [`Gamepad`] wraps the
+//! npad buffers with per-frame polling and edge detection, and
+//! [`SixAxisFusion`] turns six-axis samples into an orientation.
+//!
+//! # Stick deadzones
+//!
+//! [`Gamepad::stick_left`] and [`Gamepad::stick_right`] apply a radial
+//! deadzone of 10% around the center and 10% at the edge by default
+//! ([`StickConfig::DEFAULT`]). This is a behavior change: they used to only
+//! normalize each axis of the raw position, so small deflections now read as
+//! centered and the live range is rescaled. Setting [`StickConfig::NONE`]
+//! with [`Gamepad::set_stick_config`] removes the deadzones, though
+//! diagonals are still clamped to the unit circle; callers needing the
+//! previous per-axis values can normalize [`Gamepad::raw_stick_left`] and
+//! [`Gamepad::raw_stick_right`] themselves.
 
 #![no_std]
 
 extern crate nx_panic_handler; // Provide #![panic_handler]
 
-use core::ptr::NonNull;
+use core::{ptr::NonNull, time::Duration};
 
 use nx_service_applet::aruid::Aruid;
 use nx_service_sm::SmService;
 use nx_sf::service::Service;
 use nx_svc::ipc::Handle as SessionHandle;
 use nx_sys_mem::shmem::{self as sys_shmem, Mapped, Permissions};
+use nx_sys_thread::{Scope, ScopedJoinHandle, SpawnError};
 
 mod cmif;
+mod event;
+mod gamepad;
 mod proto;
 pub mod shmem;
+pub mod six_axis;
+pub mod types;
+pub mod vibration;
 
-use self::shmem::HidSharedMemory;
 pub use self::{
     cmif::{
-        ActivateGestureError, ActivateKeyboardError, ActivateMouseError, ActivateNpadError,
-        ActivateTouchScreenError, CreateAppletResourceError, GetSharedMemoryHandleError,
+        AcquireNpadStyleSetUpdateEventHandleError, ActivateGestureError, ActivateKeyboardError,
+        ActivateMouseError, ActivateNpadError, ActivateTouchScreenError,
+        ActivateVibrationDeviceError, CreateActiveVibrationDeviceListError,
+        CreateAppletResourceError, GetSharedMemoryHandleError, SendVibrationValueError,
+        SetNpadHandheldActivationModeError, SetNpadJoyAssignmentModeError, SetNpadJoyHoldTypeError,
         SetSupportedNpadIdTypeError, SetSupportedNpadStyleSetError,
     },
+    event::Event,
+    gamepad::{Gamepad, StickConfig},
     proto::SERVICE_NAME,
+    six_axis::{Quaternion, SixAxisFusion},
+};
+use self::{
+    shmem::{HidSharedMemory, NpadId},
+    vibration::{
+        MAX_VIBRATION_PATTERN_DURATION_MS, VibrationDeviceHandle, VibrationPattern, VibrationValue,
+    },
 };
 
 /// HID service (IHidServer) session wrapper.
@@ -110,6 +141,152 @@ impl HidService {
         cmif::set_supported_npad_id_type(self.service.session, self.aruid, ids)
     }
 
+    /// Get the event signaled when the style set of `npad_id` changes.
+    ///
+    /// The style set changes when a controller is connected to or
+    /// disconnected from the npad, e.g. when Joy-Cons are attached to or
+    /// detached from the console ([`NpadId::Handheld`]). Every id accepted by
+    /// [`set_supported_npad_id_type`](Self::set_supported_npad_id_type)
+    /// supports the event: `No1` to `No8`, `Handheld`, and `Other`. Only ids
+    /// that were set as supported are ever signaled.
+    ///
+    /// [`Gamepad::connection_changed`] reports the same transitions when
+    /// polling.
+    #[inline]
+    pub fn get_npad_style_set_update_event(
+        &self,
+        npad_id: NpadId,
+    ) -> Result<Event, AcquireNpadStyleSetUpdateEventHandleError> {
+        cmif::acquire_npad_style_set_update_event_handle(self.service.session, self.aruid, npad_id)
+            .map(Event::new)
+    }
+
+    /// Set the Joy-Con hold orientation.
+    ///
+    /// Applies to Joy-Cons in single assignment mode, which are reported as
+    /// sideways controllers with [`NpadJoyHoldType::Horizontal`].
+    ///
+    /// [`NpadJoyHoldType::Horizontal`]: types::NpadJoyHoldType::Horizontal
+    #[inline]
+    pub fn set_npad_joy_hold_type(
+        &self,
+        hold_type: types::NpadJoyHoldType,
+    ) -> Result<(), SetNpadJoyHoldTypeError> {
+        cmif::set_npad_joy_hold_type(self.service.session, self.aruid, hold_type)
+    }
+
+    /// Assign both Joy-Cons of a pair to `npad_id`, as one controller.
+    #[inline]
+    pub fn set_npad_joy_assignment_mode_dual(
+        &self,
+        npad_id: NpadId,
+    ) -> Result<(), SetNpadJoyAssignmentModeError> {
+        cmif::set_npad_joy_assignment_mode_dual(self.service.session, self.aruid, npad_id)
+    }
+
+    /// Assign a single Joy-Con of a pair to `npad_id`, as its own controller.
+    ///
+    /// `side` selects the Joy-Con kept on `npad_id` if a pair was assigned to
+    /// it; the other one is moved to a free npad. Requires firmware 3.0.0+.
+    #[inline]
+    pub fn set_npad_joy_assignment_mode_single(
+        &self,
+        npad_id: NpadId,
+        side: types::NpadJoyDeviceType,
+    ) -> Result<(), SetNpadJoyAssignmentModeError> {
+        cmif::set_npad_joy_assignment_mode_single(self.service.session, self.aruid, npad_id, side)
+    }
+
+    /// Set how Joy-Cons attached to the console are activated.
+    #[inline]
+    pub fn set_npad_handheld_activation_mode(
+        &self,
+        mode: types::NpadHandheldActivationMode,
+    ) -> Result<(), SetNpadHandheldActivationModeError> {
+        cmif::set_npad_handheld_activation_mode(self.service.session, self.aruid, mode)
+    }
+
+    /// Initialize a vibration device, before sending it values.
+    pub fn initialize_vibration_device(
+        &self,
+        device: VibrationDeviceHandle,
+    ) -> Result<(), InitializeVibrationDeviceError> {
+        let handle = cmif::create_active_vibration_device_list(self.service.session)
+            .map_err(InitializeVibrationDeviceError::CreateActiveVibrationDeviceList)?;
+        let list = Service::new_subservice(&self.service, handle);
+
+        let result = cmif::activate_vibration_device(list.session, device)
+            .map_err(InitializeVibrationDeviceError::ActivateVibrationDevice);
+        list.close();
+
+        result
+    }
+
+    /// Send a vibration value to a device.
+    ///
+    /// The device keeps playing the value until another one is sent; send
+    /// [`VibrationValue::NEUTRAL`] to stop it.
+    #[inline]
+    pub fn send_vibration_value(
+        &self,
+        device: VibrationDeviceHandle,
+        value: VibrationValue,
+    ) -> Result<(), SendVibrationValueError> {
+        cmif::send_vibration_value(self.service.session, self.aruid, device, value)
+    }
+
+    /// Play a vibration pattern on a device, then stop it.
+    ///
+    /// Sends the value of each step and sleeps for its duration. This blocks
+    /// the calling thread for the whole pattern, up to
+    /// [`MAX_VIBRATION_PATTERN_DURATION_MS`]; use
+    /// [`spawn_vibration_pattern`](Self::spawn_vibration_pattern) to keep
+    /// e.g. a frame loop running meanwhile.
+    ///
+    /// If sending a value fails, e.g. because the controller was disconnected
+    /// and the device handle is no longer valid, playback stops and the error
+    /// is returned. The device is still sent a stop value on a best-effort
+    /// basis.
+    pub fn play_vibration_pattern(
+        &self,
+        device: VibrationDeviceHandle,
+        pattern: VibrationPattern<'_>,
+    ) -> Result<(), SendVibrationValueError> {
+        let mut remaining_ms = MAX_VIBRATION_PATTERN_DURATION_MS;
+
+        for step in pattern.steps() {
+            if remaining_ms == 0 {
+                break;
+            }
+
+            if let Err(err) = self.send_vibration_value(device, step.value) {
+                let _ = self.send_vibration_value(device, VibrationValue::NEUTRAL);
+                return Err(err);
+            }
+
+            let duration_ms = step.duration_ms.min(remaining_ms);
+            nx_sys_thread::sleep(Duration::from_millis(duration_ms.into()));
+            remaining_ms -= duration_ms;
+        }
+
+        self.send_vibration_value(device, VibrationValue::NEUTRAL)
+    }
+
+    /// Play a vibration pattern on a device from a new thread in `scope`.
+    ///
+    /// The thread runs [`play_vibration_pattern`](Self::play_vibration_pattern)
+    /// and returns its result through the join handle. The scope joins it
+    /// before returning, so the service and the pattern's steps only need to
+    /// outlive the scope.
+    pub fn spawn_vibration_pattern<'scope>(
+        &'scope self,
+        scope: &'scope Scope<'scope, '_>,
+        device: VibrationDeviceHandle,
+        pattern: VibrationPattern<'scope>,
+    ) -> Result<ScopedJoinHandle<'scope, Result<(), SendVibrationValueError>>, SpawnError> {
+        scope.spawn(move || self.play_vibration_pattern(device, pattern))
+    }
+
     /// Activate touch screen input.
     #[inline]
     pub fn activate_touch_screen(&self) -> Result<(), ActivateTouchScreenError> {
@@ -151,23 +328,13 @@ pub fn connect(sm: &SmService, aruid: Option<Aruid>) -> Result<HidService, Conne
         .get_service_handle_cmif(SERVICE_NAME)
         .map_err(ConnectError::GetService)?;
 
-    let service = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let service = Service::new(handle);
 
     // Create IAppletResource sub-interface
     let applet_resource_handle = cmif::create_applet_resource(service.session, aruid)
         .map_err(ConnectError::CreateAppletResource)?;
 
-    let applet_resource = Service {
-        session: applet_resource_handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let applet_resource = Service::new_subservice(&service, applet_resource_handle);
 
     // Get shared memory handle from IAppletResource
     let shmem_handle = cmif::get_shared_memory_handle(applet_resource.session)
@@ -210,3 +377,14 @@ pub enum ConnectError {
     #[error("null pointer from mapped memory")]
     NullPointer,
 }
+
+/// Error returned by [`HidService::initialize_vibration_device`].
+#[derive(Debug, thiserror::Error)]
+pub enum InitializeVibrationDeviceError {
+    /// Failed to create the active vibration device list.
+    #[error("failed to create active vibration device list")]
+    CreateActiveVibrationDeviceList(#[source] CreateActiveVibrationDeviceListError),
+    /// Failed to activate the vibration device.
+    #[error("failed to activate vibration device")]
+    ActivateVibrationDevice(#[source] ActivateVibrationDeviceError),
+}
@@ -25,10 +25,24 @@ pub mod cmds {
     // Npad
     pub const SET_SUPPORTED_NPAD_STYLE_SET: u32 = 100;
     pub const SET_SUPPORTED_NPAD_ID_TYPE: u32 = 102;
+    pub const ACQUIRE_NPAD_STYLE_SET_UPDATE_EVENT_HANDLE: u32 = 106;
     pub const ACTIVATE_NPAD_WITH_REVISION: u32 = 109;
+    pub const SET_NPAD_JOY_HOLD_TYPE: u32 = 120;
+    pub const SET_NPAD_JOY_ASSIGNMENT_MODE_SINGLE: u32 = 123;
+    pub const SET_NPAD_JOY_ASSIGNMENT_MODE_DUAL: u32 = 124;
+    pub const SET_NPAD_HANDHELD_ACTIVATION_MODE: u32 = 128;
+
+    // Vibration
+    pub const SEND_VIBRATION_VALUE: u32 = 201;
+    pub const CREATE_ACTIVE_VIBRATION_DEVICE_LIST: u32 = 203;
 }
 
 /// IAppletResource command IDs
 pub mod applet_resource_cmds {
     pub const GET_SHARED_MEMORY_HANDLE: u32 = 0;
 }
+
+/// IActiveVibrationDeviceList command IDs
+pub mod active_vibration_device_list_cmds {
+    pub const ACTIVATE_VIBRATION_DEVICE: u32 = 0;
+}
@@ -1,9 +1,17 @@
 //! Shared memory layout and access for HID service.
 
+pub mod button;
+pub mod keyboard;
 pub mod layout;
 pub mod lifo;
+pub mod mouse;
+pub mod npad;
 pub mod types;
 
+pub use button::{ButtonState, ButtonTracker};
+pub use keyboard::{KeyboardKey, KeyboardModifiers, KeyboardState};
 pub use layout::HidSharedMemory;
 pub use lifo::{HidCommonLifoHeader, get_states};
+pub use mouse::{MouseAttributes, MouseButtons, MouseState};
+pub use npad::{NpadAttributes, NpadButtons, NpadCommonState, NpadId, NpadStyleSet};
 pub use types::*;
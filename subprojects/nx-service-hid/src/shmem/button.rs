@@ -0,0 +1,146 @@
+//! HOME, sleep and capture button shared memory sections.
+//!
+//! The three system buttons share one section format: a LIFO of
+//! [`ButtonState`] samples. HID only fills them once the button has been
+//! activated through `hid:sys` (`ActivateHomeButton`, cmd 111;
+//! `ActivateSleepButton`, cmd 131; `ActivateCaptureButton`, cmd 151), which
+//! regular applications cannot access. Until then the LIFOs stay empty and
+//! the reads return `None`. The sections are present since 1.0.0.
+
+use core::ptr;
+
+use static_assertions::const_assert_eq;
+
+use super::{
+    lifo::{HidCommonLifoHeader, get_states},
+    types::InputState,
+};
+
+/// Number of entries in a system button LIFO ring buffer.
+pub const BUTTON_LIFO_ENTRY_COUNT: usize = 17;
+
+/// System button state sample.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ButtonState {
+    /// Monotonically increasing sample counter.
+    pub sampling_number: u64,
+    /// Button bitfield; bit 0 is set while the button is held.
+    pub buttons: u64,
+}
+
+impl ButtonState {
+    /// Returns `true` if the button is held down in this sample.
+    #[inline]
+    pub fn is_held(&self) -> bool {
+        self.buttons & 1 != 0
+    }
+}
+
+impl InputState for ButtonState {
+    type Storage = ButtonStateAtomicStorage;
+
+    fn sampling_number(&self) -> u64 {
+        self.sampling_number
+    }
+
+    unsafe fn load_from_storage(storage: &Self::Storage) -> Self {
+        // SAFETY: Caller guarantees storage points to a valid LIFO entry.
+        unsafe { ptr::read_volatile(&storage.state) }
+    }
+}
+
+/// System button LIFO entry: sampling number followed by the state.
+#[repr(C)]
+pub struct ButtonStateAtomicStorage {
+    pub sampling_number: u64,
+    pub state: ButtonState,
+}
+
+/// System button LIFO ring buffer.
+#[repr(C)]
+pub struct ButtonLifo {
+    pub header: HidCommonLifoHeader,
+    pub storage: [ButtonStateAtomicStorage; BUTTON_LIFO_ENTRY_COUNT],
+}
+
+/// System button section of HID shared memory (0x200 bytes).
+#[repr(C)]
+pub struct HidButtonSharedMemoryFormat {
+    pub lifo: ButtonLifo,
+    _padding: [u8; 0x48],
+}
+
+const_assert_eq!(size_of::<ButtonStateAtomicStorage>(), 0x18);
+const_assert_eq!(size_of::<HidButtonSharedMemoryFormat>(), 0x200);
+
+impl HidButtonSharedMemoryFormat {
+    /// Reads the most recent button state.
+    ///
+    /// Returns `None` if no sample is available or a consistent read could not
+    /// be obtained.
+    pub fn latest(&self) -> Option<ButtonState> {
+        let mut out = [ButtonState::default()];
+        match get_states(&self.lifo.header, &self.lifo.storage, &mut out) {
+            0 => None,
+            _ => Some(out[0]),
+        }
+    }
+}
+
+/// Edge detection for a system button across frames.
+///
+/// Feed it the latest [`ButtonState`] once per frame with
+/// [`update`](Self::update), then query whether the button went down or up
+/// since the previous update.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ButtonTracker {
+    held_cur: bool,
+    held_old: bool,
+    sampling_number: u64,
+}
+
+impl ButtonTracker {
+    /// Creates a tracker with the button released.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            held_cur: false,
+            held_old: false,
+            sampling_number: 0,
+        }
+    }
+
+    /// Records the latest state; `None` counts as released.
+    pub fn update(&mut self, state: Option<ButtonState>) {
+        self.held_old = self.held_cur;
+        self.held_cur = state.is_some_and(|s| s.is_held());
+        if let Some(state) = state {
+            self.sampling_number = state.sampling_number;
+        }
+    }
+
+    /// Returns the sampling number of the last recorded sample.
+    #[inline]
+    pub fn sampling_number(&self) -> u64 {
+        self.sampling_number
+    }
+
+    /// Returns `true` if the button is held as of the last update.
+    #[inline]
+    pub fn is_held(&self) -> bool {
+        self.held_cur
+    }
+
+    /// Returns `true` if the button was pressed since the previous update.
+    #[inline]
+    pub fn is_down(&self) -> bool {
+        self.held_cur && !self.held_old
+    }
+
+    /// Returns `true` if the button was released since the previous update.
+    #[inline]
+    pub fn is_up(&self) -> bool {
+        !self.held_cur && self.held_old
+    }
+}
@@ -0,0 +1,253 @@
+//! Keyboard shared memory section and state types.
+
+use core::ptr;
+
+use bitflags::bitflags;
+use static_assertions::const_assert_eq;
+
+use super::{
+    lifo::{HidCommonLifoHeader, get_states},
+    types::InputState,
+};
+
+/// Number of entries in the keyboard LIFO ring buffer.
+pub const KEYBOARD_LIFO_ENTRY_COUNT: usize = 17;
+
+/// Keyboard state sample.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyboardState {
+    /// Monotonically increasing sample counter.
+    pub sampling_number: u64,
+    /// Active modifier keys and lock states.
+    pub modifiers: KeyboardModifiers,
+    /// Key bitmap indexed by USB HID usage ID (256 bits).
+    pub keys: [u64; 4],
+}
+
+impl KeyboardState {
+    /// Returns `true` if `key` is held down in this sample.
+    #[inline]
+    pub fn is_pressed(&self, key: KeyboardKey) -> bool {
+        let usage = key as usize;
+        (self.keys[usage / 64] >> (usage % 64)) & 1 != 0
+    }
+}
+
+impl InputState for KeyboardState {
+    type Storage = KeyboardStateAtomicStorage;
+
+    fn sampling_number(&self) -> u64 {
+        self.sampling_number
+    }
+
+    unsafe fn load_from_storage(storage: &Self::Storage) -> Self {
+        // SAFETY: Caller guarantees storage points to a valid LIFO entry.
+        unsafe { ptr::read_volatile(&storage.state) }
+    }
+}
+
+/// Keyboard LIFO entry: sampling number followed by the state.
+#[repr(C)]
+pub struct KeyboardStateAtomicStorage {
+    pub sampling_number: u64,
+    pub state: KeyboardState,
+}
+
+/// Keyboard LIFO ring buffer.
+#[repr(C)]
+pub struct KeyboardLifo {
+    pub header: HidCommonLifoHeader,
+    pub storage: [KeyboardStateAtomicStorage; KEYBOARD_LIFO_ENTRY_COUNT],
+}
+
+/// Keyboard section of HID shared memory (0x400 bytes).
+#[repr(C)]
+pub struct HidKeyboardSharedMemoryFormat {
+    pub lifo: KeyboardLifo,
+    _padding: [u8; 0x28],
+}
+
+const_assert_eq!(size_of::<KeyboardState>(), 0x30);
+const_assert_eq!(size_of::<KeyboardStateAtomicStorage>(), 0x38);
+const_assert_eq!(size_of::<HidKeyboardSharedMemoryFormat>(), 0x400);
+
+impl HidKeyboardSharedMemoryFormat {
+    /// Reads the most recent keyboard state.
+    ///
+    /// Returns `None` if no sample is available or a consistent read could not
+    /// be obtained.
+    pub fn latest(&self) -> Option<KeyboardState> {
+        let mut out = [KeyboardState::default()];
+        match get_states(&self.lifo.header, &self.lifo.storage, &mut out) {
+            0 => None,
+            _ => Some(out[0]),
+        }
+    }
+}
+
+bitflags! {
+    /// Keyboard modifier keys and lock states.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct KeyboardModifiers: u64 {
+        /// Either Control key is held.
+        const CONTROL = 1 << 0;
+        /// Either Shift key is held.
+        const SHIFT = 1 << 1;
+        /// Left Alt is held.
+        const LEFT_ALT = 1 << 2;
+        /// Right Alt is held.
+        const RIGHT_ALT = 1 << 3;
+        /// Either GUI (Windows/Command) key is held.
+        const GUI = 1 << 4;
+        /// Caps Lock is active.
+        const CAPS_LOCK = 1 << 8;
+        /// Scroll Lock is active.
+        const SCROLL_LOCK = 1 << 9;
+        /// Num Lock is active.
+        const NUM_LOCK = 1 << 10;
+        /// Katakana input mode is active.
+        const KATAKANA = 1 << 11;
+        /// Hiragana input mode is active.
+        const HIRAGANA = 1 << 12;
+    }
+}
+
+/// Keyboard key, identified by its USB HID usage ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum KeyboardKey {
+    A = 4,
+    B = 5,
+    C = 6,
+    D = 7,
+    E = 8,
+    F = 9,
+    G = 10,
+    H = 11,
+    I = 12,
+    J = 13,
+    K = 14,
+    L = 15,
+    M = 16,
+    N = 17,
+    O = 18,
+    P = 19,
+    Q = 20,
+    R = 21,
+    S = 22,
+    T = 23,
+    U = 24,
+    V = 25,
+    W = 26,
+    X = 27,
+    Y = 28,
+    Z = 29,
+    D1 = 30,
+    D2 = 31,
+    D3 = 32,
+    D4 = 33,
+    D5 = 34,
+    D6 = 35,
+    D7 = 36,
+    D8 = 37,
+    D9 = 38,
+    D0 = 39,
+    Return = 40,
+    Escape = 41,
+    Backspace = 42,
+    Tab = 43,
+    Space = 44,
+    Minus = 45,
+    Plus = 46,
+    OpenBracket = 47,
+    CloseBracket = 48,
+    Pipe = 49,
+    Tilde = 50,
+    Semicolon = 51,
+    Quote = 52,
+    Backquote = 53,
+    Comma = 54,
+    Period = 55,
+    Slash = 56,
+    CapsLock = 57,
+    F1 = 58,
+    F2 = 59,
+    F3 = 60,
+    F4 = 61,
+    F5 = 62,
+    F6 = 63,
+    F7 = 64,
+    F8 = 65,
+    F9 = 66,
+    F10 = 67,
+    F11 = 68,
+    F12 = 69,
+    PrintScreen = 70,
+    ScrollLock = 71,
+    Pause = 72,
+    Insert = 73,
+    Home = 74,
+    PageUp = 75,
+    Delete = 76,
+    End = 77,
+    PageDown = 78,
+    RightArrow = 79,
+    LeftArrow = 80,
+    DownArrow = 81,
+    UpArrow = 82,
+    NumLock = 83,
+    NumPadDivide = 84,
+    NumPadMultiply = 85,
+    NumPadSubtract = 86,
+    NumPadAdd = 87,
+    NumPadEnter = 88,
+    NumPad1 = 89,
+    NumPad2 = 90,
+    NumPad3 = 91,
+    NumPad4 = 92,
+    NumPad5 = 93,
+    NumPad6 = 94,
+    NumPad7 = 95,
+    NumPad8 = 96,
+    NumPad9 = 97,
+    NumPad0 = 98,
+    NumPadDot = 99,
+    Backslash = 100,
+    Application = 101,
+    Power = 102,
+    NumPadEquals = 103,
+    F13 = 104,
+    F14 = 105,
+    F15 = 106,
+    F16 = 107,
+    F17 = 108,
+    F18 = 109,
+    F19 = 110,
+    F20 = 111,
+    F21 = 112,
+    F22 = 113,
+    F23 = 114,
+    F24 = 115,
+    NumPadComma = 133,
+    Ro = 135,
+    KatakanaHiragana = 136,
+    Yen = 137,
+    Henkan = 138,
+    Muhenkan = 139,
+    NumPadCommaPc98 = 140,
+    HangulEnglish = 144,
+    Hanja = 145,
+    Katakana = 146,
+    Hiragana = 147,
+    ZenkakuHankaku = 148,
+    LeftControl = 224,
+    LeftShift = 225,
+    LeftAlt = 226,
+    LeftGui = 227,
+    RightControl = 228,
+    RightShift = 229,
+    RightAlt = 230,
+    RightGui = 231,
+}
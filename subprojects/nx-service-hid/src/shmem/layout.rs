@@ -3,6 +3,17 @@
 //! This module defines the exact memory layout of the HID shared memory region.
 //! All structures must match the official layout exactly for correct operation.
 
+use super::{
+    button::ButtonState,
+    keyboard::KeyboardState,
+    mouse::MouseState,
+    npad::{NpadCommonState, NpadId, NpadStyleSet},
+};
+pub use super::{
+    button::HidButtonSharedMemoryFormat, keyboard::HidKeyboardSharedMemoryFormat,
+    mouse::HidMouseSharedMemoryFormat, npad::HidNpadSharedMemoryFormat,
+};
+
 /// Size of the HID shared memory region.
 pub const HID_SHARED_MEMORY_SIZE: usize = 0x40000;
 
@@ -20,35 +31,19 @@ pub struct HidTouchScreenSharedMemoryFormat {
     _data: [u8; 0x3000],
 }
 
-#[repr(C)]
-pub struct HidMouseSharedMemoryFormat {
-    _data: [u8; 0x400],
-}
-
-#[repr(C)]
-pub struct HidKeyboardSharedMemoryFormat {
-    _data: [u8; 0x400],
-}
-
 #[repr(C)]
 pub struct HidDigitizerSharedMemoryFormat {
     _data: [u8; 0x400],
 }
 
-#[repr(C)]
-pub struct HidHomeButtonSharedMemoryFormat {
-    _data: [u8; 0x200],
-}
+/// HOME button section.
+pub type HidHomeButtonSharedMemoryFormat = HidButtonSharedMemoryFormat;
 
-#[repr(C)]
-pub struct HidSleepButtonSharedMemoryFormat {
-    _data: [u8; 0x200],
-}
+/// Sleep button section.
+pub type HidSleepButtonSharedMemoryFormat = HidButtonSharedMemoryFormat;
 
-#[repr(C)]
-pub struct HidCaptureButtonSharedMemoryFormat {
-    _data: [u8; 0x200],
-}
+/// Capture button section.
+pub type HidCaptureButtonSharedMemoryFormat = HidButtonSharedMemoryFormat;
 
 #[repr(C)]
 pub struct HidInputDetectorSharedMemoryFormat {
@@ -60,11 +55,6 @@ pub struct HidUniquePadSharedMemoryFormat {
     _data: [u8; 0x400],
 }
 
-#[repr(C)]
-pub struct HidNpadSharedMemoryFormat {
-    _data: [u8; 0x32000],
-}
-
 #[repr(C)]
 pub struct HidGestureSharedMemoryFormat {
     _data: [u8; 0x800],
@@ -99,4 +89,57 @@ pub struct HidSharedMemory {
 impl HidSharedMemory {
     /// Size of the shared memory region.
     pub const SIZE: usize = HID_SHARED_MEMORY_SIZE;
+
+    /// Reads the most recent keyboard state.
+    ///
+    /// Requires keyboard input to be activated. Returns `None` if no sample is
+    /// available yet.
+    #[inline]
+    pub fn read_keyboard_state(&self) -> Option<KeyboardState> {
+        self.keyboard.latest()
+    }
+
+    /// Reads the most recent HOME button state.
+    ///
+    /// Requires the HOME button to be activated through `hid:sys`. Returns
+    /// `None` if no sample is available yet.
+    #[inline]
+    pub fn read_home_button_state(&self) -> Option<ButtonState> {
+        self.home_button.latest()
+    }
+
+    /// Reads the most recent capture button state.
+    ///
+    /// Requires the capture button to be activated through `hid:sys`.
+    /// Returns `None` if no sample is available yet.
+    #[inline]
+    pub fn read_capture_button_state(&self) -> Option<ButtonState> {
+        self.capture_button.latest()
+    }
+
+    /// Reads the current controller style set of npad `id`.
+    ///
+    /// Returns an empty set if no controller is connected.
+    #[inline]
+    pub fn read_npad_style_set(&self, id: NpadId) -> NpadStyleSet {
+        self.npad.entry(id).read_style_set()
+    }
+
+    /// Reads the most recent state of npad `id`.
+    ///
+    /// Requires npad input to be activated. Returns `None` if no controller
+    /// is connected or no sample is available yet.
+    #[inline]
+    pub fn read_npad_state(&self, id: NpadId) -> Option<NpadCommonState> {
+        self.npad.entry(id).latest()
+    }
+
+    /// Reads the most recent mouse state.
+    ///
+    /// Requires mouse input to be activated. Returns `None` if no sample is
+    /// available yet.
+    #[inline]
+    pub fn read_mouse_state(&self) -> Option<MouseState> {
+        self.mouse.latest()
+    }
 }
@@ -9,6 +9,8 @@ use core::{
     sync::atomic::{AtomicU64, Ordering},
 };
 
+use nx_cpu::{barrier, spin::ExponentialBackoff};
+
 use super::types::InputState;
 
 /// Common LIFO header for all HID input types.
@@ -52,6 +54,7 @@ pub fn get_states<T: InputState>(
     const MAX_RETRIES: usize = 3;
     let max_states = storage.len() as u64;
 
+    let mut backoff = ExponentialBackoff::new();
     for _ in 0..MAX_RETRIES {
         // Atomically load tail and count
         let tail = header.tail.load(Ordering::Acquire);
@@ -78,8 +81,12 @@ pub fn get_states<T: InputState>(
             let entry = &storage[entrypos as usize];
 
             // Read sampling number before and after loading state
+            // The barriers keep the CPU from reordering the volatile state
+            // copy outside of the two sampling number reads.
             let sampling0 = unsafe { ptr::read_volatile(entry as *const T::Storage as *const u64) };
+            barrier::dmb_ishld();
             let state = unsafe { T::load_from_storage(entry) };
+            barrier::dmb_ishld();
             let sampling1 = unsafe { ptr::read_volatile(entry as *const T::Storage as *const u64) };
 
             let curr_sampling = state.sampling_number();
@@ -109,6 +116,7 @@ pub fn get_states<T: InputState>(
         }
 
         // Inconsistent read, retry
+        backoff.spin();
     }
 
     // Failed to get consistent read after retries
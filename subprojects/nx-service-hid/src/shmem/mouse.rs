@@ -0,0 +1,128 @@
+//! Mouse shared memory section and state types.
+
+use core::ptr;
+
+use bitflags::bitflags;
+use static_assertions::const_assert_eq;
+
+use super::{
+    lifo::{HidCommonLifoHeader, get_states},
+    types::InputState,
+};
+
+/// Number of entries in the mouse LIFO ring buffer.
+pub const MOUSE_LIFO_ENTRY_COUNT: usize = 17;
+
+/// Mouse state sample.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MouseState {
+    /// Monotonically increasing sample counter.
+    pub sampling_number: u64,
+    /// Cursor X position.
+    pub x: i32,
+    /// Cursor Y position.
+    pub y: i32,
+    /// X movement since the previous sample.
+    pub delta_x: i32,
+    /// Y movement since the previous sample.
+    pub delta_y: i32,
+    /// Horizontal wheel movement since the previous sample.
+    pub wheel_delta_x: i32,
+    /// Vertical wheel movement since the previous sample.
+    pub wheel_delta_y: i32,
+    /// Buttons held down.
+    pub buttons: MouseButtons,
+    /// Connection attributes.
+    pub attributes: MouseAttributes,
+}
+
+impl MouseState {
+    /// Returns `true` if a mouse is connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.attributes.contains(MouseAttributes::IS_CONNECTED)
+    }
+}
+
+impl InputState for MouseState {
+    type Storage = MouseStateAtomicStorage;
+
+    fn sampling_number(&self) -> u64 {
+        self.sampling_number
+    }
+
+    unsafe fn load_from_storage(storage: &Self::Storage) -> Self {
+        // SAFETY: Caller guarantees storage points to a valid LIFO entry.
+        unsafe { ptr::read_volatile(&storage.state) }
+    }
+}
+
+/// Mouse LIFO entry: sampling number followed by the state.
+#[repr(C)]
+pub struct MouseStateAtomicStorage {
+    pub sampling_number: u64,
+    pub state: MouseState,
+}
+
+/// Mouse LIFO ring buffer.
+#[repr(C)]
+pub struct MouseLifo {
+    pub header: HidCommonLifoHeader,
+    pub storage: [MouseStateAtomicStorage; MOUSE_LIFO_ENTRY_COUNT],
+}
+
+/// Mouse section of HID shared memory (0x400 bytes).
+#[repr(C)]
+pub struct HidMouseSharedMemoryFormat {
+    pub lifo: MouseLifo,
+    _padding: [u8; 0xB0],
+}
+
+const_assert_eq!(size_of::<MouseState>(), 0x28);
+const_assert_eq!(size_of::<MouseStateAtomicStorage>(), 0x30);
+const_assert_eq!(size_of::<HidMouseSharedMemoryFormat>(), 0x400);
+
+impl HidMouseSharedMemoryFormat {
+    /// Reads the most recent mouse state.
+    ///
+    /// Returns `None` if no sample is available or a consistent read could not
+    /// be obtained.
+    pub fn latest(&self) -> Option<MouseState> {
+        let mut out = [MouseState::default()];
+        match get_states(&self.lifo.header, &self.lifo.storage, &mut out) {
+            0 => None,
+            _ => Some(out[0]),
+        }
+    }
+}
+
+bitflags! {
+    /// Mouse buttons.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct MouseButtons: u32 {
+        /// Left button.
+        const LEFT = 1 << 0;
+        /// Right button.
+        const RIGHT = 1 << 1;
+        /// Middle (wheel) button.
+        const MIDDLE = 1 << 2;
+        /// Forward side button.
+        const FORWARD = 1 << 3;
+        /// Back side button.
+        const BACK = 1 << 4;
+    }
+}
+
+bitflags! {
+    /// Mouse connection attributes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct MouseAttributes: u32 {
+        /// Input can be transferred to another applet.
+        const TRANSFERABLE = 1 << 0;
+        /// A mouse is connected.
+        const IS_CONNECTED = 1 << 1;
+    }
+}
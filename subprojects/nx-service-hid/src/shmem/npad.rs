@@ -0,0 +1,374 @@
+//! Npad (controller) shared memory section and state types.
+//!
+//! Only the style set, colors, and the common-state LIFOs at the start of each
+//! npad entry are modeled. The six-axis sensor LIFOs and the rest of the entry
+//! are left as opaque padding.
+
+use core::ptr;
+
+use bitflags::bitflags;
+use static_assertions::const_assert_eq;
+
+use super::{
+    lifo::{HidCommonLifoHeader, get_states},
+    types::{AnalogStickState, InputState},
+};
+
+/// Number of entries in each npad LIFO ring buffer.
+pub const NPAD_LIFO_ENTRY_COUNT: usize = 17;
+
+/// Number of npad entries in shared memory.
+pub const NPAD_ENTRY_COUNT: usize = 10;
+
+/// Maximum absolute value of an analog stick axis.
+pub const ANALOG_STICK_MAX: i32 = 0x7FFF;
+
+/// Npad (controller) identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NpadId {
+    /// Player 1 controller.
+    No1 = 0,
+    /// Player 2 controller.
+    No2 = 1,
+    /// Player 3 controller.
+    No3 = 2,
+    /// Player 4 controller.
+    No4 = 3,
+    /// Player 5 controller.
+    No5 = 4,
+    /// Player 6 controller.
+    No6 = 5,
+    /// Player 7 controller.
+    No7 = 6,
+    /// Player 8 controller.
+    No8 = 7,
+    /// Unknown controller.
+    Other = 0x10,
+    /// Joy-Cons attached to the console.
+    Handheld = 0x20,
+}
+
+impl NpadId {
+    /// Returns the index of this npad's entry in shared memory.
+    #[inline]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Other => 9,
+            Self::Handheld => 8,
+            id => id as usize,
+        }
+    }
+
+    /// Returns the raw npad ID, as passed to `SetSupportedNpadIdType`.
+    #[inline]
+    pub const fn to_raw(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Npad common state sample (shared by every controller style).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NpadCommonState {
+    /// Monotonically increasing sample counter.
+    pub sampling_number: u64,
+    /// Buttons held down.
+    pub buttons: NpadButtons,
+    /// Left analog stick position.
+    pub analog_stick_l: AnalogStickState,
+    /// Right analog stick position.
+    pub analog_stick_r: AnalogStickState,
+    /// Connection attributes.
+    pub attributes: NpadAttributes,
+    _reserved: u32,
+}
+
+impl NpadCommonState {
+    /// Returns `true` if a controller is connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.attributes.contains(NpadAttributes::IS_CONNECTED)
+    }
+}
+
+impl InputState for NpadCommonState {
+    type Storage = NpadCommonStateAtomicStorage;
+
+    fn sampling_number(&self) -> u64 {
+        self.sampling_number
+    }
+
+    unsafe fn load_from_storage(storage: &Self::Storage) -> Self {
+        // SAFETY: Caller guarantees storage points to a valid LIFO entry.
+        unsafe { ptr::read_volatile(&storage.state) }
+    }
+}
+
+/// Npad LIFO entry: sampling number followed by the state.
+#[repr(C)]
+pub struct NpadCommonStateAtomicStorage {
+    pub sampling_number: u64,
+    pub state: NpadCommonState,
+}
+
+/// Npad common-state LIFO ring buffer.
+#[repr(C)]
+pub struct NpadCommonLifo {
+    pub header: HidCommonLifoHeader,
+    pub storage: [NpadCommonStateAtomicStorage; NPAD_LIFO_ENTRY_COUNT],
+}
+
+impl NpadCommonLifo {
+    /// Reads the most recent state from this LIFO.
+    fn latest(&self) -> Option<NpadCommonState> {
+        let mut out = [NpadCommonState::default()];
+        match get_states(&self.header, &self.storage, &mut out) {
+            0 => None,
+            _ => Some(out[0]),
+        }
+    }
+}
+
+/// Controller body and button colors.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NpadControllerColor {
+    /// Body color (RGBA).
+    pub main: u32,
+    /// Button color (RGBA).
+    pub sub: u32,
+}
+
+/// Npad internal state (0x5000 bytes per npad entry).
+#[repr(C)]
+pub struct NpadInternalState {
+    /// Bitfield of connected controller styles.
+    pub style_set: NpadStyleSet,
+    /// Joy-Con assignment mode (0 = dual, 1 = single).
+    pub joy_assignment_mode: u32,
+    /// Full key controller color attribute.
+    pub full_key_color_attribute: u32,
+    /// Full key controller color.
+    pub full_key_color: NpadControllerColor,
+    /// Joy-Con color attribute.
+    pub joy_color_attribute: u32,
+    /// Left Joy-Con color.
+    pub joy_color_left: NpadControllerColor,
+    /// Right Joy-Con color.
+    pub joy_color_right: NpadControllerColor,
+    /// Pro Controller style LIFO.
+    pub full_key_lifo: NpadCommonLifo,
+    /// Handheld style LIFO.
+    pub handheld_lifo: NpadCommonLifo,
+    /// Dual Joy-Con style LIFO.
+    pub joy_dual_lifo: NpadCommonLifo,
+    /// Single left Joy-Con style LIFO.
+    pub joy_left_lifo: NpadCommonLifo,
+    /// Single right Joy-Con style LIFO.
+    pub joy_right_lifo: NpadCommonLifo,
+    /// Poké Ball Plus style LIFO.
+    pub palma_lifo: NpadCommonLifo,
+    /// Generic external controller style LIFO.
+    pub system_ext_lifo: NpadCommonLifo,
+    _rest: [u8; 0x38A8],
+}
+
+impl NpadInternalState {
+    /// Reads the current style set.
+    #[inline]
+    pub fn read_style_set(&self) -> NpadStyleSet {
+        // SAFETY: The field is plain data in mapped shared memory.
+        unsafe { ptr::read_volatile(&self.style_set) }
+    }
+
+    /// Reads the most recent state from the LIFO matching the current style.
+    ///
+    /// Styles are checked in the same order as libnx: full key, handheld,
+    /// dual Joy-Con, left Joy-Con, right Joy-Con, Palma, then system ext.
+    /// Returns `None` if no style is set or no sample is available.
+    pub fn latest(&self) -> Option<NpadCommonState> {
+        let style_set = self.read_style_set();
+
+        let lifo = if style_set.contains(NpadStyleSet::FULL_KEY) {
+            &self.full_key_lifo
+        } else if style_set.contains(NpadStyleSet::HANDHELD) {
+            &self.handheld_lifo
+        } else if style_set.contains(NpadStyleSet::JOY_DUAL) {
+            &self.joy_dual_lifo
+        } else if style_set.contains(NpadStyleSet::JOY_LEFT) {
+            &self.joy_left_lifo
+        } else if style_set.contains(NpadStyleSet::JOY_RIGHT) {
+            &self.joy_right_lifo
+        } else if style_set.contains(NpadStyleSet::PALMA) {
+            &self.palma_lifo
+        } else if style_set.intersects(NpadStyleSet::SYSTEM_EXT | NpadStyleSet::SYSTEM) {
+            &self.system_ext_lifo
+        } else {
+            return None;
+        };
+
+        lifo.latest()
+    }
+}
+
+/// Npad section of HID shared memory (0x32000 bytes).
+#[repr(C)]
+pub struct HidNpadSharedMemoryFormat {
+    pub entries: [NpadInternalState; NPAD_ENTRY_COUNT],
+}
+
+impl HidNpadSharedMemoryFormat {
+    /// Returns the shared memory entry for `id`.
+    #[inline]
+    pub fn entry(&self, id: NpadId) -> &NpadInternalState {
+        &self.entries[id.index()]
+    }
+}
+
+const_assert_eq!(size_of::<NpadCommonState>(), 0x28);
+const_assert_eq!(size_of::<NpadCommonStateAtomicStorage>(), 0x30);
+const_assert_eq!(size_of::<NpadCommonLifo>(), 0x350);
+const_assert_eq!(size_of::<NpadInternalState>(), 0x5000);
+const_assert_eq!(size_of::<HidNpadSharedMemoryFormat>(), 0x32000);
+
+bitflags! {
+    /// Npad controller styles.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct NpadStyleSet: u32 {
+        /// Pro Controller.
+        const FULL_KEY = 1 << 0;
+        /// Joy-Cons attached to the console.
+        const HANDHELD = 1 << 1;
+        /// Two Joy-Cons used as one controller.
+        const JOY_DUAL = 1 << 2;
+        /// Single left Joy-Con.
+        const JOY_LEFT = 1 << 3;
+        /// Single right Joy-Con.
+        const JOY_RIGHT = 1 << 4;
+        /// GameCube controller.
+        const GC = 1 << 5;
+        /// Poké Ball Plus.
+        const PALMA = 1 << 6;
+        /// NES/Famicom controller.
+        const LARK = 1 << 7;
+        /// NES/Famicom controller in handheld mode.
+        const HANDHELD_LARK = 1 << 8;
+        /// SNES controller.
+        const LUCIA = 1 << 9;
+        /// N64 controller.
+        const LAGON = 1 << 10;
+        /// Sega Genesis controller.
+        const LAGER = 1 << 11;
+        /// Generic external controller.
+        const SYSTEM_EXT = 1 << 29;
+        /// Generic controller.
+        const SYSTEM = 1 << 30;
+
+        /// Styles used by most applications.
+        const STANDARD = Self::FULL_KEY.bits()
+            | Self::HANDHELD.bits()
+            | Self::JOY_DUAL.bits()
+            | Self::JOY_LEFT.bits()
+            | Self::JOY_RIGHT.bits();
+    }
+}
+
+bitflags! {
+    /// Npad buttons.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct NpadButtons: u64 {
+        /// A button.
+        const A = 1 << 0;
+        /// B button.
+        const B = 1 << 1;
+        /// X button.
+        const X = 1 << 2;
+        /// Y button.
+        const Y = 1 << 3;
+        /// Left stick click.
+        const STICK_L = 1 << 4;
+        /// Right stick click.
+        const STICK_R = 1 << 5;
+        /// L button.
+        const L = 1 << 6;
+        /// R button.
+        const R = 1 << 7;
+        /// ZL button.
+        const ZL = 1 << 8;
+        /// ZR button.
+        const ZR = 1 << 9;
+        /// Plus button.
+        const PLUS = 1 << 10;
+        /// Minus button.
+        const MINUS = 1 << 11;
+        /// D-pad left.
+        const LEFT = 1 << 12;
+        /// D-pad up.
+        const UP = 1 << 13;
+        /// D-pad right.
+        const RIGHT = 1 << 14;
+        /// D-pad down.
+        const DOWN = 1 << 15;
+        /// Left stick pushed left.
+        const STICK_L_LEFT = 1 << 16;
+        /// Left stick pushed up.
+        const STICK_L_UP = 1 << 17;
+        /// Left stick pushed right.
+        const STICK_L_RIGHT = 1 << 18;
+        /// Left stick pushed down.
+        const STICK_L_DOWN = 1 << 19;
+        /// Right stick pushed left.
+        const STICK_R_LEFT = 1 << 20;
+        /// Right stick pushed up.
+        const STICK_R_UP = 1 << 21;
+        /// Right stick pushed right.
+        const STICK_R_RIGHT = 1 << 22;
+        /// Right stick pushed down.
+        const STICK_R_DOWN = 1 << 23;
+        /// SL button on the left Joy-Con.
+        const LEFT_SL = 1 << 24;
+        /// SR button on the left Joy-Con.
+        const LEFT_SR = 1 << 25;
+        /// SL button on the right Joy-Con.
+        const RIGHT_SL = 1 << 26;
+        /// SR button on the right Joy-Con.
+        const RIGHT_SR = 1 << 27;
+        /// Top button on the Poké Ball Plus.
+        const PALMA = 1 << 28;
+        /// Verification.
+        const VERIFICATION = 1 << 29;
+        /// B button on the left NES handheld controller.
+        const HANDHELD_LEFT_B = 1 << 30;
+        /// C-left on the N64 controller.
+        const LAGON_C_LEFT = 1 << 31;
+        /// C-up on the N64 controller.
+        const LAGON_C_UP = 1 << 32;
+        /// C-right on the N64 controller.
+        const LAGON_C_RIGHT = 1 << 33;
+        /// C-down on the N64 controller.
+        const LAGON_C_DOWN = 1 << 34;
+    }
+}
+
+bitflags! {
+    /// Npad connection attributes.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[repr(transparent)]
+    pub struct NpadAttributes: u32 {
+        /// A controller is connected.
+        const IS_CONNECTED = 1 << 0;
+        /// The controller is connected by wire.
+        const IS_WIRED = 1 << 1;
+        /// The left Joy-Con is connected.
+        const IS_LEFT_CONNECTED = 1 << 2;
+        /// The left Joy-Con is connected by wire.
+        const IS_LEFT_WIRED = 1 << 3;
+        /// The right Joy-Con is connected.
+        const IS_RIGHT_CONNECTED = 1 << 4;
+        /// The right Joy-Con is connected by wire.
+        const IS_RIGHT_WIRED = 1 << 5;
+    }
+}
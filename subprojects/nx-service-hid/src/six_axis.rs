@@ -0,0 +1,263 @@
+//! Six-axis sensor fusion.
+//!
+//! [`SixAxisFusion`] turns successive accelerometer and gyroscope samples into
+//! an orientation [`Quaternion`], using the IMU variant of Madgwick's
+//! gradient-descent filter: the gyroscope is integrated for responsiveness,
+//! and the accelerometer's gravity reading continuously pulls the estimate
+//! back to correct the drift.
+//!
+//! Only gravity is used as a reference, so pitch and roll are drift-corrected
+//! while yaw (rotation around the gravity axis) slowly drifts. Call
+//! [`SixAxisFusion::reset`] to re-center it.
+//!
+//! # Units and sampling rate
+//!
+//! Samples use the units of the HID six-axis state: acceleration in G, and
+//! angular velocity in revolutions per second (`1.0` is 360°/s). Timestamps
+//! are in nanoseconds.
+//!
+//! The sensors are sampled at 200 Hz (every 5 ms). Feed every sample, oldest
+//! first, rather than only the latest one per frame: the filter stays stable
+//! down to roughly 60 Hz, but integrating larger steps amplifies the gyro
+//! error. Gaps longer than [`MAX_SAMPLE_GAP_NS`] are not integrated.
+//!
+//! Each sample goes through [`SixAxisFusion::update`], and the current
+//! estimate is read back with [`SixAxisFusion::orientation`].
+
+use core::f32::consts::TAU;
+
+use crate::shmem::Vector;
+
+/// Default filter gain, see [`SixAxisFusion::with_gain`].
+pub const DEFAULT_GAIN: f32 = 0.1;
+
+/// Longest gap between two samples that is still integrated (100 ms).
+///
+/// After a longer gap (e.g. the app was suspended), the next sample only
+/// restarts the integration.
+pub const MAX_SAMPLE_GAP_NS: u64 = 100_000_000;
+
+/// Orientation as a unit quaternion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    /// Scalar part.
+    pub w: f32,
+    /// X component of the vector part.
+    pub x: f32,
+    /// Y component of the vector part.
+    pub y: f32,
+    /// Z component of the vector part.
+    pub z: f32,
+}
+
+impl Quaternion {
+    /// The identity rotation.
+    pub const IDENTITY: Self = Self {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// Returns the inverse rotation.
+    #[inline]
+    pub fn conjugate(self) -> Self {
+        Self {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    /// Returns this quaternion scaled to unit length.
+    ///
+    /// Returns the identity if the length is zero.
+    fn normalized(self) -> Self {
+        let norm_sq = self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z;
+        if norm_sq == 0.0 {
+            return Self::IDENTITY;
+        }
+
+        let inv = inv_sqrt(norm_sq);
+        Self {
+            w: self.w * inv,
+            x: self.x * inv,
+            y: self.y * inv,
+            z: self.z * inv,
+        }
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Accelerometer/gyroscope fusion filter.
+///
+/// See the [module documentation](self) for the expected units and sampling
+/// rate.
+#[derive(Debug, Clone)]
+pub struct SixAxisFusion {
+    orientation: Quaternion,
+    gain: f32,
+    last_timestamp_ns: Option<u64>,
+}
+
+impl SixAxisFusion {
+    /// Creates a filter with the [`DEFAULT_GAIN`], starting at the identity
+    /// orientation.
+    pub fn new() -> Self {
+        Self::with_gain(DEFAULT_GAIN)
+    }
+
+    /// Creates a filter with the given gain.
+    ///
+    /// The gain weighs the accelerometer correction against the gyroscope
+    /// integration. Higher values converge faster and drift less but let
+    /// linear acceleration (shaking, swinging) disturb the orientation; lower
+    /// values are smoother but correct drift slowly. Values between `0.01`
+    /// and `0.5` are typical; `0.0` integrates the gyroscope only.
+    pub fn with_gain(gain: f32) -> Self {
+        Self {
+            orientation: Quaternion::IDENTITY,
+            gain,
+            last_timestamp_ns: None,
+        }
+    }
+
+    /// Returns the filter gain.
+    #[inline]
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Sets the filter gain, see [`with_gain`](Self::with_gain).
+    #[inline]
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain;
+    }
+
+    /// Returns the current orientation estimate.
+    #[inline]
+    pub fn orientation(&self) -> Quaternion {
+        self.orientation
+    }
+
+    /// Resets the orientation to the identity and restarts the integration.
+    pub fn reset(&mut self) {
+        self.orientation = Quaternion::IDENTITY;
+        self.last_timestamp_ns = None;
+    }
+
+    /// Feeds one sample into the filter.
+    ///
+    /// `timestamp_ns` is the time the sample was taken. The first sample,
+    /// samples that are not newer than the previous one, and samples after a
+    /// gap longer than [`MAX_SAMPLE_GAP_NS`] only set the reference time.
+    pub fn update(&mut self, timestamp_ns: u64, acceleration: Vector, angular_velocity: Vector) {
+        let last = self.last_timestamp_ns.replace(timestamp_ns);
+        let dt_ns = match last {
+            Some(last) if timestamp_ns > last => timestamp_ns - last,
+            _ => return,
+        };
+        if dt_ns > MAX_SAMPLE_GAP_NS {
+            return;
+        }
+
+        let dt = dt_ns as f32 * 1e-9;
+        self.orientation = madgwick_step(
+            self.orientation,
+            acceleration,
+            scale(angular_velocity, TAU),
+            self.gain,
+            dt,
+        );
+    }
+}
+
+impl Default for SixAxisFusion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Advances `q` by one step of Madgwick's IMU filter.
+///
+/// `gyro` is in radians per second, `accel` in any unit (only its direction
+/// is used).
+fn madgwick_step(q: Quaternion, accel: Vector, gyro: Vector, gain: f32, dt: f32) -> Quaternion {
+    let Quaternion {
+        w: q0,
+        x: q1,
+        y: q2,
+        z: q3,
+    } = q;
+
+    // Rate of change from the gyroscope: q' = 0.5 * q ⊗ (0, ω)
+    let mut dq0 = 0.5 * (-q1 * gyro.x - q2 * gyro.y - q3 * gyro.z);
+    let mut dq1 = 0.5 * (q0 * gyro.x + q2 * gyro.z - q3 * gyro.y);
+    let mut dq2 = 0.5 * (q0 * gyro.y - q1 * gyro.z + q3 * gyro.x);
+    let mut dq3 = 0.5 * (q0 * gyro.z + q1 * gyro.y - q2 * gyro.x);
+
+    // Free fall gives no gravity reference; integrate the gyroscope only
+    let accel_norm_sq = accel.x * accel.x + accel.y * accel.y + accel.z * accel.z;
+    if accel_norm_sq > 0.0 {
+        let inv = inv_sqrt(accel_norm_sq);
+        let (ax, ay, az) = (accel.x * inv, accel.y * inv, accel.z * inv);
+
+        // Gradient of the error between the measured and the estimated
+        // direction of gravity
+        let (q0q0, q1q1, q2q2, q3q3) = (q0 * q0, q1 * q1, q2 * q2, q3 * q3);
+        let s0 = 4.0 * q0 * q2q2 + 2.0 * q2 * ax + 4.0 * q0 * q1q1 - 2.0 * q1 * ay;
+        let s1 = 4.0 * q1 * q3q3 - 2.0 * q3 * ax + 4.0 * q0q0 * q1 - 2.0 * q0 * ay - 4.0 * q1
+            + 8.0 * q1 * q1q1
+            + 8.0 * q1 * q2q2
+            + 4.0 * q1 * az;
+        let s2 = 4.0 * q0q0 * q2 + 2.0 * q0 * ax + 4.0 * q2 * q3q3 - 2.0 * q3 * ay - 4.0 * q2
+            + 8.0 * q2 * q1q1
+            + 8.0 * q2 * q2q2
+            + 4.0 * q2 * az;
+        let s3 = 4.0 * q1q1 * q3 - 2.0 * q1 * ax + 4.0 * q2q2 * q3 - 2.0 * q2 * ay;
+
+        let s_norm_sq = s0 * s0 + s1 * s1 + s2 * s2 + s3 * s3;
+        if s_norm_sq > 0.0 {
+            let step = gain * inv_sqrt(s_norm_sq);
+            dq0 -= step * s0;
+            dq1 -= step * s1;
+            dq2 -= step * s2;
+            dq3 -= step * s3;
+        }
+    }
+
+    Quaternion {
+        w: q0 + dq0 * dt,
+        x: q1 + dq1 * dt,
+        y: q2 + dq2 * dt,
+        z: q3 + dq3 * dt,
+    }
+    .normalized()
+}
+
+fn scale(v: Vector, factor: f32) -> Vector {
+    Vector {
+        x: v.x * factor,
+        y: v.y * factor,
+        z: v.z * factor,
+    }
+}
+
+/// Computes `1 / sqrt(x)` for `x > 0`.
+///
+/// `core` has no square root, so this refines the classic bit-level estimate
+/// with Newton-Raphson steps, down to `f32` precision.
+pub(crate) fn inv_sqrt(x: f32) -> f32 {
+    let half = 0.5 * x;
+    let mut y = f32::from_bits(0x5F37_5A86 - (x.to_bits() >> 1));
+    for _ in 0..3 {
+        y *= 1.5 - half * y * y;
+    }
+    y
+}
@@ -0,0 +1,38 @@
+//! HID IPC parameter types.
+
+/// Joy-Con hold orientation, set with `SetNpadJoyHoldType`.
+///
+/// Determines how a single Joy-Con in single assignment mode is oriented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u64)]
+pub enum NpadJoyHoldType {
+    /// Joy-Cons held upright (the system default).
+    #[default]
+    Vertical = 0,
+    /// Joy-Cons held sideways.
+    Horizontal = 1,
+}
+
+/// Which Joy-Con of a pair to keep, for single assignment mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u64)]
+pub enum NpadJoyDeviceType {
+    /// The left Joy-Con.
+    Left = 0,
+    /// The right Joy-Con.
+    Right = 1,
+}
+
+/// How Joy-Cons attached to the console are activated, set with
+/// `SetNpadHandheldActivationMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u64)]
+pub enum NpadHandheldActivationMode {
+    /// Both Joy-Cons must be attached (the system default).
+    #[default]
+    Dual = 0,
+    /// A single attached Joy-Con is enough.
+    Single = 1,
+    /// Handheld mode is always active.
+    None = 2,
+}
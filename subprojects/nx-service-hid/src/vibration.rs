@@ -0,0 +1,171 @@
+//! Vibration (rumble) devices, values and patterns.
+//!
+//! Each controller exposes one or two vibration devices (motors), addressed
+//! by a [`VibrationDeviceHandle`]. A device is driven by sending it a
+//! [`VibrationValue`], which it keeps playing until the next value is sent.
+//!
+//! A [`VibrationPattern`] is a sequence of values, each held for a duration,
+//! played with [`HidService::play_vibration_pattern`] or, without blocking
+//! the calling thread, [`HidService::spawn_vibration_pattern`]. Patterns
+//! built with [`VibrationPattern::new`] from a constant slice of
+//! [`VibrationStep`]s can be kept in a `static`. The device must be set up
+//! with [`HidService::initialize_vibration_device`] before playing anything.
+//!
+//! [`HidService::play_vibration_pattern`]: crate::HidService::play_vibration_pattern
+//! [`HidService::spawn_vibration_pattern`]: crate::HidService::spawn_vibration_pattern
+//! [`HidService::initialize_vibration_device`]: crate::HidService::initialize_vibration_device
+
+use crate::shmem::{NpadId, NpadStyleSet};
+
+/// Maximum total duration of a played [`VibrationPattern`], in milliseconds.
+///
+/// Steps past this budget are skipped, and the step crossing it is cut short,
+/// so a malformed pattern cannot keep a motor running indefinitely.
+pub const MAX_VIBRATION_PATTERN_DURATION_MS: u32 = 10_000;
+
+/// Handle to a vibration device (motor) of a controller.
+///
+/// Encodes the controller style, the npad and the motor position, as
+/// expected by the vibration commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct VibrationDeviceHandle(u32);
+
+impl VibrationDeviceHandle {
+    /// Creates the handle of the motor at `position` of the controller on
+    /// `npad_id`, connected with `style`.
+    ///
+    /// `style` must be a single style among [`NpadStyleSet::FULL_KEY`],
+    /// [`NpadStyleSet::HANDHELD`], [`NpadStyleSet::JOY_DUAL`],
+    /// [`NpadStyleSet::JOY_LEFT`] and [`NpadStyleSet::JOY_RIGHT`]. Pro
+    /// Controllers, handheld and dual Joy-Cons have a left and a right motor;
+    /// a single Joy-Con only has the motor on its own side.
+    ///
+    /// Returns `None` for other styles, or a position the style lacks.
+    pub fn new(
+        npad_id: NpadId,
+        style: NpadStyleSet,
+        position: VibrationDevicePosition,
+    ) -> Option<Self> {
+        let style_index: u32 = match style {
+            NpadStyleSet::FULL_KEY => 3,
+            NpadStyleSet::HANDHELD => 4,
+            NpadStyleSet::JOY_DUAL => 5,
+            NpadStyleSet::JOY_LEFT if position == VibrationDevicePosition::Left => 6,
+            NpadStyleSet::JOY_RIGHT if position == VibrationDevicePosition::Right => 7,
+            _ => return None,
+        };
+
+        Some(Self(
+            style_index | ((npad_id.to_raw() & 0xFF) << 8) | ((position as u32) << 16),
+        ))
+    }
+
+    /// Returns the raw handle value.
+    #[inline]
+    pub const fn to_raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// Position of a vibration device within its controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VibrationDevicePosition {
+    /// The left motor, or the left Joy-Con.
+    Left = 0,
+    /// The right motor, or the right Joy-Con.
+    Right = 1,
+}
+
+/// Vibration to play on a device.
+///
+/// HD rumble motors play two bands at once; each band has an amplitude in
+/// `0.0..=1.0` and a frequency in Hz.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(C)]
+pub struct VibrationValue {
+    /// Amplitude of the low band.
+    pub amp_low: f32,
+    /// Frequency of the low band, in Hz.
+    pub freq_low: f32,
+    /// Amplitude of the high band.
+    pub amp_high: f32,
+    /// Frequency of the high band, in Hz.
+    pub freq_high: f32,
+}
+
+impl VibrationValue {
+    /// No vibration, at the default resonant frequencies (160 Hz and
+    /// 320 Hz).
+    pub const NEUTRAL: Self = Self::new(0.0, 160.0, 0.0, 320.0);
+
+    /// Creates a vibration value.
+    #[inline]
+    pub const fn new(amp_low: f32, freq_low: f32, amp_high: f32, freq_high: f32) -> Self {
+        Self {
+            amp_low,
+            freq_low,
+            amp_high,
+            freq_high,
+        }
+    }
+}
+
+impl Default for VibrationValue {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+/// Step of a [`VibrationPattern`]: a value held for a duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VibrationStep {
+    /// Value sent to the device at the start of the step.
+    pub value: VibrationValue,
+    /// How long the value is held, in milliseconds.
+    pub duration_ms: u32,
+}
+
+impl VibrationStep {
+    /// Creates a step holding `value` for `duration_ms` milliseconds.
+    #[inline]
+    pub const fn new(value: VibrationValue, duration_ms: u32) -> Self {
+        Self { value, duration_ms }
+    }
+}
+
+/// Sequence of vibration steps, played in order.
+///
+/// The device is stopped ([`VibrationValue::NEUTRAL`]) once the last step
+/// has elapsed. Patterns borrow their steps, so they can be built as
+/// `static`s.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VibrationPattern<'a> {
+    steps: &'a [VibrationStep],
+}
+
+impl<'a> VibrationPattern<'a> {
+    /// Creates a pattern playing `steps` in order.
+    #[inline]
+    pub const fn new(steps: &'a [VibrationStep]) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the steps of the pattern.
+    #[inline]
+    pub const fn steps(&self) -> &'a [VibrationStep] {
+        self.steps
+    }
+
+    /// Returns the duration of the pattern when played, in milliseconds.
+    ///
+    /// This is the sum of the step durations, clamped to
+    /// [`MAX_VIBRATION_PATTERN_DURATION_MS`].
+    pub fn duration_ms(&self) -> u32 {
+        self.steps
+            .iter()
+            .fold(0u32, |total, step| total.saturating_add(step.duration_ms))
+            .min(MAX_VIBRATION_PATTERN_DURATION_MS)
+    }
+}
@@ -113,7 +113,7 @@ pub fn ioctl(
     let resp =
         unsafe { cmif::parse_response(ipc_buf, false, 0) }.map_err(IoctlError::ParseResponse)?;
 
-    // Response contains error code
+    // Response contains the driver status
     let error = unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<u32>()) };
 
     if error != 0 {
@@ -433,11 +433,24 @@ pub enum IoctlError {
     /// Failed to parse the CMIF response.
     #[error("failed to parse response")]
     ParseResponse(#[source] cmif::ParseResponseError),
-    /// NV driver returned an error.
+    /// The IPC request succeeded, but the NV driver returned a non-zero
+    /// status.
     #[error("NV driver error")]
     NvError(#[source] IoctlNvError),
 }
 
+impl IoctlError {
+    /// Returns the driver status if the driver rejected the ioctl.
+    ///
+    /// Returns `None` if the request failed at the IPC layer instead.
+    pub fn nv_error(&self) -> Option<IoctlNvError> {
+        match self {
+            Self::NvError(err) => Some(*err),
+            _ => None,
+        }
+    }
+}
+
 /// Error returned by ioctl2 operation.
 #[derive(Debug, thiserror::Error)]
 pub enum Ioctl2Error {
@@ -447,11 +460,24 @@ pub enum Ioctl2Error {
     /// Failed to parse the CMIF response.
     #[error("failed to parse response")]
     ParseResponse(#[source] cmif::ParseResponseError),
-    /// NV driver returned an error.
+    /// The IPC request succeeded, but the NV driver returned a non-zero
+    /// status.
     #[error("NV driver error")]
     NvError(#[source] IoctlNvError),
 }
 
+impl Ioctl2Error {
+    /// Returns the driver status if the driver rejected the ioctl.
+    ///
+    /// Returns `None` if the request failed at the IPC layer instead.
+    pub fn nv_error(&self) -> Option<IoctlNvError> {
+        match self {
+            Self::NvError(err) => Some(*err),
+            _ => None,
+        }
+    }
+}
+
 /// Error returned by ioctl3 operation.
 #[derive(Debug, thiserror::Error)]
 pub enum Ioctl3Error {
@@ -461,11 +487,24 @@ pub enum Ioctl3Error {
     /// Failed to parse the CMIF response.
     #[error("failed to parse response")]
     ParseResponse(#[source] cmif::ParseResponseError),
-    /// NV driver returned an error.
+    /// The IPC request succeeded, but the NV driver returned a non-zero
+    /// status.
     #[error("NV driver error")]
     NvError(#[source] IoctlNvError),
 }
 
+impl Ioctl3Error {
+    /// Returns the driver status if the driver rejected the ioctl.
+    ///
+    /// Returns `None` if the request failed at the IPC layer instead.
+    pub fn nv_error(&self) -> Option<IoctlNvError> {
+        match self {
+            Self::NvError(err) => Some(*err),
+            _ => None,
+        }
+    }
+}
+
 /// Error returned by close operation.
 #[derive(Debug, thiserror::Error)]
 pub enum CloseError {
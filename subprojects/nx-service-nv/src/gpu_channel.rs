@@ -0,0 +1,533 @@
+//! GPU channels for command submission.
+//!
+//! GPU work is submitted through a channel (`/dev/nvhost-gpu`) bound to a
+//! GPU address space (`/dev/nvhost-as-gpu`). Command buffers are nvmap
+//! handles mapped into that address space, and the channel consumes them
+//! through its GPFIFO, a ring of [`GpfifoEntry`] pointing at command lists.
+//!
+//! [`NvGpuChannel`] performs the required ioctl sequence:
+//!
+//! 1. [`open`](NvGpuChannel::open): opens the address space and
+//!    initializes it (`NVGPU_AS_IOCTL_INITIALIZE_EX`), opens the channel,
+//!    sets its nvmap fd (`NVGPU_IOCTL_CHANNEL_SET_NVMAP_FD`), and binds it to
+//!    the address space (`NVGPU_AS_IOCTL_BIND_CHANNEL`).
+//! 2. [`alloc_gpfifo`](NvGpuChannel::alloc_gpfifo): allocates the GPFIFO
+//!    (`NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2`), once.
+//! 3. [`map_buffer`](NvGpuChannel::map_buffer): maps command and data
+//!    buffers into the address space (`NVGPU_AS_IOCTL_MAP_BUFFER_EX`).
+//! 4. [`submit`](NvGpuChannel::submit): submits entries and returns the
+//!    fence signaled once they have executed.
+//!
+//! The nvmap fd passed to [`open`](NvGpuChannel::open) comes from
+//! [`NvService::open_device`], and each entry submitted is built with
+//! [`GpfifoEntry::new`] from a mapped command buffer address and its length
+//! in words.
+//!
+//! Before work can actually run, a GPU class (e.g. 3D) must be bound with
+//! `NVGPU_IOCTL_CHANNEL_ALLOC_OBJ_CTX` on [`channel_fd`](NvGpuChannel::channel_fd).
+
+use core::mem::offset_of;
+
+use crate::{Ioctl2Error, IoctlError, NvService, OpenError, fd::Fd, nvmap::NvMapHandle};
+
+/// `NVGPU_AS_IOCTL_BIND_CHANNEL`: binds a channel to the address space.
+const NVGPU_AS_IOCTL_BIND_CHANNEL: u32 = 0x4004_4101;
+/// `NVGPU_AS_IOCTL_UNMAP_BUFFER`: unmaps a buffer from the address space.
+const NVGPU_AS_IOCTL_UNMAP_BUFFER: u32 = 0xC008_4105;
+/// `NVGPU_AS_IOCTL_MAP_BUFFER_EX`: maps an nvmap handle into the address space.
+const NVGPU_AS_IOCTL_MAP_BUFFER_EX: u32 = 0xC028_4106;
+/// `NVGPU_AS_IOCTL_INITIALIZE_EX`: sets up the address space.
+const NVGPU_AS_IOCTL_INITIALIZE_EX: u32 = 0x4028_4109;
+
+/// `NVGPU_IOCTL_CHANNEL_SET_NVMAP_FD`: sets the nvmap fd of the channel.
+const NVGPU_IOCTL_CHANNEL_SET_NVMAP_FD: u32 = 0x4004_4801;
+/// `NVGPU_IOCTL_CHANNEL_SUBMIT_GPFIFO`: submits entries passed inline.
+///
+/// The size field of the request covers the inline entries, see
+/// [`submit_gpfifo_request`].
+const NVGPU_IOCTL_CHANNEL_SUBMIT_GPFIFO: u32 = 0xC000_4808;
+/// `NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2`: allocates the GPFIFO.
+const NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2: u32 = 0xC020_481A;
+/// `NVGPU_IOCTL_CHANNEL_KICKOFF_PB`: submits entries passed in the extra
+/// ioctl2 buffer.
+const NVGPU_IOCTL_CHANNEL_KICKOFF_PB: u32 = 0xC018_481B;
+
+/// Big page size of the address space.
+const BIG_PAGE_SIZE: u32 = 0x1_0000;
+
+/// `NVGPU_SUBMIT_GPFIFO_FLAGS_FENCE_WAIT`: wait for the input fence first.
+const SUBMIT_FLAG_FENCE_WAIT: u32 = 1 << 0;
+/// `NVGPU_SUBMIT_GPFIFO_FLAGS_FENCE_GET`: return the completion fence.
+const SUBMIT_FLAG_FENCE_GET: u32 = 1 << 1;
+
+/// Maximum number of entries in a single submission without ioctl2.
+///
+/// Without ioctl2 the entries are passed inline in the ioctl arguments,
+/// which are staged on the stack.
+pub const MAX_INLINE_GPFIFO_ENTRIES: usize = 0x80;
+
+/// Syncpoint fence, signaled once the syncpoint reaches `value`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct NvFence {
+    /// Syncpoint ID.
+    pub id: u32,
+    /// Syncpoint value to wait for.
+    pub value: u32,
+}
+
+/// GPFIFO entry, pointing at a command list in the channel's address space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct GpfifoEntry(u64);
+
+impl GpfifoEntry {
+    /// Bit asking the GPU not to prefetch the command list.
+    const NO_PREFETCH: u64 = 1 << 63;
+
+    /// Creates an entry for the `num_cmds` command words at `gpu_va`.
+    ///
+    /// `gpu_va` must be 4-byte aligned and below 2^40, and `num_cmds` below
+    /// 2^21; returns `None` otherwise.
+    pub const fn new(gpu_va: u64, num_cmds: u32) -> Option<Self> {
+        if !gpu_va.is_multiple_of(4) || gpu_va >> 40 != 0 || num_cmds >> 21 != 0 {
+            return None;
+        }
+
+        Some(Self(gpu_va | ((num_cmds as u64) << 42)))
+    }
+
+    /// Returns the entry with prefetching of the command list disabled.
+    #[inline]
+    pub const fn no_prefetch(self) -> Self {
+        Self(self.0 | Self::NO_PREFETCH)
+    }
+
+    /// Returns the raw entry value.
+    #[inline]
+    pub const fn to_raw(self) -> u64 {
+        self.0
+    }
+}
+
+/// Arguments of `NVGPU_AS_IOCTL_INITIALIZE_EX`.
+#[derive(Default)]
+#[repr(C)]
+struct InitializeExArgs {
+    /// In: big page size.
+    big_page_size: u32,
+    /// In: address space fd (ignored).
+    as_fd: i32,
+    /// In: flags.
+    flags: u32,
+    _reserved: u32,
+    /// In: start of the virtual address range (0 for the default).
+    va_range_start: u64,
+    /// In: end of the virtual address range (0 for the default).
+    va_range_end: u64,
+    /// In: split between small and big pages (0 for the default).
+    va_range_split: u64,
+}
+
+/// Arguments of `NVGPU_AS_IOCTL_MAP_BUFFER_EX`.
+#[derive(Default)]
+#[repr(C)]
+struct MapBufferExArgs {
+    /// In: mapping flags.
+    flags: u32,
+    /// In: memory kind (-1 keeps the kind of the handle).
+    kind: i32,
+    /// In: nvmap handle to map.
+    nvmap_handle: u32,
+    /// In: page size of the mapping.
+    page_size: u32,
+    /// In: offset into the buffer.
+    buffer_offset: i64,
+    /// In: size of the mapping (0 maps the whole buffer).
+    mapping_size: u64,
+    /// In/out: GPU virtual address of the mapping.
+    offset: i64,
+}
+
+/// Arguments of `NVGPU_AS_IOCTL_UNMAP_BUFFER`.
+#[derive(Default)]
+#[repr(C)]
+struct UnmapBufferArgs {
+    /// In: GPU virtual address of the mapping.
+    offset: i64,
+}
+
+/// Arguments of `NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2`.
+#[derive(Default)]
+#[repr(C)]
+struct AllocGpfifoEx2Args {
+    /// In: number of entries.
+    num_entries: u32,
+    /// In: flags.
+    flags: u32,
+    _unk0: u32,
+    /// Out: fence of the channel setup.
+    fence: NvFence,
+    _unk1: [u32; 3],
+}
+
+/// Header of the `SUBMIT_GPFIFO` and `KICKOFF_PB` arguments.
+#[derive(Default)]
+#[repr(C)]
+struct SubmitGpfifoArgs {
+    /// In: pointer to the entries (ignored, they are passed in a buffer).
+    gpfifo: u64,
+    /// In: number of entries.
+    num_entries: u32,
+    /// In: submission flags.
+    flags: u32,
+    /// In: fence to wait for; out: completion fence.
+    fence: NvFence,
+}
+
+/// Size of the [`SubmitGpfifoArgs`] header, in bytes.
+const SUBMIT_ARGS_SIZE: usize = size_of::<SubmitGpfifoArgs>();
+
+const _: () = {
+    assert!(size_of::<InitializeExArgs>() == crate::nv_ioc_size(NVGPU_AS_IOCTL_INITIALIZE_EX));
+    assert!(size_of::<MapBufferExArgs>() == crate::nv_ioc_size(NVGPU_AS_IOCTL_MAP_BUFFER_EX));
+    assert!(size_of::<UnmapBufferArgs>() == crate::nv_ioc_size(NVGPU_AS_IOCTL_UNMAP_BUFFER));
+    assert!(
+        size_of::<AllocGpfifoEx2Args>() == crate::nv_ioc_size(NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2)
+    );
+    assert!(SUBMIT_ARGS_SIZE == crate::nv_ioc_size(NVGPU_IOCTL_CHANNEL_KICKOFF_PB));
+    assert!(offset_of!(AllocGpfifoEx2Args, fence) == 0xC);
+    assert!(offset_of!(SubmitGpfifoArgs, fence) == 0x10);
+    assert!(submit_gpfifo_request(MAX_INLINE_GPFIFO_ENTRIES) & 0xFFFF == 0x4808);
+};
+
+/// Returns the `SUBMIT_GPFIFO` request for `num_entries` inline entries.
+const fn submit_gpfifo_request(num_entries: usize) -> u32 {
+    let size = (SUBMIT_ARGS_SIZE + num_entries * size_of::<GpfifoEntry>()) as u32;
+    NVGPU_IOCTL_CHANNEL_SUBMIT_GPFIFO | (size << 16)
+}
+
+/// GPU channel bound to its own address space.
+///
+/// Closes the channel, then the address space, when dropped. Mappings are
+/// released with the address space.
+pub struct NvGpuChannel<'a> {
+    service: &'a NvService,
+    as_fd: Fd,
+    channel_fd: Fd,
+    gpfifo_entries: u32,
+    ioctl2_supported: bool,
+}
+
+impl<'a> NvGpuChannel<'a> {
+    /// Opens a channel and its address space.
+    ///
+    /// `nvmap_fd` is the `/dev/nvmap` fd the mapped handles are created on.
+    /// `ioctl2_supported` tells whether [`NvService::ioctl2`] is available
+    /// (firmware 3.0.0+, see `nx_rt::nv_manager::ioctl2_supported`); without
+    /// it, submissions pass their entries inline and are limited to
+    /// [`MAX_INLINE_GPFIFO_ENTRIES`].
+    ///
+    /// If a step fails, the devices opened so far are closed.
+    pub fn open(
+        service: &'a NvService,
+        nvmap_fd: Fd,
+        ioctl2_supported: bool,
+    ) -> Result<Self, OpenGpuChannelError> {
+        let as_fd = service
+            .open_device(crate::NvDevice::NvHostAsGpu)
+            .map_err(OpenGpuChannelError::OpenAddressSpace)?;
+
+        let mut init_args = InitializeExArgs {
+            big_page_size: BIG_PAGE_SIZE,
+            flags: 1,
+            ..Default::default()
+        };
+        if let Err(err) = service.ioctl(
+            as_fd,
+            NVGPU_AS_IOCTL_INITIALIZE_EX,
+            as_bytes_mut(&mut init_args),
+        ) {
+            let _ = service.close_fd(as_fd);
+            return Err(OpenGpuChannelError::InitializeAddressSpace(err));
+        }
+
+        let channel_fd = match service.open_device(crate::NvDevice::NvHostGpu) {
+            Ok(fd) => fd,
+            Err(err) => {
+                let _ = service.close_fd(as_fd);
+                return Err(OpenGpuChannelError::OpenChannel(err));
+            }
+        };
+
+        // From here on, dropping the channel closes both devices
+        let channel = Self {
+            service,
+            as_fd,
+            channel_fd,
+            gpfifo_entries: 0,
+            ioctl2_supported,
+        };
+
+        let mut nvmap_fd = nvmap_fd.to_raw();
+        service
+            .ioctl(
+                channel_fd,
+                NVGPU_IOCTL_CHANNEL_SET_NVMAP_FD,
+                as_bytes_mut(&mut nvmap_fd),
+            )
+            .map_err(OpenGpuChannelError::SetNvmapFd)?;
+
+        let mut channel_raw = channel_fd.to_raw();
+        service
+            .ioctl(
+                as_fd,
+                NVGPU_AS_IOCTL_BIND_CHANNEL,
+                as_bytes_mut(&mut channel_raw),
+            )
+            .map_err(OpenGpuChannelError::BindChannel)?;
+
+        Ok(channel)
+    }
+
+    /// Returns the channel fd (`/dev/nvhost-gpu`).
+    #[inline]
+    pub fn channel_fd(&self) -> Fd {
+        self.channel_fd
+    }
+
+    /// Returns the address space fd (`/dev/nvhost-as-gpu`).
+    #[inline]
+    pub fn as_fd(&self) -> Fd {
+        self.as_fd
+    }
+
+    /// Returns the number of GPFIFO entries, or `0` before
+    /// [`alloc_gpfifo`](Self::alloc_gpfifo).
+    #[inline]
+    pub fn gpfifo_entries(&self) -> u32 {
+        self.gpfifo_entries
+    }
+
+    /// Allocates a GPFIFO of `num_entries` entries.
+    ///
+    /// `num_entries` must be a power of two. Returns the fence of the
+    /// channel setup, to wait for before the first submission.
+    pub fn alloc_gpfifo(&mut self, num_entries: u32) -> Result<NvFence, AllocGpfifoError> {
+        if self.gpfifo_entries != 0 {
+            return Err(AllocGpfifoError::AlreadyAllocated);
+        }
+        if !num_entries.is_power_of_two() {
+            return Err(AllocGpfifoError::InvalidEntryCount(num_entries));
+        }
+
+        let mut args = AllocGpfifoEx2Args {
+            num_entries,
+            flags: 1,
+            ..Default::default()
+        };
+        self.service
+            .ioctl(
+                self.channel_fd,
+                NVGPU_IOCTL_CHANNEL_ALLOC_GPFIFO_EX2,
+                as_bytes_mut(&mut args),
+            )
+            .map_err(AllocGpfifoError::Ioctl)?;
+
+        self.gpfifo_entries = num_entries;
+        Ok(args.fence)
+    }
+
+    /// Maps `handle` into the channel's address space.
+    ///
+    /// The whole buffer is mapped with big pages, uncached, keeping the
+    /// memory kind of the handle. Returns the GPU virtual address of the
+    /// mapping.
+    pub fn map_buffer(&self, handle: &NvMapHandle<'_>) -> Result<u64, MapBufferError> {
+        let mut args = MapBufferExArgs {
+            kind: -1,
+            nvmap_handle: handle.raw(),
+            page_size: BIG_PAGE_SIZE,
+            ..Default::default()
+        };
+        self.service
+            .ioctl(
+                self.as_fd,
+                NVGPU_AS_IOCTL_MAP_BUFFER_EX,
+                as_bytes_mut(&mut args),
+            )
+            .map_err(MapBufferError::Ioctl)?;
+
+        Ok(args.offset as u64)
+    }
+
+    /// Unmaps the mapping at `gpu_va`, returned by
+    /// [`map_buffer`](Self::map_buffer).
+    pub fn unmap_buffer(&self, gpu_va: u64) -> Result<(), UnmapBufferError> {
+        let mut args = UnmapBufferArgs {
+            offset: gpu_va as i64,
+        };
+        self.service
+            .ioctl(
+                self.as_fd,
+                NVGPU_AS_IOCTL_UNMAP_BUFFER,
+                as_bytes_mut(&mut args),
+            )
+            .map_err(UnmapBufferError::Ioctl)
+    }
+
+    /// Submits `entries` to the GPFIFO.
+    ///
+    /// If `wait` is set, the GPU waits for that fence before executing the
+    /// entries. Returns the fence signaled once they have executed.
+    ///
+    /// Uses `KICKOFF_PB` through ioctl2 when available, and
+    /// `SUBMIT_GPFIFO` with inline entries otherwise.
+    pub fn submit(
+        &self,
+        entries: &[GpfifoEntry],
+        wait: Option<NvFence>,
+    ) -> Result<NvFence, SubmitError> {
+        if self.gpfifo_entries == 0 {
+            return Err(SubmitError::GpfifoNotAllocated);
+        }
+
+        let max_entries = if self.ioctl2_supported {
+            self.gpfifo_entries as usize
+        } else {
+            MAX_INLINE_GPFIFO_ENTRIES.min(self.gpfifo_entries as usize)
+        };
+        if entries.is_empty() || entries.len() > max_entries {
+            return Err(SubmitError::InvalidEntryCount(entries.len()));
+        }
+
+        let mut args = SubmitGpfifoArgs {
+            num_entries: entries.len() as u32,
+            flags: SUBMIT_FLAG_FENCE_GET,
+            ..Default::default()
+        };
+        if let Some(fence) = wait {
+            args.flags |= SUBMIT_FLAG_FENCE_WAIT;
+            args.fence = fence;
+        }
+
+        // SAFETY: GpfifoEntry is a transparent u64.
+        let entries_bytes = unsafe {
+            core::slice::from_raw_parts(entries.as_ptr().cast::<u8>(), size_of_val(entries))
+        };
+
+        if self.ioctl2_supported {
+            self.service
+                .ioctl2(
+                    self.channel_fd,
+                    NVGPU_IOCTL_CHANNEL_KICKOFF_PB,
+                    as_bytes_mut(&mut args),
+                    entries_bytes,
+                )
+                .map_err(SubmitError::Ioctl2)?;
+            return Ok(args.fence);
+        }
+
+        // Stage the header and the entries contiguously
+        let mut buf =
+            [0u8; SUBMIT_ARGS_SIZE + MAX_INLINE_GPFIFO_ENTRIES * size_of::<GpfifoEntry>()];
+        let size = SUBMIT_ARGS_SIZE + entries_bytes.len();
+        buf[..SUBMIT_ARGS_SIZE].copy_from_slice(as_bytes_mut(&mut args));
+        buf[SUBMIT_ARGS_SIZE..size].copy_from_slice(entries_bytes);
+
+        self.service
+            .ioctl(
+                self.channel_fd,
+                submit_gpfifo_request(entries.len()),
+                &mut buf[..size],
+            )
+            .map_err(SubmitError::Ioctl)?;
+
+        // SAFETY: The buffer starts with the header, which the driver updated.
+        let args = unsafe { core::ptr::read_unaligned(buf.as_ptr().cast::<SubmitGpfifoArgs>()) };
+        Ok(args.fence)
+    }
+}
+
+impl Drop for NvGpuChannel<'_> {
+    fn drop(&mut self) {
+        // Close the channel first, so it is unbound before its address space
+        // goes away
+        let _ = self.service.close_fd(self.channel_fd);
+        let _ = self.service.close_fd(self.as_fd);
+    }
+}
+
+/// Views an ioctl argument struct as its raw bytes.
+fn as_bytes_mut<T>(args: &mut T) -> &mut [u8] {
+    // SAFETY: The channel argument structs are repr(C) plain data with
+    // explicit padding.
+    unsafe { core::slice::from_raw_parts_mut((args as *mut T).cast::<u8>(), size_of::<T>()) }
+}
+
+/// Error returned by [`NvGpuChannel::open`].
+#[derive(Debug, thiserror::Error)]
+pub enum OpenGpuChannelError {
+    /// Opening `/dev/nvhost-as-gpu` failed.
+    #[error("failed to open the GPU address space")]
+    OpenAddressSpace(#[source] OpenError),
+    /// Initializing the address space failed.
+    #[error("failed to initialize the GPU address space")]
+    InitializeAddressSpace(#[source] IoctlError),
+    /// Opening `/dev/nvhost-gpu` failed.
+    #[error("failed to open the GPU channel")]
+    OpenChannel(#[source] OpenError),
+    /// Setting the nvmap fd of the channel failed.
+    #[error("failed to set the channel nvmap fd")]
+    SetNvmapFd(#[source] IoctlError),
+    /// Binding the channel to the address space failed.
+    #[error("failed to bind the channel to the address space")]
+    BindChannel(#[source] IoctlError),
+}
+
+/// Error returned by [`NvGpuChannel::alloc_gpfifo`].
+#[derive(Debug, thiserror::Error)]
+pub enum AllocGpfifoError {
+    /// The GPFIFO of the channel is already allocated.
+    #[error("GPFIFO already allocated")]
+    AlreadyAllocated,
+    /// The entry count is not a power of two.
+    #[error("invalid GPFIFO entry count: {0}")]
+    InvalidEntryCount(u32),
+    /// The alloc ioctl failed.
+    #[error("GPFIFO alloc ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
+
+/// Error returned by [`NvGpuChannel::map_buffer`].
+#[derive(Debug, thiserror::Error)]
+pub enum MapBufferError {
+    /// The map ioctl failed.
+    #[error("map buffer ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
+
+/// Error returned by [`NvGpuChannel::unmap_buffer`].
+#[derive(Debug, thiserror::Error)]
+pub enum UnmapBufferError {
+    /// The unmap ioctl failed.
+    #[error("unmap buffer ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
+
+/// Error returned by [`NvGpuChannel::submit`].
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    /// [`NvGpuChannel::alloc_gpfifo`] was not called.
+    #[error("GPFIFO not allocated")]
+    GpfifoNotAllocated,
+    /// No entries, or more than a single submission can hold.
+    #[error("invalid submission entry count: {0}")]
+    InvalidEntryCount(usize),
+    /// The `SUBMIT_GPFIFO` ioctl failed.
+    #[error("submit GPFIFO ioctl failed")]
+    Ioctl(#[source] IoctlError),
+    /// The `KICKOFF_PB` ioctl2 failed.
+    #[error("kickoff ioctl2 failed")]
+    Ioctl2(#[source] Ioctl2Error),
+}
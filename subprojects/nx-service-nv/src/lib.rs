@@ -25,6 +25,8 @@ use nx_sys_mem::tmem::{self, TransferMemoryBacking};
 
 mod cmif;
 pub mod fd;
+pub mod gpu_channel;
+pub mod nvmap;
 mod proto;
 pub mod types;
 
@@ -35,13 +37,19 @@ pub use self::{
         CloseError, InitializeError, Ioctl2Error, Ioctl3Error, IoctlError, OpenError,
         QueryEventError, SetClientPidError,
     },
+    gpu_channel::{
+        AllocGpfifoError, GpfifoEntry, MapBufferError, NvFence, NvGpuChannel, OpenGpuChannelError,
+        SubmitError, UnmapBufferError,
+    },
+    nvmap::{NvMapAllocError, NvMapCreateError, NvMapFreeError, NvMapHandle},
     proto::{
         SERVICE_NAME_APPLET, SERVICE_NAME_APPLICATION, SERVICE_NAME_FACTORY, SERVICE_NAME_SYSTEM,
     },
     types::{
-        CloseNvError, IoctlNvError, NV_IOC_NONE, NV_IOC_READ, NV_IOC_WRITE, NvConfig, NvEventId,
-        NvServiceType, OpenNvError, QueryEventNvError, nv_event_id_ctrl_syncpt, nv_ioc_dir,
-        nv_ioc_size,
+        CloseNvError, IoctlNvError, NV_DEFAULT_TRANSFER_MEM_SIZE, NV_IOC_NONE, NV_IOC_READ,
+        NV_IOC_WRITE, NV_MAX_SESSION_COUNT, NV_TRANSFER_MEM_ALIGN, NvConfig, NvConfigBuilder,
+        NvConfigError, NvDevice, NvEventId, NvServiceType, OpenNvError, QueryEventNvError,
+        nv_event_id_ctrl_syncpt, nv_ioc_dir, nv_ioc_size,
     },
 };
 
@@ -53,7 +61,9 @@ pub struct NvService {
     /// Main service session.
     main_session: Service,
     /// Clone session for parallel ioctl operations.
-    clone_session: Service,
+    ///
+    /// `None` when connected with a `session_count` of 1.
+    clone_session: Option<Service>,
     /// Transfer memory backing for cleanup.
     ///
     /// The handle is closed early (after Initialize), but we keep the backing
@@ -80,9 +90,21 @@ impl NvService {
     }
 
     /// Returns the clone service session handle.
+    ///
+    /// When connected with a `session_count` of 1, no clone session is opened
+    /// and the main session handle is returned instead, as it serves the
+    /// ioctls the clone session would. Use
+    /// [`try_clone_session`](Self::try_clone_session) to tell both cases apart.
     #[inline]
     pub fn clone_session(&self) -> SessionHandle {
-        self.clone_session.session
+        self.try_clone_session()
+            .unwrap_or(self.main_session.session)
+    }
+
+    /// Returns the clone service session handle, if one was opened.
+    #[inline]
+    pub fn try_clone_session(&self) -> Option<SessionHandle> {
+        self.clone_session.as_ref().map(|s| s.session)
     }
 
     /// Returns the appropriate session for a given ioctl request.
@@ -91,19 +113,23 @@ impl NvService {
     /// avoid contention on the main session.
     #[inline]
     fn session_for_request(&self, request: u32) -> SessionHandle {
+        let Some(clone_session) = &self.clone_session else {
+            return self.main_session.session;
+        };
+
         let masked = request & proto::IOCTL_MASK;
 
         // Check masked ioctls
         for &ioctl in proto::CLONE_SESSION_IOCTLS {
             if masked == ioctl {
-                return self.clone_session.session;
+                return clone_session.session;
             }
         }
 
         // Check exact match ioctls
         for &ioctl in proto::CLONE_SESSION_IOCTLS_EXACT {
             if request == ioctl {
-                return self.clone_session.session;
+                return clone_session.session;
             }
         }
 
@@ -112,15 +138,28 @@ impl NvService {
 
     /// Opens a device by path.
     ///
-    /// Returns the file descriptor on success.
+    /// Returns the file descriptor on success. Prefer
+    /// [`open_device`](Self::open_device) for well-known devices.
     pub fn open(&self, device_path: &str) -> Result<Fd, OpenError> {
         cmif::open(self.main_session.session, device_path.as_bytes())
     }
 
+    /// Opens a well-known device.
+    ///
+    /// Returns the file descriptor on success.
+    #[inline]
+    pub fn open_device(&self, device: NvDevice) -> Result<Fd, OpenError> {
+        self.open(device.path())
+    }
+
     /// Performs an ioctl operation.
     ///
     /// The `argp` buffer is used for both input and output based on the
     /// direction flags in the request code.
+    ///
+    /// The driver reports its status in the first word of the response
+    /// payload, separately from `argp`. A non-zero status is returned as
+    /// [`IoctlError::NvError`]; the contents of `argp` are unspecified then.
     pub fn ioctl(&self, fd: Fd, request: u32, argp: &mut [u8]) -> Result<(), IoctlError> {
         let bufsize = nv_ioc_size(request);
         let dir = nv_ioc_dir(request);
@@ -141,6 +180,9 @@ impl NvService {
     /// Performs an ioctl2 operation with an extra input buffer.
     ///
     /// Available on firmware 3.0.0+.
+    ///
+    /// A non-zero driver status is returned as [`Ioctl2Error::NvError`], as
+    /// for [`ioctl`](Self::ioctl).
     pub fn ioctl2(
         &self,
         fd: Fd,
@@ -176,6 +218,9 @@ impl NvService {
     /// Performs an ioctl3 operation with an extra output buffer.
     ///
     /// Available on firmware 3.0.0+.
+    ///
+    /// A non-zero driver status is returned as [`Ioctl3Error::NvError`], as
+    /// for [`ioctl`](Self::ioctl).
     pub fn ioctl3(
         &self,
         fd: Fd,
@@ -220,10 +265,43 @@ impl NvService {
         cmif::query_event(self.main_session.session, fd, event_id)
     }
 
+    /// Creates an nvmap handle of `size` bytes on the `/dev/nvmap` fd `map_fd`.
+    ///
+    /// `size` must be a non-zero multiple of [`nvmap::NVMAP_PAGE_SIZE`]. The
+    /// returned handle is freed when dropped.
+    pub fn nvmap_create(&self, map_fd: Fd, size: u32) -> Result<NvMapHandle<'_>, NvMapCreateError> {
+        nvmap::create(self, map_fd, size)
+    }
+
+    /// Backs an nvmap handle with the memory at `addr`.
+    ///
+    /// `align` must be a power of two of at least
+    /// [`nvmap::NVMAP_PAGE_SIZE`], and `addr` must be aligned to it. The
+    /// memory must stay valid until the handle is freed.
+    pub fn nvmap_alloc(
+        &self,
+        handle: &NvMapHandle<'_>,
+        align: u32,
+        flags: u32,
+        kind: u8,
+        addr: usize,
+    ) -> Result<(), NvMapAllocError> {
+        nvmap::alloc(self, handle, align, flags, kind, addr)
+    }
+
+    /// Frees an nvmap handle, reporting whether the driver accepted it.
+    ///
+    /// Dropping the handle frees it too, but ignores errors.
+    pub fn nvmap_free(&self, handle: NvMapHandle<'_>) -> Result<(), NvMapFreeError> {
+        nvmap::free(handle)
+    }
+
     /// Consumes and closes the NV service session.
     pub fn close(self) {
         // Close clone session first to match libnx behavior
-        self.clone_session.close();
+        if let Some(clone_session) = self.clone_session {
+            clone_session.close();
+        }
         self.main_session.close();
 
         // Wait for transfer memory permission to return to RW, then free backing.
@@ -261,6 +339,8 @@ pub fn connect(
     aruid: Option<Aruid>,
     config: NvConfig,
 ) -> Result<NvService, ConnectError> {
+    config.validate().map_err(ConnectError::InvalidConfig)?;
+
     // Determine service type
     let service_type = if config.service_type == NvServiceType::Auto {
         resolve_service_type(applet_type)
@@ -282,12 +362,7 @@ pub fn connect(
         .get_service_handle_cmif(service_name)
         .map_err(ConnectError::GetService)?;
 
-    let main_session = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let main_session = Service::new(handle);
 
     // Create transfer memory
     let transfer_mem = unsafe { tmem::create(config.transfer_mem_size, MemoryPermission::NONE) }
@@ -322,13 +397,17 @@ pub fn connect(
     };
 
     // Clone the session for parallel ioctl operations
-    let clone_session = match main_session.try_clone_ex(1) {
-        Ok(s) => s,
-        Err(e) => {
-            main_session.close();
-            unsafe { tmem::free_backing(transfer_mem_backing) };
-            return Err(ConnectError::CloneSession(e));
+    let clone_session = if config.session_count > 1 {
+        match main_session.try_clone_ex(1) {
+            Ok(s) => Some(s),
+            Err(e) => {
+                main_session.close();
+                unsafe { tmem::free_backing(transfer_mem_backing) };
+                return Err(ConnectError::CloneSession(e));
+            }
         }
+    } else {
+        None
     };
 
     // Try to set client PID (best effort, may not have ARUID)
@@ -360,6 +439,9 @@ fn resolve_service_type(applet_type: AppletType) -> NvServiceType {
 /// Error returned by [`connect`].
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectError {
+    /// The configuration failed [`NvConfig::validate`].
+    #[error("invalid configuration")]
+    InvalidConfig(#[source] NvConfigError),
     /// Failed to get service handle from SM.
     #[error("failed to get service")]
     GetService(#[source] nx_service_sm::GetServiceCmifError),
@@ -0,0 +1,222 @@
+//! nvmap memory handle helpers.
+//!
+//! GPU-visible memory is managed through `/dev/nvmap` handles. A handle is
+//! first created with a size, then backed with process memory by an
+//! allocation, and finally freed. [`NvMapHandle`] owns a created handle and
+//! frees it when dropped.
+
+use core::mem::offset_of;
+
+use crate::{IoctlError, NvService, fd::Fd};
+
+/// Page size that nvmap sizes, alignments and addresses must be multiples of.
+pub const NVMAP_PAGE_SIZE: u32 = 0x1000;
+
+/// `NVMAP_IOC_CREATE`: creates a handle of a given size.
+const NVMAP_IOC_CREATE: u32 = 0xC008_0101;
+/// `NVMAP_IOC_ALLOC`: backs a handle with process memory.
+const NVMAP_IOC_ALLOC: u32 = 0xC020_0104;
+/// `NVMAP_IOC_FREE`: releases a handle.
+const NVMAP_IOC_FREE: u32 = 0xC018_0105;
+
+/// Arguments of `NVMAP_IOC_CREATE`.
+#[derive(Default)]
+#[repr(C)]
+struct CreateArgs {
+    /// In: size of the handle in bytes.
+    size: u32,
+    /// Out: created handle.
+    handle: u32,
+}
+
+/// Arguments of `NVMAP_IOC_ALLOC`.
+#[derive(Default)]
+#[repr(C)]
+struct AllocArgs {
+    /// In: handle to back.
+    handle: u32,
+    /// In: heap mask (0 lets the driver pick).
+    heapmask: u32,
+    /// In: allocation flags.
+    flags: u32,
+    /// In: alignment of the backing memory.
+    align: u32,
+    /// In: memory kind.
+    kind: u8,
+    _pad: [u8; 7],
+    /// In: address of the backing memory.
+    addr: u64,
+}
+
+/// Arguments of `NVMAP_IOC_FREE`.
+#[derive(Default)]
+#[repr(C)]
+struct FreeArgs {
+    /// In: handle to free.
+    handle: u32,
+    _pad: u32,
+    /// Out: address of the backing memory.
+    addr: u64,
+    /// Out: size of the handle.
+    size: u32,
+    /// Out: allocation flags.
+    flags: u32,
+}
+
+const _: () = {
+    assert!(size_of::<CreateArgs>() == crate::nv_ioc_size(NVMAP_IOC_CREATE));
+    assert!(size_of::<AllocArgs>() == crate::nv_ioc_size(NVMAP_IOC_ALLOC));
+    assert!(size_of::<FreeArgs>() == crate::nv_ioc_size(NVMAP_IOC_FREE));
+    assert!(offset_of!(AllocArgs, addr) == 0x18);
+};
+
+/// Owned nvmap handle.
+///
+/// Created by [`NvService::nvmap_create`]. The handle is freed through the
+/// nvmap fd it was created on when dropped; use
+/// [`NvService::nvmap_free`] instead to observe the result.
+pub struct NvMapHandle<'a> {
+    service: &'a NvService,
+    fd: Fd,
+    raw: u32,
+}
+
+impl NvMapHandle<'_> {
+    /// Returns the raw driver handle.
+    #[inline]
+    pub fn raw(&self) -> u32 {
+        self.raw
+    }
+
+    /// Returns the nvmap fd the handle was created on.
+    #[inline]
+    pub fn fd(&self) -> Fd {
+        self.fd
+    }
+
+    /// Releases ownership of the handle without freeing it.
+    ///
+    /// Returns the raw driver handle.
+    #[inline]
+    pub fn into_raw(self) -> u32 {
+        let raw = self.raw;
+        core::mem::forget(self);
+        raw
+    }
+}
+
+impl Drop for NvMapHandle<'_> {
+    fn drop(&mut self) {
+        let _ = free_raw(self.service, self.fd, self.raw);
+    }
+}
+
+/// Creates an nvmap handle of `size` bytes.
+pub(crate) fn create(
+    service: &NvService,
+    map_fd: Fd,
+    size: u32,
+) -> Result<NvMapHandle<'_>, NvMapCreateError> {
+    if size == 0 || !size.is_multiple_of(NVMAP_PAGE_SIZE) {
+        return Err(NvMapCreateError::InvalidSize(size));
+    }
+
+    let mut args = CreateArgs {
+        size,
+        ..Default::default()
+    };
+    service
+        .ioctl(map_fd, NVMAP_IOC_CREATE, as_bytes_mut(&mut args))
+        .map_err(NvMapCreateError::Ioctl)?;
+
+    Ok(NvMapHandle {
+        service,
+        fd: map_fd,
+        raw: args.handle,
+    })
+}
+
+/// Backs `handle` with the memory at `addr`.
+pub(crate) fn alloc(
+    service: &NvService,
+    handle: &NvMapHandle<'_>,
+    align: u32,
+    flags: u32,
+    kind: u8,
+    addr: usize,
+) -> Result<(), NvMapAllocError> {
+    if !align.is_power_of_two() || align < NVMAP_PAGE_SIZE {
+        return Err(NvMapAllocError::InvalidAlignment(align));
+    }
+    if !addr.is_multiple_of(align as usize) {
+        return Err(NvMapAllocError::UnalignedAddress(addr));
+    }
+
+    let mut args = AllocArgs {
+        handle: handle.raw,
+        heapmask: 0,
+        flags,
+        align,
+        kind,
+        addr: addr as u64,
+        ..Default::default()
+    };
+    service
+        .ioctl(handle.fd, NVMAP_IOC_ALLOC, as_bytes_mut(&mut args))
+        .map_err(NvMapAllocError::Ioctl)
+}
+
+/// Frees `handle`.
+pub(crate) fn free(handle: NvMapHandle<'_>) -> Result<(), NvMapFreeError> {
+    let (service, fd) = (handle.service, handle.fd);
+    let raw = handle.into_raw();
+    free_raw(service, fd, raw).map_err(NvMapFreeError::Ioctl)
+}
+
+fn free_raw(service: &NvService, fd: Fd, raw: u32) -> Result<(), IoctlError> {
+    let mut args = FreeArgs {
+        handle: raw,
+        ..Default::default()
+    };
+    service.ioctl(fd, NVMAP_IOC_FREE, as_bytes_mut(&mut args))
+}
+
+/// Views an ioctl argument struct as its raw bytes.
+fn as_bytes_mut<T>(args: &mut T) -> &mut [u8] {
+    // SAFETY: The nvmap argument structs are repr(C) plain data with
+    // explicit padding.
+    unsafe { core::slice::from_raw_parts_mut((args as *mut T).cast::<u8>(), size_of::<T>()) }
+}
+
+/// Error returned by [`NvService::nvmap_create`].
+#[derive(Debug, thiserror::Error)]
+pub enum NvMapCreateError {
+    /// The size is zero or not a multiple of [`NVMAP_PAGE_SIZE`].
+    #[error("invalid nvmap size: {0:#x}")]
+    InvalidSize(u32),
+    /// The create ioctl failed.
+    #[error("nvmap create ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
+
+/// Error returned by [`NvService::nvmap_alloc`].
+#[derive(Debug, thiserror::Error)]
+pub enum NvMapAllocError {
+    /// The alignment is not a power of two of at least [`NVMAP_PAGE_SIZE`].
+    #[error("invalid nvmap alignment: {0:#x}")]
+    InvalidAlignment(u32),
+    /// The backing address is not a multiple of the alignment.
+    #[error("unaligned nvmap address: {0:#x}")]
+    UnalignedAddress(usize),
+    /// The alloc ioctl failed.
+    #[error("nvmap alloc ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
+
+/// Error returned by [`NvService::nvmap_free`].
+#[derive(Debug, thiserror::Error)]
+pub enum NvMapFreeError {
+    /// The free ioctl failed.
+    #[error("nvmap free ioctl failed")]
+    Ioctl(#[source] IoctlError),
+}
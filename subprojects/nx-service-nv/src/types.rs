@@ -31,6 +31,69 @@ impl NvServiceType {
     }
 }
 
+/// Well-known NV device nodes.
+///
+/// Use with [`NvService::open_device`](crate::NvService::open_device) instead of
+/// spelling out the path. Which devices can be opened depends on the service
+/// the session was opened with:
+///
+/// | Devices | Service types |
+/// |---------|---------------|
+/// | GPU (`NvMap`, `NvHostCtrl`, `NvHostCtrlGpu`, `NvHostGpu`, `NvHostAsGpu`) | All |
+/// | Multimedia (`NvHostNvDec`, `NvHostNvJpg`, `NvHostVic`) | All |
+/// | Debug/profiling (`NvHostDbgGpu`, `NvHostProfGpu`) | `System`, `Factory` |
+/// | Display (`NvDispDisp0`, `NvDispDisp1`, `NvDcCtrl`) | `System` |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NvDevice {
+    /// `/dev/nvmap`: GPU memory handle allocation.
+    NvMap,
+    /// `/dev/nvhost-ctrl`: syncpoint and event control.
+    NvHostCtrl,
+    /// `/dev/nvhost-ctrl-gpu`: GPU characteristics and control.
+    NvHostCtrlGpu,
+    /// `/dev/nvhost-gpu`: GPU channel (command submission).
+    NvHostGpu,
+    /// `/dev/nvhost-as-gpu`: GPU address space management.
+    NvHostAsGpu,
+    /// `/dev/nvhost-nvdec`: video decoder.
+    NvHostNvDec,
+    /// `/dev/nvhost-nvjpg`: JPEG engine.
+    NvHostNvJpg,
+    /// `/dev/nvhost-vic`: video image compositor.
+    NvHostVic,
+    /// `/dev/nvhost-dbg-gpu`: GPU debugger.
+    NvHostDbgGpu,
+    /// `/dev/nvhost-prof-gpu`: GPU profiler.
+    NvHostProfGpu,
+    /// `/dev/nvdisp-disp0`: internal display controller.
+    NvDispDisp0,
+    /// `/dev/nvdisp-disp1`: external display controller.
+    NvDispDisp1,
+    /// `/dev/nvdcctrl`: display controller control.
+    NvDcCtrl,
+}
+
+impl NvDevice {
+    /// Returns the device node path.
+    pub const fn path(self) -> &'static str {
+        match self {
+            Self::NvMap => "/dev/nvmap",
+            Self::NvHostCtrl => "/dev/nvhost-ctrl",
+            Self::NvHostCtrlGpu => "/dev/nvhost-ctrl-gpu",
+            Self::NvHostGpu => "/dev/nvhost-gpu",
+            Self::NvHostAsGpu => "/dev/nvhost-as-gpu",
+            Self::NvHostNvDec => "/dev/nvhost-nvdec",
+            Self::NvHostNvJpg => "/dev/nvhost-nvjpg",
+            Self::NvHostVic => "/dev/nvhost-vic",
+            Self::NvHostDbgGpu => "/dev/nvhost-dbg-gpu",
+            Self::NvHostProfGpu => "/dev/nvhost-prof-gpu",
+            Self::NvDispDisp0 => "/dev/nvdisp-disp0",
+            Self::NvDispDisp1 => "/dev/nvdisp-disp1",
+            Self::NvDcCtrl => "/dev/nvdcctrl",
+        }
+    }
+}
+
 /// Error codes returned by NV Open command.
 ///
 /// The Open command can return a limited set of error codes based on
@@ -165,34 +228,68 @@ impl QueryEventNvError {
 /// Error codes returned by NV Ioctl commands (Ioctl, Ioctl2, Ioctl3).
 ///
 /// Ioctl commands delegate to device drivers which can return various
-/// error codes. This enum covers the common cases with an `Unknown` variant
+/// error codes. The driver's status is the first word of the CMIF response
+/// payload, not part of the ioctl argument buffer; a non-zero status is
+/// reported as an error even though the IPC request itself succeeded.
+///
+/// This enum covers the codes of libnx's `NvError` with an `Unknown` variant
 /// for device-specific or rare error codes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
 pub enum IoctlNvError {
     /// Operation not implemented.
     #[error("not implemented")]
     NotImplemented,
+    /// Operation not supported.
+    #[error("not supported")]
+    NotSupported,
     /// Service not initialized.
     #[error("service not initialized")]
     NotInitialized,
-    /// Bad parameter provided.
+    /// Bad parameter provided (`EINVAL`).
     #[error("bad parameter")]
     BadParameter,
-    /// Operation timed out.
+    /// Operation timed out (`ETIMEDOUT`).
     #[error("timeout")]
     Timeout,
-    /// Insufficient memory available.
+    /// Insufficient memory available (`ENOMEM`).
     #[error("insufficient memory")]
     InsufficientMemory,
+    /// Attribute is read-only.
+    #[error("read-only attribute")]
+    ReadOnlyAttribute,
     /// Invalid state for operation.
     #[error("invalid state")]
     InvalidState,
+    /// Invalid address (`EFAULT`).
+    #[error("invalid address")]
+    InvalidAddress,
+    /// Invalid size.
+    #[error("invalid size")]
+    InvalidSize,
     /// Bad value provided.
     #[error("bad value")]
     BadValue,
-    /// Resource is busy.
+    /// Resource is already allocated.
+    #[error("already allocated")]
+    AlreadyAllocated,
+    /// Resource is busy (`EBUSY`).
     #[error("busy")]
     Busy,
+    /// Driver resource error.
+    #[error("resource error")]
+    ResourceError,
+    /// Count mismatch.
+    #[error("count mismatch")]
+    CountMismatch,
+    /// Shared memory is too small.
+    #[error("shared memory too small")]
+    SharedMemoryTooSmall,
+    /// The ioctl failed inside the driver.
+    #[error("ioctl failed")]
+    IoctlFailed,
+    /// Access to the device or operation was denied (`EACCES`).
+    #[error("access denied")]
+    AccessDenied,
     /// Unknown or device-specific error code.
     #[error("unknown error code: {0:#x}")]
     Unknown(u32),
@@ -203,13 +300,23 @@ impl IoctlNvError {
     pub fn from_raw(code: u32) -> Self {
         match code {
             0x1 => Self::NotImplemented,
+            0x2 => Self::NotSupported,
             0x3 => Self::NotInitialized,
             0x4 => Self::BadParameter,
             0x5 => Self::Timeout,
             0x6 => Self::InsufficientMemory,
+            0x7 => Self::ReadOnlyAttribute,
             0x8 => Self::InvalidState,
+            0x9 => Self::InvalidAddress,
+            0xA => Self::InvalidSize,
             0xB => Self::BadValue,
+            0xD => Self::AlreadyAllocated,
             0xE => Self::Busy,
+            0xF => Self::ResourceError,
+            0x10 => Self::CountMismatch,
+            0x1000 => Self::SharedMemoryTooSmall,
+            0x3000F => Self::IoctlFailed,
+            0x30010 => Self::AccessDenied,
             other => Self::Unknown(other),
         }
     }
@@ -218,36 +325,144 @@ impl IoctlNvError {
     pub fn to_raw(self) -> u32 {
         match self {
             Self::NotImplemented => 0x1,
+            Self::NotSupported => 0x2,
             Self::NotInitialized => 0x3,
             Self::BadParameter => 0x4,
             Self::Timeout => 0x5,
             Self::InsufficientMemory => 0x6,
+            Self::ReadOnlyAttribute => 0x7,
             Self::InvalidState => 0x8,
+            Self::InvalidAddress => 0x9,
+            Self::InvalidSize => 0xA,
             Self::BadValue => 0xB,
+            Self::AlreadyAllocated => 0xD,
             Self::Busy => 0xE,
+            Self::ResourceError => 0xF,
+            Self::CountMismatch => 0x10,
+            Self::SharedMemoryTooSmall => 0x1000,
+            Self::IoctlFailed => 0x3000F,
+            Self::AccessDenied => 0x30010,
             Self::Unknown(code) => code,
         }
     }
 }
 
+/// Page size that the transfer memory size must be aligned to.
+pub const NV_TRANSFER_MEM_ALIGN: usize = 0x1000;
+
+/// Default transfer memory size (8 MiB), matching libnx.
+pub const NV_DEFAULT_TRANSFER_MEM_SIZE: usize = 0x80_0000;
+
+/// Maximum number of sessions (main session plus one clone).
+pub const NV_MAX_SESSION_COUNT: usize = 2;
+
 /// NV configuration options.
+///
+/// Prefer [`NvConfig::builder`], which validates the values up front.
+/// [`connect`](crate::connect) validates them again before the
+/// `Initialize` command.
 #[derive(Debug, Clone)]
 pub struct NvConfig {
     /// Service type to connect to.
     pub service_type: NvServiceType,
     /// Transfer memory size for GPU operations.
+    ///
+    /// The driver maps this memory as its per-process heap, so it must be
+    /// non-zero and a multiple of [`NV_TRANSFER_MEM_ALIGN`].
     pub transfer_mem_size: usize,
+    /// Number of sessions to open, including the main session.
+    ///
+    /// With `2`, high-frequency ioctls are routed to a cloned session. With
+    /// `1`, every request goes through the main session.
+    pub session_count: usize,
+}
+
+impl NvConfig {
+    /// Returns a builder initialized with the default configuration.
+    pub fn builder() -> NvConfigBuilder {
+        NvConfigBuilder {
+            inner: Self::default(),
+        }
+    }
+
+    /// Checks the transfer memory size and session count.
+    ///
+    /// [`connect`](crate::connect) runs this check too, so a configuration
+    /// built by hand cannot bypass it.
+    pub fn validate(&self) -> Result<(), NvConfigError> {
+        let size = self.transfer_mem_size;
+        if size == 0 {
+            return Err(NvConfigError::ZeroTransferMemSize);
+        }
+        if !size.is_multiple_of(NV_TRANSFER_MEM_ALIGN) {
+            return Err(NvConfigError::UnalignedTransferMemSize(size));
+        }
+
+        let count = self.session_count;
+        if count == 0 || count > NV_MAX_SESSION_COUNT {
+            return Err(NvConfigError::InvalidSessionCount(count));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for NvConfig {
     fn default() -> Self {
         Self {
             service_type: NvServiceType::Auto,
-            transfer_mem_size: 0x80_0000, // 8 MB default
+            transfer_mem_size: NV_DEFAULT_TRANSFER_MEM_SIZE,
+            session_count: NV_MAX_SESSION_COUNT,
         }
     }
 }
 
+/// Builder for constructing a validated [`NvConfig`].
+#[derive(Debug, Clone)]
+pub struct NvConfigBuilder {
+    inner: NvConfig,
+}
+
+impl NvConfigBuilder {
+    /// Sets the service type to connect to.
+    pub fn service_type(mut self, service_type: NvServiceType) -> Self {
+        self.inner.service_type = service_type;
+        self
+    }
+
+    /// Sets the transfer memory size in bytes.
+    pub fn transfer_mem_size(mut self, size: usize) -> Self {
+        self.inner.transfer_mem_size = size;
+        self
+    }
+
+    /// Sets the number of sessions to open, including the main session.
+    pub fn session_count(mut self, count: usize) -> Self {
+        self.inner.session_count = count;
+        self
+    }
+
+    /// Validates the configuration and builds the [`NvConfig`].
+    pub fn build(self) -> Result<NvConfig, NvConfigError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+}
+
+/// Error returned by [`NvConfig::validate`] and [`NvConfigBuilder::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum NvConfigError {
+    /// The transfer memory size is zero.
+    #[error("transfer memory size must be non-zero")]
+    ZeroTransferMemSize,
+    /// The transfer memory size is not a multiple of the page size.
+    #[error("transfer memory size {0:#x} is not page-aligned")]
+    UnalignedTransferMemSize(usize),
+    /// The session count is outside `1..=NV_MAX_SESSION_COUNT`.
+    #[error("invalid session count: {0}")]
+    InvalidSessionCount(usize),
+}
+
 // Ioctl direction flags (matching linux ioctl convention)
 /// No data transfer.
 pub const NV_IOC_NONE: u32 = 0;
@@ -8,7 +8,10 @@ use core::mem::size_of;
 use nx_sf::cmif;
 use nx_svc::ipc::{self, Handle as SessionHandle};
 
-use crate::proto::{CMD_GET_FIRMWARE_VERSION, CMD_GET_FIRMWARE_VERSION_2, FirmwareVersion};
+use crate::proto::{
+    CMD_GET_FIRMWARE_VERSION, CMD_GET_FIRMWARE_VERSION_2, CMD_GET_REGION_CODE, FirmwareVersion,
+    RegionCode,
+};
 
 /// Gets the system firmware version using CMIF protocol.
 ///
@@ -61,6 +64,33 @@ fn get_firmware_version_inner(
     Ok(out)
 }
 
+/// Gets the console region using CMIF protocol.
+///
+/// Uses set:sys command ID 56 (GetRegionCode).
+pub fn get_region_code(session: SessionHandle) -> Result<RegionCode, GetRegionCodeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(CMD_GET_REGION_CODE).build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let _req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    ipc::send_sync_request(session).map_err(GetRegionCodeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 4) }
+        .map_err(GetRegionCodeError::ParseResponse)?;
+
+    // Response contains a single i32
+    if resp.data.len() < 4 {
+        return Err(GetRegionCodeError::InvalidResponse);
+    }
+
+    let raw = i32::from_le_bytes([resp.data[0], resp.data[1], resp.data[2], resp.data[3]]);
+
+    Ok(RegionCode::from_raw(raw))
+}
+
 /// Error returned by [`get_firmware_version`].
 #[derive(Debug, thiserror::Error)]
 pub enum GetFirmwareVersionError {
@@ -71,3 +101,17 @@ pub enum GetFirmwareVersionError {
     #[error("failed to parse response")]
     ParseResponse(#[source] cmif::ParseResponseError),
 }
+
+/// Error returned by [`get_region_code`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetRegionCodeError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+    /// Response data was too short.
+    #[error("invalid response data")]
+    InvalidResponse,
+}
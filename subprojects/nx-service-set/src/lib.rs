@@ -1,7 +1,7 @@
 //! System Settings Service (set:sys) Implementation.
 //!
 //! This crate provides access to the Nintendo Switch's system settings service,
-//! which allows querying firmware version and other system configuration.
+//! which allows querying firmware version, region and other system configuration.
 //!
 //! ## Protocol Support
 //!
@@ -25,9 +25,15 @@ mod proto;
 mod tipc;
 
 pub use self::{
-    cmif::GetFirmwareVersionError as GetFirmwareVersionCmifError,
-    proto::{FirmwareVersion, SERVICE_NAME},
-    tipc::GetFirmwareVersionError as GetFirmwareVersionTipcError,
+    cmif::{
+        GetFirmwareVersionError as GetFirmwareVersionCmifError,
+        GetRegionCodeError as GetRegionCodeCmifError,
+    },
+    proto::{FirmwareVersion, RegionCode, SERVICE_NAME},
+    tipc::{
+        GetFirmwareVersionError as GetFirmwareVersionTipcError,
+        GetRegionCodeError as GetRegionCodeTipcError,
+    },
 };
 
 /// System Settings Service (set:sys) session wrapper.
@@ -72,6 +78,15 @@ impl SetSysService {
     ) -> Result<FirmwareVersion, GetFirmwareVersionCmifError> {
         cmif::get_firmware_version_legacy(self.0.session)
     }
+
+    /// Gets the console region using CMIF protocol.
+    ///
+    /// Uses command ID 56 (GetRegionCode). Region values this crate does not
+    /// know map to [`RegionCode::Unknown`].
+    #[inline]
+    pub fn get_region_code_cmif(&self) -> Result<RegionCode, GetRegionCodeCmifError> {
+        cmif::get_region_code(self.0.session)
+    }
 }
 
 /// TIPC protocol methods.
@@ -99,6 +114,16 @@ impl SetSysService {
     ) -> Result<FirmwareVersion, GetFirmwareVersionTipcError> {
         tipc::get_firmware_version_legacy(self.0.session)
     }
+
+    /// Gets the console region using TIPC protocol.
+    ///
+    /// Uses command ID 56 (GetRegionCode). Region values this crate does not
+    /// know map to [`RegionCode::Unknown`].
+    /// Requires HOS 12.0.0+ or Atmosphere.
+    #[inline]
+    pub fn get_region_code_tipc(&self) -> Result<RegionCode, GetRegionCodeTipcError> {
+        tipc::get_region_code(self.0.session)
+    }
 }
 
 /// Connects to the set:sys (System Settings) service using CMIF.
@@ -110,12 +135,7 @@ pub fn connect_cmif(sm: &SmService) -> Result<SetSysService, ConnectCmifError> {
         .get_service_handle_cmif(SERVICE_NAME)
         .map_err(ConnectCmifError)?;
 
-    let service = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let service = Service::new(handle);
 
     Ok(SetSysService(service))
 }
@@ -134,6 +154,8 @@ pub fn connect_tipc(sm: &SmService) -> Result<SetSysService, ConnectTipcError> {
         .get_service_handle_tipc(SERVICE_NAME)
         .map_err(ConnectTipcError)?;
 
+    // TIPC sessions have no CMIF control interface, so the pointer buffer
+    // size can't be queried.
     let service = Service {
         session: handle,
         own_handle: 1,
@@ -18,6 +18,61 @@ pub const CMD_GET_FIRMWARE_VERSION: u32 = 3;
 /// This command preserves the revision field in the output.
 pub const CMD_GET_FIRMWARE_VERSION_2: u32 = 4;
 
+/// Command ID for GetRegionCode.
+///
+/// This is the set:sys command; the unprivileged `set` service exposes the
+/// same query as its command 4.
+pub const CMD_GET_REGION_CODE: u32 = 56;
+
+/// Console region, as returned by `GetRegionCode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionCode {
+    /// Japan.
+    Japan,
+    /// The Americas.
+    Americas,
+    /// Europe.
+    Europe,
+    /// Australia and New Zealand.
+    Australia,
+    /// Hong Kong, Taiwan and Korea, which share a single region.
+    HongKongTaiwanKorea,
+    /// Mainland China.
+    China,
+    /// A value this crate does not know about.
+    Unknown(i32),
+}
+
+impl RegionCode {
+    /// Converts a raw region code.
+    ///
+    /// Unexpected values map to [`RegionCode::Unknown`].
+    pub fn from_raw(raw: i32) -> Self {
+        match raw {
+            0 => Self::Japan,
+            1 => Self::Americas,
+            2 => Self::Europe,
+            3 => Self::Australia,
+            4 => Self::HongKongTaiwanKorea,
+            5 => Self::China,
+            raw => Self::Unknown(raw),
+        }
+    }
+
+    /// Returns the raw region code.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            Self::Japan => 0,
+            Self::Americas => 1,
+            Self::Europe => 2,
+            Self::Australia => 3,
+            Self::HongKongTaiwanKorea => 4,
+            Self::China => 5,
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
 /// Firmware version information returned by `setsysGetFirmwareVersion`.
 ///
 /// This structure contains detailed information about the system firmware,
@@ -8,7 +8,10 @@ use core::mem::size_of;
 use nx_sf::{hipc::BufferMode, tipc};
 use nx_svc::ipc::{self, Handle as SessionHandle};
 
-use crate::proto::{CMD_GET_FIRMWARE_VERSION, CMD_GET_FIRMWARE_VERSION_2, FirmwareVersion};
+use crate::proto::{
+    CMD_GET_FIRMWARE_VERSION, CMD_GET_FIRMWARE_VERSION_2, CMD_GET_REGION_CODE, FirmwareVersion,
+    RegionCode,
+};
 
 /// Gets the system firmware version using TIPC protocol.
 ///
@@ -73,6 +76,42 @@ fn get_firmware_version_inner(
     Ok(out)
 }
 
+/// Gets the console region using TIPC protocol.
+///
+/// Uses set:sys command ID 56 (GetRegionCode).
+/// Requires HOS 12.0.0+ or Atmosphere.
+pub fn get_region_code(session: SessionHandle) -> Result<RegionCode, GetRegionCodeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = tipc::RequestFormat {
+        request_id: CMD_GET_REGION_CODE,
+        data_size: 0, // No input data
+        num_in_buffers: 0,
+        num_out_buffers: 0,
+        num_inout_buffers: 0,
+        num_handles: 0,
+        send_pid: false,
+    };
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let _req = unsafe { tipc::make_request(ipc_buf, fmt) };
+
+    ipc::send_sync_request(session).map_err(GetRegionCodeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp =
+        unsafe { tipc::parse_response(ipc_buf, 4) }.map_err(GetRegionCodeError::ParseResponse)?;
+
+    // Response contains a single i32
+    if resp.data.len() < 4 {
+        return Err(GetRegionCodeError::InvalidResponse);
+    }
+
+    let raw = i32::from_le_bytes([resp.data[0], resp.data[1], resp.data[2], resp.data[3]]);
+
+    Ok(RegionCode::from_raw(raw))
+}
+
 /// Error returned by [`get_firmware_version`].
 #[derive(Debug, thiserror::Error)]
 pub enum GetFirmwareVersionError {
@@ -83,3 +122,17 @@ pub enum GetFirmwareVersionError {
     #[error("failed to parse response")]
     ParseResponse(#[source] tipc::ParseResponseError),
 }
+
+/// Error returned by [`get_region_code`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetRegionCodeError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the TIPC response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] tipc::ParseResponseError),
+    /// Response data was too short.
+    #[error("invalid response data")]
+    InvalidResponse,
+}
@@ -21,6 +21,7 @@ use nx_sf::service::Service;
 use nx_svc::ipc::{self, Handle as SessionHandle};
 
 mod cmif;
+mod port;
 mod proto;
 mod tipc;
 
@@ -31,6 +32,7 @@ pub use self::{
         RegisterServiceError as RegisterServiceCmifError,
         UnregisterServiceError as UnregisterServiceCmifError,
     },
+    port::{AcceptError, ServicePort},
     proto::SM_PORT_NAME,
     tipc::{
         DetachClientError as DetachClientTipcError, GetServiceError as GetServiceTipcError,
@@ -85,6 +87,21 @@ impl SmService {
         cmif::register_service(self.0.session, name, is_light, max_sessions)
     }
 
+    /// Registers a service using CMIF protocol and wraps its server port.
+    ///
+    /// See [`ServicePort`] for accepting client sessions.
+    #[inline]
+    pub fn register_service_port_cmif(
+        &self,
+        name: ServiceName,
+        is_light: bool,
+        max_sessions: i32,
+    ) -> Result<ServicePort, RegisterServiceCmifError> {
+        let handle = cmif::register_service(self.0.session, name, is_light, max_sessions)?;
+        // SAFETY: SM returned a freshly created server port handle we own.
+        Ok(unsafe { ServicePort::from_handle(handle) })
+    }
+
     /// Unregisters a service using CMIF protocol.
     #[inline]
     pub fn unregister_service_cmif(
@@ -129,6 +146,21 @@ impl SmService {
         tipc::register_service(self.0.session, name, is_light, max_sessions)
     }
 
+    /// Registers a service using TIPC protocol and wraps its server port.
+    ///
+    /// See [`ServicePort`] for accepting client sessions.
+    #[inline]
+    pub fn register_service_port_tipc(
+        &self,
+        name: ServiceName,
+        is_light: bool,
+        max_sessions: i32,
+    ) -> Result<ServicePort, RegisterServiceTipcError> {
+        let handle = tipc::register_service(self.0.session, name, is_light, max_sessions)?;
+        // SAFETY: SM returned a freshly created server port handle we own.
+        Ok(unsafe { ServicePort::from_handle(handle) })
+    }
+
     /// Unregisters a service using TIPC protocol.
     #[inline]
     pub fn unregister_service_tipc(
@@ -166,7 +198,8 @@ pub fn connect() -> Result<SmService, ConnectError> {
         }
     };
 
-    // Create a minimal service (no pointer buffer query yet)
+    // Create a minimal service. SM never uses pointer buffers, so skip the
+    // pointer buffer size query.
     let service = Service {
         session: handle,
         own_handle: 1,
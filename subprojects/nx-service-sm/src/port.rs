@@ -0,0 +1,80 @@
+//! Server side of a registered service.
+//!
+//! Registering a service with SM returns the server port of the service.
+//! [`ServicePort`] owns that port and accepts the sessions clients open on it,
+//! which the service then serves with `svcReplyAndReceive`, parsing each
+//! request with [`nx_sf::hipc::parse_request`].
+//!
+//! Each accepted session is served until its client closes it, at which point
+//! `svcReplyAndReceive` fails and the service closes the session handle.
+
+use nx_svc::{
+    ipc::{self, AcceptSessionError, Handle as SessionHandle},
+    sync::{self, WaitSyncError},
+};
+
+/// Server port of a registered service.
+///
+/// Created by [`SmService::register_service_port_cmif`] or
+/// [`SmService::register_service_port_tipc`].
+///
+/// The `max_sessions` limit passed when registering caps the sessions open
+/// on the port at once. Once reached, clients can no longer connect until an
+/// accepted session is closed, so the service must close the sessions it
+/// accepted once their client is gone.
+///
+/// The port is closed when dropped, which stops new clients from connecting;
+/// sessions already accepted stay open. Unregister the service from SM
+/// separately to remove its name.
+///
+/// [`SmService::register_service_port_cmif`]: crate::SmService::register_service_port_cmif
+/// [`SmService::register_service_port_tipc`]: crate::SmService::register_service_port_tipc
+pub struct ServicePort(SessionHandle);
+
+impl ServicePort {
+    /// Wraps a server port handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid server port handle owned by the caller. It is
+    /// closed when the `ServicePort` is dropped.
+    #[inline]
+    pub unsafe fn from_handle(handle: SessionHandle) -> Self {
+        Self(handle)
+    }
+
+    /// Returns the underlying port handle.
+    #[inline]
+    pub fn handle(&self) -> SessionHandle {
+        self.0
+    }
+
+    /// Waits for a client to connect and accepts its session.
+    ///
+    /// Blocks until a session is pending on the port. Returns the server
+    /// session handle, which the caller owns and must close.
+    pub fn accept(&self) -> Result<SessionHandle, AcceptError> {
+        // SAFETY: The port handle is valid for the lifetime of `self`.
+        unsafe { sync::wait_synchronization_single(&self.0, u64::MAX) }
+            .map_err(AcceptError::Wait)?;
+
+        ipc::accept_session(self.0).map_err(AcceptError::Accept)
+    }
+}
+
+impl Drop for ServicePort {
+    fn drop(&mut self) {
+        let _ = ipc::close_handle(self.0);
+    }
+}
+
+/// Error returned by [`ServicePort::accept`].
+#[derive(Debug, thiserror::Error)]
+pub enum AcceptError {
+    /// Failed to wait on the port.
+    #[error("failed to wait on port")]
+    Wait(#[source] WaitSyncError),
+    /// Failed to accept the pending session.
+    #[error("failed to accept session")]
+    Accept(#[source] AcceptSessionError),
+}
@@ -1,9 +1,7 @@
 //! SM protocol constants.
 
-use core::ffi::CStr;
-
 /// SM named port.
-pub const SM_PORT_NAME: &CStr = c"sm:";
+pub const SM_PORT_NAME: &str = "sm:";
 
 /// Register client (sends PID).
 pub const REGISTER_CLIENT: u32 = 0;
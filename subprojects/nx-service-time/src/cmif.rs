@@ -5,12 +5,15 @@
 
 use core::ptr;
 
-use nx_sf::cmif;
+use nx_sf::{cmif, hipc::BufferMode};
 use nx_svc::ipc::{self, Handle as SessionHandle};
 
 use crate::{
-    proto::{static_service_cmds, system_clock_cmds, timezone_service_cmds},
-    types::{TimeCalendarAdditionalInfo, TimeCalendarTime},
+    proto::{static_service_cmds, steady_clock_cmds, system_clock_cmds, timezone_service_cmds},
+    types::{
+        TimeCalendarAdditionalInfo, TimeCalendarTime, TimeLocationName, TimeSteadyClockTimePoint,
+        TimeZoneRule,
+    },
 };
 
 /// Gets the standard user system clock (ISystemClock).
@@ -34,6 +37,18 @@ pub fn get_standard_network_system_clock(
     )
 }
 
+/// Gets the standard local system clock (ISystemClock).
+///
+/// This is IStaticService command 4.
+pub fn get_standard_local_system_clock(
+    session: SessionHandle,
+) -> Result<SessionHandle, GetSystemClockError> {
+    get_clock_session(
+        session,
+        static_service_cmds::GET_STANDARD_LOCAL_SYSTEM_CLOCK,
+    )
+}
+
 /// Gets the standard steady clock (ISteadyClock).
 ///
 /// This is IStaticService command 2.
@@ -150,6 +165,67 @@ pub fn get_current_time(session: SessionHandle) -> Result<u64, GetCurrentTimeErr
     Ok(timestamp)
 }
 
+/// Result code returned when the session lacks the permission to write a
+/// clock (module 116 `time`, description 1).
+const RESULT_PERMISSION_DENIED: u32 = 0x274;
+
+/// Sets the current time of a system clock.
+///
+/// This is ISystemClock command 1.
+pub fn set_current_time(session: SessionHandle, timestamp: u64) -> Result<(), SetTimeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(system_clock_cmds::SET_CURRENT_TIME)
+        .data_size(8) // timestamp
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // SAFETY: req.data has space for the u64 timestamp.
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<u64>().cast_mut(), timestamp);
+    }
+
+    ipc::send_sync_request(session).map_err(SetTimeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    match unsafe { cmif::parse_response(ipc_buf, false, 0) } {
+        Ok(_) => Ok(()),
+        Err(cmif::ParseResponseError::ServiceError(RESULT_PERMISSION_DENIED)) => {
+            Err(SetTimeError::PermissionDenied)
+        }
+        Err(err) => Err(SetTimeError::ParseResponse(err)),
+    }
+}
+
+/// Gets the current steady clock time point.
+///
+/// This is ISteadyClock command 0.
+pub fn get_current_time_point(
+    session: SessionHandle,
+) -> Result<TimeSteadyClockTimePoint, GetCurrentTimePointError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(steady_clock_cmds::GET_CURRENT_TIME_POINT).build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let _req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    ipc::send_sync_request(session).map_err(GetCurrentTimePointError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp =
+        unsafe { cmif::parse_response(ipc_buf, false, size_of::<TimeSteadyClockTimePoint>()) }
+            .map_err(GetCurrentTimePointError::ParseResponse)?;
+
+    // SAFETY: resp.data contains a TimeSteadyClockTimePoint.
+    let time_point =
+        unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<TimeSteadyClockTimePoint>()) };
+
+    Ok(time_point)
+}
+
 /// Converts a POSIX timestamp to calendar time with the device's timezone rule.
 ///
 /// This is ITimeZoneService command 101.
@@ -191,6 +267,198 @@ pub fn to_calendar_time_with_my_rule(
     Ok((output.caltime, output.info))
 }
 
+/// Sets the device location name.
+///
+/// This is ITimeZoneService command 1. Requires a time service with write
+/// access (e.g. `time:s`).
+pub fn set_device_location_name(
+    session: SessionHandle,
+    name: &TimeLocationName,
+) -> Result<(), SetDeviceLocationNameError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(timezone_service_cmds::SET_DEVICE_LOCATION_NAME)
+        .data_size(size_of::<TimeLocationName>())
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // SAFETY: req.data points to valid payload area with space for the name.
+    unsafe {
+        ptr::write_unaligned(
+            req.data.as_ptr().cast::<TimeLocationName>().cast_mut(),
+            *name,
+        );
+    }
+
+    ipc::send_sync_request(session).map_err(SetDeviceLocationNameError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(SetDeviceLocationNameError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Gets the total number of location names known to the service.
+///
+/// This is ITimeZoneService command 2.
+pub fn get_total_location_name_count(
+    session: SessionHandle,
+) -> Result<u32, GetTotalLocationNameCountError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(timezone_service_cmds::GET_TOTAL_LOCATION_NAME_COUNT)
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let _req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    ipc::send_sync_request(session).map_err(GetTotalLocationNameCountError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(GetTotalLocationNameCountError::ParseResponse)?;
+
+    // SAFETY: resp.data contains at least 4 bytes for u32.
+    Ok(unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<u32>()) })
+}
+
+/// Loads a page of location names starting at `index` into `names`.
+///
+/// Returns the number of entries written.
+///
+/// This is ITimeZoneService command 3.
+pub fn load_location_name_list(
+    session: SessionHandle,
+    index: u32,
+    names: &mut [TimeLocationName],
+) -> Result<u32, LoadLocationNameListError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(timezone_service_cmds::LOAD_LOCATION_NAME_LIST)
+        .data_size(4) // u32 index
+        .out_buffers(1)
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let mut req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // SAFETY: req.data points to valid payload area with space for u32.
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<u32>().cast_mut(), index);
+    }
+
+    req.add_out_buffer(
+        names.as_mut_ptr().cast::<u8>(),
+        size_of_val(names),
+        BufferMode::Normal,
+    );
+
+    ipc::send_sync_request(session).map_err(LoadLocationNameListError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(LoadLocationNameListError::ParseResponse)?;
+
+    // SAFETY: resp.data contains at least 4 bytes for u32.
+    let count = unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<u32>()) };
+
+    // Never trust the service to report more entries than the buffer holds
+    Ok(count.min(names.len() as u32))
+}
+
+/// Loads the time zone rule for a location name.
+///
+/// This is ITimeZoneService command 4.
+pub fn load_time_zone_rule(
+    session: SessionHandle,
+    name: &TimeLocationName,
+    rule: &mut TimeZoneRule,
+) -> Result<(), LoadTimeZoneRuleError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(timezone_service_cmds::LOAD_TIME_ZONE_RULE)
+        .data_size(size_of::<TimeLocationName>())
+        .out_buffers(1)
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let mut req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // SAFETY: req.data points to valid payload area with space for the name.
+    unsafe {
+        ptr::write_unaligned(
+            req.data.as_ptr().cast::<TimeLocationName>().cast_mut(),
+            *name,
+        );
+    }
+
+    req.add_out_buffer(
+        ptr::from_mut(rule).cast::<u8>(),
+        size_of::<TimeZoneRule>(),
+        BufferMode::Normal,
+    );
+
+    ipc::send_sync_request(session).map_err(LoadTimeZoneRuleError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(LoadTimeZoneRuleError::ParseResponse)?;
+
+    Ok(())
+}
+
+/// Converts a POSIX timestamp to calendar time using the given timezone rule.
+///
+/// This is ITimeZoneService command 100.
+pub fn to_calendar_time(
+    session: SessionHandle,
+    timestamp: u64,
+    rule: &TimeZoneRule,
+) -> Result<(TimeCalendarTime, TimeCalendarAdditionalInfo), ToCalendarTimeError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(timezone_service_cmds::TO_CALENDAR_TIME)
+        .data_size(8) // u64 timestamp
+        .in_buffers(1) // TimeZoneRule, mapped alias (0x5) like libnx
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let mut req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    // Write timestamp
+    // SAFETY: req.data points to valid payload area with space for u64.
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<u64>().cast_mut(), timestamp);
+    }
+
+    req.add_in_buffer(
+        ptr::from_ref(rule).cast::<u8>(),
+        size_of::<TimeZoneRule>(),
+        BufferMode::Normal,
+    );
+
+    ipc::send_sync_request(session).map_err(ToCalendarTimeError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(ToCalendarTimeError::ParseResponse)?;
+
+    // Read output structure
+    // SAFETY: resp.data contains TimeCalendarTime + TimeCalendarAdditionalInfo.
+    #[repr(C)]
+    struct Output {
+        caltime: TimeCalendarTime,
+        info: TimeCalendarAdditionalInfo,
+    }
+
+    let output = unsafe { ptr::read_unaligned(resp.data.as_ptr().cast::<Output>()) };
+
+    Ok((output.caltime, output.info))
+}
+
 /// Helper function to get a clock session (used by user and network system clocks).
 fn get_clock_session(
     session: SessionHandle,
@@ -263,13 +531,18 @@ pub enum GetTimeZoneServiceError {
 }
 
 /// Error returned by shared memory retrieval operation.
+///
+/// The command only exists on firmware 6.0.0+; older firmware rejects it
+/// and the error surfaces as [`ParseResponse`](Self::ParseResponse).
 #[derive(Debug, thiserror::Error)]
 pub enum GetSharedMemoryError {
     /// Failed to send the IPC request.
     #[error("failed to send request")]
     SendRequest(#[source] ipc::SendSyncError),
     /// Failed to parse the CMIF response.
-    #[error("failed to parse response")]
+    ///
+    /// This is the expected failure on firmware older than 6.0.0.
+    #[error("failed to parse response (shared memory requires firmware 6.0.0+)")]
     ParseResponse(#[source] cmif::ParseResponseError),
     /// Missing shared memory handle in response.
     #[error("missing shared memory handle in response")]
@@ -288,14 +561,45 @@ pub enum GetCurrentTimeError {
     /// Network clock is not available.
     #[error("network clock is not available")]
     NetworkClockUnavailable,
-    /// Local clock is not supported in minimal scope.
-    #[error("local clock is not supported")]
-    LocalClockNotSupported,
+    /// Local clock is not available.
+    #[error("local clock is not available")]
+    LocalClockUnavailable,
     /// Source ID mismatch in shared memory read.
     #[error("source ID mismatch in shared memory read")]
     SourceIdMismatch,
 }
 
+/// Error returned by set current time operation.
+#[derive(Debug, thiserror::Error)]
+pub enum SetTimeError {
+    /// The time service variant is not allowed to write this clock.
+    #[error("permission denied")]
+    PermissionDenied,
+    /// Network clock is not available.
+    #[error("network clock is not available")]
+    NetworkClockUnavailable,
+    /// Local clock is not available.
+    #[error("local clock is not available")]
+    LocalClockUnavailable,
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by [`get_current_time_point`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetCurrentTimePointError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
 /// Error returned by calendar time conversion operation.
 #[derive(Debug, thiserror::Error)]
 pub enum ToCalendarTimeError {
@@ -306,3 +610,47 @@ pub enum ToCalendarTimeError {
     #[error("failed to parse response")]
     ParseResponse(#[source] cmif::ParseResponseError),
 }
+
+/// Error returned by set device location name operation.
+#[derive(Debug, thiserror::Error)]
+pub enum SetDeviceLocationNameError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by get total location name count operation.
+#[derive(Debug, thiserror::Error)]
+pub enum GetTotalLocationNameCountError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by load location name list operation.
+#[derive(Debug, thiserror::Error)]
+pub enum LoadLocationNameListError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
+/// Error returned by load time zone rule operation.
+#[derive(Debug, thiserror::Error)]
+pub enum LoadTimeZoneRuleError {
+    /// Failed to send the IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse the CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
@@ -21,23 +21,30 @@ use nx_svc::ipc::Handle as SessionHandle;
 use nx_sys_mem::shmem::{self as sys_shmem, Mapped, Permissions};
 
 mod cmif;
+mod periodic;
 mod proto;
 pub mod shmem;
+mod stopwatch;
 pub mod types;
 
 pub use self::{
     cmif::{
-        GetCurrentTimeError, GetSharedMemoryError, GetSteadyClockError, GetSystemClockError,
-        GetTimeZoneServiceError, ToCalendarTimeError,
+        GetCurrentTimeError, GetCurrentTimePointError, GetSharedMemoryError, GetSteadyClockError,
+        GetSystemClockError, GetTimeZoneServiceError, GetTotalLocationNameCountError,
+        LoadLocationNameListError, LoadTimeZoneRuleError, SetDeviceLocationNameError, SetTimeError,
+        ToCalendarTimeError,
     },
+    periodic::PeriodicTimer,
     proto::{
         SERVICE_NAME_MENU, SERVICE_NAME_REPAIR, SERVICE_NAME_SYSTEM, SERVICE_NAME_SYSTEM_USER,
         SERVICE_NAME_USER,
     },
+    stopwatch::Stopwatch,
     types::{
-        TimeCalendarAdditionalInfo, TimeCalendarTime, TimeServiceType,
+        CTm, CTmRangeError, SourceId, TimeCalendarAdditionalInfo, TimeCalendarTime,
+        TimeLocationName, TimeLocationNameList, TimeServiceType,
         TimeStandardSteadyClockTimePointType, TimeSteadyClockTimePoint, TimeSystemClockContext,
-        TimeType,
+        TimeType, TimeZoneRule,
     },
 };
 
@@ -48,9 +55,11 @@ const SHMEM_SIZE: usize = 0x1000;
 ///
 /// Provides access to system clocks, steady clock, and timezone operations.
 pub struct TimeService {
+    service_type: TimeServiceType,
     service: Service,
     user_system_clock: Service,
     network_system_clock: Option<Service>,
+    local_system_clock: Option<Service>,
     steady_clock: Service,
     timezone_service: Service,
     shmem_ptr: Option<NonNull<u8>>,
@@ -69,6 +78,12 @@ unsafe impl Send for TimeService {}
 unsafe impl Sync for TimeService {}
 
 impl TimeService {
+    /// Returns the time service variant this session was opened on.
+    #[inline]
+    pub fn service_type(&self) -> TimeServiceType {
+        self.service_type
+    }
+
     /// Returns the underlying service session handle.
     #[inline]
     pub fn session(&self) -> SessionHandle {
@@ -87,6 +102,12 @@ impl TimeService {
         self.network_system_clock.as_ref().map(|svc| svc.session)
     }
 
+    /// Returns the local system clock session handle, if available.
+    #[inline]
+    pub fn local_system_clock_session(&self) -> Option<SessionHandle> {
+        self.local_system_clock.as_ref().map(|svc| svc.session)
+    }
+
     /// Returns the steady clock session handle.
     #[inline]
     pub fn steady_clock_session(&self) -> SessionHandle {
@@ -105,6 +126,24 @@ impl TimeService {
         self.shmem_ptr.map(|ptr| ptr.as_ptr() as *const u8)
     }
 
+    /// Returns `true` if time reads go through shared memory.
+    ///
+    /// Shared memory is mapped once at [`connect`] and is unavailable on
+    /// firmware older than 6.0.0, in which case every read is an IPC call.
+    #[inline]
+    pub fn has_shared_memory(&self) -> bool {
+        self.shmem_ptr.is_some()
+    }
+
+    /// Stops using shared memory, forcing every read through IPC.
+    ///
+    /// This reproduces the pre-6.0.0 behavior on newer firmware, which is
+    /// mainly useful to exercise the IPC fallback.
+    #[inline]
+    pub fn disable_shared_memory(&mut self) {
+        self.shmem_ptr = None;
+    }
+
     /// Consumes and closes the time service session.
     pub fn close(self) {
         self.service.close();
@@ -112,6 +151,9 @@ impl TimeService {
         if let Some(svc) = self.network_system_clock {
             svc.close();
         }
+        if let Some(svc) = self.local_system_clock {
+            svc.close();
+        }
         self.steady_clock.close();
         self.timezone_service.close();
     }
@@ -120,6 +162,8 @@ impl TimeService {
     ///
     /// On firmware 6.0.0+, uses lock-free shared memory reads when available.
     /// Falls back to IPC calls on older firmware or if shared memory is unavailable.
+    /// The local system clock has no shared memory context and is always read
+    /// through IPC.
     pub fn get_current_time(&self, clock_type: TimeType) -> Result<u64, GetCurrentTimeError> {
         // Try shared memory read first if available (6.0.0+)
         if let Some(shmem_ptr) = self.shmem_ptr {
@@ -127,6 +171,11 @@ impl TimeService {
         }
 
         // Fall back to IPC call
+        cmif::get_current_time(self.clock_session(clock_type)?)
+    }
+
+    /// Returns the system clock session for the given clock type.
+    fn clock_session(&self, clock_type: TimeType) -> Result<SessionHandle, GetCurrentTimeError> {
         let session = match clock_type {
             TimeType::UserSystemClock => self.user_system_clock.session,
             TimeType::NetworkSystemClock => self
@@ -134,13 +183,59 @@ impl TimeService {
                 .as_ref()
                 .map(|svc| svc.session)
                 .ok_or(GetCurrentTimeError::NetworkClockUnavailable)?,
-            TimeType::LocalSystemClock => {
-                // LocalSystemClock not supported in minimal scope
-                return Err(GetCurrentTimeError::LocalClockNotSupported);
-            }
+            TimeType::LocalSystemClock => self
+                .local_system_clock
+                .as_ref()
+                .map(|svc| svc.session)
+                .ok_or(GetCurrentTimeError::LocalClockUnavailable)?,
+        };
+
+        Ok(session)
+    }
+
+    /// Sets the current time of the specified clock, as a POSIX timestamp.
+    ///
+    /// Only some time service variants may write each clock:
+    ///
+    /// | Clock | Writable through |
+    /// |-------|------------------|
+    /// | User system clock | [`TimeServiceType::Menu`] (`time:a`) |
+    /// | Local system clock | [`TimeServiceType::Menu`] (`time:a`) |
+    /// | Network system clock | [`TimeServiceType::System`] (`time:s`) |
+    ///
+    /// Other combinations fail with [`SetTimeError::PermissionDenied`] without
+    /// sending a request; the server is the final authority, and its own
+    /// permission error is reported the same way. The local system clock is
+    /// only exposed on 9.0.0+.
+    ///
+    /// Setting the time replaces the clock's system clock context: the offset
+    /// is recomputed against a fresh steady clock time point, which carries
+    /// the current steady clock source ID. Shared memory reads pick up the new
+    /// context once the service has published it.
+    pub fn set_current_time(
+        &self,
+        clock_type: TimeType,
+        timestamp: u64,
+    ) -> Result<(), SetTimeError> {
+        if !can_write_clock(self.service_type, clock_type) {
+            return Err(SetTimeError::PermissionDenied);
+        }
+
+        let session = match clock_type {
+            TimeType::UserSystemClock => self.user_system_clock.session,
+            TimeType::NetworkSystemClock => self
+                .network_system_clock
+                .as_ref()
+                .map(|svc| svc.session)
+                .ok_or(SetTimeError::NetworkClockUnavailable)?,
+            TimeType::LocalSystemClock => self
+                .local_system_clock
+                .as_ref()
+                .map(|svc| svc.session)
+                .ok_or(SetTimeError::LocalClockUnavailable)?,
         };
 
-        cmif::get_current_time(session)
+        cmif::set_current_time(session, timestamp)
     }
 
     /// Gets current time from shared memory (6.0.0+).
@@ -159,7 +254,8 @@ impl TimeService {
                     shmem::read_network_system_clock(shmem_ptr.as_ptr())
                 }
                 TimeType::LocalSystemClock => {
-                    return Err(GetCurrentTimeError::LocalClockNotSupported);
+                    // No shared memory context for the local clock
+                    return cmif::get_current_time(self.clock_session(clock_type)?);
                 }
             };
 
@@ -174,20 +270,68 @@ impl TimeService {
         }
     }
 
+    /// Gets the current steady clock time point.
+    ///
+    /// On firmware 6.0.0+, reads from shared memory when available; otherwise
+    /// falls back to an IPC call.
+    pub fn get_current_time_point(
+        &self,
+    ) -> Result<TimeSteadyClockTimePoint, GetCurrentTimePointError> {
+        if let Some(shmem_ptr) = self.shmem_ptr {
+            // SAFETY: shmem_ptr points to valid shared memory mapping
+            let steady = unsafe { shmem::read_steady_clock(shmem_ptr.as_ptr()) };
+            return Ok(TimeSteadyClockTimePoint {
+                time_point: Self::compute_steady_time(&steady) as i64,
+                source_id: steady.source_id,
+            });
+        }
+
+        cmif::get_current_time_point(self.steady_clock.session)
+    }
+
+    /// Gets the source ID of the steady clock.
+    ///
+    /// The source ID changes when the steady clock is reset (e.g. on reboot).
+    /// Callers caching a time point across suspend/resume must compare its
+    /// source ID against this before trusting a delta computed from it.
+    #[inline]
+    pub fn steady_clock_source_id(&self) -> Result<SourceId, GetCurrentTimePointError> {
+        self.get_current_time_point().map(|point| point.source_id)
+    }
+
+    /// Returns the seconds elapsed since `earlier`.
+    ///
+    /// Returns `Ok(None)` if `earlier` was taken from a different steady clock
+    /// source, in which case the difference is meaningless.
+    pub fn elapsed_since(
+        &self,
+        earlier: TimeSteadyClockTimePoint,
+    ) -> Result<Option<u64>, GetCurrentTimePointError> {
+        let now = self.get_current_time_point()?;
+        if now.source_id != earlier.source_id {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            now.time_point.saturating_sub(earlier.time_point).max(0) as u64,
+        ))
+    }
+
     /// Computes the steady clock time from the time point context.
     fn compute_steady_time(context: &TimeStandardSteadyClockTimePointType) -> u64 {
-        // Read current system tick counter
-        let current_tick = unsafe { nx_cpu::control_regs::cntpct_el0() };
-
-        // Convert ticks to nanoseconds
-        // Formula: (tick * 1_000_000_000ns) / 19_200_000Hz = (tick * 625) / 12
-        // This matches libnx's armTicksToNs() function
-        let tick_ns = (current_tick * 625) / 12;
+        let tick_ns = nx_svc::misc::ticks_to_nanos(system_tick());
 
         // Add base time and convert to seconds
         ((context.base_time + tick_ns as i64) / 1_000_000_000) as u64
     }
 
+    /// Starts a stopwatch on the steady clock.
+    ///
+    /// See [`Stopwatch`] for how the elapsed time is measured.
+    pub fn stopwatch(&self) -> Result<Stopwatch<'_>, GetCurrentTimePointError> {
+        Stopwatch::start(self)
+    }
+
     /// Converts a POSIX timestamp to calendar time using the device's timezone rule.
     #[inline]
     pub fn to_calendar_time_with_my_rule(
@@ -196,6 +340,82 @@ impl TimeService {
     ) -> Result<(TimeCalendarTime, TimeCalendarAdditionalInfo), ToCalendarTimeError> {
         cmif::to_calendar_time_with_my_rule(self.timezone_service.session, timestamp)
     }
+
+    /// Sets the device's time zone location name.
+    ///
+    /// Requires a time service with write access (e.g. `time:s`).
+    #[inline]
+    pub fn set_device_location_name(
+        &self,
+        name: &TimeLocationName,
+    ) -> Result<(), SetDeviceLocationNameError> {
+        cmif::set_device_location_name(self.timezone_service.session, name)
+    }
+
+    /// Lists available time zone location names, one page at a time.
+    ///
+    /// Fills `buf` with names starting at `offset` and returns the filled
+    /// slice along with the total count, so callers can page through the list.
+    pub fn list_time_zone_rules<'a>(
+        &self,
+        offset: u32,
+        buf: &'a mut [TimeLocationName],
+    ) -> Result<TimeLocationNameList<'a>, ListTimeZoneRulesError> {
+        let session = self.timezone_service.session;
+
+        let total = cmif::get_total_location_name_count(session)
+            .map_err(ListTimeZoneRulesError::GetTotalCount)?;
+
+        let count = if offset < total && !buf.is_empty() {
+            cmif::load_location_name_list(session, offset, buf)
+                .map_err(ListTimeZoneRulesError::LoadList)?
+        } else {
+            0
+        };
+
+        Ok(TimeLocationNameList {
+            names: &buf[..count as usize],
+            total,
+        })
+    }
+
+    /// Loads the time zone rule for a location name.
+    ///
+    /// Callers converting many timestamps for the same zone should load the
+    /// rule once and reuse it with [`to_calendar_time`](Self::to_calendar_time).
+    #[inline]
+    pub fn load_time_zone_rule(
+        &self,
+        name: &TimeLocationName,
+        rule: &mut TimeZoneRule,
+    ) -> Result<(), LoadTimeZoneRuleError> {
+        cmif::load_time_zone_rule(self.timezone_service.session, name, rule)
+    }
+
+    /// Converts a POSIX timestamp to calendar time using the given rule.
+    #[inline]
+    pub fn to_calendar_time(
+        &self,
+        timestamp: u64,
+        rule: &TimeZoneRule,
+    ) -> Result<(TimeCalendarTime, TimeCalendarAdditionalInfo), ToCalendarTimeError> {
+        cmif::to_calendar_time(self.timezone_service.session, timestamp, rule)
+    }
+
+    /// Converts a POSIX timestamp to calendar time for a named time zone.
+    ///
+    /// Loads the rule into a 16 KiB stack buffer on every call.
+    pub fn to_calendar_time_with_name(
+        &self,
+        timestamp: u64,
+        name: &TimeLocationName,
+    ) -> Result<(TimeCalendarTime, TimeCalendarAdditionalInfo), ToCalendarTimeWithNameError> {
+        let mut rule = TimeZoneRule::new();
+        self.load_time_zone_rule(name, &mut rule)
+            .map_err(ToCalendarTimeWithNameError::LoadRule)?;
+        self.to_calendar_time(timestamp, &rule)
+            .map_err(ToCalendarTimeWithNameError::Convert)
+    }
 }
 
 /// Connects to the time service.
@@ -222,55 +442,35 @@ pub fn connect(sm: &SmService, service_type: TimeServiceType) -> Result<TimeServ
         .get_service_handle_cmif(service_name)
         .map_err(ConnectError::GetService)?;
 
-    let service = Service {
-        session: handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let service = Service::new(handle);
 
     // Get user system clock (always required)
     let user_clock_handle = cmif::get_standard_user_system_clock(service.session)
         .map_err(ConnectError::GetUserSystemClock)?;
 
-    let user_system_clock = Service {
-        session: user_clock_handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let user_system_clock = Service::new_subservice(&service, user_clock_handle);
 
     // Get network system clock (best effort, may fail)
     let network_system_clock = cmif::get_standard_network_system_clock(service.session)
         .ok()
-        .map(|handle| Service {
-            session: handle,
-            own_handle: 1,
-            object_id: 0,
-            pointer_buffer_size: 0,
-        });
+        .map(|handle| Service::new_subservice(&service, handle));
+
+    // Get local system clock (best effort, may fail)
+    let local_system_clock = cmif::get_standard_local_system_clock(service.session)
+        .ok()
+        .map(|handle| Service::new_subservice(&service, handle));
 
     // Get steady clock
     let steady_clock_handle =
         cmif::get_standard_steady_clock(service.session).map_err(ConnectError::GetSteadyClock)?;
 
-    let steady_clock = Service {
-        session: steady_clock_handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let steady_clock = Service::new_subservice(&service, steady_clock_handle);
 
     // Get timezone service
     let timezone_handle =
         cmif::get_time_zone_service(service.session).map_err(ConnectError::GetTimeZoneService)?;
 
-    let timezone_service = Service {
-        session: timezone_handle,
-        own_handle: 1,
-        object_id: 0,
-        pointer_buffer_size: 0,
-    };
+    let timezone_service = Service::new_subservice(&service, timezone_handle);
 
     // Try to get shared memory (6.0.0+, best effort)
     let (shmem_ptr, _shmem) = match cmif::get_shared_memory_native_handle(service.session) {
@@ -290,9 +490,11 @@ pub fn connect(sm: &SmService, service_type: TimeServiceType) -> Result<TimeServ
     };
 
     Ok(TimeService {
+        service_type,
         service,
         user_system_clock,
         network_system_clock,
+        local_system_clock,
         steady_clock,
         timezone_service,
         shmem_ptr,
@@ -300,6 +502,25 @@ pub fn connect(sm: &SmService, service_type: TimeServiceType) -> Result<TimeServ
     })
 }
 
+/// Returns whether `service_type` sessions may write the `clock_type` clock.
+///
+/// Mirrors the per-service write permissions of the time service.
+fn can_write_clock(service_type: TimeServiceType, clock_type: TimeType) -> bool {
+    matches!(
+        (service_type, clock_type),
+        (
+            TimeServiceType::Menu,
+            TimeType::UserSystemClock | TimeType::LocalSystemClock
+        ) | (TimeServiceType::System, TimeType::NetworkSystemClock)
+    )
+}
+
+/// Reads the current system tick counter.
+fn system_tick() -> u64 {
+    // SAFETY: CNTPCT_EL0 is readable from EL0 on Horizon.
+    unsafe { nx_cpu::control_regs::cntpct_el0() }
+}
+
 /// Error returned by [`connect`].
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectError {
@@ -316,3 +537,25 @@ pub enum ConnectError {
     #[error("failed to get timezone service")]
     GetTimeZoneService(#[source] GetTimeZoneServiceError),
 }
+
+/// Error returned by [`TimeService::list_time_zone_rules`].
+#[derive(Debug, thiserror::Error)]
+pub enum ListTimeZoneRulesError {
+    /// Failed to get the total location name count.
+    #[error("failed to get total location name count")]
+    GetTotalCount(#[source] GetTotalLocationNameCountError),
+    /// Failed to load the location name page.
+    #[error("failed to load location name list")]
+    LoadList(#[source] LoadLocationNameListError),
+}
+
+/// Error returned by [`TimeService::to_calendar_time_with_name`].
+#[derive(Debug, thiserror::Error)]
+pub enum ToCalendarTimeWithNameError {
+    /// Failed to load the time zone rule.
+    #[error("failed to load time zone rule")]
+    LoadRule(#[source] LoadTimeZoneRuleError),
+    /// Failed to convert the timestamp.
+    #[error("failed to convert to calendar time")]
+    Convert(#[source] ToCalendarTimeError),
+}
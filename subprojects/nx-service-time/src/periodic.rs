@@ -0,0 +1,123 @@
+//! Fixed-rate timer for fixed-timestep loops.
+
+use nx_svc::misc::SYSTEM_TICK_FREQ;
+
+use crate::system_tick;
+
+/// Timer reporting how many fixed periods elapsed between polls.
+///
+/// Drives fixed-timestep logic decoupled from rendering: each frame, call
+/// [`poll`](Self::poll) and step the simulation once per returned period.
+///
+/// Periods are counted on the system tick from the moment the timer was
+/// started, so a period that is not a whole number of ticks does not
+/// accumulate rounding error: after `n` periods' worth of time, exactly `n`
+/// periods have been reported, however long the session runs.
+///
+/// # Long gaps
+///
+/// A gap much longer than a period (system sleep, applet suspension, a
+/// debugger break) would otherwise be reported as a burst of thousands of
+/// periods. A single poll reports at most [`max_steps`](Self::max_steps)
+/// periods ([`DEFAULT_MAX_STEPS`](Self::DEFAULT_MAX_STEPS) by default); the
+/// periods past that are dropped, and the timer carries on from the present
+/// as if they had been reported.
+#[derive(Debug, Clone)]
+pub struct PeriodicTimer {
+    period_ns: u64,
+    max_steps: u32,
+    start_tick: u64,
+    reported: u64,
+}
+
+impl PeriodicTimer {
+    /// Default cap on the periods reported by a single poll.
+    pub const DEFAULT_MAX_STEPS: u32 = 8;
+
+    /// Starts a timer with a period of `period_ns` nanoseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period_ns` is zero.
+    pub fn new(period_ns: u64) -> Self {
+        Self::new_at(period_ns, system_tick())
+    }
+
+    /// Starts a timer at `start_tick`, a system tick sampled by the caller.
+    ///
+    /// Use it with [`poll_at`](Self::poll_at) when the loop already samples
+    /// the tick counter once per frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period_ns` is zero.
+    pub fn new_at(period_ns: u64, start_tick: u64) -> Self {
+        assert!(period_ns > 0, "timer period must be non-zero");
+
+        Self {
+            period_ns,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            start_tick,
+            reported: 0,
+        }
+    }
+
+    /// Sets the cap on the periods reported by a single poll.
+    ///
+    /// A cap of `0` is raised to `1`.
+    #[inline]
+    pub fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Returns the period, in nanoseconds.
+    #[inline]
+    pub fn period_ns(&self) -> u64 {
+        self.period_ns
+    }
+
+    /// Returns the cap on the periods reported by a single poll.
+    #[inline]
+    pub fn max_steps(&self) -> u32 {
+        self.max_steps
+    }
+
+    /// Returns the number of whole periods elapsed since the previous poll, or
+    /// since the timer was started.
+    ///
+    /// The count is capped at [`max_steps`](Self::max_steps), see
+    /// [Long gaps](Self#long-gaps).
+    pub fn poll(&mut self) -> u32 {
+        self.poll_at(system_tick())
+    }
+
+    /// Like [`poll`](Self::poll), with the current system tick sampled by the
+    /// caller.
+    ///
+    /// `now_tick` must not go backwards between polls.
+    pub fn poll_at(&mut self, now_tick: u64) -> u32 {
+        let total = self.periods_at(now_tick);
+        // A tick going backwards must not underflow into a burst of periods
+        let pending = total.saturating_sub(self.reported);
+
+        // Periods past the cap are dropped, not carried over to later polls
+        self.reported = total;
+        pending.min(self.max_steps as u64) as u32
+    }
+
+    /// Restarts the timer at the current time, dropping any partial period.
+    pub fn reset(&mut self) {
+        self.start_tick = system_tick();
+        self.reported = 0;
+    }
+
+    /// Returns the number of whole periods elapsed from the start to
+    /// `now_tick`.
+    fn periods_at(&self, now_tick: u64) -> u64 {
+        // Stay exact instead of rounding the elapsed time to whole nanoseconds:
+        // periods = (ticks * 1e9) / (period_ns * SYSTEM_TICK_FREQ)
+        let ticks = now_tick.wrapping_sub(self.start_tick) as u128;
+        (ticks * 1_000_000_000 / (self.period_ns as u128 * SYSTEM_TICK_FREQ as u128)) as u64
+    }
+}
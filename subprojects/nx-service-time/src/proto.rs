@@ -32,7 +32,6 @@ pub mod static_service_cmds {
     pub const GET_TIME_ZONE_SERVICE: u32 = 3;
 
     /// Get standard local system clock (ISystemClock).
-    #[expect(dead_code)]
     pub const GET_STANDARD_LOCAL_SYSTEM_CLOCK: u32 = 4;
 
     /// [6.0.0+] Get shared memory native handle.
@@ -45,14 +44,12 @@ pub mod system_clock_cmds {
     pub const GET_CURRENT_TIME: u32 = 0;
 
     /// Set current time (POSIX timestamp).
-    #[expect(dead_code)]
     pub const SET_CURRENT_TIME: u32 = 1;
 }
 
 /// ISteadyClock command IDs
 pub mod steady_clock_cmds {
     /// Get current time point.
-    #[expect(dead_code)]
     pub const GET_CURRENT_TIME_POINT: u32 = 0;
 
     /// [3.0.0+] Get standard steady clock internal offset.
@@ -67,13 +64,20 @@ pub mod timezone_service_cmds {
     pub const GET_DEVICE_LOCATION_NAME: u32 = 0;
 
     /// Set device location name.
-    #[expect(dead_code)]
     pub const SET_DEVICE_LOCATION_NAME: u32 = 1;
 
     /// Get total location name count.
-    #[expect(dead_code)]
     pub const GET_TOTAL_LOCATION_NAME_COUNT: u32 = 2;
 
+    /// Load a page of location names.
+    pub const LOAD_LOCATION_NAME_LIST: u32 = 3;
+
+    /// Load the time zone rule for a location name.
+    pub const LOAD_TIME_ZONE_RULE: u32 = 4;
+
+    /// To calendar time with a caller-provided rule.
+    pub const TO_CALENDAR_TIME: u32 = 100;
+
     /// To calendar time with my rule.
     pub const TO_CALENDAR_TIME_WITH_MY_RULE: u32 = 101;
 
@@ -2,9 +2,11 @@
 
 use core::{
     ptr,
-    sync::atomic::{AtomicU32, Ordering, compiler_fence},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
+use nx_cpu::{barrier, spin::ExponentialBackoff};
+
 use crate::types::{TimeStandardSteadyClockTimePointType, TimeSystemClockContext};
 
 /// Offsets in shared memory for time data structures.
@@ -48,6 +50,7 @@ unsafe fn read_shared_mem_obj<T: Copy>(base_ptr: *const u8, offset: usize) -> T
     // SAFETY: entry_ptr was just computed from valid base_ptr + offset
     let entry = unsafe { &*entry_ptr };
 
+    let mut backoff = ExponentialBackoff::new();
     loop {
         // Read the counter to determine which buffer is current
         let cur_counter = entry.counter.load(Ordering::Acquire);
@@ -57,15 +60,16 @@ unsafe fn read_shared_mem_obj<T: Copy>(base_ptr: *const u8, offset: usize) -> T
         // SAFETY: Accessing shared memory buffer that's guaranteed initialized
         let value = unsafe { ptr::read_volatile(&entry.buffers[buffer_index]) };
 
-        // Memory fence to ensure read completes before counter check
-        compiler_fence(Ordering::Acquire);
+        // Ensure the buffer read completes before the counter check
+        barrier::dmb_ishld();
 
         // Verify counter hasn't changed during our read
-        let new_counter = entry.counter.load(Ordering::Acquire);
+        let new_counter = entry.counter.load(Ordering::Relaxed);
         if cur_counter == new_counter {
             return value;
         }
         // Counter changed, retry
+        backoff.spin();
     }
 }
 
@@ -0,0 +1,59 @@
+//! Monotonic stopwatch for measuring short intervals.
+
+use crate::{GetCurrentTimePointError, SourceId, TimeService, system_tick};
+
+/// Stopwatch on the steady clock.
+///
+/// Created by [`TimeService::stopwatch`]. The elapsed time is the system tick
+/// delta since the stopwatch was started, so it has tick resolution and is
+/// unaffected by adjustments of the system clocks. The steady clock source ID
+/// is captured alongside; if the steady clock is reset afterwards, the
+/// baseline is no longer valid and the elapsed time is reported as `None`.
+pub struct Stopwatch<'a> {
+    time: &'a TimeService,
+    source_id: SourceId,
+    start_tick: u64,
+}
+
+impl<'a> Stopwatch<'a> {
+    /// Starts a stopwatch at the current time.
+    pub(crate) fn start(time: &'a TimeService) -> Result<Self, GetCurrentTimePointError> {
+        let source_id = time.steady_clock_source_id()?;
+
+        Ok(Self {
+            time,
+            source_id,
+            start_tick: system_tick(),
+        })
+    }
+
+    /// Returns the nanoseconds elapsed since the stopwatch was started.
+    ///
+    /// Returns `Ok(None)` if the steady clock source changed since then.
+    pub fn elapsed_ns(&self) -> Result<Option<u64>, GetCurrentTimePointError> {
+        let ticks = system_tick().wrapping_sub(self.start_tick);
+        if self.time.steady_clock_source_id()? != self.source_id {
+            return Ok(None);
+        }
+
+        Ok(Some(nx_svc::misc::ticks_to_nanos(ticks)))
+    }
+
+    /// Returns the milliseconds elapsed since the stopwatch was started.
+    ///
+    /// Returns `Ok(None)` if the steady clock source changed since then.
+    #[inline]
+    pub fn elapsed_ms(&self) -> Result<Option<u64>, GetCurrentTimePointError> {
+        self.elapsed_ns().map(|ns| ns.map(|ns| ns / 1_000_000))
+    }
+
+    /// Restarts the stopwatch at the current time.
+    ///
+    /// This also re-captures the steady clock source, making the stopwatch
+    /// valid again after a source change.
+    pub fn reset(&mut self) -> Result<(), GetCurrentTimePointError> {
+        self.source_id = self.time.steady_clock_source_id()?;
+        self.start_tick = system_tick();
+        Ok(())
+    }
+}
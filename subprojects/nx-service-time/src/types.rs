@@ -1,5 +1,7 @@
 //! Time service data types.
 
+use core::ffi::c_int;
+
 /// Time service type selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u32)]
@@ -65,6 +67,160 @@ pub struct TimeCalendarAdditionalInfo {
     pub offset: i32,
 }
 
+/// C `struct tm` as laid out by newlib.
+///
+/// Converting from the service's calendar types takes care of the POSIX field
+/// offsets: `tm_year` counts years since 1900 and `tm_mon` is 0-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(C)]
+pub struct CTm {
+    /// Seconds (0-60).
+    pub tm_sec: c_int,
+    /// Minutes (0-59).
+    pub tm_min: c_int,
+    /// Hours (0-23).
+    pub tm_hour: c_int,
+    /// Day of the month (1-31).
+    pub tm_mday: c_int,
+    /// Month (0-11).
+    pub tm_mon: c_int,
+    /// Years since 1900.
+    pub tm_year: c_int,
+    /// Day of the week (0-6, 0 = Sunday).
+    pub tm_wday: c_int,
+    /// Day of the year (0-365).
+    pub tm_yday: c_int,
+    /// DST flag: positive if DST is in effect, zero if not, negative if
+    /// unknown.
+    pub tm_isdst: c_int,
+}
+
+/// Cumulative day count before each month of a non-leap year.
+const DAYS_BEFORE_MONTH: [u16; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+
+/// Returns whether `year` is a Gregorian leap year.
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// Builds a `tm` from a calendar time, computing the day of the week and day
+/// of the year from the date.
+///
+/// `tm_isdst` is set to -1 (unknown). Prefer the conversion from the
+/// `(TimeCalendarTime, TimeCalendarAdditionalInfo)` pair returned by the
+/// service when it is available.
+impl From<TimeCalendarTime> for CTm {
+    fn from(time: TimeCalendarTime) -> Self {
+        let year = time.year as u32;
+        let month = time.month.clamp(1, 12) as u32;
+        let day = time.day as u32;
+
+        let leap_day = (month > 2 && is_leap_year(year)) as u32;
+        let yday = DAYS_BEFORE_MONTH[month as usize - 1] as u32 + day.saturating_sub(1) + leap_day;
+
+        // Sakamoto's day-of-week algorithm, with years starting in March
+        const MONTH_OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let y = if month < 3 {
+            year.saturating_sub(1)
+        } else {
+            year
+        };
+        let wday = (y + y / 4 - y / 100 + y / 400 + MONTH_OFFSETS[month as usize - 1] + day) % 7;
+
+        Self {
+            tm_sec: time.second as c_int,
+            tm_min: time.minute as c_int,
+            tm_hour: time.hour as c_int,
+            tm_mday: time.day as c_int,
+            tm_mon: time.month as c_int - 1,
+            tm_year: time.year as c_int - 1900,
+            tm_wday: wday as c_int,
+            tm_yday: yday as c_int,
+            tm_isdst: -1,
+        }
+    }
+}
+
+/// Builds a `tm` from the pair returned by the `ToCalendarTime*` calls,
+/// taking the day of the week, day of the year and DST flag from the
+/// service's additional info.
+impl From<(TimeCalendarTime, TimeCalendarAdditionalInfo)> for CTm {
+    fn from((time, info): (TimeCalendarTime, TimeCalendarAdditionalInfo)) -> Self {
+        Self {
+            tm_wday: info.wday as c_int,
+            tm_yday: info.yday as c_int,
+            tm_isdst: (info.dst != 0) as c_int,
+            ..Self::from(time)
+        }
+    }
+}
+
+/// Converts a `tm` back to a calendar time.
+///
+/// `tm_wday`, `tm_yday` and `tm_isdst` are ignored. Fields are not normalized
+/// (as `mktime` would); out-of-range values are rejected instead.
+impl TryFrom<CTm> for TimeCalendarTime {
+    type Error = CTmRangeError;
+
+    fn try_from(tm: CTm) -> Result<Self, Self::Error> {
+        fn field<T: TryFrom<c_int>>(
+            value: c_int,
+            range: core::ops::RangeInclusive<c_int>,
+            err: CTmRangeError,
+        ) -> Result<T, CTmRangeError> {
+            if !range.contains(&value) {
+                return Err(err);
+            }
+            T::try_from(value).map_err(|_| err)
+        }
+
+        Ok(Self {
+            year: field(
+                tm.tm_year.saturating_add(1900),
+                0..=u16::MAX as c_int,
+                CTmRangeError::Year,
+            )?,
+            month: field(tm.tm_mon.saturating_add(1), 1..=12, CTmRangeError::Month)?,
+            day: field(tm.tm_mday, 1..=31, CTmRangeError::Day)?,
+            hour: field(tm.tm_hour, 0..=23, CTmRangeError::Hour)?,
+            minute: field(tm.tm_min, 0..=59, CTmRangeError::Minute)?,
+            second: field(tm.tm_sec, 0..=59, CTmRangeError::Second)?,
+            pad: 0,
+        })
+    }
+}
+
+/// Error returned when a [`CTm`] field does not fit a [`TimeCalendarTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum CTmRangeError {
+    /// `tm_year + 1900` is negative or does not fit in 16 bits.
+    #[error("tm_year out of range")]
+    Year,
+    /// `tm_mon` is not in 0-11.
+    #[error("tm_mon out of range")]
+    Month,
+    /// `tm_mday` is not in 1-31.
+    #[error("tm_mday out of range")]
+    Day,
+    /// `tm_hour` is not in 0-23.
+    #[error("tm_hour out of range")]
+    Hour,
+    /// `tm_min` is not in 0-59.
+    #[error("tm_min out of range")]
+    Minute,
+    /// `tm_sec` is not in 0-59.
+    #[error("tm_sec out of range")]
+    Second,
+}
+
+/// Steady clock source ID (UUID).
+///
+/// Regenerated whenever the steady clock is reset, e.g. on reboot. Time points
+/// from different sources cannot be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct SourceId(pub [u8; 16]);
+
 /// Steady clock time point.
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
@@ -72,7 +228,7 @@ pub struct TimeSteadyClockTimePoint {
     /// Monotonic count in seconds.
     pub time_point: i64,
     /// An ID representing the clock source (UUID).
-    pub source_id: [u8; 16],
+    pub source_id: SourceId,
 }
 
 /// Standard steady clock time point type (used in shared memory).
@@ -82,7 +238,7 @@ pub struct TimeStandardSteadyClockTimePointType {
     /// Base time in nanoseconds.
     pub base_time: i64,
     /// An ID representing the clock source (UUID).
-    pub source_id: [u8; 16],
+    pub source_id: SourceId,
 }
 
 /// System clock context.
@@ -94,3 +250,105 @@ pub struct TimeSystemClockContext {
     /// Steady clock timestamp.
     pub timestamp: TimeSteadyClockTimePoint,
 }
+
+/// Time zone location name (e.g. `"Europe/Madrid"`).
+///
+/// Fixed-size, NUL-padded buffer as used by the time zone service.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct TimeLocationName {
+    /// Raw NUL-padded name bytes.
+    pub name: [u8; 0x24],
+}
+
+impl TimeLocationName {
+    /// Maximum name length in bytes, excluding the NUL terminator.
+    pub const MAX_LEN: usize = 0x23;
+
+    /// An empty location name.
+    pub const EMPTY: Self = Self { name: [0; 0x24] };
+
+    /// Creates a location name from a string.
+    ///
+    /// Returns `None` if `name` is longer than [`Self::MAX_LEN`] bytes.
+    pub const fn new(name: &str) -> Option<Self> {
+        let bytes = name.as_bytes();
+        if bytes.len() > Self::MAX_LEN {
+            return None;
+        }
+
+        let mut out = Self::EMPTY;
+        let mut i = 0;
+        while i < bytes.len() {
+            out.name[i] = bytes[i];
+            i += 1;
+        }
+        Some(out)
+    }
+
+    /// Returns the name bytes up to the first NUL.
+    pub fn as_bytes(&self) -> &[u8] {
+        let len = self
+            .name
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(self.name.len());
+        &self.name[..len]
+    }
+
+    /// Returns the name as a string slice, or `None` if it is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(self.as_bytes()).ok()
+    }
+}
+
+impl core::fmt::Debug for TimeLocationName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.as_str() {
+            Some(name) => f.debug_tuple("TimeLocationName").field(&name).finish(),
+            None => f
+                .debug_tuple("TimeLocationName")
+                .field(&self.as_bytes())
+                .finish(),
+        }
+    }
+}
+
+/// A page of time zone location names.
+///
+/// Returned by [`TimeService::list_time_zone_rules`](crate::TimeService::list_time_zone_rules).
+#[derive(Debug)]
+pub struct TimeLocationNameList<'a> {
+    /// Names written into the caller's buffer for this page.
+    pub names: &'a [TimeLocationName],
+    /// Total number of location names known to the service.
+    pub total: u32,
+}
+
+/// Opaque time zone rule, as loaded by `LoadTimeZoneRule`.
+///
+/// This is 16 KiB; avoid placing it on small thread stacks.
+#[derive(Clone)]
+#[repr(C)]
+pub struct TimeZoneRule {
+    data: [u8; 0x4000],
+}
+
+impl TimeZoneRule {
+    /// Creates a zeroed time zone rule, ready to be filled by the service.
+    pub const fn new() -> Self {
+        Self { data: [0; 0x4000] }
+    }
+}
+
+impl Default for TimeZoneRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for TimeZoneRule {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TimeZoneRule").finish_non_exhaustive()
+    }
+}
@@ -8,7 +8,7 @@ use nx_svc::raw::Handle as RawHandle;
 
 use crate::{
     cmif,
-    parcel::{PARCEL_MAX_PAYLOAD, Parcel, ParcelHeader},
+    parcel::{PARCEL_MAX_PAYLOAD, PARCEL_OBJECT_OFFSET_SIZE, Parcel, ParcelHeader},
     types::BinderObjectId,
 };
 
@@ -32,6 +32,36 @@ impl Binder {
         }
     }
 
+    /// Creates a binder from the native window of a layer.
+    ///
+    /// The native window returned by [`ViService::open_layer`] and
+    /// [`ViService::create_stray_layer`] is a parcel holding the flattened
+    /// IGraphicBufferProducer, whose binder object ID is the third word of the
+    /// payload. Returns `None` if the parcel is malformed.
+    ///
+    /// The binder is not yet initialized, as with [`create`](Self::create).
+    ///
+    /// [`ViService::open_layer`]: crate::ViService::open_layer
+    /// [`ViService::create_stray_layer`]: crate::ViService::create_stray_layer
+    pub fn from_native_window(native_window: &[u8]) -> Option<Self> {
+        if native_window.len() < ParcelHeader::SIZE {
+            return None;
+        }
+
+        // SAFETY: The slice holds at least a full header.
+        let header =
+            unsafe { core::ptr::read_unaligned(native_window.as_ptr().cast::<ParcelHeader>()) };
+
+        let payload_off = header.payload_off as usize;
+        let payload_size = header.payload_size as usize;
+        let payload = native_window.get(payload_off..payload_off.checked_add(payload_size)?)?;
+
+        let id = payload.get(8..12)?;
+        let id = i32::from_ne_bytes([id[0], id[1], id[2], id[3]]);
+
+        Some(Self::create(BinderObjectId::new(id)))
+    }
+
     /// Returns the binder object ID.
     #[inline]
     pub fn id(&self) -> BinderObjectId {
@@ -101,8 +131,10 @@ impl Binder {
         // Build the input buffer with header
         let mut in_buf = [0u8; PARCEL_MAX_PAYLOAD];
         let payload_size = in_parcel.payload_size();
+        let objects = in_parcel.objects();
+        let objects_size = objects.len() * PARCEL_OBJECT_OFFSET_SIZE;
 
-        if payload_size > PARCEL_MAX_PAYLOAD - ParcelHeader::SIZE {
+        if payload_size + objects_size > PARCEL_MAX_PAYLOAD - ParcelHeader::SIZE {
             return Err(TransactError::InputTooLarge);
         }
 
@@ -110,7 +142,7 @@ impl Binder {
         let header = ParcelHeader {
             payload_size: payload_size as u32,
             payload_off: ParcelHeader::SIZE as u32,
-            objects_size: 0,
+            objects_size: objects_size as u32,
             objects_off: (ParcelHeader::SIZE + payload_size) as u32,
         };
 
@@ -123,7 +155,15 @@ impl Binder {
         in_buf[ParcelHeader::SIZE..ParcelHeader::SIZE + payload_size]
             .copy_from_slice(in_parcel.payload());
 
-        let total_in_size = ParcelHeader::SIZE + payload_size;
+        // Write the objects table after the payload
+        let objects_off = ParcelHeader::SIZE + payload_size;
+        for (i, &offset) in objects.iter().enumerate() {
+            let entry = objects_off + i * PARCEL_OBJECT_OFFSET_SIZE;
+            in_buf[entry..entry + PARCEL_OBJECT_OFFSET_SIZE]
+                .copy_from_slice(&(offset as u64).to_ne_bytes());
+        }
+
+        let total_in_size = objects_off + objects_size;
 
         // Output buffer
         let mut out_buf = [0u8; PARCEL_MAX_PAYLOAD];
@@ -170,6 +210,7 @@ impl Binder {
         out_parcel.payload_mut()[..payload_data.len()].copy_from_slice(payload_data);
         out_parcel.set_payload_size(payload_data.len());
         out_parcel.reset_read_pos();
+        out_parcel.clear_objects();
 
         Ok(())
     }
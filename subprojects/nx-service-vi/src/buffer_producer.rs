@@ -0,0 +1,590 @@
+//! Typed IGraphicBufferProducer transactions.
+//!
+//! A layer's buffer queue is driven through the IGraphicBufferProducer
+//! interface of its [`Binder`]. Each method below builds the request parcel
+//! of one transaction, sends it with [`Binder::transact`] and parses the
+//! reply:
+//!
+//! | Code | Transaction      | Method                        |
+//! |------|------------------|-------------------------------|
+//! | 1    | `REQUEST_BUFFER` | [`Binder::request_buffer`]    |
+//! | 3    | `DEQUEUE_BUFFER` | [`Binder::dequeue_buffer`]    |
+//! | 7    | `QUEUE_BUFFER`   | [`Binder::queue_buffer`]      |
+//! | 8    | `CANCEL_BUFFER`  | [`Binder::cancel_buffer`]     |
+//! | 10   | `CONNECT`        | [`Binder::connect`]           |
+//! | 11   | `DISCONNECT`     | [`Binder::disconnect`]        |
+//!
+//! Presenting a frame goes through the usual Android cycle: connect once,
+//! then for every frame dequeue a buffer, wait on its fence before rendering
+//! into its slot, and queue the slot back with the fence of the rendering.
+//!
+//! Fences and graphic buffers travel as flattened objects. Their layouts are
+//! the ones of the Switch's Android port: a fence is an NVIDIA multi-fence
+//! (up to four syncpoint fences), and a graphic buffer carries the NVIDIA
+//! native handle as plain integers, never file descriptors.
+
+use nx_sf::service::Service;
+
+use crate::{
+    binder::{Binder, BinderError, TransactError},
+    parcel::Parcel,
+};
+
+/// Interface descriptor written at the start of every request.
+pub const INTERFACE_DESCRIPTOR: &str = "android.gui.IGraphicBufferProducer";
+
+/// Transaction code of [`Binder::request_buffer`].
+const REQUEST_BUFFER: u32 = 1;
+/// Transaction code of [`Binder::dequeue_buffer`].
+const DEQUEUE_BUFFER: u32 = 3;
+/// Transaction code of [`Binder::queue_buffer`].
+const QUEUE_BUFFER: u32 = 7;
+/// Transaction code of [`Binder::cancel_buffer`].
+const CANCEL_BUFFER: u32 = 8;
+/// Transaction code of [`Binder::connect`].
+const CONNECT: u32 = 10;
+/// Transaction code of [`Binder::disconnect`].
+const DISCONNECT: u32 = 11;
+
+/// Dequeue status bit: the buffer in the slot must be requested again.
+const BUFFER_NEEDS_REALLOCATION: i32 = 0x1;
+/// Dequeue status bit: every previously requested buffer is stale.
+const RELEASE_ALL_BUFFERS: i32 = 0x2;
+
+/// Magic of a flattened graphic buffer (`'GBFR'`).
+const GRAPHIC_BUFFER_MAGIC: u32 = 0x4742_4652;
+
+/// Number of words in the header of a flattened graphic buffer.
+const GRAPHIC_BUFFER_HEADER_WORDS: usize = 10;
+
+/// Maximum number of native handle integers in a [`GraphicBuffer`].
+///
+/// Large enough for the NVIDIA native handle of the Switch.
+pub const GRAPHIC_BUFFER_MAX_INTS: usize = 0x80;
+
+/// Maximum number of fences in a [`MultiFence`].
+pub const MULTI_FENCE_MAX_FENCES: usize = 4;
+
+/// Syncpoint fence.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct Fence {
+    /// Syncpoint ID.
+    pub id: u32,
+    /// Syncpoint value to wait for.
+    pub value: u32,
+}
+
+/// Set of syncpoint fences, signaled once all of them are.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct MultiFence {
+    /// Number of valid entries in `fences`.
+    pub num_fences: u32,
+    /// Fences; only the first `num_fences` are meaningful.
+    pub fences: [Fence; MULTI_FENCE_MAX_FENCES],
+}
+
+impl MultiFence {
+    /// Size of a flattened multi-fence, in bytes.
+    pub const SIZE: usize = 4 + MULTI_FENCE_MAX_FENCES * 8;
+
+    /// Already signaled fence.
+    pub const NONE: Self = Self {
+        num_fences: 0,
+        fences: [Fence { id: 0, value: 0 }; MULTI_FENCE_MAX_FENCES],
+    };
+
+    /// Returns the valid fences.
+    pub fn fences(&self) -> &[Fence] {
+        let count = (self.num_fences as usize).min(MULTI_FENCE_MAX_FENCES);
+        &self.fences[..count]
+    }
+
+    /// Flattens the fence into `buf`.
+    fn write_to(&self, buf: &mut [u8]) {
+        write_u32_at(buf, 0, self.num_fences);
+        for (i, fence) in self.fences.iter().enumerate() {
+            write_u32_at(buf, 1 + i * 2, fence.id);
+            write_u32_at(buf, 2 + i * 2, fence.value);
+        }
+    }
+
+    /// Parses a flattened fence.
+    fn read_from(buf: &[u8]) -> Option<Self> {
+        if buf.len() < Self::SIZE {
+            return None;
+        }
+
+        let mut fence = Self {
+            num_fences: read_u32_at(buf, 0),
+            ..Self::NONE
+        };
+        if fence.num_fences as usize > MULTI_FENCE_MAX_FENCES {
+            return None;
+        }
+
+        for (i, entry) in fence.fences.iter_mut().enumerate() {
+            entry.id = read_u32_at(buf, 1 + i * 2);
+            entry.value = read_u32_at(buf, 2 + i * 2);
+        }
+        Some(fence)
+    }
+}
+
+/// Rectangle, in pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct Rect {
+    /// Left edge.
+    pub left: i32,
+    /// Top edge.
+    pub top: i32,
+    /// Right edge (exclusive).
+    pub right: i32,
+    /// Bottom edge (exclusive).
+    pub bottom: i32,
+}
+
+/// Producer API connected to a buffer queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+pub enum NativeWindowApi {
+    /// OpenGL ES / EGL.
+    Egl = 1,
+    /// Software rendering.
+    Cpu = 2,
+    /// Video decoder.
+    Media = 3,
+    /// Camera.
+    Camera = 4,
+}
+
+/// Buffer attached to a slot of the queue, as returned by
+/// [`Binder::request_buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphicBuffer {
+    /// Width, in pixels.
+    pub width: i32,
+    /// Height, in pixels.
+    pub height: i32,
+    /// Stride, in pixels.
+    pub stride: i32,
+    /// Pixel format.
+    pub format: i32,
+    /// Gralloc usage flags.
+    pub usage: i32,
+    /// ID of the process that allocated the buffer.
+    pub pid: u32,
+    /// Reference count.
+    pub refcount: u32,
+    /// Number of valid entries in `native_handle`.
+    num_ints: usize,
+    /// Native handle integers.
+    native_handle: [u32; GRAPHIC_BUFFER_MAX_INTS],
+}
+
+impl GraphicBuffer {
+    /// Returns the integers of the native handle.
+    #[inline]
+    pub fn native_handle(&self) -> &[u32] {
+        &self.native_handle[..self.num_ints]
+    }
+
+    /// Parses a flattened graphic buffer.
+    ///
+    /// The buffer is a `'GBFR'` header of ten words followed by the native
+    /// handle integers; file descriptors are not supported.
+    fn read_from(buf: &[u8]) -> Option<Self> {
+        if buf.len() < GRAPHIC_BUFFER_HEADER_WORDS * 4 {
+            return None;
+        }
+        if read_u32_at(buf, 0) != GRAPHIC_BUFFER_MAGIC {
+            return None;
+        }
+
+        let num_fds = read_u32_at(buf, 8);
+        let num_ints = read_u32_at(buf, 9) as usize;
+        if num_fds != 0 || num_ints > GRAPHIC_BUFFER_MAX_INTS {
+            return None;
+        }
+        if buf.len() != (GRAPHIC_BUFFER_HEADER_WORDS + num_ints) * 4 {
+            return None;
+        }
+
+        let mut native_handle = [0; GRAPHIC_BUFFER_MAX_INTS];
+        for (i, word) in native_handle[..num_ints].iter_mut().enumerate() {
+            *word = read_u32_at(buf, GRAPHIC_BUFFER_HEADER_WORDS + i);
+        }
+
+        Some(Self {
+            width: read_u32_at(buf, 1) as i32,
+            height: read_u32_at(buf, 2) as i32,
+            stride: read_u32_at(buf, 3) as i32,
+            format: read_u32_at(buf, 4) as i32,
+            usage: read_u32_at(buf, 5) as i32,
+            pid: read_u32_at(buf, 6),
+            refcount: read_u32_at(buf, 7),
+            num_ints,
+            native_handle,
+        })
+    }
+}
+
+/// Slot handed out by [`Binder::dequeue_buffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DequeuedBuffer {
+    /// Slot of the buffer.
+    pub slot: i32,
+    /// Fence to wait on before writing to the buffer.
+    pub fence: MultiFence,
+    /// The buffer in the slot changed; call [`Binder::request_buffer`]
+    /// before using it.
+    pub needs_reallocation: bool,
+    /// Every buffer requested so far is stale and must be requested again.
+    pub release_all_buffers: bool,
+}
+
+/// Parameters of a [`Binder::queue_buffer`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueBufferInput {
+    /// Presentation timestamp, in nanoseconds.
+    pub timestamp: i64,
+    /// Whether the timestamp was generated automatically.
+    pub is_auto_timestamp: bool,
+    /// Crop rectangle.
+    pub crop: Rect,
+    /// Scaling mode.
+    pub scaling_mode: i32,
+    /// Transform to apply (`NATIVE_WINDOW_TRANSFORM_*`).
+    pub transform: u32,
+    /// Transform that persists across queued buffers.
+    pub sticky_transform: u32,
+    /// Swap interval, in vertical syncs.
+    pub swap_interval: u32,
+    /// Fence the compositor waits on before reading the buffer.
+    pub fence: MultiFence,
+}
+
+impl QueueBufferInput {
+    /// Size of a flattened input, in bytes.
+    pub const SIZE: usize = 0x30 + MultiFence::SIZE;
+
+    /// Creates the input for a `width`x`height` buffer, without timestamp,
+    /// transform or fence, presented on every vertical sync.
+    pub const fn new(width: i32, height: i32) -> Self {
+        Self {
+            timestamp: 0,
+            is_auto_timestamp: false,
+            crop: Rect {
+                left: 0,
+                top: 0,
+                right: width,
+                bottom: height,
+            },
+            scaling_mode: 0,
+            transform: 0,
+            sticky_transform: 0,
+            swap_interval: 1,
+            fence: MultiFence::NONE,
+        }
+    }
+
+    /// Flattens the input.
+    ///
+    /// The timestamp is packed at offset 0, so the following fields are only
+    /// 4-byte aligned.
+    fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut buf = [0; Self::SIZE];
+        buf[..8].copy_from_slice(&self.timestamp.to_ne_bytes());
+        write_u32_at(&mut buf, 2, self.is_auto_timestamp as u32);
+        write_u32_at(&mut buf, 3, self.crop.left as u32);
+        write_u32_at(&mut buf, 4, self.crop.top as u32);
+        write_u32_at(&mut buf, 5, self.crop.right as u32);
+        write_u32_at(&mut buf, 6, self.crop.bottom as u32);
+        write_u32_at(&mut buf, 7, self.scaling_mode as u32);
+        write_u32_at(&mut buf, 8, self.transform);
+        write_u32_at(&mut buf, 9, self.sticky_transform);
+        // Word 10 is unused
+        write_u32_at(&mut buf, 11, self.swap_interval);
+        self.fence.write_to(&mut buf[0x30..]);
+        buf
+    }
+}
+
+/// State of the queue, returned by [`Binder::connect`] and
+/// [`Binder::queue_buffer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct QueueBufferOutput {
+    /// Default width of the buffers.
+    pub width: u32,
+    /// Default height of the buffers.
+    pub height: u32,
+    /// Transform hint for the producer.
+    pub transform_hint: u32,
+    /// Number of queued buffers not yet acquired by the compositor.
+    pub num_pending_buffers: u32,
+}
+
+impl QueueBufferOutput {
+    /// Reads the output from a reply parcel.
+    fn read_from(parcel: &mut Parcel) -> Option<Self> {
+        Some(Self {
+            width: parcel.read_u32()?,
+            height: parcel.read_u32()?,
+            transform_hint: parcel.read_u32()?,
+            num_pending_buffers: parcel.read_u32()?,
+        })
+    }
+}
+
+impl Binder {
+    /// Returns the buffer attached to `slot`.
+    ///
+    /// Returns `None` if no buffer is attached to the slot. Call after
+    /// [`dequeue_buffer`](Self::dequeue_buffer) reports a reallocation.
+    pub fn request_buffer(
+        &self,
+        relay: &Service,
+        slot: i32,
+    ) -> Result<Option<GraphicBuffer>, BufferQueueError> {
+        let mut request = new_request()?;
+        request.write_i32(slot);
+
+        let mut reply = Parcel::new();
+        self.transact(relay, REQUEST_BUFFER, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)?;
+
+        parse_request_buffer_reply(&mut reply)
+    }
+
+    /// Dequeues a free buffer slot to render into.
+    ///
+    /// `width`, `height` and `format` of `0` select the queue defaults.
+    /// Rendering must wait for the returned fence.
+    pub fn dequeue_buffer(
+        &self,
+        relay: &Service,
+        is_async: bool,
+        width: u32,
+        height: u32,
+        format: i32,
+        usage: u32,
+    ) -> Result<DequeuedBuffer, BufferQueueError> {
+        let request = dequeue_buffer_request(is_async, width, height, format, usage)?;
+
+        let mut reply = Parcel::new();
+        self.transact(relay, DEQUEUE_BUFFER, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)?;
+
+        parse_dequeue_buffer_reply(&mut reply)
+    }
+
+    /// Queues the buffer in `slot` for presentation.
+    pub fn queue_buffer(
+        &self,
+        relay: &Service,
+        slot: i32,
+        input: &QueueBufferInput,
+    ) -> Result<QueueBufferOutput, BufferQueueError> {
+        let request = queue_buffer_request(slot, input)?;
+
+        let mut reply = Parcel::new();
+        self.transact(relay, QUEUE_BUFFER, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)?;
+
+        parse_output_reply(&mut reply)
+    }
+
+    /// Returns the buffer in `slot` to the queue without presenting it.
+    ///
+    /// The buffer is reused once `fence` is signaled.
+    pub fn cancel_buffer(
+        &self,
+        relay: &Service,
+        slot: i32,
+        fence: &MultiFence,
+    ) -> Result<(), BufferQueueError> {
+        let mut request = new_request()?;
+        request.write_i32(slot);
+        let mut buf = [0; MultiFence::SIZE];
+        fence.write_to(&mut buf);
+        request
+            .write_flattened_object(&buf)
+            .ok_or(BufferQueueError::ParcelOverflow)?;
+
+        // The transaction has no reply
+        let mut reply = Parcel::new();
+        self.transact(relay, CANCEL_BUFFER, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)
+    }
+
+    /// Connects a producer `api` to the queue.
+    ///
+    /// Must precede any other transaction. No producer listener is
+    /// registered.
+    pub fn connect(
+        &self,
+        relay: &Service,
+        api: NativeWindowApi,
+        producer_controlled_by_app: bool,
+    ) -> Result<QueueBufferOutput, BufferQueueError> {
+        let mut request = new_request()?;
+        // No IProducerListener binder
+        request.write_i32(0);
+        request.write_i32(api as i32);
+        request.write_i32(producer_controlled_by_app as i32);
+
+        let mut reply = Parcel::new();
+        self.transact(relay, CONNECT, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)?;
+
+        parse_output_reply(&mut reply)
+    }
+
+    /// Disconnects the producer `api` from the queue.
+    pub fn disconnect(
+        &self,
+        relay: &Service,
+        api: NativeWindowApi,
+    ) -> Result<(), BufferQueueError> {
+        let mut request = new_request()?;
+        request.write_i32(api as i32);
+
+        let mut reply = Parcel::new();
+        self.transact(relay, DISCONNECT, &request, &mut reply, 0)
+            .map_err(BufferQueueError::Transact)?;
+
+        read_status(&mut reply)?;
+        Ok(())
+    }
+}
+
+/// Builds the request parcel of a [`Binder::dequeue_buffer`] transaction.
+pub fn dequeue_buffer_request(
+    is_async: bool,
+    width: u32,
+    height: u32,
+    format: i32,
+    usage: u32,
+) -> Result<Parcel, BufferQueueError> {
+    let mut request = new_request()?;
+    request.write_i32(is_async as i32);
+    request.write_u32(width);
+    request.write_u32(height);
+    request.write_i32(format);
+    request.write_u32(usage);
+    Ok(request)
+}
+
+/// Builds the request parcel of a [`Binder::queue_buffer`] transaction.
+pub fn queue_buffer_request(
+    slot: i32,
+    input: &QueueBufferInput,
+) -> Result<Parcel, BufferQueueError> {
+    let mut request = new_request()?;
+    request.write_i32(slot);
+    request
+        .write_flattened_object(&input.to_bytes())
+        .ok_or(BufferQueueError::ParcelOverflow)?;
+    Ok(request)
+}
+
+/// Creates a request parcel holding the interface token.
+fn new_request() -> Result<Parcel, BufferQueueError> {
+    let mut parcel = Parcel::new();
+    parcel
+        .write_interface_token(INTERFACE_DESCRIPTOR)
+        .ok_or(BufferQueueError::ParcelOverflow)?;
+    Ok(parcel)
+}
+
+/// Reads the trailing status of a reply.
+///
+/// Returns the status, which may carry flags when non-negative.
+fn read_status(reply: &mut Parcel) -> Result<i32, BufferQueueError> {
+    let status = reply.read_i32().ok_or(BufferQueueError::InvalidReply)?;
+    BinderError::from_code(status).map_err(BufferQueueError::Binder)?;
+    Ok(status)
+}
+
+/// Parses the reply of a `REQUEST_BUFFER` transaction.
+fn parse_request_buffer_reply(
+    reply: &mut Parcel,
+) -> Result<Option<GraphicBuffer>, BufferQueueError> {
+    let non_null = reply.read_i32().ok_or(BufferQueueError::InvalidReply)?;
+    let buffer = if non_null != 0 {
+        let data = reply
+            .read_flattened_object()
+            .ok_or(BufferQueueError::InvalidReply)?;
+        Some(GraphicBuffer::read_from(data).ok_or(BufferQueueError::InvalidReply)?)
+    } else {
+        None
+    };
+
+    read_status(reply)?;
+    Ok(buffer)
+}
+
+/// Parses the reply of a [`Binder::dequeue_buffer`] transaction.
+pub fn parse_dequeue_buffer_reply(reply: &mut Parcel) -> Result<DequeuedBuffer, BufferQueueError> {
+    let slot = reply.read_i32().ok_or(BufferQueueError::InvalidReply)?;
+
+    let has_fence = reply.read_i32().ok_or(BufferQueueError::InvalidReply)?;
+    let fence = if has_fence != 0 {
+        let data = reply
+            .read_flattened_object()
+            .ok_or(BufferQueueError::InvalidReply)?;
+        if data.len() != MultiFence::SIZE {
+            return Err(BufferQueueError::InvalidReply);
+        }
+        MultiFence::read_from(data).ok_or(BufferQueueError::InvalidReply)?
+    } else {
+        MultiFence::NONE
+    };
+
+    let status = read_status(reply)?;
+    Ok(DequeuedBuffer {
+        slot,
+        fence,
+        needs_reallocation: status & BUFFER_NEEDS_REALLOCATION != 0,
+        release_all_buffers: status & RELEASE_ALL_BUFFERS != 0,
+    })
+}
+
+/// Parses a reply holding a [`QueueBufferOutput`] and a status.
+fn parse_output_reply(reply: &mut Parcel) -> Result<QueueBufferOutput, BufferQueueError> {
+    let output = QueueBufferOutput::read_from(reply).ok_or(BufferQueueError::InvalidReply)?;
+    read_status(reply)?;
+    Ok(output)
+}
+
+/// Reads the native-endian word at `index` of `buf`.
+fn read_u32_at(buf: &[u8], index: usize) -> u32 {
+    let off = index * 4;
+    u32::from_ne_bytes([buf[off], buf[off + 1], buf[off + 2], buf[off + 3]])
+}
+
+/// Writes `value` as the native-endian word at `index` of `buf`.
+fn write_u32_at(buf: &mut [u8], index: usize, value: u32) {
+    let off = index * 4;
+    buf[off..off + 4].copy_from_slice(&value.to_ne_bytes());
+}
+
+/// Error from the IGraphicBufferProducer methods of [`Binder`].
+#[derive(Debug, thiserror::Error)]
+pub enum BufferQueueError {
+    /// The request does not fit in a parcel.
+    #[error("request parcel overflow")]
+    ParcelOverflow,
+    /// The binder transaction failed.
+    #[error("binder transaction failed")]
+    Transact(#[source] TransactError),
+    /// The reply parcel is truncated or malformed.
+    #[error("invalid reply parcel")]
+    InvalidReply,
+    /// The buffer queue returned an error status.
+    #[error("buffer queue error")]
+    Binder(#[source] BinderError),
+}
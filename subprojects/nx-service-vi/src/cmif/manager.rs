@@ -276,7 +276,6 @@ pub fn set_display_power_state(
 }
 
 /// Adds a layer to a stack.
-#[expect(dead_code)]
 pub fn add_to_layer_stack(
     session: SessionHandle,
     layer_stack: ViLayerStack,
@@ -317,6 +316,47 @@ pub fn add_to_layer_stack(
     Ok(())
 }
 
+/// Removes a layer from a stack.
+pub fn remove_from_layer_stack(
+    session: SessionHandle,
+    layer_stack: ViLayerStack,
+    layer_id: LayerId,
+) -> Result<(), RemoveFromLayerStackError> {
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    let fmt = cmif::RequestFormatBuilder::new(manager_cmds::REMOVE_FROM_LAYER_STACK)
+        .data_size(16) // layer_stack(4) + pad(4) + layer_id(8)
+        .build();
+
+    // SAFETY: ipc_buf points to valid TLS IPC buffer.
+    let req = unsafe { cmif::make_request(ipc_buf, fmt) };
+
+    #[repr(C)]
+    struct Input {
+        layer_stack: u32,
+        pad: u32,
+        layer_id: u64,
+    }
+
+    let input = Input {
+        layer_stack: layer_stack as u32,
+        pad: 0,
+        layer_id: layer_id.to_raw(),
+    };
+
+    unsafe {
+        ptr::write_unaligned(req.data.as_ptr().cast::<Input>().cast_mut(), input);
+    }
+
+    ipc::send_sync_request(session).map_err(RemoveFromLayerStackError::SendRequest)?;
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let _ = unsafe { cmif::parse_response(ipc_buf, false, 0) }
+        .map_err(RemoveFromLayerStackError::ParseResponse)?;
+
+    Ok(())
+}
+
 /// Sets content visibility.
 pub fn set_content_visibility(
     session: SessionHandle,
@@ -423,6 +463,17 @@ pub enum AddToLayerStackError {
     ParseResponse(#[source] cmif::ParseResponseError),
 }
 
+/// Error from [`remove_from_layer_stack`].
+#[derive(Debug, thiserror::Error)]
+pub enum RemoveFromLayerStackError {
+    /// Failed to send IPC request.
+    #[error("failed to send request")]
+    SendRequest(#[source] ipc::SendSyncError),
+    /// Failed to parse CMIF response.
+    #[error("failed to parse response")]
+    ParseResponse(#[source] cmif::ParseResponseError),
+}
+
 /// Error from [`set_content_visibility`].
 #[derive(Debug, thiserror::Error)]
 pub enum SetContentVisibilityError {
@@ -0,0 +1,140 @@
+//! Layer stack composition (Manager only).
+//!
+//! Putting several managed layers on a display takes one `SetLayerZ` and one
+//! `AddToLayerStack` call per layer, followed by `SetDisplayLayerStack`.
+//! [`LayerStackBuilder`] collects the layers and performs the whole sequence,
+//! removing the layers it already added from the stack if a later step fails.
+//!
+//! Each layer is added with its z-order through [`LayerStackBuilder::layer`],
+//! and the sequence runs on [`LayerStackBuilder::apply`].
+
+use crate::{
+    ViService, ViServiceType, cmif,
+    cmif::{
+        manager::{AddToLayerStackError, SetDisplayLayerStackError},
+        system::SetLayerZError,
+    },
+    types::{DisplayId, LayerId, ViLayerStack},
+};
+
+/// Maximum number of layers a [`LayerStackBuilder`] can compose.
+pub const MAX_STACK_LAYERS: usize = 16;
+
+/// Builder composing managed layers onto a display's layer stack.
+///
+/// Requires the Manager service type: z-orders are set through
+/// ISystemDisplayService and the stack through IManagerDisplayService.
+pub struct LayerStackBuilder<'a> {
+    vi: &'a ViService,
+    display_id: DisplayId,
+    stack: ViLayerStack,
+    layers: [(LayerId, i32); MAX_STACK_LAYERS],
+    len: usize,
+    overflowed: bool,
+}
+
+impl<'a> LayerStackBuilder<'a> {
+    /// Creates a builder composing layers onto `stack` of `display_id`.
+    pub fn new(vi: &'a ViService, display_id: DisplayId, stack: ViLayerStack) -> Self {
+        Self {
+            vi,
+            display_id,
+            stack,
+            layers: [(LayerId::new(0), 0); MAX_STACK_LAYERS],
+            len: 0,
+            overflowed: false,
+        }
+    }
+
+    /// Adds a managed layer with the given z-order.
+    ///
+    /// Layers are added to the stack in the order of these calls. Adding more
+    /// than [`MAX_STACK_LAYERS`] layers makes [`apply`](Self::apply) fail with
+    /// [`LayerStackError::TooManyLayers`].
+    pub fn layer(mut self, layer_id: LayerId, z: i32) -> Self {
+        if self.len < MAX_STACK_LAYERS {
+            self.layers[self.len] = (layer_id, z);
+            self.len += 1;
+        } else {
+            self.overflowed = true;
+        }
+        self
+    }
+
+    /// Sets every layer's z-order, adds it to the stack, then makes the stack
+    /// the display's active layer stack.
+    ///
+    /// On failure, the layers already added are removed from the stack again
+    /// (best effort; removal errors are ignored). Z-orders that were already
+    /// set are not restored.
+    pub fn apply(self) -> Result<(), LayerStackError> {
+        if self.overflowed {
+            return Err(LayerStackError::TooManyLayers);
+        }
+
+        if self.vi.service_type != ViServiceType::Manager {
+            return Err(LayerStackError::NotAvailable);
+        }
+        let (Some(system), Some(manager)) = (
+            self.vi.system_display.as_ref(),
+            self.vi.manager_display.as_ref(),
+        ) else {
+            return Err(LayerStackError::NotAvailable);
+        };
+
+        let layers = &self.layers[..self.len];
+        for (added, &(layer_id, z)) in layers.iter().enumerate() {
+            let result = cmif::system::set_layer_z(system.session, layer_id, z as i64)
+                .map_err(|err| LayerStackError::SetLayerZ(layer_id, err))
+                .and_then(|()| {
+                    cmif::manager::add_to_layer_stack(manager.session, self.stack, layer_id)
+                        .map_err(|err| LayerStackError::AddToLayerStack(layer_id, err))
+                });
+
+            if let Err(err) = result {
+                self.rollback(&layers[..added]);
+                return Err(err);
+            }
+        }
+
+        if let Err(err) =
+            cmif::manager::set_display_layer_stack(manager.session, self.display_id, self.stack)
+        {
+            self.rollback(layers);
+            return Err(LayerStackError::SetDisplayLayerStack(err));
+        }
+
+        Ok(())
+    }
+
+    /// Removes `layers` from the stack, most recently added first.
+    fn rollback(&self, layers: &[(LayerId, i32)]) {
+        let Some(manager) = self.vi.manager_display.as_ref() else {
+            return;
+        };
+
+        for &(layer_id, _) in layers.iter().rev() {
+            let _ = cmif::manager::remove_from_layer_stack(manager.session, self.stack, layer_id);
+        }
+    }
+}
+
+/// Error returned by [`LayerStackBuilder::apply`].
+#[derive(Debug, thiserror::Error)]
+pub enum LayerStackError {
+    /// The service is not a Manager service.
+    #[error("layer stack composition requires the manager display service")]
+    NotAvailable,
+    /// More than [`MAX_STACK_LAYERS`] layers were added to the builder.
+    #[error("too many layers for one layer stack")]
+    TooManyLayers,
+    /// Setting a layer's z-order failed.
+    #[error("failed to set z-order of layer {0:?}")]
+    SetLayerZ(LayerId, #[source] SetLayerZError),
+    /// Adding a layer to the stack failed.
+    #[error("failed to add layer {0:?} to the layer stack")]
+    AddToLayerStack(LayerId, #[source] AddToLayerStackError),
+    /// Activating the layer stack on the display failed.
+    #[error("failed to set the display layer stack")]
+    SetDisplayLayerStack(#[source] SetDisplayLayerStackError),
+}
@@ -11,18 +11,30 @@
 
 extern crate nx_panic_handler; // Provide #![panic_handler]
 
+use core::sync::atomic::{AtomicU64, Ordering};
+
 use nx_service_sm::SmService;
 use nx_sf::service::Service;
-use nx_svc::ipc::Handle as SessionHandle;
+use nx_svc::{
+    ipc::Handle as SessionHandle,
+    sync::{EventHandle, WaitSyncError},
+};
 
 pub mod binder;
+pub mod buffer_producer;
 mod cmif;
+pub mod layer_stack;
+pub mod managed_layer;
 pub mod parcel;
 mod proto;
 pub mod types;
 
 pub use self::{
     binder::{Binder, BinderError, GetNativeHandleError, InitSessionError, TransactError},
+    buffer_producer::{
+        BufferQueueError, DequeuedBuffer, Fence, GraphicBuffer, MultiFence, NativeWindowApi,
+        QueueBufferInput, QueueBufferOutput, Rect,
+    },
     cmif::{
         application::{
             CloseDisplayError, CloseLayerError, CreateStrayLayerError, CreateStrayLayerOutput,
@@ -34,8 +46,8 @@ pub use self::{
         },
         manager::{
             AddToLayerStackError, CreateManagedLayerError, DestroyManagedLayerError,
-            SetContentVisibilityError, SetDisplayAlphaError, SetDisplayLayerStackError,
-            SetDisplayPowerStateError,
+            RemoveFromLayerStackError, SetContentVisibilityError, SetDisplayAlphaError,
+            SetDisplayLayerStackError, SetDisplayPowerStateError,
         },
         root::{
             DrawFatalRectangleError, DrawFatalText32Error, GetDisplayServiceError,
@@ -47,11 +59,17 @@ pub use self::{
             SetLayerVisibilityError, SetLayerZError,
         },
     },
-    parcel::{PARCEL_MAX_PAYLOAD, Parcel, ParcelHeader},
+    layer_stack::{LayerStackBuilder, LayerStackError, MAX_STACK_LAYERS},
+    managed_layer::ManagedLayer,
+    parcel::{
+        FlatBinderObject, PARCEL_MAX_OBJECTS, PARCEL_MAX_PAYLOAD, PARCEL_OBJECT_OFFSET_SIZE,
+        Parcel, ParcelHeader,
+    },
     proto::{SERVICE_NAME_APPLICATION, SERVICE_NAME_MANAGER, SERVICE_NAME_SYSTEM},
     types::{
-        BinderObjectId, DEFAULT_DISPLAY, DisplayId, DisplayName, LayerId, ViColorRgba4444,
-        ViColorRgba8888, ViLayerFlags, ViLayerStack, ViPowerState, ViScalingMode, ViServiceType,
+        BinderObjectId, CanvasScale, DEFAULT_DISPLAY, DisplayId, DisplayName, LayerId, LayerZ,
+        ViColorRgba4444, ViColorRgba8888, ViLayerFlags, ViLayerStack, ViPowerState, ViScalingMode,
+        ViServiceType,
     },
 };
 
@@ -73,11 +91,15 @@ pub struct ViService {
     manager_display: Option<Service>,
     /// IHOSBinderDriverIndirect session (System/Manager, 2.0.0+).
     binder_indirect: Option<Service>,
+    /// Last display resolution, packed by [`pack_resolution`] (0 = empty).
+    resolution_cache: AtomicU64,
+    /// Last display Z-order range, packed by [`pack_z_order_range`] (0 = empty).
+    z_order_cache: AtomicU64,
 }
 
 // SAFETY: ViService is safe to send across threads because:
 // - All Service instances are just session handles (u32)
-// - No mutable state that requires synchronization
+// - The only mutable state is the atomic resolution and Z-order caches
 unsafe impl Send for ViService {}
 
 // SAFETY: ViService is safe to share across threads because:
@@ -162,6 +184,10 @@ impl ViService {
     }
 
     /// Gets display resolution.
+    ///
+    /// The resolution changes on dock/undock (operation mode change). Render
+    /// loops polling it every frame should use
+    /// [`get_display_resolution_cached`](Self::get_display_resolution_cached).
     pub fn get_display_resolution(
         &self,
         display_id: DisplayId,
@@ -169,6 +195,42 @@ impl ViService {
         cmif::application::get_display_resolution(self.application_display.session, display_id)
     }
 
+    /// Gets display resolution, reusing the last value fetched for `display_id`.
+    ///
+    /// Only issues an IPC request on a cache miss. The cache holds a single
+    /// display and must be invalidated on operation mode change, see
+    /// [`invalidate_display_resolution_cache`](Self::invalidate_display_resolution_cache).
+    pub fn get_display_resolution_cached(
+        &self,
+        display_id: DisplayId,
+    ) -> Result<DisplayResolution, GetDisplayResolutionError> {
+        let cached = self.resolution_cache.load(Ordering::Acquire);
+        if let Some(res) = unpack_resolution(cached, display_id) {
+            return Ok(res);
+        }
+
+        self.refresh_display_resolution(display_id)
+    }
+
+    /// Fetches the display resolution and stores it in the cache.
+    pub fn refresh_display_resolution(
+        &self,
+        display_id: DisplayId,
+    ) -> Result<DisplayResolution, GetDisplayResolutionError> {
+        let res = self.get_display_resolution(display_id)?;
+        self.resolution_cache
+            .store(pack_resolution(display_id, res), Ordering::Release);
+        Ok(res)
+    }
+
+    /// Drops the cached display resolution.
+    ///
+    /// Call this when the applet receives `OperationModeChanged`.
+    #[inline]
+    pub fn invalidate_display_resolution_cache(&self) {
+        self.resolution_cache.store(0, Ordering::Release);
+    }
+
     /// Opens a layer.
     pub fn open_layer(
         &self,
@@ -202,6 +264,45 @@ impl ViService {
         )
     }
 
+    /// Creates a stray layer and the binder of its buffer queue.
+    ///
+    /// Parses the IGraphicBufferProducer binder out of the layer's native
+    /// window and initializes its session on the binder relay, so it is ready
+    /// for transactions. If either step fails, the layer is destroyed before
+    /// returning the error.
+    ///
+    /// On success, the caller owns both: close the binder with
+    /// [`Binder::close`] on [`binder_relay`](Self::binder_relay), then
+    /// destroy the layer with [`destroy_stray_layer`](Self::destroy_stray_layer).
+    pub fn create_stray_layer_with_binder(
+        &self,
+        layer_flags: ViLayerFlags,
+        display_id: DisplayId,
+    ) -> Result<(LayerId, Binder), CreateStrayLayerWithBinderError> {
+        let output = self
+            .create_stray_layer(layer_flags, display_id)
+            .map_err(CreateStrayLayerWithBinderError::CreateStrayLayer)?;
+
+        let size = (output.native_window_size as usize).min(NATIVE_WINDOW_SIZE);
+        let binder = Binder::from_native_window(&output.native_window[..size])
+            .ok_or(CreateStrayLayerWithBinderError::InvalidNativeWindow)
+            .and_then(|mut binder| {
+                binder
+                    .init_session(&self.binder_relay)
+                    .map(|()| binder)
+                    .map_err(CreateStrayLayerWithBinderError::InitSession)
+            });
+
+        match binder {
+            Ok(binder) => Ok((output.layer_id, binder)),
+            Err(err) => {
+                // The layer is of no use without its binder
+                let _ = self.destroy_stray_layer(output.layer_id);
+                Err(err)
+            }
+        }
+    }
+
     /// Destroys a stray layer.
     pub fn destroy_stray_layer(&self, layer_id: LayerId) -> Result<(), DestroyStrayLayerError> {
         cmif::application::destroy_stray_layer(self.application_display.session, layer_id)
@@ -339,6 +440,56 @@ impl ViService {
             .map_err(SetDisplayMagnificationWrapperError::Cmif)
     }
 
+    /// Sets up a logical canvas of `logical_w` x `logical_h` covering the
+    /// whole display (3.0.0+).
+    ///
+    /// Reads the display's logical resolution and magnifies the
+    /// `logical_w` x `logical_h` region at its origin to the full display, so
+    /// content laid out in canvas coordinates fills the screen whatever the
+    /// display resolution is. Returns the scale factors from canvas to display
+    /// coordinates.
+    ///
+    /// Each axis is scaled on its own: when the canvas and the display have
+    /// different aspect ratios, the factors differ and the canvas is
+    /// stretched rather than letterboxed. Pick a canvas size with the
+    /// display's aspect ratio (16:9) to keep [`CanvasScale::is_uniform`].
+    ///
+    /// Magnification can only enlarge, so the canvas must fit in the display;
+    /// larger sizes fail with [`SetVirtualCanvasError::InvalidCanvasSize`].
+    ///
+    /// Requires System or Manager service type.
+    pub fn set_virtual_canvas(
+        &self,
+        display_id: DisplayId,
+        logical_w: i32,
+        logical_h: i32,
+    ) -> Result<CanvasScale, SetVirtualCanvasError> {
+        let display = self
+            .get_display_logical_resolution(display_id)
+            .map_err(SetVirtualCanvasError::GetDisplayLogicalResolution)?;
+
+        if logical_w <= 0
+            || logical_h <= 0
+            || logical_w > display.width
+            || logical_h > display.height
+        {
+            return Err(SetVirtualCanvasError::InvalidCanvasSize {
+                width: logical_w,
+                height: logical_h,
+                display_width: display.width,
+                display_height: display.height,
+            });
+        }
+
+        self.set_display_magnification(display_id, 0, 0, logical_w, logical_h)
+            .map_err(SetVirtualCanvasError::SetDisplayMagnification)?;
+
+        Ok(CanvasScale {
+            x: display.width as f32 / logical_w as f32,
+            y: display.height as f32 / logical_h as f32,
+        })
+    }
+
     /// Sets layer position.
     ///
     /// Requires System or Manager service type.
@@ -390,14 +541,63 @@ impl ViService {
         cmif::system::set_layer_z(session, layer_id, z as i64).map_err(SetLayerZWrapperError::Cmif)
     }
 
-    /// Sets layer visibility.
+    /// Sets layer Z-order, clamped into the display's valid range.
+    ///
+    /// The range is queried with [`get_z_order_count_min`](Self::get_z_order_count_min)
+    /// and [`get_z_order_count_max`](Self::get_z_order_count_max) on first use
+    /// and cached for the most recently used display. Returns the Z-order that
+    /// was actually applied, as [`LayerZ::ClampedTo`] if `z` was out of range.
     ///
     /// Requires System or Manager service type.
+    pub fn set_layer_z_clamped(
+        &self,
+        layer_id: LayerId,
+        display_id: DisplayId,
+        z: i32,
+    ) -> Result<LayerZ, SetLayerZClampedError> {
+        let cached = self.z_order_cache.load(Ordering::Acquire);
+        let (min, max) = match unpack_z_order_range(cached, display_id) {
+            Some(range) => range,
+            None => {
+                let min = self
+                    .get_z_order_count_min(display_id)
+                    .map_err(SetLayerZClampedError::GetZOrderCountMin)?;
+                let max = self
+                    .get_z_order_count_max(display_id)
+                    .map_err(SetLayerZClampedError::GetZOrderCountMax)?;
+                self.z_order_cache
+                    .store(pack_z_order_range(display_id, min, max), Ordering::Release);
+                (min, max)
+            }
+        };
+
+        // Guard against an inverted range rather than panicking in `clamp`
+        let applied = z.max(min).min(max);
+        self.set_layer_z(layer_id, applied)
+            .map_err(SetLayerZClampedError::SetLayerZ)?;
+
+        Ok(if applied == z {
+            LayerZ::Applied(z)
+        } else {
+            LayerZ::ClampedTo(applied)
+        })
+    }
+
+    /// Sets layer visibility.
+    ///
+    /// Requires System or Manager service type. IApplicationDisplayService has
+    /// no visibility or alpha command, so application-type callers cannot
+    /// toggle their stray layers and get
+    /// [`SetLayerVisibilityWrapperError::NotAvailableForApplicationService`].
     pub fn set_layer_visibility(
         &self,
         layer_id: LayerId,
         visible: bool,
     ) -> Result<(), SetLayerVisibilityWrapperError> {
+        if self.service_type == ViServiceType::Application {
+            return Err(SetLayerVisibilityWrapperError::NotAvailableForApplicationService);
+        }
+
         let session = self
             .system_display
             .as_ref()
@@ -414,7 +614,9 @@ impl ViService {
 
     /// Creates a managed layer.
     ///
-    /// Requires Manager service type.
+    /// Requires Manager service type. The layer must be destroyed with
+    /// [`destroy_managed_layer`](Self::destroy_managed_layer); use
+    /// [`ManagedLayer`] to have that done on drop.
     pub fn create_managed_layer(
         &self,
         layer_flags: ViLayerFlags,
@@ -448,6 +650,25 @@ impl ViService {
             .map_err(DestroyManagedLayerWrapperError::Cmif)
     }
 
+    /// Adds a layer to a layer stack.
+    ///
+    /// Requires Manager service type. To compose several layers at once, use
+    /// [`LayerStackBuilder`].
+    pub fn add_to_layer_stack(
+        &self,
+        layer_stack: ViLayerStack,
+        layer_id: LayerId,
+    ) -> Result<(), AddToLayerStackWrapperError> {
+        let session = self
+            .manager_display
+            .as_ref()
+            .ok_or(AddToLayerStackWrapperError::NotAvailable)?
+            .session;
+
+        cmif::manager::add_to_layer_stack(session, layer_stack, layer_id)
+            .map_err(AddToLayerStackWrapperError::Cmif)
+    }
+
     /// Sets display alpha.
     ///
     /// Requires Manager service type.
@@ -468,7 +689,8 @@ impl ViService {
 
     /// Sets display layer stack.
     ///
-    /// Requires Manager service type.
+    /// Requires Manager service type. To add layers to the stack before
+    /// activating it, use [`LayerStackBuilder`].
     pub fn set_display_layer_stack(
         &self,
         display_id: DisplayId,
@@ -502,6 +724,49 @@ impl ViService {
             .map_err(SetDisplayPowerStateWrapperError::Cmif)
     }
 
+    /// Sets display power state and waits for the display to settle in it.
+    ///
+    /// VI has no query for the current power state, so the display's vsync
+    /// event is used as a proxy: the display is considered on once it has
+    /// produced [`POWER_STATE_SETTLE_VSYNCS`] vsyncs, and off (or not
+    /// scanning) once no vsync arrived for [`POWER_STATE_SETTLE_NS`]. Returns
+    /// [`SetDisplayPowerStateAndWaitError::Timeout`] if the display doesn't
+    /// settle within `timeout_ns`.
+    ///
+    /// The vsync event is acquired and closed internally, so this must not be
+    /// called while the caller holds the display's vsync event.
+    ///
+    /// Requires Manager service type.
+    pub fn set_display_power_state_and_wait(
+        &self,
+        display_id: DisplayId,
+        power_state: ViPowerState,
+        timeout_ns: u64,
+    ) -> Result<(), SetDisplayPowerStateAndWaitError> {
+        let session = self
+            .manager_display
+            .as_ref()
+            .ok_or(SetDisplayPowerStateAndWaitError::NotAvailable)?
+            .session;
+
+        let vsync = cmif::application::get_display_vsync_event(
+            self.application_display.session,
+            display_id,
+        )
+        .map_err(SetDisplayPowerStateAndWaitError::GetVsyncEvent)?;
+        // SAFETY: The handle was just returned by GetDisplayVsyncEvent.
+        let vsync = unsafe { EventHandle::from_raw(vsync) };
+
+        let result = cmif::manager::set_display_power_state(session, display_id, power_state)
+            .map_err(SetDisplayPowerStateAndWaitError::SetPowerState)
+            .and_then(|()| wait_power_state_settled(&vsync, power_state, timeout_ns));
+
+        // SAFETY: The event handle is owned here and not used afterwards.
+        let _ = unsafe { nx_svc::raw::close_handle(vsync.to_raw()) };
+
+        result
+    }
+
     /// Sets content visibility.
     ///
     /// Requires Manager service type.
@@ -706,12 +971,7 @@ pub fn connect(sm: &SmService, service_type: ViServiceType) -> Result<ViService,
     // TODO: Check HOS version for 16.0.0+ detection
     let keep_root = actual_type == ViServiceType::Manager;
     if keep_root {
-        root_service_handle = Some(Service {
-            session: root_handle,
-            own_handle: 1,
-            object_id: 0,
-            pointer_buffer_size: 0,
-        });
+        root_service_handle = Some(Service::new(root_handle));
     } else {
         // Close root service handle
         let _ = nx_svc::ipc::close_handle(root_handle);
@@ -781,9 +1041,135 @@ pub fn connect(sm: &SmService, service_type: ViServiceType) -> Result<ViService,
         system_display,
         manager_display,
         binder_indirect,
+        resolution_cache: AtomicU64::new(0),
+        z_order_cache: AtomicU64::new(0),
+    })
+}
+
+/// Number of consecutive vsyncs after which a powered-on display is
+/// considered settled.
+pub const POWER_STATE_SETTLE_VSYNCS: u32 = 2;
+
+/// Vsync-free interval after which a powered-off display is considered
+/// settled (three frames at 60 Hz).
+pub const POWER_STATE_SETTLE_NS: u64 = 50_000_000;
+
+/// Waits until `vsync` reflects `power_state`, or `timeout_ns` elapses.
+fn wait_power_state_settled(
+    vsync: &EventHandle,
+    power_state: ViPowerState,
+    timeout_ns: u64,
+) -> Result<(), SetDisplayPowerStateAndWaitError> {
+    let start = nx_svc::misc::get_system_tick();
+    let remaining_ns = || {
+        timeout_ns.saturating_sub(nx_svc::misc::ticks_to_nanos(
+            nx_svc::misc::get_system_tick() - start,
+        ))
+    };
+
+    // Drop any vsync signaled before the state change
+    // SAFETY: The handle is a valid readable event.
+    let _ = unsafe { nx_svc::sync::reset_signal(vsync) };
+
+    let mut vsyncs = 0;
+    loop {
+        let remaining = remaining_ns();
+        if remaining == 0 {
+            return Err(SetDisplayPowerStateAndWaitError::Timeout);
+        }
+
+        let window = match power_state {
+            ViPowerState::On => remaining,
+            ViPowerState::Off | ViPowerState::NotScanning => remaining.min(POWER_STATE_SETTLE_NS),
+        };
+
+        // SAFETY: The handle is a valid readable event.
+        match unsafe { nx_svc::sync::wait_synchronization_single(vsync, window) } {
+            Ok(()) => {
+                // SAFETY: The handle is a valid readable event.
+                let _ = unsafe { nx_svc::sync::reset_signal(vsync) };
+
+                vsyncs += 1;
+                if power_state == ViPowerState::On && vsyncs >= POWER_STATE_SETTLE_VSYNCS {
+                    return Ok(());
+                }
+            }
+            Err(WaitSyncError::TimedOut) => {
+                // A full window without a vsync means the display stopped
+                // scanning; a timeout of the whole budget is not settled.
+                if power_state != ViPowerState::On && window == POWER_STATE_SETTLE_NS {
+                    return Ok(());
+                }
+            }
+            Err(err) => return Err(SetDisplayPowerStateAndWaitError::Wait(err)),
+        }
+    }
+}
+
+/// Packs a display ID and resolution into one cache word.
+///
+/// Layout: `[display_id:32][width:16][height:16]`. Returns 0 (empty) when a
+/// value doesn't fit, so such displays are simply never cached.
+fn pack_resolution(display_id: DisplayId, res: DisplayResolution) -> u64 {
+    let (Ok(id), Ok(width), Ok(height)) = (
+        u32::try_from(display_id.to_raw()),
+        u16::try_from(res.width),
+        u16::try_from(res.height),
+    ) else {
+        return 0;
+    };
+
+    if width == 0 || height == 0 {
+        return 0;
+    }
+
+    ((id as u64) << 32) | ((width as u64) << 16) | height as u64
+}
+
+/// Unpacks a cache word, returning the resolution if it belongs to `display_id`.
+fn unpack_resolution(packed: u64, display_id: DisplayId) -> Option<DisplayResolution> {
+    if packed == 0 || (packed >> 32) != display_id.to_raw() {
+        return None;
+    }
+
+    Some(DisplayResolution {
+        width: ((packed >> 16) & 0xFFFF) as i64,
+        height: (packed & 0xFFFF) as i64,
     })
 }
 
+/// Packs a display ID and Z-order range into one cache word.
+///
+/// Layout: `[1][display_id:31][min:16][max:16]`, the top bit marking the word
+/// as non-empty. Returns 0 (empty) when a value doesn't fit, so such displays
+/// are simply never cached.
+fn pack_z_order_range(display_id: DisplayId, min: i32, max: i32) -> u64 {
+    let (Ok(id), Ok(min), Ok(max)) = (
+        u32::try_from(display_id.to_raw()),
+        i16::try_from(min),
+        i16::try_from(max),
+    ) else {
+        return 0;
+    };
+
+    if id >= 1 << 31 {
+        return 0;
+    }
+
+    (1 << 63) | ((id as u64) << 32) | ((min as u16 as u64) << 16) | max as u16 as u64
+}
+
+/// Unpacks a cache word, returning the Z-order range if it belongs to `display_id`.
+fn unpack_z_order_range(packed: u64, display_id: DisplayId) -> Option<(i32, i32)> {
+    if packed == 0 || ((packed >> 32) & 0x7FFF_FFFF) != display_id.to_raw() {
+        return None;
+    }
+
+    let min = ((packed >> 16) & 0xFFFF) as u16 as i16;
+    let max = (packed & 0xFFFF) as u16 as i16;
+    Some((min as i32, max as i32))
+}
+
 // =========================================================================
 // Wrapper error types for methods that check service availability
 // =========================================================================
@@ -832,6 +1218,43 @@ pub enum SetDisplayMagnificationWrapperError {
     Cmif(#[source] SetDisplayMagnificationError),
 }
 
+/// Error for [`ViService::set_virtual_canvas`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetVirtualCanvasError {
+    /// Querying the display logical resolution failed.
+    #[error("failed to get the display logical resolution")]
+    GetDisplayLogicalResolution(#[source] GetDisplayLogicalResolutionWrapperError),
+    /// The canvas is empty or larger than the display.
+    #[error("invalid canvas size {width}x{height} for a {display_width}x{display_height} display")]
+    InvalidCanvasSize {
+        /// Requested canvas width.
+        width: i32,
+        /// Requested canvas height.
+        height: i32,
+        /// Display logical width.
+        display_width: i32,
+        /// Display logical height.
+        display_height: i32,
+    },
+    /// Setting the display magnification failed.
+    #[error("failed to set the display magnification")]
+    SetDisplayMagnification(#[source] SetDisplayMagnificationWrapperError),
+}
+
+/// Error for [`ViService::create_stray_layer_with_binder`].
+#[derive(Debug, thiserror::Error)]
+pub enum CreateStrayLayerWithBinderError {
+    /// Creating the stray layer failed.
+    #[error("failed to create the stray layer")]
+    CreateStrayLayer(#[source] CreateStrayLayerError),
+    /// The native window of the layer is not a valid binder parcel.
+    #[error("invalid native window parcel")]
+    InvalidNativeWindow,
+    /// Initializing the binder session failed.
+    #[error("failed to initialize the binder session")]
+    InitSession(#[source] InitSessionError),
+}
+
 /// Error for set_layer_position wrapper.
 #[derive(Debug, thiserror::Error)]
 pub enum SetLayerPositionWrapperError {
@@ -865,9 +1288,26 @@ pub enum SetLayerZWrapperError {
     Cmif(#[source] SetLayerZError),
 }
 
+/// Error for [`ViService::set_layer_z_clamped`].
+#[derive(Debug, thiserror::Error)]
+pub enum SetLayerZClampedError {
+    /// Querying the minimum Z-order failed.
+    #[error("failed to get the minimum Z-order")]
+    GetZOrderCountMin(#[source] GetZOrderCountMinError),
+    /// Querying the maximum Z-order failed.
+    #[error("failed to get the maximum Z-order")]
+    GetZOrderCountMax(#[source] GetZOrderCountMaxError),
+    /// Setting the Z-order failed.
+    #[error("failed to set the layer Z-order")]
+    SetLayerZ(#[source] SetLayerZWrapperError),
+}
+
 /// Error for set_layer_visibility wrapper.
 #[derive(Debug, thiserror::Error)]
 pub enum SetLayerVisibilityWrapperError {
+    /// Layer visibility cannot be changed through the Application service.
+    #[error("layer visibility requires the system or manager display service")]
+    NotAvailableForApplicationService,
     /// System display service not available.
     #[error("system display service not available")]
     NotAvailable,
@@ -898,6 +1338,17 @@ pub enum DestroyManagedLayerWrapperError {
     Cmif(#[source] DestroyManagedLayerError),
 }
 
+/// Error for add_to_layer_stack wrapper.
+#[derive(Debug, thiserror::Error)]
+pub enum AddToLayerStackWrapperError {
+    /// Manager display service not available.
+    #[error("manager display service not available")]
+    NotAvailable,
+    /// CMIF operation failed.
+    #[error("CMIF operation failed")]
+    Cmif(#[source] AddToLayerStackError),
+}
+
 /// Error for set_display_alpha wrapper.
 #[derive(Debug, thiserror::Error)]
 pub enum SetDisplayAlphaWrapperError {
@@ -931,6 +1382,26 @@ pub enum SetDisplayPowerStateWrapperError {
     Cmif(#[source] SetDisplayPowerStateError),
 }
 
+/// Error for set_display_power_state_and_wait wrapper.
+#[derive(Debug, thiserror::Error)]
+pub enum SetDisplayPowerStateAndWaitError {
+    /// Manager display service not available.
+    #[error("manager display service not available")]
+    NotAvailable,
+    /// Failed to get the display vsync event.
+    #[error("failed to get display vsync event")]
+    GetVsyncEvent(#[source] GetDisplayVsyncEventError),
+    /// Failed to set the power state.
+    #[error("failed to set display power state")]
+    SetPowerState(#[source] SetDisplayPowerStateError),
+    /// Failed to wait on the vsync event.
+    #[error("failed to wait on vsync event")]
+    Wait(#[source] WaitSyncError),
+    /// The display did not settle in the requested state in time.
+    #[error("timed out waiting for display power state")]
+    Timeout,
+}
+
 /// Error for set_content_visibility wrapper.
 #[derive(Debug, thiserror::Error)]
 pub enum SetContentVisibilityWrapperError {
@@ -0,0 +1,95 @@
+//! Owned managed layers (Manager only).
+//!
+//! Managed layers are created for an applet resource user and must be
+//! destroyed explicitly, or they stay on the display after the creator is
+//! gone. [`ManagedLayer`] owns the layer and destroys it when dropped.
+//!
+//! To show the layer, add it to a layer stack with
+//! [`ManagedLayer::add_to_stack`] and make it visible with
+//! [`ManagedLayer::set_visibility`].
+
+use crate::{
+    AddToLayerStackWrapperError, CreateManagedLayerWrapperError, SetLayerVisibilityWrapperError,
+    ViService,
+    types::{DisplayId, LayerId, ViLayerFlags, ViLayerStack},
+};
+
+/// Managed layer destroyed on drop.
+///
+/// Only obtainable from a Manager service, see [`ManagedLayer::create`].
+pub struct ManagedLayer<'a> {
+    vi: &'a ViService,
+    layer_id: LayerId,
+    display_id: DisplayId,
+    aruid: u64,
+}
+
+impl<'a> ManagedLayer<'a> {
+    /// Creates a managed layer on `display_id` for the applet resource user
+    /// `aruid`.
+    ///
+    /// Fails with [`CreateManagedLayerWrapperError::NotAvailable`] unless
+    /// `vi` is a Manager service.
+    pub fn create(
+        vi: &'a ViService,
+        layer_flags: ViLayerFlags,
+        display_id: DisplayId,
+        aruid: u64,
+    ) -> Result<Self, CreateManagedLayerWrapperError> {
+        let layer_id = vi.create_managed_layer(layer_flags, display_id, aruid)?;
+
+        Ok(Self {
+            vi,
+            layer_id,
+            display_id,
+            aruid,
+        })
+    }
+
+    /// Returns the layer ID.
+    #[inline]
+    pub fn id(&self) -> LayerId {
+        self.layer_id
+    }
+
+    /// Returns the display the layer was created on.
+    #[inline]
+    pub fn display_id(&self) -> DisplayId {
+        self.display_id
+    }
+
+    /// Returns the applet resource user ID the layer was created for.
+    #[inline]
+    pub fn aruid(&self) -> u64 {
+        self.aruid
+    }
+
+    /// Adds the layer to `stack`.
+    pub fn add_to_stack(&self, stack: ViLayerStack) -> Result<(), AddToLayerStackWrapperError> {
+        self.vi.add_to_layer_stack(stack, self.layer_id)
+    }
+
+    /// Shows or hides the layer.
+    pub fn set_visibility(&self, visible: bool) -> Result<(), SetLayerVisibilityWrapperError> {
+        self.vi.set_layer_visibility(self.layer_id, visible)
+    }
+
+    /// Releases ownership of the layer without destroying it.
+    ///
+    /// The caller becomes responsible for destroying it with
+    /// [`ViService::destroy_managed_layer`].
+    pub fn leak(self) -> LayerId {
+        let layer_id = self.layer_id;
+        core::mem::forget(self);
+        layer_id
+    }
+}
+
+impl Drop for ManagedLayer<'_> {
+    fn drop(&mut self) {
+        // The only way to get a ManagedLayer is from a Manager service, so
+        // this cannot fail with NotAvailable; IPC errors are ignored, as
+        // there is nothing left to do with the layer.
+        let _ = self.vi.destroy_managed_layer(self.layer_id);
+    }
+}
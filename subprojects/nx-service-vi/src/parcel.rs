@@ -3,10 +3,26 @@
 //! Parcels are used for serializing data in Binder transactions.
 //! This implementation follows the Android Parcel format used by
 //! IGraphicBufferProducer.
+//!
+//! ## Objects
+//!
+//! Binder references and file descriptors are written as
+//! [`FlatBinderObject`]s, inline in the payload. The binder driver must be
+//! able to find them without parsing the payload, so the parcel also keeps
+//! the offset of each object in an objects table, sent after the payload
+//! (see [`ParcelHeader`]).
 
 /// Maximum parcel payload size.
 pub const PARCEL_MAX_PAYLOAD: usize = 0x400;
 
+/// Maximum number of objects in a parcel.
+pub const PARCEL_MAX_OBJECTS: usize = 16;
+
+/// Size of an entry of the objects table, in bytes.
+///
+/// Entries are 64-bit payload offsets (`binder_size_t`).
+pub const PARCEL_OBJECT_OFFSET_SIZE: usize = 8;
+
 /// Parcel header structure.
 #[derive(Debug, Clone, Copy, Default)]
 #[repr(C)]
@@ -26,6 +42,85 @@ impl ParcelHeader {
     pub const SIZE: usize = 16;
 }
 
+/// Binder object flattened into a parcel.
+///
+/// Depending on [`kind`](Self::kind), `handle` is a local binder, a remote
+/// binder handle or a file descriptor. The layout is the one used by the
+/// Switch's display driver, whose objects carry an 8-byte service name
+/// (e.g. `dispdrv` in the native window of a layer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlatBinderObject {
+    /// Object type, one of the `TYPE_*` constants.
+    pub kind: u32,
+    /// Object flags.
+    pub flags: u32,
+    /// Binder, handle or file descriptor, depending on the type.
+    pub handle: u64,
+    /// Cookie of a local binder.
+    pub cookie: u64,
+    /// NUL-padded name of the service owning the object.
+    pub service_name: [u8; 8],
+}
+
+impl FlatBinderObject {
+    /// Size of a flattened object, in bytes.
+    pub const SIZE: usize = 0x28;
+
+    /// Strong reference to a local binder (`'sb*'`).
+    pub const TYPE_BINDER: u32 = 0x7362_2A85;
+    /// Weak reference to a local binder (`'wb*'`).
+    pub const TYPE_WEAK_BINDER: u32 = 0x7762_2A85;
+    /// Strong reference to a remote binder handle (`'sh*'`).
+    pub const TYPE_HANDLE: u32 = 0x7368_2A85;
+    /// Weak reference to a remote binder handle (`'wh*'`).
+    pub const TYPE_WEAK_HANDLE: u32 = 0x7768_2A85;
+    /// File descriptor (`'fd*'`).
+    pub const TYPE_FD: u32 = 0x6664_2A85;
+
+    /// Flag asking the receiver to accept file descriptors.
+    pub const FLAG_ACCEPTS_FDS: u32 = 0x100;
+
+    /// Creates the object of a file descriptor.
+    pub const fn fd(fd: i32) -> Self {
+        Self {
+            kind: Self::TYPE_FD,
+            flags: Self::FLAG_ACCEPTS_FDS | 0x7F,
+            handle: fd as u32 as u64,
+            cookie: 0,
+            service_name: [0; 8],
+        }
+    }
+
+    /// Flattens the object.
+    fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut buf = [0; Self::SIZE];
+        buf[0..4].copy_from_slice(&self.kind.to_ne_bytes());
+        buf[4..8].copy_from_slice(&self.flags.to_ne_bytes());
+        buf[8..16].copy_from_slice(&self.handle.to_ne_bytes());
+        buf[16..24].copy_from_slice(&self.cookie.to_ne_bytes());
+        buf[24..32].copy_from_slice(&self.service_name);
+        buf
+    }
+
+    /// Parses a flattened object.
+    fn from_bytes(buf: &[u8; Self::SIZE]) -> Self {
+        let word =
+            |off: usize| u32::from_ne_bytes([buf[off], buf[off + 1], buf[off + 2], buf[off + 3]]);
+        let dword = |off: usize| word(off) as u64 | (word(off + 4) as u64) << 32;
+
+        let mut service_name = [0; 8];
+        service_name.copy_from_slice(&buf[24..32]);
+
+        Self {
+            kind: word(0),
+            flags: word(4),
+            handle: dword(8),
+            cookie: dword(16),
+            service_name,
+        }
+    }
+}
+
 /// Parcel for Binder IPC serialization.
 ///
 /// Used to serialize data for IGraphicBufferProducer transactions.
@@ -36,6 +131,10 @@ pub struct Parcel {
     payload_size: usize,
     /// Current read position.
     pos: usize,
+    /// Payload offsets of the written objects.
+    objects: [u32; PARCEL_MAX_OBJECTS],
+    /// Number of entries in `objects`.
+    objects_count: usize,
 }
 
 impl Parcel {
@@ -45,6 +144,8 @@ impl Parcel {
             payload: [0; PARCEL_MAX_PAYLOAD],
             payload_size: 0,
             pos: 0,
+            objects: [0; PARCEL_MAX_OBJECTS],
+            objects_count: 0,
         }
     }
 
@@ -87,6 +188,19 @@ impl Parcel {
         self.pos = 0;
     }
 
+    /// Returns the objects table: the payload offsets of the objects written
+    /// with [`write_object`](Self::write_object).
+    #[inline]
+    pub fn objects(&self) -> &[u32] {
+        &self.objects[..self.objects_count]
+    }
+
+    /// Clears the objects table after external writes to the payload.
+    #[inline]
+    pub fn clear_objects(&mut self) {
+        self.objects_count = 0;
+    }
+
     /// Writes raw data to the parcel, aligned to 4 bytes.
     ///
     /// Returns a pointer to the written data, or `None` if there's not enough space.
@@ -144,7 +258,12 @@ impl Parcel {
 
         let aligned_size = (size + 3) & !3;
 
-        if self.pos + aligned_size > self.payload_size {
+        // The payload size may have been set externally, so do not rely on
+        // the writes having kept the position aligned
+        if !self.pos.is_multiple_of(4) {
+            return None;
+        }
+        if self.payload_size > PARCEL_MAX_PAYLOAD || self.pos + aligned_size > self.payload_size {
             return None;
         }
 
@@ -201,40 +320,97 @@ impl Parcel {
         Some(u64::from_ne_bytes(unsafe { *(ptr as *const [u8; 8]) }))
     }
 
-    /// Writes a UTF-16 string (from ASCII).
+    /// Writes a UTF-16 string.
+    ///
+    /// The string is written as its length in UTF-16 code units, followed by
+    /// the code units and a null terminator, padded to 4 bytes.
     ///
-    /// The string is converted to UTF-16 and null-terminated.
-    pub fn write_string16(&mut self, s: &str) {
-        let len = s.len();
+    /// Returns `None` if there's not enough space.
+    pub fn write_string16(&mut self, s: &str) -> Option<()> {
+        if self.payload_size + string16_size(s) > PARCEL_MAX_PAYLOAD {
+            return None;
+        }
+
+        let len = s.encode_utf16().count();
         self.write_i32(len as i32);
 
-        // Write UTF-16 characters (len+1 for null terminator)
-        let char_count = len + 1;
-        let byte_count = char_count * 2;
-
-        if let Some(slice) = self.write_data_uninit(byte_count) {
-            // Convert ASCII to UTF-16 (only works for ASCII strings)
-            let mut i = 0;
-            for byte in s.bytes() {
-                if i + 1 < slice.len() {
-                    slice[i] = byte;
-                    slice[i + 1] = 0;
-                    i += 2;
-                }
-            }
-            // Null terminator
-            if i + 1 < slice.len() {
-                slice[i] = 0;
-                slice[i + 1] = 0;
-            }
+        // The slice is zeroed, which leaves the null terminator and padding in place
+        let slice = self.write_data_uninit((len + 1) * 2)?;
+        slice.fill(0);
+        for (chunk, unit) in slice.chunks_exact_mut(2).zip(s.encode_utf16()) {
+            chunk.copy_from_slice(&unit.to_ne_bytes());
         }
+        Some(())
     }
 
-    /// Writes an interface token (strict mode + interface name).
-    pub fn write_interface_token(&mut self, interface: &str) {
-        // Strict mode policy
+    /// Writes an interface token.
+    ///
+    /// The token is the Android descriptor header checked by the receiving
+    /// interface: the strict mode policy, followed by the interface
+    /// descriptor as a UTF-16 string (e.g.
+    /// `android.gui.IGraphicBufferProducer`).
+    ///
+    /// Returns `None` if there's not enough space.
+    pub fn write_interface_token(&mut self, interface: &str) -> Option<()> {
+        // Check up front, so a failed write leaves no partial token behind
+        if self.payload_size + 4 + string16_size(interface) > PARCEL_MAX_PAYLOAD {
+            return None;
+        }
+
+        // Strict mode policy (STRICT_MODE_PENALTY_GATHER)
         self.write_i32(0x100);
-        self.write_string16(interface);
+        self.write_string16(interface)
+    }
+
+    /// Writes a binder object, recording its offset in the objects table.
+    ///
+    /// Returns `None` if there's not enough space, in the payload or in the
+    /// objects table.
+    pub fn write_object(&mut self, object: &FlatBinderObject) -> Option<()> {
+        if self.objects_count >= PARCEL_MAX_OBJECTS {
+            return None;
+        }
+
+        let offset = self.payload_size;
+        self.write_data(&object.to_bytes())?;
+
+        self.objects[self.objects_count] = offset as u32;
+        self.objects_count += 1;
+        Some(())
+    }
+
+    /// Reads a binder object.
+    ///
+    /// Returns `None` if there's not enough data.
+    pub fn read_object(&mut self) -> Option<FlatBinderObject> {
+        let ptr = self.read_data(FlatBinderObject::SIZE)?;
+        // SAFETY: We have at least FlatBinderObject::SIZE bytes available.
+        Some(FlatBinderObject::from_bytes(unsafe {
+            &*(ptr as *const [u8; FlatBinderObject::SIZE])
+        }))
+    }
+
+    /// Writes a file descriptor, as a [`FlatBinderObject::TYPE_FD`] object.
+    ///
+    /// Returns `None` if there's not enough space.
+    pub fn write_fd(&mut self, fd: i32) -> Option<()> {
+        self.write_object(&FlatBinderObject::fd(fd))
+    }
+
+    /// Reads a file descriptor.
+    ///
+    /// Returns `None` if there's not enough data, or if the next object is
+    /// not a file descriptor.
+    pub fn read_fd(&mut self) -> Option<i32> {
+        let pos = self.pos;
+        let object = self.read_object()?;
+        if object.kind != FlatBinderObject::TYPE_FD {
+            // Leave the object to be read as what it is
+            self.pos = pos;
+            return None;
+        }
+
+        Some(object.handle as i32)
     }
 
     /// Reads a flattened object from the parcel.
@@ -266,6 +442,12 @@ impl Parcel {
     }
 }
 
+/// Returns the size of `s` written with [`Parcel::write_string16`], in bytes.
+fn string16_size(s: &str) -> usize {
+    let units = s.encode_utf16().count() + 1;
+    4 + ((units * 2 + 3) & !3)
+}
+
 impl Default for Parcel {
     fn default() -> Self {
         Self::new()
@@ -121,6 +121,8 @@ pub mod manager_cmds {
 
     /// Add layer to stack.
     pub const ADD_TO_LAYER_STACK: u32 = 6000;
+    /// Remove layer from stack.
+    pub const REMOVE_FROM_LAYER_STACK: u32 = 6001;
 
     /// Set content visibility.
     pub const SET_CONTENT_VISIBILITY: u32 = 7000;
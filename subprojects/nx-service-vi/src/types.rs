@@ -236,6 +236,46 @@ pub enum ViLayerStack {
     Null = 10,
 }
 
+/// Z-order applied by [`ViService::set_layer_z_clamped`](crate::ViService::set_layer_z_clamped).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerZ {
+    /// The requested Z-order was in range and applied as is.
+    Applied(i32),
+    /// The requested Z-order was out of range; this clamped value was applied.
+    ClampedTo(i32),
+}
+
+impl LayerZ {
+    /// Returns the Z-order that was applied.
+    #[inline]
+    pub fn value(self) -> i32 {
+        match self {
+            Self::Applied(z) | Self::ClampedTo(z) => z,
+        }
+    }
+}
+
+/// Scale factors applied by [`ViService::set_virtual_canvas`](crate::ViService::set_virtual_canvas).
+///
+/// Multiply a logical coordinate by the factor of its axis to get the display
+/// coordinate it is shown at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CanvasScale {
+    /// Horizontal scale factor (display width / logical width).
+    pub x: f32,
+    /// Vertical scale factor (display height / logical height).
+    pub y: f32,
+}
+
+impl CanvasScale {
+    /// Returns `true` if both axes are scaled by the same factor, i.e. the
+    /// canvas is not stretched.
+    #[inline]
+    pub fn is_uniform(self) -> bool {
+        self.x == self.y
+    }
+}
+
 /// RGBA4444 color format (16-bit).
 pub type ViColorRgba4444 = u16;
 
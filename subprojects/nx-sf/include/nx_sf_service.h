@@ -0,0 +1,66 @@
+/**
+ * @file nx_sf_service.h
+ * @brief Service session wrapper exposed by the nx-sf crate.
+ *        These functions are implemented in Rust (see ffi.rs). libnx's
+ *        service helpers are inline, so C code calls them explicitly.
+ * @author LNSD
+ * @copyright libnx Authors
+ */
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include <switch/sf/service.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * @brief Closes a service.
+ * @param s Service object.
+ */
+void __nx_sf__service_close(Service* s);
+
+/**
+ * @brief Clones a service via the CMIF CloneCurrentObject control command.
+ * @param s Service object to clone.
+ * @param out_s Output service object, owning a new session to the same server object.
+ * @return Result code.
+ */
+uint32_t __nx_sf__service_clone(const Service* s, Service* out_s);
+
+/**
+ * @brief Clones a service via the CMIF CloneCurrentObjectEx control command.
+ * @param s Service object to clone.
+ * @param tag Tag the server uses to tell the sessions apart.
+ * @param out_s Output service object, owning a new session to the same server object.
+ * @return Result code.
+ */
+uint32_t __nx_sf__service_clone_ex(const Service* s, uint32_t tag, Service* out_s);
+
+/**
+ * @brief Builds a CMIF request with no input and one Type C output pointer.
+ * @param buf 16-byte aligned buffer of at least 0x200 bytes the request is written to.
+ * @param request_id CMIF command ID.
+ * @param out Output buffer described by the receive list entry.
+ * @param out_size Size of the output buffer, at most 0xFFFF bytes.
+ * @return Result code. LibnxError_BadInput if out_size is too large.
+ */
+uint32_t __nx_sf__cmif_make_out_pointer_request(void* buf, uint32_t request_id, void* out, size_t out_size);
+
+/**
+ * @brief Sends a CMIF request with no input and one Type C output pointer.
+ * @param s Non-domain service object.
+ * @param request_id CMIF command ID.
+ * @param out Output buffer the server writes to.
+ * @param out_size Size of the output buffer, at most 0xFFFF bytes.
+ * @param out_written Output number of bytes the server wrote.
+ * @return Result code. LibnxError_BadInput, without sending, if out_size is too large.
+ */
+uint32_t __nx_sf__service_dispatch_out_pointer(const Service* s, uint32_t request_id, void* out, size_t out_size, size_t* out_written);
+
+#ifdef __cplusplus
+}
+#endif
@@ -60,10 +60,13 @@
 
 use core::{mem::size_of, ptr, ptr::NonNull, slice};
 
-use nx_svc::raw::Handle as RawHandle;
+use nx_svc::{r_try, raw::Handle as RawHandle};
 use static_assertions::const_assert_eq;
 
-use crate::hipc::{self, BufferMode};
+use crate::hipc::{self, BufferMode, OutPointerBuffer};
+
+#[cfg(feature = "trace")]
+pub mod trace;
 
 /// Magic number for CMIF input headers ("SFCI" - Service Framework Command Input).
 const IN_HEADER_MAGIC: u32 = 0x49434653;
@@ -195,6 +198,14 @@ pub unsafe fn make_request(base: NonNull<u8>, fmt: RequestFormat) -> Request<'st
         slice::from_raw_parts_mut(out_pointer_sizes_ptr, out_pointer_size_table_size as usize)
     };
 
+    #[cfg(feature = "trace")]
+    trace::emit(&trace::TraceEvent::Request {
+        kind: trace::RequestKind::Request,
+        object_id: fmt.object_id.map_or(0, ObjectId::to_raw),
+        command_id: fmt.request_id,
+        request_size: fmt.data_size,
+    });
+
     Request {
         hipc: hipc_req,
         data,
@@ -237,6 +248,14 @@ pub unsafe fn make_control_request(base: NonNull<u8>, request_id: u32, size: u32
     let start = get_aligned_data_start(hipc_req.data_words.as_mut_ptr(), base.as_ptr());
     let hdr = start as *mut InHeader;
 
+    #[cfg(feature = "trace")]
+    trace::emit(&trace::TraceEvent::Request {
+        kind: trace::RequestKind::Control,
+        object_id: 0,
+        command_id: request_id,
+        request_size: size as usize,
+    });
+
     // SAFETY: hdr points to aligned location within valid buffer.
     unsafe {
         ptr::write(
@@ -261,6 +280,14 @@ pub unsafe fn make_control_request(base: NonNull<u8>, request_id: u32, size: u32
 ///
 /// `base` must point to a valid buffer with sufficient space.
 pub unsafe fn make_close_request(base: NonNull<u8>, object_id: Option<ObjectId>) {
+    #[cfg(feature = "trace")]
+    trace::emit(&trace::TraceEvent::Request {
+        kind: trace::RequestKind::Close,
+        object_id: object_id.map_or(0, ObjectId::to_raw),
+        command_id: 0,
+        request_size: 0,
+    });
+
     if let Some(object_id) = object_id {
         // Domain object close
         let num_data_words = (16 + size_of::<DomainInHeader>() as u32) / 4;
@@ -336,15 +363,24 @@ pub unsafe fn parse_response(
     // SAFETY: out_header_ptr points to valid aligned OutHeader.
     let out_header = unsafe { ptr::read(out_header_ptr) };
 
+    #[cfg(feature = "trace")]
+    trace::emit(&trace::TraceEvent::Response {
+        outcome: if out_header.magic != OUT_HEADER_MAGIC {
+            trace::TraceOutcome::InvalidResponse
+        } else if out_header.result != 0 {
+            trace::TraceOutcome::ServiceError(out_header.result)
+        } else {
+            trace::TraceOutcome::Success
+        },
+    });
+
     // Validate magic
     if out_header.magic != OUT_HEADER_MAGIC {
         return Err(ParseResponseError::InvalidMagic);
     }
 
     // Check result
-    if out_header.result != 0 {
-        return Err(ParseResponseError::ServiceError(out_header.result));
-    }
+    r_try!(out_header.result, ParseResponseError::ServiceError);
 
     // SAFETY: out_header_ptr is valid, advancing by one OutHeader.
     let data_ptr = unsafe { out_header_ptr.add(1) } as *const u8;
@@ -354,6 +390,7 @@ pub unsafe fn parse_response(
     Ok(Response {
         data,
         objects,
+        statics: hipc_resp.statics,
         copy_handles: hipc_resp.copy_handles,
         move_handles: hipc_resp.move_handles,
     })
@@ -714,6 +751,17 @@ impl Request<'_> {
         self.server_pointer_size = self.server_pointer_size.saturating_sub(size);
     }
 
+    /// Adds a fixed-size output pointer backed by an [`OutPointerBuffer`].
+    ///
+    /// After parsing the response, pass [`Response::statics`] to
+    /// [`OutPointerBuffer::written_len`] to get the number of bytes written.
+    pub fn add_out_pointer_buffer(&mut self, buffer: &mut OutPointerBuffer<'_>) {
+        let idx = self.recv_list_idx;
+        self.hipc.recv_list[idx] = buffer.recv_list_entry();
+        self.recv_list_idx += 1;
+        self.server_pointer_size = self.server_pointer_size.saturating_sub(buffer.len());
+    }
+
     /// Adds a variable-size output pointer with size tracking.
     pub fn add_out_pointer(&mut self, buffer: *mut u8, size: usize) {
         self.add_out_fixed_pointer(buffer, size);
@@ -774,6 +822,8 @@ pub struct Response<'a> {
     pub data: &'a [u8],
     /// Returned domain object IDs.
     pub objects: &'a [u32],
+    /// Send static descriptors for data written to output pointers.
+    pub statics: &'a [hipc::StaticDescriptor],
     /// Returned copy handles.
     pub copy_handles: &'a [RawHandle],
     /// Returned move handles.
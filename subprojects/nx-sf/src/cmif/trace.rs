@@ -0,0 +1,104 @@
+//! IPC request tracing.
+//!
+//! Available with the `trace` feature. A single hook can be registered with
+//! [`set_hook`]; the CMIF message builders and parser then report to it:
+//!
+//! - [`make_request`](super::make_request),
+//!   [`make_control_request`](super::make_control_request) and
+//!   [`make_close_request`](super::make_close_request) report each request once
+//!   it has been written to the message buffer, right before it is sent.
+//! - [`parse_response`](super::parse_response) reports the outcome of each
+//!   response it parses.
+//!
+//! Since every CMIF client request goes through these functions, whether it is
+//! sent through [`Dispatch`](crate::service::Dispatch) or built by hand in an
+//! `nx-service-*` crate, every request is traced. TIPC requests
+//! ([`tipc`](crate::tipc)) are not. This is meant for bring-up of new service
+//! bindings, e.g. to log every command ID and result code.
+//!
+//! The hook is stored in a single atomic function pointer, so an unset hook
+//! costs one atomic load per event, and tracing never allocates.
+
+use core::{
+    mem, ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+/// Trace hook, stored as a type-erased [`TraceHook`] (null = unset).
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Callback invoked for every traced IPC event.
+///
+/// The hook runs on the thread performing the request, while the request or
+/// response is in the TLS IPC buffer, so it must not perform IPC itself. It
+/// must not allocate either.
+///
+/// Events from one thread arrive in order: a [`Request`](TraceEvent::Request)
+/// is followed by its [`Response`](TraceEvent::Response) once the caller
+/// parses the reply. A request without a response was a close request, failed
+/// to send, or had its reply ignored.
+pub type TraceHook = fn(&TraceEvent);
+
+/// An IPC event reported to the [`TraceHook`].
+#[derive(Debug)]
+pub enum TraceEvent {
+    /// A request has been built and is about to be sent.
+    Request {
+        /// Kind of request.
+        kind: RequestKind,
+        /// Domain object ID (0 for non-domain sessions).
+        object_id: u32,
+        /// CMIF command ID (0 for close requests).
+        command_id: u32,
+        /// Size of the raw request payload in bytes.
+        request_size: usize,
+    },
+    /// A response has been parsed.
+    Response {
+        /// Outcome of the request.
+        outcome: TraceOutcome,
+    },
+}
+
+/// Kind of a traced CMIF request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// A service command.
+    Request,
+    /// A control command, e.g. session cloning or domain conversion.
+    Control,
+    /// A session or domain object close. The server does not reply.
+    Close,
+}
+
+/// Outcome of a traced IPC request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceOutcome {
+    /// The service returned a zero result code.
+    Success,
+    /// The service returned a non-zero result code.
+    ServiceError(u32),
+    /// The response had an invalid CMIF header.
+    InvalidResponse,
+}
+
+/// Sets the trace hook, replacing the previous one.
+///
+/// Passing `None` disables tracing.
+pub fn set_hook(hook: Option<TraceHook>) {
+    let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut ());
+    HOOK.store(hook, Ordering::Release);
+}
+
+/// Reports `event` to the trace hook, if set.
+#[inline]
+pub(crate) fn emit(event: &TraceEvent) {
+    let hook = HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+
+    // SAFETY: Non-null values are only ever stored by `set_hook` from a `TraceHook`.
+    let hook = unsafe { mem::transmute::<*mut (), TraceHook>(hook) };
+    hook(event);
+}
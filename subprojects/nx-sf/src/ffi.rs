@@ -12,13 +12,18 @@
 //! FFI exports follow the pattern: `__nx_sf__<fn_name>`
 //! See `docs/libnx_overrides.md` for details.
 
-use core::mem;
+use core::{mem, ptr::NonNull, slice};
 
-use nx_svc::{error::ToRawResultCode, ipc::Handle as SessionHandle, raw::INVALID_HANDLE};
+use nx_svc::{
+    error::ToRawResultCode,
+    ipc::{self, Handle as SessionHandle},
+    raw::INVALID_HANDLE,
+};
 
 use crate::{
     cmif,
     cmif::ObjectId,
+    hipc::{OutPointerBuffer, OutPointerBufferError},
     service::{
         self, CloneObjectError, CloneObjectExError, Service, ServiceConvertToDomainError,
         TryCloneError, TryCloneExError,
@@ -28,6 +33,9 @@ use crate::{
 /// Generic error code for FFI when no specific result code is available.
 const GENERIC_ERROR: u32 = 0xFFFF;
 
+/// libnx `LibnxError_BadInput` result code.
+const LIBNX_ERR_BAD_INPUT: u32 = 345 | (11 << 9);
+
 /// Creates a service object from an IPC session handle.
 ///
 /// # Safety
@@ -254,6 +262,108 @@ pub unsafe extern "C" fn __nx_sf__service_get_object_id(s: *const Service) -> u3
 }
 
 /// Converts a clone object error to a raw result code for FFI.
+/// Builds a CMIF request with no input and one Type C output pointer.
+///
+/// The request is written to `buf` instead of the TLS IPC buffer, so the
+/// caller can inspect the encoded message.
+///
+/// Returns `LIBNX_ERR_BAD_INPUT` if `out_size` exceeds
+/// [`OutPointerBuffer::MAX_SIZE`].
+///
+/// # Safety
+///
+/// `buf` must point to at least 0x200 writable bytes, aligned to 16 bytes.
+/// `out` must point to `out_size` writable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sf__cmif_make_out_pointer_request(
+    buf: *mut u8,
+    request_id: u32,
+    out: *mut u8,
+    out_size: usize,
+) -> u32 {
+    // SAFETY: Caller guarantees out points to out_size writable bytes.
+    let out = unsafe { slice::from_raw_parts_mut(out, out_size) };
+    let mut out_buf = match OutPointerBuffer::new(out) {
+        Ok(out_buf) => out_buf,
+        Err(OutPointerBufferError::TooLarge(_)) => return LIBNX_ERR_BAD_INPUT,
+    };
+
+    let Some(base) = NonNull::new(buf) else {
+        return LIBNX_ERR_BAD_INPUT;
+    };
+
+    // SAFETY: Caller guarantees buf is a valid 0x200-byte message buffer.
+    unsafe { make_out_pointer_request(base, request_id, &mut out_buf) };
+    0
+}
+
+/// Sends a CMIF request with no input and one Type C output pointer.
+///
+/// On success, stores the number of bytes the server wrote to `out` in
+/// `out_written`. Returns `LIBNX_ERR_BAD_INPUT` without sending if `out_size`
+/// exceeds [`OutPointerBuffer::MAX_SIZE`].
+///
+/// # Safety
+///
+/// `s` must point to a valid, non-domain Service struct.
+/// `out` must point to `out_size` writable bytes.
+/// `out_written` must point to valid writable memory.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sf__service_dispatch_out_pointer(
+    s: *const Service,
+    request_id: u32,
+    out: *mut u8,
+    out_size: usize,
+    out_written: *mut usize,
+) -> u32 {
+    // SAFETY: Caller guarantees s points to a valid Service.
+    let srv = unsafe { &*s };
+    // SAFETY: Caller guarantees out points to out_size writable bytes.
+    let out = unsafe { slice::from_raw_parts_mut(out, out_size) };
+    let mut out_buf = match OutPointerBuffer::new(out) {
+        Ok(out_buf) => out_buf,
+        Err(OutPointerBufferError::TooLarge(_)) => return LIBNX_ERR_BAD_INPUT,
+    };
+
+    let ipc_buf = nx_sys_thread_tls::ipc_buffer_ptr();
+
+    // SAFETY: ipc_buf points to the TLS IPC buffer.
+    unsafe { make_out_pointer_request(ipc_buf, request_id, &mut out_buf) };
+
+    if let Err(err) = ipc::send_sync_request(srv.session) {
+        return err.to_rc();
+    }
+
+    // SAFETY: Response is in TLS buffer after successful send.
+    let resp = match unsafe { cmif::parse_response(ipc_buf, false, 0) } {
+        Ok(resp) => resp,
+        Err(err) => return parse_response_error_to_rc(err),
+    };
+
+    // SAFETY: Caller guarantees out_written points to valid memory.
+    unsafe { *out_written = out_buf.written_len(resp.statics).unwrap_or(0) };
+    0
+}
+
+/// Writes a request with no input and `out_buf` as its only Type C output.
+///
+/// # Safety
+///
+/// `base` must point to a valid 0x200-byte message buffer.
+unsafe fn make_out_pointer_request(
+    base: NonNull<u8>,
+    request_id: u32,
+    out_buf: &mut OutPointerBuffer<'_>,
+) {
+    let fmt = cmif::RequestFormatBuilder::new(request_id)
+        .out_fixed_pointers(1)
+        .build();
+
+    // SAFETY: Caller guarantees base is a valid message buffer.
+    let mut req = unsafe { cmif::make_request(base, fmt) };
+    req.add_out_pointer_buffer(out_buf);
+}
+
 fn clone_error_to_rc(err: CloneObjectError) -> u32 {
     match err {
         CloneObjectError::SendRequest(e) => e.to_rc(),
@@ -627,6 +627,95 @@ impl RecvListEntry {
     }
 }
 
+/// Client buffer for a Type C (receive list) output pointer.
+///
+/// Type C buffers live in client memory; the server stages its reply data in
+/// its own pointer buffer and the kernel copies it into this buffer. Two limits
+/// apply:
+///
+/// - The descriptor size field is 16 bits, so the buffer cannot exceed 64 KB.
+/// - The server can only send as much as fits in its pointer buffer (see
+///   `Service::pointer_buffer_size`). A buffer larger than that is still valid
+///   but the server will never fill more than `pointer_buffer_size` bytes, and
+///   auto-select buffers fall back to Type B in that case.
+///
+/// The buffer has no alignment requirement beyond that of the data the server
+/// writes into it.
+///
+/// On reply the kernel rewrites the server's send static (Type X) descriptor to
+/// point at this buffer, with its size set to the number of bytes written. Use
+/// [`written_len`](Self::written_len) on the response statics to recover it.
+#[derive(Debug)]
+pub struct OutPointerBuffer<'a> {
+    buf: &'a mut [u8],
+}
+
+impl<'a> OutPointerBuffer<'a> {
+    /// Maximum size of a Type C buffer (16-bit size field).
+    pub const MAX_SIZE: usize = u16::MAX as usize;
+
+    /// Wraps `buf` as a Type C output buffer.
+    pub fn new(buf: &'a mut [u8]) -> Result<Self, OutPointerBufferError> {
+        if buf.len() > Self::MAX_SIZE {
+            return Err(OutPointerBufferError::TooLarge(buf.len()));
+        }
+
+        Ok(Self { buf })
+    }
+
+    /// Returns the buffer size in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if the buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns `true` if the whole buffer fits in a server pointer buffer of
+    /// `pointer_buffer_size` bytes.
+    #[inline]
+    pub fn fits(&self, pointer_buffer_size: u16) -> bool {
+        self.buf.len() <= pointer_buffer_size as usize
+    }
+
+    /// Builds the receive list entry describing this buffer.
+    #[inline]
+    pub fn recv_list_entry(&mut self) -> RecvListEntry {
+        RecvListEntry::new_recv(self.buf.as_mut_ptr(), self.buf.len())
+    }
+
+    /// Returns the number of bytes the server wrote into this buffer.
+    ///
+    /// `statics` are the send static descriptors of the parsed response.
+    /// Returns `None` if none of them targets this buffer.
+    pub fn written_len(&self, statics: &[StaticDescriptor]) -> Option<usize> {
+        let addr = self.buf.as_ptr() as usize;
+        statics
+            .iter()
+            .find(|desc| desc.address() == addr)
+            .map(|desc| (desc.size() as usize).min(self.buf.len()))
+    }
+
+    /// Returns the part of the buffer the server wrote, or an empty slice if
+    /// the server did not write to it.
+    pub fn filled(&self, statics: &[StaticDescriptor]) -> &[u8] {
+        let len = self.written_len(statics).unwrap_or(0);
+        &self.buf[..len]
+    }
+}
+
+/// Error returned by [`OutPointerBuffer::new`].
+#[derive(Debug, thiserror::Error)]
+pub enum OutPointerBufferError {
+    /// Buffer exceeds the 64 KB Type C size limit.
+    #[error("buffer too large for a pointer descriptor: {0:#x} bytes")]
+    TooLarge(usize),
+}
+
 /// High-level metadata for constructing a request.
 ///
 /// This structure describes the layout of an HIPC request
@@ -47,3 +47,6 @@ pub use service_name::ServiceName;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
+
+#[cfg(feature = "track-handles")]
+pub mod track;
@@ -18,7 +18,7 @@
 //!
 //! - libnx `sf/service.h`
 
-use core::{mem::size_of, ptr};
+use core::{fmt, mem::size_of, ptr};
 
 use nx_svc::ipc::{self, Handle as SessionHandle};
 use static_assertions::const_assert_eq;
@@ -41,11 +41,21 @@ pub const MAX_IN_OBJECTS: usize = 8;
 /// Maximum number of input handles in a single dispatch.
 pub const MAX_IN_HANDLES: usize = 8;
 
+/// Maximum input or output data size of [`Service::send_simple_request`].
+///
+/// The request has to fit in the 0x100-byte TLS IPC buffer, after the HIPC,
+/// padding, domain and CMIF headers.
+pub const SIMPLE_REQUEST_MAX_DATA_SIZE: usize = 0xC0;
+
 /// IPC service wrapper.
 ///
 /// Wraps a session handle with metadata for domain support and pointer buffer
 /// tracking. The struct layout matches libnx's `Service` exactly for FFI.
-#[derive(Debug, Clone, Copy)]
+///
+/// The [`Debug`](fmt::Debug) output redacts the raw session handle unless the
+/// `debug-handles` feature is enabled; use [`raw_session`](Self::raw_session)
+/// when the value is actually needed.
+#[derive(Clone, Copy)]
 #[repr(C)]
 pub struct Service {
     /// IPC session handle.
@@ -59,27 +69,71 @@ pub struct Service {
 }
 const_assert_eq!(size_of::<Service>(), 16);
 
+impl fmt::Debug for Service {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut dbg = f.debug_struct("Service");
+
+        #[cfg(feature = "debug-handles")]
+        dbg.field("session", &self.session);
+        #[cfg(not(feature = "debug-handles"))]
+        dbg.field("session", &format_args!("<redacted>"));
+
+        dbg.field("object_id", &self.object_id)
+            .field("own_handle", &(self.own_handle != 0))
+            .field("domain", &(self.object_id != 0))
+            .field("pointer_buffer_size", &self.pointer_buffer_size)
+            .finish()
+    }
+}
+
+/// Two services are equal if they refer to the same object: the same session
+/// and, for domains, the same object ID.
+impl PartialEq for Service {
+    fn eq(&self, other: &Self) -> bool {
+        self.session == other.session && self.object_id == other.object_id
+    }
+}
+
+impl Eq for Service {}
+
 impl Service {
     /// Creates a new service from a session handle.
     ///
-    /// Queries the server's pointer buffer size automatically.
-    /// If the query fails, pointer buffer size defaults to 0.
+    /// Queries the server's pointer buffer size automatically. If the query
+    /// fails, pointer buffer size defaults to 0 (see
+    /// [`query_pointer_buffer_size`](Self::query_pointer_buffer_size)).
+    #[track_caller]
     pub fn new(handle: SessionHandle) -> Self {
-        let pointer_buffer_size = query_pointer_buffer_size(handle).unwrap_or(0);
-
-        Self {
+        track_open(handle);
+        let mut service = Self {
             session: handle,
             own_handle: 1,
             object_id: 0,
-            pointer_buffer_size,
-        }
+            pointer_buffer_size: 0,
+        };
+        let _ = service.query_pointer_buffer_size();
+        service
+    }
+
+    /// Queries the server's pointer buffer size and stores it in this service.
+    ///
+    /// On failure (e.g. TIPC-only sessions, which don't implement CMIF control
+    /// commands) the stored size is left unchanged. A size of 0 makes
+    /// auto-select buffers fall back to mapped buffers, which every server
+    /// accepts.
+    pub fn query_pointer_buffer_size(&mut self) -> Result<u16, QueryPointerBufferSizeError> {
+        let size = query_pointer_buffer_size(self.session)?;
+        self.pointer_buffer_size = size;
+        Ok(size)
     }
 
     /// Creates a non-domain subservice from a parent service's handle.
     ///
     /// The new service inherits the parent's pointer buffer size but owns
     /// the provided handle independently.
+    #[track_caller]
     pub fn new_subservice(parent: &Service, handle: SessionHandle) -> Self {
+        track_open(handle);
         Self {
             session: handle,
             own_handle: 1,
@@ -124,15 +178,27 @@ impl Service {
 
         // Close the handle if we own it
         if self.own_handle != 0 {
+            track_close(self.session);
             let _ = ipc::close_handle(self.session);
         }
     }
 
-    /// Clones the current service.
+    /// Clones the current service via the CMIF `CloneCurrentObject` control
+    /// command (control request 2).
     ///
-    /// Returns a new service with a cloned session handle.
+    /// Unlike copying the [`Service`] struct, which only duplicates the handle
+    /// value, this opens a new session to the same server object. The clone
+    /// owns its handle and can be used from another thread, or closed,
+    /// independently of `self`.
+    ///
+    /// Use [`try_clone_ex`](Self::try_clone_ex) instead when the server expects
+    /// a tag to tell sessions apart (e.g. `nvdrv`). To extract a domain object
+    /// as a standalone session, use
+    /// [`copy_object_to_session`](Self::copy_object_to_session).
+    #[track_caller]
     pub fn try_clone(&self) -> Result<Service, TryCloneError> {
         let new_handle = clone_current_object(self.session).map_err(TryCloneError)?;
+        track_open(new_handle);
 
         Ok(Self {
             session: new_handle,
@@ -145,8 +211,10 @@ impl Service {
     /// Clones the current service with a tag.
     ///
     /// Returns a new service with a cloned session handle.
+    #[track_caller]
     pub fn try_clone_ex(&self, tag: u32) -> Result<Service, TryCloneExError> {
         let new_handle = clone_current_object_ex(self.session, tag).map_err(TryCloneExError)?;
+        track_open(new_handle);
 
         Ok(Self {
             session: new_handle,
@@ -171,6 +239,7 @@ impl Service {
     ///
     /// This extracts the specified domain object as an independent service
     /// with its own session handle. Only valid for domain services.
+    #[track_caller]
     pub fn copy_object_to_session(
         &self,
         object_id: ObjectId,
@@ -181,6 +250,7 @@ impl Service {
 
         let new_handle = copy_from_current_domain(self.session, object_id)
             .map_err(CopyObjectToSessionError::CopyFailed)?;
+        track_open(new_handle);
 
         Ok(Self {
             session: new_handle,
@@ -198,6 +268,12 @@ impl Service {
         self.own_handle == 0 && self.object_id == 0
     }
 
+    /// Returns the raw session handle value.
+    #[inline]
+    pub fn raw_session(&self) -> u32 {
+        self.session.to_raw()
+    }
+
     /// Returns whether this is a domain service (owns handle with object ID).
     #[inline]
     pub fn is_domain(&self) -> bool {
@@ -215,6 +291,65 @@ impl Service {
     pub fn dispatch(&self, request_id: u32) -> Dispatch<'_> {
         Dispatch::new(self, request_id)
     }
+
+    /// Sends a command carrying only raw input and output data.
+    ///
+    /// An escape hatch to try a command no binding wraps yet: `input` is sent
+    /// as the raw request data, and the first `output.len()` bytes of the
+    /// response data are copied to `output`. Both are limited to
+    /// [`SIMPLE_REQUEST_MAX_DATA_SIZE`] bytes.
+    ///
+    /// The request has no handles, objects, PID or buffers (neither mapped nor
+    /// pointer buffers), and uses context 0. A command returning handles or
+    /// objects must not be sent this way: they would be leaked. Use
+    /// [`dispatch`](Self::dispatch) for anything beyond plain data.
+    pub fn send_simple_request(
+        &self,
+        command_id: u32,
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), SendSimpleRequestError> {
+        let size = input.len().max(output.len());
+        if size > SIMPLE_REQUEST_MAX_DATA_SIZE {
+            return Err(SendSimpleRequestError::DataTooLarge(size));
+        }
+
+        let dispatch = self.dispatch(command_id).out_size(output.len());
+
+        // SAFETY: input is valid and lives until send() completes.
+        let dispatch = unsafe { dispatch.in_raw(input.as_ptr(), input.len()) };
+
+        let result = dispatch.send().map_err(SendSimpleRequestError::Dispatch)?;
+        output.copy_from_slice(result.data);
+
+        Ok(())
+    }
+}
+
+/// Reports an opened session handle to the leak tracker, if enabled.
+#[inline]
+#[track_caller]
+fn track_open(_handle: SessionHandle) {
+    #[cfg(feature = "track-handles")]
+    crate::track::record_open(_handle);
+}
+
+/// Reports a closed session handle to the leak tracker, if enabled.
+#[inline]
+fn track_close(_handle: SessionHandle) {
+    #[cfg(feature = "track-handles")]
+    crate::track::record_close(_handle);
+}
+
+/// Error returned by [`Service::send_simple_request`].
+#[derive(Debug, thiserror::Error)]
+pub enum SendSimpleRequestError {
+    /// The input or output data exceeds [`SIMPLE_REQUEST_MAX_DATA_SIZE`].
+    #[error("request data too large: {0} bytes")]
+    DataTooLarge(usize),
+    /// Failed to dispatch the request.
+    #[error("failed to dispatch request")]
+    Dispatch(#[source] DispatchError),
 }
 
 /// Error returned by [`Service::try_clone`].
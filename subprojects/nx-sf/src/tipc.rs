@@ -54,7 +54,7 @@
 
 use core::{ptr::NonNull, slice};
 
-use nx_svc::raw::Handle as RawHandle;
+use nx_svc::{r_try, raw::Handle as RawHandle};
 
 use crate::hipc::{self, BufferMode};
 
@@ -158,10 +158,7 @@ pub unsafe fn parse_response(
         return Err(ParseResponseError::EmptyResponse);
     }
 
-    let result = hipc_resp.data_words[0];
-    if result != 0 {
-        return Err(ParseResponseError::ServiceError(result));
-    }
+    r_try!(hipc_resp.data_words[0], ParseResponseError::ServiceError);
 
     // SAFETY: We verified data_words is non-empty, so index 1 is within bounds
     // when data_words.len() > 1 (which is implied by having payload data).
@@ -0,0 +1,132 @@
+//! Session handle leak tracking.
+//!
+//! Available with the `track-handles` feature. Every session handle opened
+//! through [`Service`](crate::service::Service) is recorded together with the
+//! source file of the call that opened it, and removed again when the service
+//! is closed with [`Service::close`](crate::service::Service::close). Calling
+//! [`leaked_handles`] at shutdown then lists the sessions that were never
+//! closed.
+//!
+//! Handles are kept in a fixed table of [`CAPACITY`] atomic slots, so tracking
+//! never allocates or locks. Opens that do not fit in the table are counted by
+//! [`overflowed`] instead of being recorded.
+//!
+//! Services built from a struct literal, or whose handle is closed directly
+//! with [`ipc::close_handle`](nx_svc::ipc::close_handle), bypass the tracker;
+//! report those with [`record_open`] and [`record_close`].
+//!
+//! This is a debugging aid: the table is process-global and its contents are
+//! only as accurate as the open and close reports it receives.
+
+use core::{
+    panic::Location,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicU32, AtomicUsize, Ordering},
+};
+
+use nx_svc::ipc::Handle as SessionHandle;
+
+/// Number of session handles that can be tracked at once.
+pub const CAPACITY: usize = 256;
+
+/// Slot value of an unused slot.
+const FREE: u32 = 0;
+/// Slot value of a slot that is being written or cleared.
+///
+/// Session handles are never `u32::MAX`, so this cannot clash with one.
+const BUSY: u32 = u32::MAX;
+
+/// A tracked session handle.
+struct Slot {
+    /// Raw handle value, or [`FREE`] / [`BUSY`].
+    handle: AtomicU32,
+    /// Call site that opened the handle.
+    location: AtomicPtr<Location<'static>>,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY_SLOT: Slot = Slot {
+    handle: AtomicU32::new(FREE),
+    location: AtomicPtr::new(ptr::null_mut()),
+};
+
+static SLOTS: [Slot; CAPACITY] = [EMPTY_SLOT; CAPACITY];
+static OVERFLOWED: AtomicUsize = AtomicUsize::new(0);
+
+/// Records `handle` as opened by the caller.
+///
+/// If the table is full, the open is only counted in [`overflowed`].
+#[track_caller]
+pub fn record_open(handle: SessionHandle) {
+    let raw = handle.to_raw();
+    if raw == FREE || raw == BUSY {
+        return;
+    }
+
+    let location = ptr::from_ref(Location::caller()).cast_mut();
+    for slot in &SLOTS {
+        if slot
+            .handle
+            .compare_exchange(FREE, BUSY, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            slot.location.store(location, Ordering::Relaxed);
+            slot.handle.store(raw, Ordering::Release);
+            return;
+        }
+    }
+
+    OVERFLOWED.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Records `handle` as closed.
+///
+/// Closing a handle that is not in the table (e.g. one that overflowed it) is
+/// a no-op.
+pub fn record_close(handle: SessionHandle) {
+    let raw = handle.to_raw();
+    if raw == FREE || raw == BUSY {
+        return;
+    }
+
+    for slot in &SLOTS {
+        if slot
+            .handle
+            .compare_exchange(raw, BUSY, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            slot.location.store(ptr::null_mut(), Ordering::Relaxed);
+            slot.handle.store(FREE, Ordering::Release);
+            return;
+        }
+    }
+}
+
+/// Returns the session handles that were opened but not closed yet.
+///
+/// Each handle is paired with the source file of the call that opened it.
+/// Handles opened or closed concurrently with the iteration may or may not be
+/// reported.
+pub fn leaked_handles() -> impl Iterator<Item = (SessionHandle, &'static str)> {
+    SLOTS.iter().filter_map(|slot| {
+        let raw = slot.handle.load(Ordering::Acquire);
+        if raw == FREE || raw == BUSY {
+            return None;
+        }
+
+        // SAFETY: Non-null locations are only ever stored from
+        // `Location::caller()`, which is 'static.
+        let location = unsafe { slot.location.load(Ordering::Relaxed).as_ref() }?;
+
+        // SAFETY: The raw value was recorded from a valid session handle.
+        let handle = unsafe { SessionHandle::from_raw(raw) };
+        Some((handle, location.file()))
+    })
+}
+
+/// Returns the number of opens that did not fit in the table.
+///
+/// A non-zero count means [`leaked_handles`] may be incomplete.
+pub fn overflowed() -> usize {
+    OVERFLOWED.load(Ordering::Relaxed)
+}
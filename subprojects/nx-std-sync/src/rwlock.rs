@@ -45,6 +45,15 @@ pub use crate::result::{TryLockError, TryLockResult};
 ///
 /// </details>
 ///
+/// Writers are given priority over new readers, so a steady stream of readers
+/// does not starve a writer. The flip side is that a thread which re-acquires a
+/// read lock while a writer is waiting, as above, blocks.
+///
+/// A read guard can be turned into a write guard with
+/// [`RwLockReadGuard::try_upgrade`], which only succeeds if no other thread
+/// holds a read lock. There is no blocking upgrade: two readers each waiting
+/// for the other to release its read lock would deadlock.
+///
 /// The type parameter `T` represents the data that this lock protects. It is
 /// required that `T` satisfies [`Send`] to be shared across threads and
 /// [`Sync`] to allow concurrent access through readers. The RAII guards
@@ -76,6 +85,8 @@ pub struct RwLockReadGuard<'a, T: ?Sized + 'a> {
     // `NonNull` is also covariant over `T`, just like we would have with `&T`. `NonNull`
     // is preferable over `const* T` to allow for niche optimization.
     data: NonNull<T>,
+    // Borrow only the raw lock, not the `RwLock<T>`: its `UnsafeCell<T>` would make the guard
+    // invariant over `T`.
     inner_lock: &'a sys::RwLock,
     _marker: PhantomData<*const ()>,
 }
@@ -93,7 +104,12 @@ unsafe impl<T: ?Sized + Sync> Sync for RwLockReadGuard<'_, T> {}
 #[must_use = "if unused the RwLock will immediately unlock"]
 #[clippy::has_significant_drop]
 pub struct RwLockWriteGuard<'a, T: ?Sized + 'a> {
-    lock: &'a RwLock<T>,
+    // Built from the raw lock and data pointer, so that a read guard can be upgraded without
+    // borrowing the whole `RwLock<T>`. `PhantomData<&'a mut T>` keeps the guard invariant over
+    // `T`, as `&'a RwLock<T>` would.
+    data: NonNull<T>,
+    inner_lock: &'a sys::RwLock,
+    _variance: PhantomData<&'a mut T>,
     _marker: PhantomData<*const ()>,
 }
 
@@ -266,13 +282,51 @@ impl<'rwlock, T: ?Sized> RwLockReadGuard<'rwlock, T> {
     }
 }
 
+impl<'rwlock, T: ?Sized> RwLockReadGuard<'rwlock, T> {
+    /// Attempts to atomically upgrade the shared read access to exclusive write
+    /// access.
+    ///
+    /// This succeeds only if no other thread holds a read lock on the same
+    /// `RwLock`. No other writer can acquire the lock in between. On failure,
+    /// the original read guard is returned unchanged.
+    ///
+    /// This function does not block. If it fails, drop the read guard and call
+    /// [`RwLock::write`] instead, re-validating any state observed under the
+    /// read lock.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockReadGuard::try_upgrade(guard)`, so it does not conflict with a
+    /// method on `T`.
+    pub fn try_upgrade(orig: Self) -> Result<RwLockWriteGuard<'rwlock, T>, Self> {
+        if !orig.inner_lock.try_upgrade() {
+            return Err(orig);
+        }
+
+        let (data, inner_lock) = (orig.data, orig.inner_lock);
+        // The read lock was consumed by the upgrade, so it must not be released.
+        core::mem::forget(orig);
+
+        // The current thread holds the write lock after a successful upgrade, and `data` points
+        // into the same `RwLock`.
+        Ok(RwLockWriteGuard {
+            data,
+            inner_lock,
+            _variance: PhantomData,
+            _marker: PhantomData,
+        })
+    }
+}
+
 impl<'rwlock, T: ?Sized> RwLockWriteGuard<'rwlock, T> {
     /// Creates a new instance of `RwLockWriteGuard<T>` from a `RwLock<T>`.
     // SAFETY: if and only if `lock.inner.write()` (or `lock.inner.try_write()`) has been
-    // successfully called from the same thread before instantiating this object.
+    // successfully called from the same thread
+    // before instantiating this object.
     unsafe fn new(lock: &'rwlock RwLock<T>) -> RwLockWriteGuard<'rwlock, T> {
         RwLockWriteGuard {
-            lock,
+            data: unsafe { NonNull::new_unchecked(lock.data.get()) },
+            inner_lock: &lock.inner,
+            _variance: PhantomData,
             _marker: Default::default(),
         }
     }
@@ -316,14 +370,14 @@ impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
 
     fn deref(&self) -> &T {
         // SAFETY: the conditions of `RwLockWriteGuard::new` were satisfied when created.
-        unsafe { &*self.lock.data.get() }
+        unsafe { self.data.as_ref() }
     }
 }
 
 impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
     fn deref_mut(&mut self) -> &mut T {
         // SAFETY: the conditions of `RwLockWriteGuard::new` were satisfied when created.
-        unsafe { &mut *self.lock.data.get() }
+        unsafe { self.data.as_mut() }
     }
 }
 
@@ -335,6 +389,6 @@ impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
 
 impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
     fn drop(&mut self) {
-        self.lock.inner.write_unlock();
+        self.inner_lock.write_unlock();
     }
 }
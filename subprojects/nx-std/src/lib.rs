@@ -14,6 +14,8 @@ pub extern crate nx_alloc; // Provides #[global_allocator]
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+pub mod prelude;
+
 #[cfg(feature = "rand")]
 pub mod rand {
     pub use nx_rand::*;
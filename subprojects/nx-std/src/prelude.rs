@@ -0,0 +1,43 @@
+//! Commonly used items, re-exported for glob import with
+//! `use nx_std::prelude::*`.
+//!
+//! Each group of items is gated by the feature that provides it, so builds
+//! without `alloc` do not pull in any allocating types. Service `connect`
+//! functions are renamed after their service (e.g. `connect_hid`) to avoid
+//! clashes.
+
+#[cfg(feature = "alloc")]
+pub use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "alloc")]
+pub use nx_alloc::global::{heap_stats, init as init_allocator};
+#[cfg(feature = "service-apm")]
+pub use nx_service_apm::{ApmService, connect as connect_apm};
+#[cfg(feature = "service-applet")]
+pub use nx_service_applet::{AppletService, AppletType, connect as connect_applet};
+#[cfg(feature = "service-hid")]
+pub use nx_service_hid::{HidService, connect as connect_hid};
+#[cfg(feature = "service-nv")]
+pub use nx_service_nv::{NvService, connect as connect_nv};
+#[cfg(feature = "service-set")]
+pub use nx_service_set::{SetSysService, connect_cmif as connect_set_sys};
+#[cfg(feature = "service-sm")]
+pub use nx_service_sm::{SmService, connect as connect_sm};
+#[cfg(feature = "service-time")]
+pub use nx_service_time::{TimeService, TimeServiceType, connect as connect_time};
+#[cfg(feature = "service-vi")]
+pub use nx_service_vi::{ViService, ViServiceType, connect as connect_vi};
+#[cfg(feature = "sync")]
+pub use nx_std_sync::{
+    condvar::Condvar,
+    mutex::{Mutex, MutexGuard},
+    once_lock::OnceLock,
+    rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+#[cfg(feature = "time")]
+pub use nx_time::{Duration, Instant, SystemTime};
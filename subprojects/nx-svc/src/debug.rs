@@ -1,5 +1,102 @@
+//! Debugging supervisor calls: break events and debug string output.
+//!
+//! [`output`] and the [`debug_print!`](crate::debug_print) and
+//! [`debug_println!`](crate::debug_println) macros send text to an attached
+//! debugger via `svcOutputDebugString`. They need no console, network, or heap,
+//! but the output is only visible while a debugger is attached; otherwise it is
+//! discarded by the kernel.
+//!
+//! With the `strip-debug-output` feature enabled, debug string output is a
+//! no-op in builds without `debug_assertions`.
+
+use core::fmt;
+
 use super::raw;
 
+/// Size of the stack buffer used by [`debug_print!`](crate::debug_print).
+///
+/// Longer messages are truncated.
+pub const PRINT_BUFFER_SIZE: usize = 256;
+
+/// Outputs a string to the attached debugger.
+///
+/// The result of the SVC is ignored: there is nothing useful to do when the
+/// debug output itself fails.
+#[inline]
+pub fn output(s: &str) {
+    if cfg!(all(feature = "strip-debug-output", not(debug_assertions))) {
+        return;
+    }
+
+    // SAFETY: `s` points to `s.len()` readable bytes.
+    let _ = unsafe { raw::output_debug_string(s.as_ptr().cast(), s.len() as u64) };
+}
+
+/// Formats `args` into a stack buffer and outputs it to the attached debugger.
+///
+/// Implementation detail of the [`debug_print!`](crate::debug_print) and
+/// [`debug_println!`](crate::debug_println) macros.
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments<'_>) {
+    if cfg!(all(feature = "strip-debug-output", not(debug_assertions))) {
+        return;
+    }
+
+    let mut buf = [0u8; PRINT_BUFFER_SIZE];
+    let mut cursor = Cursor {
+        buf: &mut buf,
+        pos: 0,
+    };
+    let _ = fmt::write(&mut cursor, args);
+
+    let len = cursor.pos;
+    // SAFETY: `buf` holds `len` initialized bytes.
+    let _ = unsafe { raw::output_debug_string(buf.as_ptr().cast(), len as u64) };
+}
+
+/// Prints to the attached debugger.
+///
+/// Formats like [`core::format_args!`] into a
+/// [`PRINT_BUFFER_SIZE`](crate::debug::PRINT_BUFFER_SIZE)-byte stack buffer,
+/// truncating longer messages, and outputs it with `svcOutputDebugString`.
+#[macro_export]
+macro_rules! debug_print {
+    ($($arg:tt)*) => {
+        $crate::debug::_print(::core::format_args!($($arg)*))
+    };
+}
+
+/// Prints to the attached debugger, with a newline.
+///
+/// See [`debug_print!`](crate::debug_print).
+#[macro_export]
+macro_rules! debug_println {
+    () => {
+        $crate::debug::output("\n")
+    };
+    ($($arg:tt)*) => {
+        $crate::debug::_print(::core::format_args!("{}\n", ::core::format_args!($($arg)*)))
+    };
+}
+
+/// Byte buffer writer that silently truncates once full.
+struct Cursor<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl fmt::Write for Cursor<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = s.as_bytes();
+        let to_write = bytes.len().min(self.buf.len() - self.pos);
+
+        self.buf[self.pos..self.pos + to_write].copy_from_slice(&bytes[..to_write]);
+        self.pos += to_write;
+
+        Ok(())
+    }
+}
+
 /// Trigger a debug event
 ///
 /// This function is used to trigger a debug event.
@@ -12,7 +109,30 @@ pub fn break_event(reason: BreakReason, address: usize, size: usize) -> ! {
     unreachable!()
 }
 
+/// Breaks into the debugger, passing `buf` along as the break payload.
+///
+/// This is the safe way for application code to stop with a diagnostic
+/// message: the debugger (or the crash report, when none is attached) gets
+/// `reason` and the contents of `buf`. The panic handler does the same with
+/// [`BreakReason::Panic`] and the formatted panic message.
+///
+/// Without [`BreakReason::NotificationOnlyFlag`], the kernel terminates the
+/// process. With it, the kernel only notifies an attached debugger and
+/// returns; since this function cannot return, the process is then exited.
+pub fn break_with(reason: BreakReason, buf: &[u8]) -> ! {
+    // SAFETY: `buf` points to `buf.len()` readable bytes for the debugger.
+    let _ = unsafe { raw::r#break(reason.into(), buf.as_ptr() as usize, buf.len()) };
+
+    // SAFETY: Exiting the process is always allowed; it never returns.
+    unsafe { raw::exit_process() }
+}
+
 /// Break reasons for debug events
+///
+/// The values match `svcBreak`'s reason argument. `nx-panic-handler` keeps
+/// its own copy of the `Panic` value because `nx-svc` depends on it, not the
+/// other way around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BreakReason {
     /// Panic
     Panic,
@@ -32,6 +152,11 @@ pub enum BreakReason {
     CppException,
 
     /// NotificationOnlyFlag
+    ///
+    /// In the kernel ABI this is a flag (`0x80000000`) OR'ed into the reason,
+    /// asking the kernel to notify an attached debugger and return instead of
+    /// terminating the process. As a reason of its own it is `Panic` with the
+    /// flag set; other combinations are not expressible with this enum.
     NotificationOnlyFlag,
 }
 
@@ -218,6 +218,7 @@ unsafe extern "C" fn __nx_svc__svc_start_thread(handle: Handle) -> ResultCode {
 /// Ref: <https://switchbrew.org/wiki/SVC#ExitThread>
 #[unsafe(no_mangle)]
 unsafe extern "C" fn __nx_svc__svc_exit_thread() -> ! {
+    crate::thread::run_exit_hook();
     unsafe { raw::exit_thread() }
 }
 
@@ -335,7 +336,7 @@ unsafe extern "C" fn __nx_svc__svc_set_thread_core_mask(
 ///
 /// Ref: <https://switchbrew.org/wiki/SVC#GetCurrentProcessorNumber>
 #[unsafe(no_mangle)]
-unsafe extern "C" fn __nx_svc__svc_get_current_processor_number() -> ResultCode {
+unsafe extern "C" fn __nx_svc__svc_get_current_processor_number() -> u32 {
     unsafe { raw::get_current_processor_number() }
 }
 
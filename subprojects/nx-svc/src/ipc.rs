@@ -12,12 +12,11 @@
 //! - **Client Session**: The handle held by the client side of an IPC session,
 //!   used to send requests to the server.
 
-use core::ffi::CStr;
-
 use crate::{
     error::{KernelError as KError, ToRawResultCode},
     raw,
     result::{Error, ResultCode, raw::Result as RawResult},
+    sync::{self, WaitSyncError},
 };
 
 define_waitable_handle_type! {
@@ -33,12 +32,33 @@ define_waitable_handle_type! {
     pub struct Handle
 }
 
+/// Maximum length of a named port name in bytes, excluding the NUL terminator.
+pub const PORT_NAME_MAX_LEN: usize = 11;
+
 /// Connects to a registered named port and returns a session handle.
-pub fn connect_to_named_port(name: &CStr) -> Result<Handle, ConnectError> {
+///
+/// The kernel expects a NUL-terminated name of at most
+/// [`PORT_NAME_MAX_LEN`] bytes. The name is copied into a NUL-terminated
+/// stack buffer, so `name` itself must not contain a terminator.
+///
+/// Returns [`ConnectError::OutOfRange`] without issuing the SVC if `name` is
+/// too long or contains a NUL byte, and [`ConnectError::NotFound`] if no
+/// port is registered under `name` yet, which callers waiting for a service
+/// to come up can retry on.
+pub fn connect_to_named_port(name: &str) -> Result<Handle, ConnectError> {
+    let name = name.as_bytes();
+    if name.len() > PORT_NAME_MAX_LEN || name.contains(&0) {
+        return Err(ConnectError::OutOfRange);
+    }
+
+    let mut buf = [0u8; PORT_NAME_MAX_LEN + 1];
+    buf[..name.len()].copy_from_slice(name);
+
     let mut handle = raw::INVALID_HANDLE;
-    // SAFETY: `name` is a valid null-terminated C string (guaranteed by CStr),
-    // and `handle` is a valid mutable pointer to receive the output handle.
-    let rc = unsafe { raw::connect_to_named_port(&mut handle, name.as_ptr()) };
+    // SAFETY: `buf` is NUL-terminated since `name` is shorter than the buffer
+    // and contains no NUL bytes, and `handle` is a valid mutable pointer to
+    // receive the output handle.
+    let rc = unsafe { raw::connect_to_named_port(&mut handle, buf.as_ptr().cast()) };
 
     RawResult::from_raw(rc).map(Handle(handle), |rc| match rc.description() {
         desc if KError::OutOfRange == desc => ConnectError::OutOfRange,
@@ -54,7 +74,7 @@ pub fn connect_to_named_port(name: &CStr) -> Result<Handle, ConnectError> {
 /// Error returned by [`connect_to_named_port`].
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectError {
-    /// Port name exceeds 11 characters or is not null-terminated.
+    /// Port name exceeds [`PORT_NAME_MAX_LEN`] bytes or contains a NUL byte.
     #[error("Port name out of range")]
     OutOfRange,
     /// No port registered with the given name.
@@ -144,6 +164,54 @@ impl ToRawResultCode for ConnectToPortError {
     }
 }
 
+/// Accepts a pending session on a server port and returns its server session handle.
+///
+/// The port handle is the server side of a port, e.g. as returned by SM when
+/// registering a service. Wait on it until it is signalled before calling
+/// this; if no client is waiting to connect, this fails with
+/// [`AcceptSessionError::NotFound`].
+pub fn accept_session(port: Handle) -> Result<Handle, AcceptSessionError> {
+    let mut session = raw::INVALID_HANDLE;
+    // SAFETY: `session` is a valid mutable pointer to receive the output handle.
+    // The kernel validates the port handle and returns an error if invalid.
+    let rc = unsafe { raw::accept_session(&mut session, port.to_raw()) };
+
+    RawResult::from_raw(rc).map(Handle(session), |rc| match rc.description() {
+        desc if KError::NotFound == desc => AcceptSessionError::NotFound,
+        desc if KError::OutOfHandles == desc => AcceptSessionError::OutOfHandles,
+        desc if KError::InvalidHandle == desc => AcceptSessionError::InvalidHandle,
+        _ => AcceptSessionError::Unknown(rc.into()),
+    })
+}
+
+/// Error returned by [`accept_session`].
+#[derive(Debug, thiserror::Error)]
+pub enum AcceptSessionError {
+    /// No client session is pending on the port.
+    #[error("Not found")]
+    NotFound,
+    /// Process handle table is full.
+    #[error("Out of handles")]
+    OutOfHandles,
+    /// Invalid port handle.
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// Unexpected kernel error.
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for AcceptSessionError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::NotFound => KError::NotFound.to_rc(),
+            Self::OutOfHandles => KError::OutOfHandles.to_rc(),
+            Self::InvalidHandle => KError::InvalidHandle.to_rc(),
+            Self::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
 /// Sends a synchronous IPC request on a session.
 pub fn send_sync_request(handle: Handle) -> Result<(), SendSyncError> {
     // SAFETY: The kernel validates the session handle and returns an error if invalid.
@@ -450,6 +518,101 @@ impl ToRawResultCode for SendAsyncWithBufferError {
     }
 }
 
+/// Sends an asynchronous IPC request and returns a handle to its completion.
+///
+/// Like [`send_async_request_with_user_buffer`], but the returned
+/// [`AsyncRequest`] owns the completion event and mutably borrows `buffer`
+/// until the request has completed: the kernel writes the reply into the
+/// buffer, so it must stay valid and untouched until then. Dropping an
+/// unfinished [`AsyncRequest`] therefore blocks until the request completes.
+///
+/// The buffer must be page-aligned and its size must be page-aligned and non-zero.
+///
+/// # Safety
+///
+/// The returned [`AsyncRequest`] must not be leaked (e.g. with
+/// [`mem::forget`](core::mem::forget) or a reference cycle) while the request
+/// is in flight. Its `Drop` is what keeps the buffer borrowed until the kernel
+/// has written the reply; leaking it ends the borrow early, and the kernel may
+/// then write into memory that has been reused. Either let it drop, or wait for
+/// completion (see [`AsyncRequest::wait`]) before leaking it.
+pub unsafe fn send_async(
+    session: Handle,
+    buffer: &mut [u8],
+) -> Result<AsyncRequest<'_>, SendAsyncWithBufferError> {
+    let event = send_async_request_with_user_buffer(buffer, session)?;
+    Ok(AsyncRequest {
+        event,
+        buffer,
+        completed: false,
+    })
+}
+
+/// An in-flight asynchronous IPC request, as returned by [`send_async`].
+///
+/// Closes the completion event when dropped, after waiting for the request
+/// to complete if it has not yet. See [`send_async`] for why it must not be
+/// leaked while in flight.
+#[derive(Debug)]
+pub struct AsyncRequest<'a> {
+    event: EventHandle,
+    buffer: &'a mut [u8],
+    completed: bool,
+}
+
+impl AsyncRequest<'_> {
+    /// Waits up to `timeout_ns` nanoseconds for the request to complete.
+    ///
+    /// Use `u64::MAX` for an infinite wait, `0` for an immediate check.
+    /// Returns [`WaitSyncError::TimedOut`] if the request is still in flight.
+    pub fn wait(&mut self, timeout_ns: u64) -> Result<(), WaitSyncError> {
+        if self.completed {
+            return Ok(());
+        }
+
+        // SAFETY: The event handle is owned by this request and stays open
+        // until it is dropped.
+        unsafe { sync::wait_synchronization_single(&self.event, timeout_ns) }?;
+        self.completed = true;
+        Ok(())
+    }
+
+    /// Returns `true` if the request has completed, without blocking.
+    pub fn poll(&mut self) -> bool {
+        self.wait(0).is_ok()
+    }
+
+    /// Returns the message buffer holding the reply, once the request has
+    /// completed.
+    ///
+    /// Returns `None` while the request is in flight.
+    pub fn buffer(&mut self) -> Option<&mut [u8]> {
+        self.completed.then_some(&mut *self.buffer)
+    }
+
+    /// Returns the completion event handle, e.g. to wait on it together with
+    /// other handles.
+    ///
+    /// The handle stays owned by this request; do not close it.
+    #[inline]
+    pub fn event(&self) -> EventHandle {
+        self.event
+    }
+}
+
+impl Drop for AsyncRequest<'_> {
+    fn drop(&mut self) {
+        // The kernel may still write the reply into the borrowed buffer
+        if !self.completed {
+            let _ = self.wait(u64::MAX);
+        }
+
+        // SAFETY: The event handle is owned by this request and is closed
+        // exactly once.
+        let _ = unsafe { raw::close_handle(self.event.to_raw()) };
+    }
+}
+
 /// Closes a session handle, decrementing the kernel reference count.
 pub fn close_handle(handle: Handle) -> Result<(), CloseHandleError> {
     // SAFETY: The kernel validates the handle and returns an error if invalid.
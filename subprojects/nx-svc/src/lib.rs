@@ -25,6 +25,7 @@ pub mod mem;
 pub mod misc;
 pub mod process;
 pub mod raw;
+pub mod resource;
 pub mod result;
 pub mod sync;
 pub mod thread;
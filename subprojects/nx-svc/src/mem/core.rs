@@ -11,28 +11,51 @@ use crate::{
 /// Page information
 pub type PageInfo = u32;
 
+/// Alignment required of [`set_heap_size`] sizes (2 MiB).
+pub const HEAP_SIZE_ALIGN: usize = 0x200000;
+
+/// Exclusive upper bound of [`set_heap_size`] sizes (8 GiB).
+///
+/// This is the bound enforced by the kernel since 2.0.0; 1.0.0 rejects
+/// anything past the (smaller) heap region instead.
+pub const HEAP_SIZE_MAX: usize = 0x2_0000_0000;
+
 /// Sets the process heap to a given size.
 ///
-/// It can extend and shrink the heap.
+/// It can extend and shrink the heap; shrinking unmaps the memory past the
+/// new size, and a size of zero releases the heap entirely. The heap always
+/// starts at the same address, so memory below the new size stays valid.
+///
+/// `size` must be a multiple of [`HEAP_SIZE_ALIGN`] and below
+/// [`HEAP_SIZE_MAX`]; other sizes fail with [`SetHeapSizeError::InvalidSize`]
+/// without issuing the SVC.
 ///
 /// Returns the address of the heap (randomized and fixed by the kernel) if the heap was
 /// successfully set, or a [`SetHeapSizeError`] on failure.
-pub fn set_heap_size(size: usize) -> Result<*mut c_void, SetHeapSizeError> {
+pub fn set_heap_size(size: usize) -> Result<NonNull<u8>, SetHeapSizeError> {
+    if !size.is_multiple_of(HEAP_SIZE_ALIGN) || size >= HEAP_SIZE_MAX {
+        return Err(SetHeapSizeError::InvalidSize);
+    }
+
     let mut addr = ptr::null_mut();
     let rc = unsafe { raw::set_heap_size(&mut addr, size) };
-    RawResult::from_raw(rc).map(addr, |rc| match rc.description() {
-        desc if KError::InvalidSize == desc => SetHeapSizeError::InvalidSize,
-        desc if KError::OutOfResource == desc => SetHeapSizeError::OutOfResource,
-        desc if KError::OutOfMemory == desc => SetHeapSizeError::OutOfMemory,
-        desc if KError::InvalidCurrentMemory == desc => SetHeapSizeError::InvalidCurrentMemory,
-        desc if KError::InvalidNewMemoryPermission == desc => {
-            SetHeapSizeError::InvalidNewMemoryPermission
-        }
-        desc if KError::InvalidMemoryRegion == desc => SetHeapSizeError::InvalidMemoryRegion,
-        desc if KError::InvalidState == desc => SetHeapSizeError::InvalidState,
-        desc if KError::LimitReached == desc => SetHeapSizeError::LimitReached,
-        _ => SetHeapSizeError::Unknown(rc.into()),
-    })
+    RawResult::from_raw(rc)
+        .map((), |rc| match rc.description() {
+            desc if KError::InvalidSize == desc => SetHeapSizeError::InvalidSize,
+            desc if KError::OutOfResource == desc => SetHeapSizeError::OutOfResource,
+            desc if KError::OutOfMemory == desc => SetHeapSizeError::OutOfMemory,
+            desc if KError::InvalidCurrentMemory == desc => SetHeapSizeError::InvalidCurrentMemory,
+            desc if KError::InvalidNewMemoryPermission == desc => {
+                SetHeapSizeError::InvalidNewMemoryPermission
+            }
+            desc if KError::InvalidMemoryRegion == desc => SetHeapSizeError::InvalidMemoryRegion,
+            desc if KError::InvalidState == desc => SetHeapSizeError::InvalidState,
+            desc if KError::LimitReached == desc => SetHeapSizeError::LimitReached,
+            _ => SetHeapSizeError::Unknown(rc.into()),
+        })
+        // SAFETY: On success the kernel returns the heap region base, which is
+        // never at address zero.
+        .map(|()| unsafe { NonNull::new_unchecked(addr.cast::<u8>()) })
 }
 
 /// Error type for set_heap_size operations.
@@ -41,9 +64,8 @@ pub enum SetHeapSizeError {
     /// The size parameter is invalid.
     ///
     /// This occurs when:
-    /// - The size is not aligned to 4KB
-    /// - The size is 0
-    /// - The size would cause an overflow
+    /// - The size is not aligned to [`HEAP_SIZE_ALIGN`]
+    /// - The size is not below [`HEAP_SIZE_MAX`]
     #[error("Invalid size")]
     InvalidSize,
 
@@ -352,6 +374,182 @@ impl ToRawResultCode for UnmapMemoryError {
     }
 }
 
+/// Alignment required of [`map_physical_memory`] addresses and sizes (2 MiB).
+pub const PHYSICAL_MEMORY_ALIGN: usize = 0x200000;
+
+/// Backs a range of the alias region with new physical memory. [3.0.0+]
+///
+/// Unlike [`set_heap_size`], which grows the single heap region at an address
+/// chosen by the kernel, this maps memory at a caller-chosen address inside the
+/// alias region, so a heap can grow in independent 2 MiB chunks. Both draw from
+/// the same process memory limit, and the kernel only accepts this call from
+/// processes with a system resource (personal mm heap).
+///
+/// The SVC may not be permitted for the current process; callers must check
+/// the loader's syscall hints before calling it (e.g.,
+/// `nx_rt::mem::map_physical_memory`).
+///
+/// `addr` and `size` must be non-zero multiples of [`PHYSICAL_MEMORY_ALIGN`];
+/// this is checked before issuing the SVC.
+pub fn map_physical_memory(
+    addr: NonNull<c_void>,
+    size: usize,
+) -> Result<(), MapPhysicalMemoryError> {
+    if !(addr.as_ptr() as usize).is_multiple_of(PHYSICAL_MEMORY_ALIGN) {
+        return Err(MapPhysicalMemoryError::InvalidAddress);
+    }
+    if size == 0
+        || !size.is_multiple_of(PHYSICAL_MEMORY_ALIGN)
+        || (addr.as_ptr() as usize).checked_add(size).is_none()
+    {
+        return Err(MapPhysicalMemoryError::InvalidSize);
+    }
+
+    let rc = unsafe { raw::map_physical_memory(addr.as_ptr(), size as u64) };
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::InvalidAddress == desc => MapPhysicalMemoryError::InvalidAddress,
+        desc if KError::InvalidSize == desc => MapPhysicalMemoryError::InvalidSize,
+        desc if KError::InvalidMemoryRegion == desc => MapPhysicalMemoryError::InvalidMemoryRegion,
+        desc if KError::InvalidState == desc => MapPhysicalMemoryError::InvalidState,
+        desc if KError::LimitReached == desc => MapPhysicalMemoryError::LimitReached,
+        desc if KError::OutOfResource == desc => MapPhysicalMemoryError::OutOfResource,
+        desc if KError::OutOfMemory == desc => MapPhysicalMemoryError::OutOfMemory,
+        _ => MapPhysicalMemoryError::Unknown(rc.into()),
+    })
+}
+
+/// Error type for map_physical_memory operations.
+#[derive(Debug, thiserror::Error)]
+pub enum MapPhysicalMemoryError {
+    /// The address is not aligned to [`PHYSICAL_MEMORY_ALIGN`].
+    #[error("Invalid address")]
+    InvalidAddress,
+
+    /// The size is zero, not a multiple of [`PHYSICAL_MEMORY_ALIGN`], or the
+    /// range would overflow.
+    #[error("Invalid size")]
+    InvalidSize,
+
+    /// The range is not within the alias region.
+    #[error("Invalid memory range")]
+    InvalidMemoryRegion,
+
+    /// The process has no system resource to map physical memory with.
+    #[error("Invalid state")]
+    InvalidState,
+
+    /// The process memory limit would be exceeded.
+    #[error("Limit reached")]
+    LimitReached,
+
+    /// System resources are exhausted.
+    #[error("Out of resource")]
+    OutOfResource,
+
+    /// Not enough physical memory is available.
+    #[error("Out of memory")]
+    OutOfMemory,
+
+    /// An unknown error occurred
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for MapPhysicalMemoryError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            MapPhysicalMemoryError::InvalidAddress => KError::InvalidAddress.to_rc(),
+            MapPhysicalMemoryError::InvalidSize => KError::InvalidSize.to_rc(),
+            MapPhysicalMemoryError::InvalidMemoryRegion => KError::InvalidMemoryRegion.to_rc(),
+            MapPhysicalMemoryError::InvalidState => KError::InvalidState.to_rc(),
+            MapPhysicalMemoryError::LimitReached => KError::LimitReached.to_rc(),
+            MapPhysicalMemoryError::OutOfResource => KError::OutOfResource.to_rc(),
+            MapPhysicalMemoryError::OutOfMemory => KError::OutOfMemory.to_rc(),
+            MapPhysicalMemoryError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Releases physical memory mapped with [`map_physical_memory`]. [3.0.0+]
+///
+/// As with [`map_physical_memory`], callers must check that the SVC is
+/// permitted before calling it, and `addr` and `size` must be non-zero
+/// multiples of [`PHYSICAL_MEMORY_ALIGN`].
+///
+/// # Safety
+///
+/// The range must not be in use anymore; its contents are discarded.
+pub unsafe fn unmap_physical_memory(
+    addr: NonNull<c_void>,
+    size: usize,
+) -> Result<(), UnmapPhysicalMemoryError> {
+    if !(addr.as_ptr() as usize).is_multiple_of(PHYSICAL_MEMORY_ALIGN) {
+        return Err(UnmapPhysicalMemoryError::InvalidAddress);
+    }
+    if size == 0
+        || !size.is_multiple_of(PHYSICAL_MEMORY_ALIGN)
+        || (addr.as_ptr() as usize).checked_add(size).is_none()
+    {
+        return Err(UnmapPhysicalMemoryError::InvalidSize);
+    }
+
+    let rc = unsafe { raw::unmap_physical_memory(addr.as_ptr(), size as u64) };
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::InvalidAddress == desc => UnmapPhysicalMemoryError::InvalidAddress,
+        desc if KError::InvalidSize == desc => UnmapPhysicalMemoryError::InvalidSize,
+        desc if KError::InvalidMemoryRegion == desc => {
+            UnmapPhysicalMemoryError::InvalidMemoryRegion
+        }
+        desc if KError::InvalidState == desc => UnmapPhysicalMemoryError::InvalidState,
+        desc if KError::InvalidCurrentMemory == desc => {
+            UnmapPhysicalMemoryError::InvalidCurrentMemory
+        }
+        _ => UnmapPhysicalMemoryError::Unknown(rc.into()),
+    })
+}
+
+/// Error type for unmap_physical_memory operations.
+#[derive(Debug, thiserror::Error)]
+pub enum UnmapPhysicalMemoryError {
+    /// The address is not aligned to [`PHYSICAL_MEMORY_ALIGN`].
+    #[error("Invalid address")]
+    InvalidAddress,
+
+    /// The size is zero, not a multiple of [`PHYSICAL_MEMORY_ALIGN`], or the
+    /// range would overflow.
+    #[error("Invalid size")]
+    InvalidSize,
+
+    /// The range is not within the alias region.
+    #[error("Invalid memory range")]
+    InvalidMemoryRegion,
+
+    /// The process has no system resource to map physical memory with.
+    #[error("Invalid state")]
+    InvalidState,
+
+    /// The range was not mapped with [`map_physical_memory`].
+    #[error("Invalid memory state")]
+    InvalidCurrentMemory,
+
+    /// An unknown error occurred
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for UnmapPhysicalMemoryError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            UnmapPhysicalMemoryError::InvalidAddress => KError::InvalidAddress.to_rc(),
+            UnmapPhysicalMemoryError::InvalidSize => KError::InvalidSize.to_rc(),
+            UnmapPhysicalMemoryError::InvalidMemoryRegion => KError::InvalidMemoryRegion.to_rc(),
+            UnmapPhysicalMemoryError::InvalidState => KError::InvalidState.to_rc(),
+            UnmapPhysicalMemoryError::InvalidCurrentMemory => KError::InvalidCurrentMemory.to_rc(),
+            UnmapPhysicalMemoryError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
 /// Information about a memory region.
 #[derive(Debug, Clone)]
 pub struct MemoryInfo {
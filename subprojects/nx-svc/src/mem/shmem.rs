@@ -163,6 +163,95 @@ impl ToRawResultCode for UnmapSharedMemoryError {
     }
 }
 
+/// Page size that shared memory mappings must be aligned to.
+const PAGE_SIZE: usize = 0x1000;
+
+/// Maps a shared memory object at `addr`, returning a guard that unmaps it on drop.
+///
+/// Unlike [`map_shared_memory`], the arguments are validated before issuing the
+/// SVC: `addr` and `size` must be page-aligned, `size` non-zero, and `perm`
+/// either [`MemoryPermission::R`] or [`MemoryPermission::RW`].
+pub fn map(
+    handle: Handle,
+    addr: NonNull<c_void>,
+    size: usize,
+    perm: MemoryPermission,
+) -> Result<SharedMapping, MapError> {
+    if size == 0 || !size.is_multiple_of(PAGE_SIZE) {
+        return Err(MapError::InvalidSize(size));
+    }
+    if !(addr.as_ptr() as usize).is_multiple_of(PAGE_SIZE) {
+        return Err(MapError::UnalignedAddress(addr.as_ptr() as usize));
+    }
+    if perm != MemoryPermission::R && perm != MemoryPermission::RW {
+        return Err(MapError::InvalidPermission(perm));
+    }
+
+    map_shared_memory(handle, addr, size, perm).map_err(MapError::Svc)?;
+
+    Ok(SharedMapping { handle, addr, size })
+}
+
+/// Error returned by [`map`].
+#[derive(Debug, thiserror::Error)]
+pub enum MapError {
+    /// The size is zero or not page-aligned.
+    #[error("invalid size: {0:#x}")]
+    InvalidSize(usize),
+    /// The address is not page-aligned.
+    #[error("unaligned address: {0:#x}")]
+    UnalignedAddress(usize),
+    /// The permission is neither read-only nor read/write.
+    #[error("invalid permission: {0:?}")]
+    InvalidPermission(MemoryPermission),
+    /// The map SVC failed.
+    #[error("failed to map shared memory")]
+    Svc(#[source] MapSharedMemoryError),
+}
+
+/// A mapped shared memory region, unmapped when dropped.
+///
+/// The guard does not own the shared memory handle; it must stay open for as
+/// long as the mapping exists.
+#[derive(Debug)]
+pub struct SharedMapping {
+    handle: Handle,
+    addr: NonNull<c_void>,
+    size: usize,
+}
+
+impl SharedMapping {
+    /// Returns the shared memory handle backing this mapping.
+    #[inline]
+    pub fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Returns the base address of the mapping.
+    #[inline]
+    pub fn addr(&self) -> NonNull<c_void> {
+        self.addr
+    }
+
+    /// Returns the size of the mapping in bytes.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Unmaps the region, reporting any error instead of ignoring it on drop.
+    pub fn unmap(self) -> Result<(), UnmapSharedMemoryError> {
+        let this = core::mem::ManuallyDrop::new(self);
+        unmap_shared_memory(this.handle, this.addr, this.size)
+    }
+}
+
+impl Drop for SharedMapping {
+    fn drop(&mut self) {
+        let _ = unmap_shared_memory(self.handle, self.addr, self.size);
+    }
+}
+
 /// Closes a shared memory kernel object handle.
 pub fn close_handle(handle: Handle) -> Result<(), CloseHandleError> {
     let rc = unsafe { raw::close_handle(handle.0) };
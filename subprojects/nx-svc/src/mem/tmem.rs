@@ -43,6 +43,74 @@ pub fn create_transfer_memory(
     })
 }
 
+/// Size of a memory page, the alignment required for transfer memory ranges.
+pub const PAGE_SIZE: usize = 0x1000;
+
+/// Creates a transfer memory object, returning a handle that closes on drop.
+///
+/// This is the checked counterpart to [`create_transfer_memory`]: `addr` and
+/// `size` must be page-aligned ([`PAGE_SIZE`]) and `size` non-zero, otherwise
+/// [`CreateTransferMemoryError::InvalidAddress`] or
+/// [`CreateTransferMemoryError::InvalidSize`] is returned without issuing the
+/// SVC.
+///
+/// While the transfer memory exists, the kernel reprotects the source range:
+/// `perm` is the access this process keeps to it, [`MemoryPermission::NONE`]
+/// (no access, which most services require) or [`MemoryPermission::R`]
+/// (read-only). Any other access to the range faults until the handle is
+/// closed, at which point the original permissions are restored. The
+/// receiving process maps the memory with the permission it is given by the
+/// protocol, independently of `perm`.
+pub fn create(
+    addr: *mut u8,
+    size: usize,
+    perm: MemoryPermission,
+) -> Result<OwnedTmemHandle, CreateTransferMemoryError> {
+    let Some(addr) = NonNull::new(addr) else {
+        return Err(CreateTransferMemoryError::InvalidAddress);
+    };
+    if !(addr.as_ptr() as usize).is_multiple_of(PAGE_SIZE) {
+        return Err(CreateTransferMemoryError::InvalidAddress);
+    }
+    if size == 0 || !size.is_multiple_of(PAGE_SIZE) {
+        return Err(CreateTransferMemoryError::InvalidSize);
+    }
+
+    create_transfer_memory(addr.cast(), size, perm).map(OwnedTmemHandle)
+}
+
+/// Transfer memory handle that is closed when dropped.
+///
+/// Returned by [`create`]. Closing the handle destroys the transfer memory
+/// object once no other process holds it, which gives the source range back
+/// its original permissions.
+#[derive(Debug)]
+pub struct OwnedTmemHandle(Handle);
+
+impl OwnedTmemHandle {
+    /// Returns the underlying handle, e.g. to send it to a service.
+    ///
+    /// The handle stays owned by this value; do not close it.
+    #[inline]
+    pub fn handle(&self) -> Handle {
+        self.0
+    }
+
+    /// Releases ownership of the handle without closing it.
+    #[inline]
+    pub fn into_handle(self) -> Handle {
+        let handle = self.0;
+        core::mem::forget(self);
+        handle
+    }
+}
+
+impl Drop for OwnedTmemHandle {
+    fn drop(&mut self) {
+        let _ = close_handle(self.0);
+    }
+}
+
 /// Maps a transfer memory object into the current process.
 pub fn map_transfer_memory(
     handle: Handle,
@@ -111,6 +111,46 @@ pub fn get_stack_region_info() -> Result<(usize, usize), GetInfoError> {
     Ok((base as usize, size as usize))
 }
 
+/// Retrieves the program ID of the current process.
+///
+/// This function provides a safe wrapper around the `svcGetInfo` system call, allowing
+/// retrieval of the program ID of the current process ([3.0.0+]).
+///
+/// Returns the program ID on success, or a [`GetInfoError`] on failure.
+pub fn get_program_id() -> Result<u64, GetInfoError> {
+    get_info(InfoType::ProgramId, raw::CUR_PROCESS_HANDLE)
+}
+
+/// Frequency of the system tick counter, in Hz.
+pub const SYSTEM_TICK_FREQ: u64 = 19_200_000;
+
+/// Returns the current value of the system tick counter.
+///
+/// This function provides a safe wrapper around the `svcGetSystemTick` system call.
+/// The counter runs at 19.2 MHz ([`SYSTEM_TICK_FREQ`]).
+pub fn get_system_tick() -> u64 {
+    // SAFETY: svcGetSystemTick has no preconditions.
+    unsafe { raw::get_system_tick() }
+}
+
+/// Converts a number of system ticks to nanoseconds, rounding down.
+///
+/// Matches libnx's `armTicksToNs`, but saturates at `u64::MAX`.
+pub const fn ticks_to_nanos(ticks: u64) -> u64 {
+    let nanos = ticks as u128 * 1_000_000_000 / SYSTEM_TICK_FREQ as u128;
+    if nanos > u64::MAX as u128 {
+        u64::MAX
+    } else {
+        nanos as u64
+    }
+}
+
+/// Converts a number of nanoseconds to system ticks, rounding down.
+pub const fn nanos_to_ticks(nanos: u64) -> u64 {
+    // Cannot overflow: the tick frequency is below 1 GHz
+    (nanos as u128 * SYSTEM_TICK_FREQ as u128 / 1_000_000_000) as u64
+}
+
 /// Returns true if the current process has a debugger attached.
 ///
 /// This queries the kernel using [`InfoType::DebuggerAttached`] and returns
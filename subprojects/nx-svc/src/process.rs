@@ -1,6 +1,10 @@
-//! Process handle types.
+//! Process handle types and process queries.
 
-use crate::raw;
+use crate::{
+    error::{KernelError as KError, ToRawResultCode},
+    raw,
+    result::{Error, ResultCode, raw::Result as RawResult},
+};
 
 define_handle_type! {
     /// A handle to a process kernel object.
@@ -21,3 +25,47 @@ impl Handle {
         self.0 == raw::CUR_PROCESS_HANDLE
     }
 }
+
+/// Returns the process ID of the process referred to by `handle`.
+///
+/// Accepts the [`Handle::current_process`] pseudo-handle, as well as thread
+/// and debug handles, for which the ID of the owning process is returned. The
+/// process ID is assigned by the kernel and, unlike the handle, is the same
+/// in every process that refers to it.
+pub fn get_process_id(handle: Handle) -> Result<u64, GetProcessIdError> {
+    let mut process_id = 0;
+    let rc = unsafe { raw::get_process_id(&mut process_id, handle.0) };
+    RawResult::from_raw(rc).map(process_id, |rc| match rc.description() {
+        desc if KError::InvalidHandle == desc => GetProcessIdError::InvalidHandle,
+        _ => GetProcessIdError::Unknown(rc.into()),
+    })
+}
+
+/// Returns the process ID of the current process.
+///
+/// See [`get_process_id`].
+pub fn current_process_id() -> Result<u64, GetProcessIdError> {
+    get_process_id(Handle::current_process())
+}
+
+/// Error type for [`get_process_id`].
+#[derive(Debug, thiserror::Error)]
+pub enum GetProcessIdError {
+    /// The supplied handle does not refer to a process, thread or debug
+    /// object — `KernelError::InvalidHandle` (raw code `0xE401`).
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// Any unforeseen kernel error. Contains the original [`Error`] so callers
+    /// can inspect the raw result (`Error::to_raw`).
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for GetProcessIdError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::InvalidHandle => KError::InvalidHandle.to_rc(),
+            Self::Unknown(err) => err.to_raw(),
+        }
+    }
+}
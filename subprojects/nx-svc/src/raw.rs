@@ -361,6 +361,54 @@ impl ThreadContext {
     pub fn is_aarch64(&self) -> bool {
         (self.psr & 0x10) == 0
     }
+
+    /// Returns general-purpose register `Xi` (0..=30), including FP (x29) and LR (x30).
+    ///
+    /// For AArch32 threads only the low 32 bits are meaningful (`Ri`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i > 30`.
+    pub fn gpr(&self, i: usize) -> u64 {
+        match i {
+            // SAFETY: Every view of the union is plain data; any bit pattern is valid.
+            0..=28 => unsafe { self.cpu_gprs[i].x },
+            29 => self.fp,
+            30 => self.lr,
+            _ => panic!("invalid general-purpose register index: {i}"),
+        }
+    }
+
+    /// Returns the program counter.
+    pub fn pc(&self) -> u64 {
+        // SAFETY: Every view of the union is plain data; any bit pattern is valid.
+        unsafe { self.pc.x }
+    }
+
+    /// Returns the stack pointer.
+    pub fn sp(&self) -> u64 {
+        self.sp
+    }
+
+    /// Returns the frame pointer (x29).
+    pub fn fp(&self) -> u64 {
+        self.fp
+    }
+
+    /// Returns the link register (x30).
+    pub fn lr(&self) -> u64 {
+        self.lr
+    }
+
+    /// Returns NEON register `Vi` (0..=31) as a 128-bit value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i > 31`.
+    pub fn fpr(&self, i: usize) -> u128 {
+        // SAFETY: Every view of the union is plain data; any bit pattern is valid.
+        unsafe { self.fpu_gprs[i].v }
+    }
 }
 
 /// Armv8 CPU register
@@ -879,7 +927,7 @@ pub unsafe extern "C" fn set_thread_core_mask(
 ///
 /// This function is safe to call from any context.
 #[unsafe(naked)]
-pub unsafe extern "C" fn get_current_processor_number() -> ResultCode {
+pub unsafe extern "C" fn get_current_processor_number() -> u32 {
     core::arch::naked_asm!(
         "svc {code}", // Issue the SVC call with immediate value 0x10
         "ret",
@@ -0,0 +1,178 @@
+//! Resource limit handles and queries.
+//!
+//! Every process is bound to a resource limit object that caps how much
+//! memory, how many threads, events, transfer memories and sessions it may
+//! use. [`ResourceLimit`] wraps a handle to such an object and reads its
+//! values; [`ResourceLimit::current_process`] returns the limit of the
+//! current process.
+
+use crate::{
+    error::{KernelError as KError, ToRawResultCode},
+    misc::{self, GetInfoError, InfoType},
+    raw::{self, LimitableResource},
+    result::{Error, ResultCode, raw::Result as RawResult},
+};
+
+define_handle_type! {
+    /// A handle to a resource limit kernel object.
+    pub struct Handle
+}
+
+/// A resource limit object.
+///
+/// Values are in bytes for [`LimitableResource::Memory`], and in number of
+/// objects for the other resources.
+///
+/// Closes its handle when dropped.
+#[derive(Debug)]
+pub struct ResourceLimit(Handle);
+
+impl ResourceLimit {
+    /// Returns the resource limit of the current process.
+    ///
+    /// The handle is obtained through `svcGetInfo` and owned by the returned
+    /// value. Returns `None` if the process is not bound to a resource limit.
+    pub fn current_process() -> Result<Option<Self>, GetInfoError> {
+        // The kernel only accepts the invalid handle for this info type
+        let raw = misc::get_info(InfoType::ResourceLimit, raw::INVALID_HANDLE)?;
+        if raw == 0 {
+            return Ok(None);
+        }
+
+        // SAFETY: The kernel returned a new resource limit handle.
+        Ok(Some(unsafe {
+            Self::from_handle(Handle::from_raw(raw as raw::Handle))
+        }))
+    }
+
+    /// Wraps a resource limit handle.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid resource limit handle owned by the caller.
+    pub const unsafe fn from_handle(handle: Handle) -> Self {
+        Self(handle)
+    }
+
+    /// Returns the underlying handle.
+    ///
+    /// The handle stays owned by this object; do not close it.
+    #[inline]
+    pub fn handle(&self) -> Handle {
+        self.0
+    }
+
+    /// Releases ownership of the handle without closing it.
+    #[inline]
+    pub fn into_handle(self) -> Handle {
+        let handle = self.0;
+        core::mem::forget(self);
+        handle
+    }
+
+    /// Returns the maximum value of `which`.
+    pub fn limit(&self, which: LimitableResource) -> Result<i64, ResourceLimitValueError> {
+        let mut value = 0;
+        // SAFETY: `value` is a valid output location; the kernel validates the handle.
+        let rc = unsafe { raw::get_resource_limit_limit_value(&mut value, self.0.0, which) };
+        resource_limit_value_result(rc, value)
+    }
+
+    /// Returns the amount of `which` currently in use.
+    pub fn current(&self, which: LimitableResource) -> Result<i64, ResourceLimitValueError> {
+        let mut value = 0;
+        // SAFETY: `value` is a valid output location; the kernel validates the handle.
+        let rc = unsafe { raw::get_resource_limit_current_value(&mut value, self.0.0, which) };
+        resource_limit_value_result(rc, value)
+    }
+
+    /// Returns the highest amount of `which` that was in use at once. [11.0.0+]
+    ///
+    /// `svcGetResourceLimitPeakValue` does not exist before 11.0.0, and
+    /// issuing it there terminates the process. Check the syscall hints first,
+    /// or use the checked `nx_rt::resource::peak`.
+    pub fn peak(&self, which: LimitableResource) -> Result<i64, ResourceLimitValueError> {
+        let mut value = 0;
+        // SAFETY: `value` is a valid output location; the kernel validates the handle.
+        let rc = unsafe { raw::get_resource_limit_peak_value(&mut value, self.0.0, which) };
+        resource_limit_value_result(rc, value)
+    }
+
+    /// Closes the resource limit handle, reporting the kernel result that
+    /// dropping it would ignore.
+    pub fn close(self) -> Result<(), CloseHandleError> {
+        let handle = self.into_handle();
+        // SAFETY: The handle was owned by `self`, which is consumed.
+        let rc = unsafe { raw::close_handle(handle.0) };
+        RawResult::from_raw(rc).map((), |rc| match rc.description() {
+            desc if KError::InvalidHandle == desc => CloseHandleError::InvalidHandle,
+            _ => CloseHandleError::Unknown(rc.into()),
+        })
+    }
+}
+
+impl Drop for ResourceLimit {
+    fn drop(&mut self) {
+        // SAFETY: The handle is owned by this object and is closed exactly once.
+        let _ = unsafe { raw::close_handle(self.0.0) };
+    }
+}
+
+/// Translates the result code of the resource limit value queries.
+fn resource_limit_value_result(rc: ResultCode, value: i64) -> Result<i64, ResourceLimitValueError> {
+    RawResult::from_raw(rc).map(value, |rc| match rc.description() {
+        desc if KError::InvalidHandle == desc => ResourceLimitValueError::InvalidHandle,
+        desc if KError::InvalidEnumValue == desc => ResourceLimitValueError::InvalidResource,
+        _ => ResourceLimitValueError::Unknown(rc.into()),
+    })
+}
+
+/// Error type for [`ResourceLimit::limit`], [`ResourceLimit::current`] and
+/// [`ResourceLimit::peak`].
+#[derive(Debug, thiserror::Error)]
+pub enum ResourceLimitValueError {
+    /// The handle is not a valid resource limit handle —
+    /// `KernelError::InvalidHandle` (raw code `0xE401`).
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// The resource is not known to the kernel —
+    /// `KernelError::InvalidEnumValue` (raw code `0xF001`).
+    #[error("Invalid resource")]
+    InvalidResource,
+    /// Any unforeseen kernel error. Contains the original [`Error`] so callers
+    /// can inspect the raw result (`Error::to_raw`).
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for ResourceLimitValueError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::InvalidHandle => KError::InvalidHandle.to_rc(),
+            Self::InvalidResource => KError::InvalidEnumValue.to_rc(),
+            Self::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Error type for [`ResourceLimit::close`].
+#[derive(Debug, thiserror::Error)]
+pub enum CloseHandleError {
+    /// The handle is not valid —
+    /// `KernelError::InvalidHandle` (raw code `0xE401`).
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// Any unforeseen kernel error. Contains the original [`Error`] so callers
+    /// can inspect the raw result (`Error::to_raw`).
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for CloseHandleError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::InvalidHandle => KError::InvalidHandle.to_rc(),
+            Self::Unknown(err) => err.to_raw(),
+        }
+    }
+}
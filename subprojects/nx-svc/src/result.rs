@@ -48,16 +48,6 @@ pub type ResultCode = u32;
 /// The error code is formatted as `2XXX-YYYY` where:
 ///  - `XXX` is `2000` + module number
 ///  - `YYYY` is the `description`
-///
-/// ```rust
-/// use nx_svc::rc::{Error, ErrorModule};
-///
-/// let err = Error::from_parts(ErrorModule::Kernel, 404);
-///
-/// println!("{}", err); // "2001-0404"
-///
-/// # assert_eq!(format!("{}", err), "2001-0404");
-/// ```
 #[derive(Copy, Clone, Eq, PartialEq)]
 #[repr(transparent)]
 pub struct Error(raw::ResultCode);
@@ -91,14 +81,6 @@ impl ToRawResultCode for Error {
 
 impl core::fmt::Display for Error {
     /// Formats the error code as a `2XXX-YYYY` string.
-    ///
-    /// ```rust
-    /// use nx_svc::rc::{Error, ErrorModule};
-    ///
-    /// let err = Error::from_parts(ErrorModule::Kernel, 500);
-    ///
-    /// assert_eq!(format!("{}", err), "2001-0500");
-    /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
@@ -111,17 +93,6 @@ impl core::fmt::Display for Error {
 
 impl core::fmt::Debug for Error {
     /// Formats the error code as a debug string.
-    ///
-    /// ```rust
-    /// use nx_svc::rc::{Error, ErrorModule};
-    ///
-    /// let error = Error::from_parts(ErrorModule::FS, 500);
-    ///
-    /// assert_eq!(
-    ///     format!("{:?}", error),
-    ///     "Error { code: 2002-0500, module: FS, description: 500, raw: 0x20020500 }"
-    /// );
-    /// ```
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("Error")
             .field("code", &format_args!("{}", self))
@@ -141,6 +112,89 @@ impl From<raw::ResultCode> for Error {
     }
 }
 
+/// Success check and field decoding for raw [`ResultCode`] values.
+///
+/// Service marshalling code gets result codes as plain `u32` words (IPC
+/// response headers, SVC return values); this trait decodes them in place
+/// and maps failures to typed errors, see also [`r_try!`](crate::r_try).
+pub trait ResultCodeExt: Copy {
+    /// Returns `true` if the result code represents a success (zero).
+    fn is_success(self) -> bool;
+
+    /// Returns the module number (bits 0-8).
+    ///
+    /// This is a plain number rather than an [`error::Module`](Module): result
+    /// codes received from services may carry modules that enum does not list.
+    fn module(self) -> u32;
+
+    /// Returns the description (bits 9-21).
+    fn description(self) -> u32;
+
+    /// Returns `Ok(())` on success, or `Err(f(rc))` with the raw result code.
+    fn ok_or<E>(self, f: impl FnOnce(ResultCode) -> E) -> Result<(), E>;
+}
+
+impl ResultCodeExt for ResultCode {
+    #[inline]
+    fn is_success(self) -> bool {
+        raw::ResultCode::from_raw(self).is_success()
+    }
+
+    #[inline]
+    fn module(self) -> u32 {
+        module_number(self)
+    }
+
+    #[inline]
+    fn description(self) -> u32 {
+        raw::ResultCode::from_raw(self).description()
+    }
+
+    #[inline]
+    fn ok_or<E>(self, f: impl FnOnce(ResultCode) -> E) -> Result<(), E> {
+        if self.is_success() {
+            Ok(())
+        } else {
+            Err(f(self))
+        }
+    }
+}
+
+/// Returns the module number (bits 0-8) of a raw result code.
+#[inline]
+const fn module_number(rc: ResultCode) -> u32 {
+    rc & raw::MODULE_MASK
+}
+
+// Field decoding used by `ResultCodeExt`, checked at compile time
+const _: () = {
+    // 2002-0263: module FS, description 0x107
+    assert!(module_number(0x20E02) == 2);
+    assert!(raw::ResultCode::from_raw(0x20E02).description() == 0x107);
+    assert!(!raw::ResultCode::from_raw(0x20E02).is_success());
+    assert!(raw::ResultCode::from_raw(0).is_success());
+
+    // All module and description bits set, reserved bits clear
+    assert!(module_number(0x003F_FFFF) == 0x1FF);
+    assert!(raw::ResultCode::from_raw(0x003F_FFFF).description() == 0x1FFF);
+
+    // Reserved bits are ignored
+    assert!(module_number(0xFFC0_0000 | 0x20E02) == 2);
+    assert!(raw::ResultCode::from_raw(0xFFC0_0000 | 0x20E02).description() == 0x107);
+};
+
+/// Returns early with a mapped error if a raw [`ResultCode`] is not a success.
+///
+/// `r_try!(rc, f)` is `rc.ok_or(f)?`: on failure, `f` is called with the raw
+/// result code and its return value is converted into the function's error
+/// type with [`From`].
+#[macro_export]
+macro_rules! r_try {
+    ($rc:expr, $f:expr $(,)?) => {
+        $crate::result::ResultCodeExt::ok_or($rc, $f)?
+    };
+}
+
 /// Raw representation of the result code
 // NOTE: For internal use only
 pub(crate) mod raw {
@@ -150,7 +204,7 @@ pub(crate) mod raw {
     const SUCCESS: u32 = 0;
 
     /// Mask for the module field (9 bits)
-    const MODULE_MASK: u32 = 0x1FF;
+    pub(super) const MODULE_MASK: u32 = 0x1FF;
     /// Mask for the description field (13 bits)
     const DESCRIPTION_MASK: u32 = 0x1FFF;
     /// Shift amount for the description field
@@ -1,9 +1,11 @@
 //! Synchronization primitives
 
+use core::sync::atomic::{AtomicU32, AtomicU64};
+
 use crate::{
     error::{KernelError as KError, ResultCode, ToRawResultCode},
     handle::{Reset, Waitable},
-    raw::{self, Handle},
+    raw::{self, ArbitrationType, Handle, SignalType},
     result::{Error, Result, raw::Result as RawResult},
 };
 
@@ -35,6 +37,14 @@ define_reset_handle_type! {
     pub struct EventHandle
 }
 
+define_handle_type! {
+    /// A handle to the writable side of a kernel event object (KWritableEvent).
+    ///
+    /// Created by [`create_event`] along with the readable [`EventHandle`]. The writable
+    /// side signals and clears the event; it cannot be waited on.
+    pub struct WritableEventHandle
+}
+
 /// Arbitrates a mutex lock operation in userspace
 ///
 /// Attempts to acquire a mutex by arbitrating the lock with the owner thread.
@@ -550,3 +560,392 @@ impl ToRawResultCode for ResetSignalError {
         }
     }
 }
+
+/// Creates a kernel event, returning its writable and readable sides.
+///
+/// The kernel event has two handles:
+/// - The writable side ([`EventWriter`]) signals and clears the event.
+/// - The readable side ([`EventReader`]) is waited on, and can be cleared too.
+///
+/// Each side owns its handle and closes it when dropped. The event is destroyed once both
+/// handles are closed; signaling an event whose reader is gone still succeeds, but nobody
+/// observes it. Events created this way do not clear on their own: a waiter must clear the
+/// event before waiting again.
+///
+/// Ref: <https://switchbrew.org/wiki/SVC#CreateEvent>
+pub fn create_event() -> Result<(EventWriter, EventReader), CreateEventError> {
+    let mut writable = raw::INVALID_HANDLE;
+    let mut readable = raw::INVALID_HANDLE;
+
+    // SAFETY: Both pointers refer to local, writable handles.
+    let rc = unsafe { raw::create_event(&mut writable, &mut readable) };
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::LimitReached == desc => CreateEventError::LimitReached,
+        desc if KError::OutOfResource == desc => CreateEventError::OutOfResource,
+        desc if KError::OutOfHandles == desc => CreateEventError::OutOfHandles,
+        _ => CreateEventError::Unknown(Error::from(rc)),
+    })?;
+
+    Ok((
+        EventWriter(WritableEventHandle(writable)),
+        EventReader(EventHandle(readable)),
+    ))
+}
+
+/// Error type returned by [`create_event`].
+#[derive(Debug, thiserror::Error)]
+pub enum CreateEventError {
+    /// The process resource limit on events was reached.
+    #[error("event limit reached")]
+    LimitReached,
+    /// The kernel could not allocate the event object.
+    #[error("out of resource")]
+    OutOfResource,
+    /// The process handle table is full.
+    #[error("out of handles")]
+    OutOfHandles,
+    /// An unknown error occurred.
+    #[error("unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for CreateEventError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            CreateEventError::LimitReached => KError::LimitReached.to_rc(),
+            CreateEventError::OutOfResource => KError::OutOfResource.to_rc(),
+            CreateEventError::OutOfHandles => KError::OutOfHandles.to_rc(),
+            CreateEventError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Writable side of an event created with [`create_event`].
+///
+/// Closes its handle when dropped.
+#[derive(Debug)]
+pub struct EventWriter(WritableEventHandle);
+
+impl EventWriter {
+    /// Returns the writable event handle.
+    ///
+    /// The handle stays owned by this writer; do not close it.
+    #[inline]
+    pub fn handle(&self) -> WritableEventHandle {
+        self.0
+    }
+
+    /// Signals the event, waking the threads waiting on its readable side.
+    ///
+    /// The event stays signaled until cleared.
+    pub fn signal(&self) -> Result<(), SignalEventError> {
+        // SAFETY: The handle is owned by this writer and stays open until it is dropped.
+        let rc = unsafe { raw::signal_event(self.0.to_raw()) };
+        RawResult::from_raw(rc).map((), |rc| match rc.description() {
+            desc if KError::InvalidHandle == desc => SignalEventError::InvalidHandle,
+            _ => SignalEventError::Unknown(Error::from(rc)),
+        })
+    }
+
+    /// Takes the event out of the signaled state, if it is signaled.
+    pub fn clear(&self) -> Result<(), ClearEventError> {
+        clear_event(self.0.to_raw())
+    }
+
+    /// Releases ownership of the handle without closing it.
+    #[inline]
+    pub fn into_handle(self) -> WritableEventHandle {
+        let handle = self.0;
+        core::mem::forget(self);
+        handle
+    }
+}
+
+impl Drop for EventWriter {
+    fn drop(&mut self) {
+        // SAFETY: The handle is owned by this writer and is closed exactly once.
+        let _ = unsafe { raw::close_handle(self.0.to_raw()) };
+    }
+}
+
+/// Readable side of an event created with [`create_event`].
+///
+/// Closes its handle when dropped.
+#[derive(Debug)]
+pub struct EventReader(EventHandle);
+
+impl EventReader {
+    /// Returns the readable event handle, e.g. to wait on it together with other handles.
+    ///
+    /// The handle stays owned by this reader; do not close it.
+    #[inline]
+    pub fn handle(&self) -> EventHandle {
+        self.0
+    }
+
+    /// Waits up to `timeout_ns` nanoseconds for the event to be signaled.
+    ///
+    /// Use `u64::MAX` for an infinite wait, `0` for an immediate check. The event is left
+    /// signaled; [`clear`](Self::clear) it before waiting again.
+    pub fn wait(&self, timeout_ns: u64) -> Result<(), WaitSyncError> {
+        // SAFETY: The handle is owned by this reader and stays open until it is dropped.
+        unsafe { wait_synchronization_single(&self.0, timeout_ns) }
+    }
+
+    /// Takes the event out of the signaled state, if it is signaled.
+    pub fn clear(&self) -> Result<(), ClearEventError> {
+        clear_event(self.0.to_raw())
+    }
+
+    /// Releases ownership of the handle without closing it.
+    #[inline]
+    pub fn into_handle(self) -> EventHandle {
+        let handle = self.0;
+        core::mem::forget(self);
+        handle
+    }
+}
+
+impl Drop for EventReader {
+    fn drop(&mut self) {
+        // SAFETY: The handle is owned by this reader and is closed exactly once.
+        let _ = unsafe { raw::close_handle(self.0.to_raw()) };
+    }
+}
+
+/// Clears either side of an event.
+fn clear_event(handle: Handle) -> Result<(), ClearEventError> {
+    // SAFETY: The callers pass a handle they own, which stays open during the call.
+    let rc = unsafe { raw::clear_event(handle) };
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::InvalidHandle == desc => ClearEventError::InvalidHandle,
+        _ => ClearEventError::Unknown(Error::from(rc)),
+    })
+}
+
+/// Error type returned by [`EventWriter::signal`].
+#[derive(Debug, thiserror::Error)]
+pub enum SignalEventError {
+    /// The handle does not refer to a writable event.
+    #[error("invalid handle")]
+    InvalidHandle,
+    /// An unknown error occurred.
+    #[error("unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for SignalEventError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            SignalEventError::InvalidHandle => KError::InvalidHandle.to_rc(),
+            SignalEventError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Error type returned by [`EventWriter::clear`] and [`EventReader::clear`].
+#[derive(Debug, thiserror::Error)]
+pub enum ClearEventError {
+    /// The handle does not refer to an event.
+    #[error("invalid handle")]
+    InvalidHandle,
+    /// An unknown error occurred.
+    #[error("unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for ClearEventError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            ClearEventError::InvalidHandle => KError::InvalidHandle.to_rc(),
+            ClearEventError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Waits on a 32-bit address until it is signaled or a timeout expires. [4.0.0+]
+///
+/// Depending on `arb_type`, the kernel compares the value at `addr` with `value` and only puts
+/// the thread to sleep if the condition holds:
+///
+/// | Type | Waits if |
+/// | --- | --- |
+/// | [`WaitIfLessThan`](ArbitrationType::WaitIfLessThan) | `*addr < value` |
+/// | [`DecrementAndWaitIfLessThan`](ArbitrationType::DecrementAndWaitIfLessThan) | `*addr < value` (decrementing `*addr` if so) |
+/// | [`WaitIfEqual`](ArbitrationType::WaitIfEqual) | `*addr == value` |
+///
+/// If the condition does not hold, the call returns [`WaitForAddressError::ValueMismatch`]
+/// immediately. Otherwise the thread sleeps until woken with [`signal_to_address`] or until
+/// `timeout_ns` expires (negative for an infinite wait, `0` for an immediate check).
+///
+/// [`ArbitrationType::WaitIfEqual64`] compares 64 bits and is rejected here with
+/// [`WaitForAddressError::InvalidEnumValue`]; use [`wait_for_address_64`] instead.
+///
+/// This SVC is not available before 4.0.0: check the syscall hints before calling it.
+///
+/// Ref: <https://switchbrew.org/wiki/SVC#WaitForAddress>
+pub fn wait_for_address(
+    addr: &AtomicU32,
+    arb_type: ArbitrationType,
+    value: i64,
+    timeout_ns: i64,
+) -> Result<(), WaitForAddressError> {
+    if matches!(arb_type, ArbitrationType::WaitIfEqual64) {
+        return Err(WaitForAddressError::InvalidEnumValue);
+    }
+
+    // SAFETY: The reference guarantees a valid, aligned 32-bit value for the whole call.
+    let rc = unsafe { raw::wait_for_address(addr.as_ptr().cast(), arb_type, value, timeout_ns) };
+    wait_for_address_result(rc)
+}
+
+/// Waits on a 64-bit address while it equals `value`. [19.0.0+]
+///
+/// This is [`wait_for_address`] with [`ArbitrationType::WaitIfEqual64`]: the thread sleeps if
+/// `*addr == value`, until woken with [`signal_to_address`] (which signals by address, whatever
+/// the width of the value) or until `timeout_ns` expires.
+///
+/// Firmware before 19.0.0 rejects the arbitration type with
+/// [`WaitForAddressError::InvalidEnumValue`].
+///
+/// Ref: <https://switchbrew.org/wiki/SVC#WaitForAddress>
+pub fn wait_for_address_64(
+    addr: &AtomicU64,
+    value: i64,
+    timeout_ns: i64,
+) -> Result<(), WaitForAddressError> {
+    // SAFETY: The reference guarantees a valid, aligned 64-bit value for the whole call.
+    let rc = unsafe {
+        raw::wait_for_address(
+            addr.as_ptr().cast(),
+            ArbitrationType::WaitIfEqual64,
+            value,
+            timeout_ns,
+        )
+    };
+    wait_for_address_result(rc)
+}
+
+/// Translates the result code of `svcWaitForAddress`.
+fn wait_for_address_result(rc: ResultCode) -> Result<(), WaitForAddressError> {
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::InvalidAddress == desc => WaitForAddressError::InvalidAddress,
+        desc if KError::InvalidCurrentMemory == desc => WaitForAddressError::InvalidMemState,
+        desc if KError::InvalidEnumValue == desc => WaitForAddressError::InvalidEnumValue,
+        desc if KError::InvalidState == desc => WaitForAddressError::ValueMismatch,
+        desc if KError::TimedOut == desc => WaitForAddressError::TimedOut,
+        desc if KError::TerminationRequested == desc => WaitForAddressError::TerminationRequested,
+        _ => WaitForAddressError::Unknown(Error::from(rc)),
+    })
+}
+
+/// Error type for [`wait_for_address`] and [`wait_for_address_64`]
+#[derive(Debug, thiserror::Error)]
+pub enum WaitForAddressError {
+    /// The address is not suitably aligned or not in user memory.
+    #[error("Invalid address")]
+    InvalidAddress,
+    /// The memory at the address cannot be accessed.
+    #[error("Invalid memory state")]
+    InvalidMemState,
+    /// The arbitration type is not supported.
+    #[error("Invalid arbitration type")]
+    InvalidEnumValue,
+    /// The value did not satisfy the wait condition; the thread did not sleep.
+    #[error("Value mismatch")]
+    ValueMismatch,
+    /// The wait operation timed out.
+    #[error("Operation timed out")]
+    TimedOut,
+    /// Thread termination was requested while waiting.
+    #[error("Termination requested")]
+    TerminationRequested,
+    /// An unknown error occurred.
+    ///
+    /// This variant is used when the error code is not recognized.
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for WaitForAddressError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            WaitForAddressError::InvalidAddress => KError::InvalidAddress.to_rc(),
+            WaitForAddressError::InvalidMemState => KError::InvalidCurrentMemory.to_rc(),
+            WaitForAddressError::InvalidEnumValue => KError::InvalidEnumValue.to_rc(),
+            WaitForAddressError::ValueMismatch => KError::InvalidState.to_rc(),
+            WaitForAddressError::TimedOut => KError::TimedOut.to_rc(),
+            WaitForAddressError::TerminationRequested => KError::TerminationRequested.to_rc(),
+            WaitForAddressError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Signals threads waiting on an address, optionally updating its value. [4.0.0+]
+///
+/// Wakes up to `count` threads waiting on `addr` with [`wait_for_address`] or
+/// [`wait_for_address_64`] (all of them if `count` is less than or equal to 0). Depending on
+/// `signal_type`, the value at `addr` is updated first:
+///
+/// | Type | Effect |
+/// | --- | --- |
+/// | [`Signal`](SignalType::Signal) | Only signals |
+/// | [`SignalAndIncrementIfEqual`](SignalType::SignalAndIncrementIfEqual) | Increments `*addr` if it equals `value` |
+/// | [`SignalAndModifyBasedOnWaitingThreadCountIfEqual`](SignalType::SignalAndModifyBasedOnWaitingThreadCountIfEqual) | Updates `*addr` based on the number of waiters if it equals `value` |
+///
+/// For the conditional types, a value other than `value` makes the call fail with
+/// [`SignalToAddressError::ValueMismatch`] without waking anyone.
+///
+/// This SVC is not available before 4.0.0: check the syscall hints before calling it.
+///
+/// Ref: <https://switchbrew.org/wiki/SVC#SignalToAddress>
+pub fn signal_to_address(
+    addr: &AtomicU32,
+    signal_type: SignalType,
+    value: i32,
+    count: i32,
+) -> Result<(), SignalToAddressError> {
+    // SAFETY: The reference guarantees a valid, aligned 32-bit value for the whole call.
+    let rc = unsafe { raw::signal_to_address(addr.as_ptr().cast(), signal_type, value, count) };
+    RawResult::from_raw(rc).map((), |rc| match rc.description() {
+        desc if KError::InvalidAddress == desc => SignalToAddressError::InvalidAddress,
+        desc if KError::InvalidCurrentMemory == desc => SignalToAddressError::InvalidMemState,
+        desc if KError::InvalidEnumValue == desc => SignalToAddressError::InvalidEnumValue,
+        desc if KError::InvalidState == desc => SignalToAddressError::ValueMismatch,
+        _ => SignalToAddressError::Unknown(Error::from(rc)),
+    })
+}
+
+/// Error type for [`signal_to_address`]
+#[derive(Debug, thiserror::Error)]
+pub enum SignalToAddressError {
+    /// The address is not suitably aligned or not in user memory.
+    #[error("Invalid address")]
+    InvalidAddress,
+    /// The memory at the address cannot be accessed.
+    #[error("Invalid memory state")]
+    InvalidMemState,
+    /// The signal type is not supported.
+    #[error("Invalid signal type")]
+    InvalidEnumValue,
+    /// The value did not match `value`; nothing was updated or signaled.
+    #[error("Value mismatch")]
+    ValueMismatch,
+    /// An unknown error occurred.
+    ///
+    /// This variant is used when the error code is not recognized.
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for SignalToAddressError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            SignalToAddressError::InvalidAddress => KError::InvalidAddress.to_rc(),
+            SignalToAddressError::InvalidMemState => KError::InvalidCurrentMemory.to_rc(),
+            SignalToAddressError::InvalidEnumValue => KError::InvalidEnumValue.to_rc(),
+            SignalToAddressError::ValueMismatch => KError::InvalidState.to_rc(),
+            SignalToAddressError::Unknown(err) => err.to_raw(),
+        }
+    }
+}
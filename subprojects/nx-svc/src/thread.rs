@@ -5,8 +5,13 @@
 //! its underlying system call while translating raw [`ResultCode`] values into
 //! strongly typed Rust error enums.
 
-use core::ffi::c_void;
+use core::{
+    ffi::c_void,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
 
+pub use crate::raw::{CpuRegister, FpuRegister, ThreadContext};
 use crate::{
     error::{KernelError as KError, ToRawResultCode},
     raw,
@@ -225,10 +230,38 @@ impl ToRawResultCode for ResumeThreadError {
 ///
 /// Internally this issues the `svcExitThread` syscall. The kernel will perform
 /// final housekeeping, dispose of TLS, and pick another thread to schedule.
+///
+/// The [exit hook](set_exit_hook) does not run.
 pub fn exit() -> ! {
     unsafe { raw::exit_thread() }
 }
 
+/// Exit hook, stored as a type-erased `fn()` (null = unset).
+static EXIT_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets a hook run on every thread exiting through the C `svcExitThread`.
+///
+/// This is the last step of libnx's `threadExit`, so the hook sees every
+/// thread created with libnx's `threadCreate`. It runs on the exiting thread,
+/// right before the syscall, while its TLS is still valid. [`exit`] does not
+/// run the hook.
+pub fn set_exit_hook(hook: fn()) {
+    EXIT_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Runs the exit hook, if set.
+#[cfg(feature = "ffi")]
+pub(crate) fn run_exit_hook() {
+    let hook = EXIT_HOOK.load(Ordering::Acquire);
+    if hook.is_null() {
+        return;
+    }
+
+    // SAFETY: Non-null values are only ever stored by `set_exit_hook` from a `fn()`.
+    let hook = unsafe { core::mem::transmute::<*mut (), fn()>(hook) };
+    hook();
+}
+
 /// Closes (dereferences) a thread handle without affecting the thread's
 /// execution.
 ///
@@ -265,28 +298,93 @@ impl ToRawResultCode for CloseHandleError {
     }
 }
 
-/// Dumps the CPU context of a *paused* thread into `ctx`.
+/// Dumps the CPU context of a *paused* thread.
 ///
-/// The target thread must have been paused beforehand (see [`pause`]) to ensure
-/// a consistent snapshot.
-pub fn get_context3(thread: Handle) -> Result<raw::ThreadContext, GetContext3Error> {
-    let mut ctx = raw::ThreadContext::zeroed();
+/// Under the hood this invokes [`raw::get_thread_context3`]. The target thread
+/// must belong to the current process and must have been paused beforehand
+/// (see [`pause`], i.e. `svcSetThreadActivity(Paused)`) so the snapshot is
+/// consistent; otherwise the kernel rejects the request with
+/// [`GetContextError::InvalidState`].
+///
+/// Use the accessors on [`ThreadContext`] (e.g. [`ThreadContext::gpr`],
+/// [`ThreadContext::pc`]) to read the register unions.
+pub fn get_context(thread: Handle) -> Result<ThreadContext, GetContextError> {
+    let mut ctx = ThreadContext::zeroed();
     let rc = unsafe { raw::get_thread_context3(&mut ctx, thread.0) };
     RawResult::from_raw(rc).map(ctx, |rc| match rc.description() {
-        desc if KError::InvalidHandle == desc => GetContext3Error::InvalidHandle,
-        _ => GetContext3Error::Unknown(rc.into()),
+        desc if KError::InvalidHandle == desc => GetContextError::InvalidHandle,
+        desc if KError::Busy == desc => GetContextError::Busy,
+        desc if KError::InvalidState == desc => GetContextError::InvalidState,
+        _ => GetContextError::Unknown(rc.into()),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetContextError {
+    /// The supplied handle is not a valid thread handle of the current process —
+    /// `KernelError::InvalidHandle` (raw code `0xE401`).
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// The target is the calling thread — `KernelError::Busy` (raw code `0xF401`).
+    #[error("Busy")]
+    Busy,
+    /// The target thread is not paused — `KernelError::InvalidState`
+    /// (raw code `0xFA01`).
+    #[error("Invalid state")]
+    InvalidState,
+    /// Any unforeseen kernel error. Contains the original [`Error`] so callers
+    /// can inspect the raw result (`Error::to_raw`).
+    #[error("Unknown error: {0}")]
+    Unknown(Error),
+}
+
+impl ToRawResultCode for GetContextError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::InvalidHandle => KError::InvalidHandle.to_rc(),
+            Self::Busy => KError::Busy.to_rc(),
+            Self::InvalidState => KError::InvalidState.to_rc(),
+            Self::Unknown(err) => err.to_raw(),
+        }
+    }
+}
+
+/// Returns the thread ID of the thread referred to by `handle`.
+///
+/// Accepts the [`Handle::current_thread`] pseudo-handle. The thread ID is
+/// assigned by the kernel when the thread is created and stays the same for
+/// the thread's whole lifetime. Unlike the handle, which is only an index in
+/// the calling process's handle table, it identifies the thread system-wide.
+pub fn get_thread_id(handle: Handle) -> Result<u64, GetThreadIdError> {
+    let mut thread_id = 0;
+    let rc = unsafe { raw::get_thread_id(&mut thread_id, handle.0) };
+    RawResult::from_raw(rc).map(thread_id, |rc| match rc.description() {
+        desc if KError::InvalidHandle == desc => GetThreadIdError::InvalidHandle,
+        _ => GetThreadIdError::Unknown(rc.into()),
     })
 }
 
+/// Returns the thread ID of the calling thread.
+///
+/// See [`get_thread_id`].
+pub fn current_thread_id() -> Result<u64, GetThreadIdError> {
+    get_thread_id(Handle::current_thread())
+}
+
+/// Error type for [`get_thread_id`].
 #[derive(Debug, thiserror::Error)]
-pub enum GetContext3Error {
+pub enum GetThreadIdError {
+    /// The supplied handle is not a valid thread handle —
+    /// `KernelError::InvalidHandle` (raw code `0xE401`).
     #[error("Invalid handle")]
     InvalidHandle,
+    /// Any unforeseen kernel error. Contains the original [`Error`] so callers
+    /// can inspect the raw result (`Error::to_raw`).
     #[error("Unknown error: {0}")]
     Unknown(Error),
 }
 
-impl ToRawResultCode for GetContext3Error {
+impl ToRawResultCode for GetThreadIdError {
     fn to_rc(self) -> ResultCode {
         match self {
             Self::InvalidHandle => KError::InvalidHandle.to_rc(),
@@ -295,6 +393,67 @@ impl ToRawResultCode for GetContext3Error {
     }
 }
 
+/// Pauses a thread, passes its CPU context to `f`, then resumes it.
+///
+/// This pairs [`pause`] and [`resume`] around [`get_context`]: the thread is
+/// resumed whether reading the context fails or `f` unwinds, so it is never
+/// left paused by accident. Returns the value produced by `f`.
+///
+/// Fails with [`InspectError::Pause`] without calling `f` if the thread could
+/// not be paused, and with [`InspectError::Resume`] if it could not be
+/// resumed afterwards, in which case the result of `f` is dropped. The kernel
+/// refuses to pause the calling thread with `KernelError::Busy`, so passing
+/// it fails with [`InspectError::Pause`].
+pub fn inspect_thread<R>(
+    thread: Handle,
+    f: impl FnOnce(&ThreadContext) -> R,
+) -> Result<R, InspectError> {
+    pause(thread).map_err(InspectError::Pause)?;
+
+    // Resumes the thread if reading its context fails or `f` unwinds
+    struct ResumeGuard(Handle);
+
+    impl Drop for ResumeGuard {
+        fn drop(&mut self) {
+            let _ = resume(self.0);
+        }
+    }
+
+    let guard = ResumeGuard(thread);
+    let ctx = get_context(thread).map_err(InspectError::GetContext)?;
+    let value = f(&ctx);
+
+    // Resume explicitly to report failures
+    core::mem::forget(guard);
+    resume(thread).map_err(InspectError::Resume)?;
+
+    Ok(value)
+}
+
+/// Error returned by [`inspect_thread`].
+#[derive(Debug, thiserror::Error)]
+pub enum InspectError {
+    /// Failed to pause the thread.
+    #[error("Failed to pause thread")]
+    Pause(#[source] PauseThreadError),
+    /// Failed to read the context of the paused thread.
+    #[error("Failed to get thread context")]
+    GetContext(#[source] GetContextError),
+    /// Failed to resume the thread.
+    #[error("Failed to resume thread")]
+    Resume(#[source] ResumeThreadError),
+}
+
+impl ToRawResultCode for InspectError {
+    fn to_rc(self) -> ResultCode {
+        match self {
+            Self::Pause(err) => err.to_rc(),
+            Self::GetContext(err) => err.to_rc(),
+            Self::Resume(err) => err.to_rc(),
+        }
+    }
+}
+
 /// Suspends the current thread for *at least* the specified number of
 /// nanoseconds.
 ///
@@ -306,6 +465,38 @@ pub fn sleep(nanos: u64) {
     unsafe { raw::sleep_thread(nanos) }
 }
 
+/// Suspends the current thread until the system tick counter reaches
+/// `deadline_ticks`.
+///
+/// The remaining time is computed from [`get_system_tick`](crate::misc::get_system_tick)
+/// and slept with [`sleep`]; if the thread wakes up before the deadline, it
+/// sleeps again for what is left. Returns immediately if the deadline has
+/// already passed.
+///
+/// Sleeping against an absolute deadline keeps a loop on a fixed cadence: a
+/// late wakeup shortens the next sleep instead of accumulating drift. Advance
+/// the deadline by a fixed period, converted with
+/// [`nanos_to_ticks`](crate::misc::nanos_to_ticks), on every iteration.
+///
+/// The deadline is a lower bound. The thread becomes runnable once the
+/// kernel's timer fires, and then runs when the scheduler picks it; expect
+/// wakeups from tens of microseconds to a few milliseconds late, depending
+/// on priority and the load on the core.
+pub fn sleep_until(deadline_ticks: u64) {
+    loop {
+        let now = crate::misc::get_system_tick();
+        if now >= deadline_ticks {
+            return;
+        }
+
+        // Round up so the last sleep does not wake up a few nanoseconds early,
+        // and clamp to `i64::MAX`, since larger values read as negative yields
+        let nanos = (deadline_ticks - now) as u128 * 1_000_000_000;
+        let nanos = nanos.div_ceil(crate::misc::SYSTEM_TICK_FREQ as u128);
+        sleep(nanos.min(i64::MAX as u128) as u64);
+    }
+}
+
 /// Yields execution to a different thread that is scheduled on the *same* CPU
 /// core.
 ///
@@ -346,8 +537,13 @@ pub fn yield_to_any_thread() {
 
 /// Gets the current processor/CPU core number.
 ///
-/// Returns the ID of the CPU core that the current thread is running on.
-/// The returned value is in the range 0..3 for the Switch's quad-core processor.
+/// Returns the ID of the CPU core that the current thread is running on, in
+/// the range `0..=3` for the Switch's quad-core processor. The syscall reports
+/// the core id directly, not a result code, so there is no error case.
+///
+/// The value is a snapshot: unless the thread's affinity mask pins it to a
+/// single core (see [`set_core_mask`]), the scheduler may migrate it,
+/// and two consecutive calls can return different cores.
 ///
 /// This is a safe wrapper around [`raw::get_current_processor_number`].
 pub fn get_current_processor_number() -> u32 {
@@ -0,0 +1,49 @@
+/**
+ * @file nx_bump.h
+ * @brief Bump allocator exposed by the nx-sys-mem crate.
+ * @remark The backing buffer is provided, and freed, by the caller.
+ */
+#pragma once
+
+#include <stddef.h>
+
+/// Opaque bump allocator.
+typedef struct NxBump NxBump;
+
+/**
+ * @brief Creates a bump allocator over a caller-provided buffer.
+ * @warning @p buf must not be used other than through the allocator until it is destroyed.
+ * @param buf Backing buffer.
+ * @param size Size of the backing buffer in bytes.
+ * @return The allocator, or NULL on failure.
+ */
+NxBump* __nx_sys_mem__bump_create(void* buf, size_t size);
+
+/**
+ * @brief Destroys a bump allocator. The backing buffer is not freed.
+ * @param bump Allocator to destroy (may be NULL).
+ */
+void __nx_sys_mem__bump_destroy(NxBump* bump);
+
+/**
+ * @brief Allocates a block from the arena.
+ * @param bump Allocator to allocate from.
+ * @param size Size of the block in bytes.
+ * @param align Alignment of the block, a power of two.
+ * @return The block, or NULL if the arena is exhausted.
+ */
+void* __nx_sys_mem__bump_alloc(NxBump* bump, size_t size, size_t align);
+
+/**
+ * @brief Releases every allocation at once.
+ * @warning No block handed out by @p bump may be used afterwards.
+ * @param bump Allocator to reset.
+ */
+void __nx_sys_mem__bump_reset(NxBump* bump);
+
+/**
+ * @brief Gets the number of bytes handed out so far, including alignment padding.
+ * @param bump Allocator to inspect.
+ * @return Bytes used.
+ */
+size_t __nx_sys_mem__bump_used(NxBump* bump);
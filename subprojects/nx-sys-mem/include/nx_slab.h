@@ -0,0 +1,63 @@
+/**
+ * @file nx_slab.h
+ * @brief Fixed-size block allocator exposed by the nx-sys-mem crate.
+ * @remark Only slabs of 256-byte blocks are exposed to C.
+ */
+#pragma once
+
+#include <stdint.h>
+#include <stddef.h>
+
+/// Opaque slab of 256-byte blocks.
+typedef struct NxSlab256 NxSlab256;
+
+/// Slab utilization.
+typedef struct {
+    size_t block_size; ///< Size of each block in bytes.
+    size_t chunks;     ///< Number of chunks taken from the global allocator.
+    size_t capacity;   ///< Total number of blocks in all chunks.
+    size_t in_use;     ///< Number of blocks currently handed out.
+} NxSlabStats;
+
+/**
+ * @brief Creates an empty slab of 256-byte blocks.
+ * @note No chunk is allocated until the first allocation or reservation.
+ * @return The slab, or NULL on failure.
+ */
+NxSlab256* __nx_sys_mem__slab256_create(void);
+
+/**
+ * @brief Destroys a slab, returning all its chunks to the global allocator.
+ * @warning Every block of the slab must have been freed.
+ * @param slab Slab to destroy (may be NULL).
+ */
+void __nx_sys_mem__slab256_destroy(NxSlab256* slab);
+
+/**
+ * @brief Grows a slab until at least @p blocks blocks are free.
+ * @param slab Slab to grow.
+ * @param blocks Number of free blocks to reserve.
+ * @return Result code.
+ */
+uint32_t __nx_sys_mem__slab256_reserve(NxSlab256* slab, size_t blocks);
+
+/**
+ * @brief Allocates a 256-byte block, aligned to 16 bytes.
+ * @param slab Slab to allocate from.
+ * @return The block, or NULL on failure.
+ */
+void* __nx_sys_mem__slab256_alloc(NxSlab256* slab);
+
+/**
+ * @brief Returns a block to its slab.
+ * @param slab Slab the block was allocated from.
+ * @param block Block to free (may be NULL).
+ */
+void __nx_sys_mem__slab256_free(NxSlab256* slab, void* block);
+
+/**
+ * @brief Gets the current utilization of a slab.
+ * @param slab Slab to inspect.
+ * @param[out] out Slab utilization.
+ */
+void __nx_sys_mem__slab256_stats(NxSlab256* slab, NxSlabStats* out);
@@ -10,6 +10,8 @@
 //! - [`Buf`] trait: A common interface for memory buffer implementations
 //! - [`Buffer`]: An owned buffer with custom layout support
 //! - [`BufferRef`]: A non-owning reference to externally managed memory
+//! - [`AlignedBuf`]: An owned, zeroed buffer with a large guaranteed alignment,
+//!   for GPU and DMA memory (see [`alloc_aligned`])
 
 use alloc::alloc::{Layout, alloc_zeroed, dealloc};
 use core::{ffi::c_void, marker::PhantomData, mem::align_of, ptr::NonNull};
@@ -207,6 +209,106 @@ pub enum BufWithCapacityError {
     AllocationFailed,
 }
 
+/// Allocates a zeroed buffer of `size` bytes aligned to `align`.
+///
+/// Intended for GPU and DMA memory, which often needs page (0x1000) or larger
+/// (e.g. 0x20000) alignment. The memory comes from the global allocator, which
+/// honours any power-of-two alignment by returning the padding in front of the
+/// buffer to the heap, so no over-allocation is needed. The size is not
+/// rounded; pass a multiple of the page size for memory handed to nvmap.
+pub fn alloc_aligned(size: usize, align: usize) -> Result<AlignedBuf, AllocAlignedError> {
+    if !align.is_power_of_two() {
+        return Err(AllocAlignedError::InvalidAlignment(align));
+    }
+    if size == 0 {
+        return Err(AllocAlignedError::ZeroSize);
+    }
+
+    let layout =
+        Layout::from_size_align(size, align).map_err(|_| AllocAlignedError::InvalidLayout)?;
+    let buffer =
+        Buffer::try_with_layout(layout).map_err(|_| AllocAlignedError::AllocationFailed)?;
+
+    Ok(AlignedBuf(buffer))
+}
+
+/// Owned, zeroed buffer with a guaranteed alignment.
+///
+/// Created by [`alloc_aligned`]. The memory is freed when the buffer is
+/// dropped, so it must outlive any GPU or DMA mapping of it.
+#[derive(Debug)]
+pub struct AlignedBuf(Buffer);
+
+impl AlignedBuf {
+    /// Returns a raw pointer to the start of the buffer.
+    ///
+    /// The pointer is aligned to [`Buf::align`], and suitable for passing to
+    /// nvmap as the backing address.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.0.ptr.as_ptr().cast()
+    }
+
+    /// Returns the size of the buffer in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.layout.size()
+    }
+
+    /// Returns `true` if the buffer is empty, which never happens.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the buffer contents.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: The buffer owns `len` initialized (zeroed) bytes.
+        unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Returns the buffer contents mutably.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: The buffer owns `len` initialized (zeroed) bytes, and `&mut self`
+        // guarantees exclusive access.
+        unsafe { core::slice::from_raw_parts_mut(self.as_ptr(), self.len()) }
+    }
+}
+
+impl Buf for AlignedBuf {
+    fn ptr(&self) -> NonNull<c_void> {
+        self.0.ptr
+    }
+
+    fn layout(&self) -> Layout {
+        self.0.layout
+    }
+}
+
+/// Errors that can occur in [`alloc_aligned`].
+#[derive(Debug, thiserror::Error)]
+pub enum AllocAlignedError {
+    /// The alignment is not a power of two.
+    #[error("Alignment is not a power of two: {0:#x}")]
+    InvalidAlignment(usize),
+
+    /// The size is zero.
+    #[error("Size is zero")]
+    ZeroSize,
+
+    /// The size, rounded up to the alignment, overflows.
+    #[error("Invalid layout parameters")]
+    InvalidLayout,
+
+    /// Memory allocation failed.
+    ///
+    /// The system allocator was unable to allocate the requested memory.
+    #[error("Memory allocation failed")]
+    AllocationFailed,
+}
+
 /// A non-owning reference to a memory buffer.
 ///
 /// `BufferRef` wraps a pointer to memory that is managed externally. It does not
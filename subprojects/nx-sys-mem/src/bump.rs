@@ -0,0 +1,124 @@
+//! Bump allocator for early-boot scratch memory.
+//!
+//! The global allocator is not usable until the heap has been set up late in
+//! startup. [`BumpAllocator`] hands out memory from a caller-provided static
+//! byte array instead, without issuing syscalls or touching the heap, so that
+//! environment and TLS setup code can use temporary parsing buffers.
+//!
+//! Individual allocations are never freed; the whole arena is reclaimed at
+//! once with [`BumpAllocator::reset`].
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Arena allocator over a fixed static byte array.
+///
+/// Allocations bump an offset into the backing array and are only released
+/// in bulk by [`reset`](Self::reset). Once the array is exhausted, further
+/// allocations fail with [`AllocError`].
+///
+/// # Thread safety
+///
+/// The allocator is `Sync` so it can be placed in a `static`, but it is
+/// intended for early startup, when only the main thread is running. The
+/// offset is updated atomically, so concurrent allocations do not overlap;
+/// no other synchronization is provided.
+pub struct BumpAllocator {
+    /// Start of the backing array.
+    base: NonNull<u8>,
+    /// Size of the backing array in bytes.
+    size: usize,
+    /// Offset of the first free byte.
+    offset: AtomicUsize,
+}
+
+// SAFETY: The backing array is exclusively owned by the allocator, and the
+// bump offset is only updated atomically. See the type-level docs for the
+// single-threaded usage assumption.
+unsafe impl Send for BumpAllocator {}
+// SAFETY: See above.
+unsafe impl Sync for BumpAllocator {}
+
+impl BumpAllocator {
+    /// Creates an allocator that hands out memory from `buf`.
+    pub fn new(buf: &'static mut [u8]) -> Self {
+        Self {
+            size: buf.len(),
+            base: NonNull::from(buf).cast(),
+            offset: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the size of the backing array in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of bytes handed out so far, including alignment
+    /// padding.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes still available, ignoring alignment.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.size - self.used()
+    }
+
+    /// Releases every allocation at once.
+    ///
+    /// Taking `&mut self` guarantees no allocation made through a shared
+    /// reference to this allocator is still alive.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self.offset.get_mut() = 0;
+    }
+}
+
+unsafe impl Allocator for BumpAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.base.as_ptr() as usize;
+
+        let mut current = self.offset.load(Ordering::Relaxed);
+        loop {
+            // Align the absolute address, not the offset, since the backing
+            // array may have any alignment.
+            let start = (base + current)
+                .checked_next_multiple_of(layout.align())
+                .ok_or(AllocError)?
+                - base;
+            let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+            if end > self.size {
+                return Err(AllocError);
+            }
+
+            match self.offset.compare_exchange_weak(
+                current,
+                end,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    // SAFETY: `start + layout.size() <= self.size`, so the
+                    // range lies within the backing array.
+                    let ptr = unsafe { self.base.add(start) };
+                    return Ok(NonNull::slice_from_raw_parts(ptr, layout.size()));
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        // Individual allocations are never freed; see `reset`.
+    }
+}
@@ -0,0 +1,94 @@
+//! C FFI bindings for the bump allocator
+//!
+//! The allocator is boxed and opaque to C. The signatures align with the
+//! declarations in `nx_bump.h`.
+
+use alloc::boxed::Box;
+use core::{
+    alloc::{Allocator, Layout},
+    ffi::c_void,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use super::BumpAllocator;
+
+/// Creates a bump allocator over `size` bytes at `buf`.
+///
+/// Returns null if `buf` is null or the allocator itself cannot be
+/// allocated.
+///
+/// # Safety
+///
+/// `buf` must be valid for reads and writes of `size` bytes, and must not be
+/// accessed other than through the allocator until it is destroyed.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__bump_create(buf: *mut c_void, size: usize) -> *mut c_void {
+    if buf.is_null() {
+        return ptr::null_mut();
+    }
+
+    // SAFETY: The caller guarantees `buf` is valid and exclusively owned by
+    // the allocator for as long as it lives.
+    let buf = unsafe { slice::from_raw_parts_mut(buf.cast::<u8>(), size) };
+    Box::try_new(BumpAllocator::new(buf)).map_or(ptr::null_mut(), |bump| Box::into_raw(bump).cast())
+}
+
+/// Destroys a bump allocator. The backing buffer is not freed.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__bump_destroy(bump: *mut c_void) {
+    if bump.is_null() {
+        return;
+    }
+
+    // SAFETY: The caller guarantees `bump` was returned by `bump_create`.
+    drop(unsafe { Box::from_raw(bump.cast::<BumpAllocator>()) });
+}
+
+/// Allocates `size` bytes aligned to `align`.
+///
+/// Returns null if the arena is exhausted or `align` is not a power of two.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__bump_alloc(
+    bump: *mut c_void,
+    size: usize,
+    align: usize,
+) -> *mut c_void {
+    let (Some(bump), Ok(layout)) = (
+        NonNull::new(bump.cast::<BumpAllocator>()),
+        Layout::from_size_align(size, align),
+    ) else {
+        return ptr::null_mut();
+    };
+
+    // SAFETY: The caller guarantees `bump` was returned by `bump_create`.
+    unsafe { bump.as_ref() }
+        .allocate(layout)
+        .map_or(ptr::null_mut(), |block| block.as_ptr().cast())
+}
+
+/// Releases every allocation at once.
+///
+/// No block handed out by `bump` may be used afterwards.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__bump_reset(bump: *mut c_void) {
+    let Some(mut bump) = NonNull::new(bump.cast::<BumpAllocator>()) else {
+        return;
+    };
+
+    // SAFETY: The caller guarantees `bump` was returned by `bump_create` and
+    // that no block is still in use.
+    unsafe { bump.as_mut() }.reset();
+}
+
+/// Returns the number of bytes handed out so far, including alignment
+/// padding.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__bump_used(bump: *mut c_void) -> usize {
+    let Some(bump) = NonNull::new(bump.cast::<BumpAllocator>()) else {
+        return 0;
+    };
+
+    // SAFETY: The caller guarantees `bump` was returned by `bump_create`.
+    unsafe { bump.as_ref() }.used()
+}
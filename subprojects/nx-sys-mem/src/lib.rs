@@ -1,4 +1,5 @@
 #![no_std]
+#![feature(allocator_api)]
 
 extern crate nx_panic_handler as _; // provides #[panic_handler]
 
@@ -8,7 +9,9 @@ extern crate nx_alloc;
 
 pub mod alignment;
 pub mod buf;
+pub mod bump;
 pub mod shmem;
+pub mod slab;
 pub mod stack;
 pub mod tmem;
 pub mod vmm;
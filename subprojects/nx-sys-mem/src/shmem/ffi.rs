@@ -118,8 +118,8 @@ unsafe extern "C" fn __nx_sys_mem__shmem_map(s: *mut SharedMemory) -> u32 {
             0
         }
         Err(err) => match err {
-            sys::MapError::VirtAddressAllocFailed => LIBNX_ERR_OUT_OF_MEMORY,
-            sys::MapError::Svc(svc_err) => svc_err.to_rc(),
+            sys::MapError::VirtAddressAllocFailed { .. } => LIBNX_ERR_OUT_OF_MEMORY,
+            sys::MapError::Svc { reason, .. } => reason.to_rc(),
         },
     }
 }
@@ -125,11 +125,15 @@ pub unsafe fn map(shm: SharedMemory<Unmapped>) -> Result<SharedMemory<Mapped>, M
 
     // Ask the VMM for a free slice of ASLR address-space.
     let Some(addr) = vmm::lock().find_aslr(size, GUARD_SIZE) else {
-        return Err(MapError::VirtAddressAllocFailed);
+        return Err(MapError::VirtAddressAllocFailed { size });
     };
 
     // Attempt to map the shared memory into that slice.
-    svc::map_shared_memory(handle, addr, size, perm).map_err(MapError::Svc)?;
+    svc::map_shared_memory(handle, addr, size, perm).map_err(|reason| MapError::Svc {
+        addr: addr.as_ptr() as usize,
+        size,
+        reason,
+    })?;
 
     Ok(SharedMemory(Mapped {
         handle,
@@ -139,6 +143,23 @@ pub unsafe fn map(shm: SharedMemory<Unmapped>) -> Result<SharedMemory<Mapped>, M
     }))
 }
 
+/// Map a shared-memory handle at an address picked by the VMM.
+///
+/// Returns a [`svc::SharedMapping`] guard that unmaps the region when dropped.
+/// The handle is not consumed and must outlive the mapping.
+pub fn map_anywhere(
+    handle: Handle,
+    size: usize,
+    perm: Permissions,
+) -> Result<svc::SharedMapping, MapAnywhereError> {
+    // Ask the VMM for a free slice of ASLR address-space.
+    let Some(addr) = vmm::lock().find_aslr(size, GUARD_SIZE) else {
+        return Err(MapAnywhereError::VirtAddressAllocFailed);
+    };
+
+    svc::map(handle, addr, size, perm).map_err(MapAnywhereError::Map)
+}
+
 /// Unmap the shared-memory object from the current process.
 ///
 /// # Safety
@@ -185,6 +206,11 @@ pub unsafe fn close(shm: SharedMemory<Unmapped>) -> Result<(), CloseError> {
 #[error(transparent)]
 pub struct CreateError(#[from] svc::CreateSharedMemoryError);
 
+/// Error returned by [`map`].
+///
+/// Both variants record the size of the attempted mapping, and the kernel
+/// variant the address it was attempted at, so a failure can be logged with
+/// enough detail to tell why it happened; see [`MapError::describe`].
 #[derive(Debug, thiserror::Error)]
 pub enum MapError {
     /// Failed to allocate a virtual address range.
@@ -195,8 +221,11 @@ pub enum MapError {
     /// - The process address space is fragmented
     /// - The requested size is too large for available regions
     /// - The virtual memory manager is unable to reserve the required space
-    #[error("Failed to allocate virtual address range")]
-    VirtAddressAllocFailed,
+    #[error("Failed to allocate virtual address range of {size:#x} bytes")]
+    VirtAddressAllocFailed {
+        /// Size of the requested range.
+        size: usize,
+    },
 
     /// System call to map shared memory failed.
     ///
@@ -206,8 +235,61 @@ pub enum MapError {
     /// - Invalid address alignment
     /// - Permission denied
     /// - Address range already in use
-    #[error(transparent)]
-    Svc(#[from] svc::MapSharedMemoryError),
+    #[error("Failed to map {size:#x} bytes of shared memory at {addr:#x}")]
+    Svc {
+        /// Address the mapping was attempted at.
+        addr: usize,
+        /// Size of the attempted mapping.
+        size: usize,
+        /// Error returned by the kernel, which carries its result code.
+        #[source]
+        reason: svc::MapSharedMemoryError,
+    },
+}
+
+impl MapError {
+    /// Returns a short explanation of the most likely cause of the failure.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::VirtAddressAllocFailed { .. } => {
+                "no free range of this size in the ASLR region; the address space is exhausted or fragmented"
+            }
+            Self::Svc { reason, .. } => match reason {
+                svc::MapSharedMemoryError::InvalidHandle => {
+                    "the handle is not a shared memory handle, or it was already closed"
+                }
+                svc::MapSharedMemoryError::InvalidAddress => "the address is not page-aligned",
+                svc::MapSharedMemoryError::InvalidCurrentMemory => {
+                    "the address range is already in use"
+                }
+                svc::MapSharedMemoryError::InvalidMemoryRegion => {
+                    "the address range lies outside the ASLR region"
+                }
+                svc::MapSharedMemoryError::InvalidSize => {
+                    "the size does not match the shared memory object, or is not page-aligned"
+                }
+                svc::MapSharedMemoryError::InvalidPermission => {
+                    "the permission conflicts with the one the shared memory object allows this process"
+                }
+                svc::MapSharedMemoryError::OutOfResource
+                | svc::MapSharedMemoryError::OutOfMemory => {
+                    "the kernel ran out of resources for the mapping"
+                }
+                svc::MapSharedMemoryError::Unknown(_) => "unexpected kernel error",
+            },
+        }
+    }
+}
+
+/// Error returned by [`map_anywhere`].
+#[derive(Debug, thiserror::Error)]
+pub enum MapAnywhereError {
+    /// Failed to allocate a virtual address range.
+    #[error("failed to allocate virtual address range")]
+    VirtAddressAllocFailed,
+    /// Failed to map the shared memory.
+    #[error("failed to map shared memory")]
+    Map(#[source] svc::MapError),
 }
 
 /// Error that occurs when unmapping shared memory fails.
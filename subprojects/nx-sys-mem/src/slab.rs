@@ -0,0 +1,235 @@
+//! Slab allocator for fixed-size blocks.
+//!
+//! Services allocate and free same-sized request buffers constantly. Routing
+//! them through the general heap churns its free list; [`Slab`] instead
+//! carves `N`-byte blocks out of large chunks and recycles freed blocks
+//! through an intrusive free list, so both allocation and deallocation are
+//! O(1).
+//!
+//! Chunks are taken from the global allocator when the slab runs out of free
+//! blocks, and only returned to it when the slab is dropped.
+//!
+//! A slab is typically placed in a `static` and passed by reference to the
+//! `*_in` constructors of the allocator-aware collections, such as `Box::new_in`.
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+use alloc::alloc as global;
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::{self, NonNull},
+};
+
+use nx_std_sync::mutex::Mutex;
+
+/// Alignment of every block handed out by a [`Slab`].
+pub const BLOCK_ALIGN: usize = 16;
+
+/// Target size of a chunk, in bytes.
+///
+/// Chunks hold as many blocks as fit in this size, and at least one.
+pub const CHUNK_SIZE: usize = 0x1000;
+
+/// Fixed-size block allocator handing out `N`-byte blocks.
+///
+/// Implements [`Allocator`] for layouts of at most `N` bytes and
+/// [`BLOCK_ALIGN`] alignment; larger layouts fail with [`AllocError`].
+/// The slab is `Sync`: its free list is protected by a mutex.
+pub struct Slab<const N: usize> {
+    state: Mutex<SlabState>,
+}
+
+/// Free list and chunk list of a [`Slab`].
+struct SlabState {
+    /// First free block.
+    free: Option<NonNull<FreeBlock>>,
+    /// Most recently allocated chunk.
+    chunks: Option<NonNull<ChunkHeader>>,
+    /// Number of chunks allocated.
+    chunk_count: usize,
+    /// Total number of blocks in all chunks.
+    capacity: usize,
+    /// Number of blocks currently handed out.
+    in_use: usize,
+}
+
+// SAFETY: The state exclusively owns the chunks its pointers refer to.
+unsafe impl Send for SlabState {}
+
+/// Free block, linked through its first bytes.
+struct FreeBlock {
+    next: Option<NonNull<FreeBlock>>,
+}
+
+/// Header at the start of every chunk.
+#[repr(align(16))]
+struct ChunkHeader {
+    next: Option<NonNull<ChunkHeader>>,
+}
+
+const _: () = assert!(size_of::<ChunkHeader>() == BLOCK_ALIGN);
+
+impl<const N: usize> Slab<N> {
+    /// Distance between two blocks in a chunk.
+    const STRIDE: usize = {
+        assert!(N > 0, "slab blocks must not be empty");
+        let stride = N.next_multiple_of(BLOCK_ALIGN);
+        assert!(stride >= size_of::<FreeBlock>());
+        stride
+    };
+
+    /// Number of blocks in a chunk.
+    const BLOCKS_PER_CHUNK: usize = {
+        let blocks = (CHUNK_SIZE - size_of::<ChunkHeader>()) / Self::STRIDE;
+        if blocks == 0 { 1 } else { blocks }
+    };
+
+    /// Layout of a chunk.
+    const CHUNK_LAYOUT: Layout = match Layout::from_size_align(
+        size_of::<ChunkHeader>() + Self::BLOCKS_PER_CHUNK * Self::STRIDE,
+        BLOCK_ALIGN,
+    ) {
+        Ok(layout) => layout,
+        Err(_) => panic!("slab chunk too large"),
+    };
+
+    /// Creates an empty slab.
+    ///
+    /// No memory is allocated until the first allocation or
+    /// [`reserve`](Self::reserve).
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(SlabState {
+                free: None,
+                chunks: None,
+                chunk_count: 0,
+                capacity: 0,
+                in_use: 0,
+            }),
+        }
+    }
+
+    /// Grows the pool until at least `blocks` blocks are free.
+    ///
+    /// Pre-growing the pool keeps chunk allocations out of hot paths.
+    pub fn reserve(&self, blocks: usize) -> Result<(), AllocError> {
+        let mut state = self.state.lock();
+        while state.capacity - state.in_use < blocks {
+            Self::grow(&mut state)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the current utilization of the slab.
+    pub fn stats(&self) -> SlabStats {
+        let state = self.state.lock();
+        SlabStats {
+            block_size: N,
+            chunks: state.chunk_count,
+            capacity: state.capacity,
+            in_use: state.in_use,
+        }
+    }
+
+    /// Allocates a new chunk and pushes its blocks onto the free list.
+    fn grow(state: &mut SlabState) -> Result<(), AllocError> {
+        // SAFETY: The chunk layout has a non-zero size.
+        let chunk = NonNull::new(unsafe { global::alloc(Self::CHUNK_LAYOUT) }).ok_or(AllocError)?;
+
+        let header = chunk.cast::<ChunkHeader>();
+        // SAFETY: The chunk is freshly allocated, aligned, and starts with room
+        // for the header.
+        unsafe { header.write(ChunkHeader { next: state.chunks }) };
+        state.chunks = Some(header);
+
+        // Push the blocks in reverse so they are handed out in address order
+        for index in (0..Self::BLOCKS_PER_CHUNK).rev() {
+            let offset = size_of::<ChunkHeader>() + index * Self::STRIDE;
+            // SAFETY: The block lies within the chunk and is aligned to
+            // BLOCK_ALIGN, which is enough for a free block node.
+            unsafe {
+                let block = chunk.add(offset).cast::<FreeBlock>();
+                block.write(FreeBlock { next: state.free });
+                state.free = Some(block);
+            }
+        }
+
+        state.chunk_count += 1;
+        state.capacity += Self::BLOCKS_PER_CHUNK;
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Slab<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Drop for Slab<N> {
+    fn drop(&mut self) {
+        let state = self.state.get_mut();
+        let mut chunk = state.chunks.take();
+        while let Some(header) = chunk {
+            // SAFETY: Every chunk in the list was allocated by `grow` with
+            // CHUNK_LAYOUT and is only freed here, once.
+            unsafe {
+                chunk = header.as_ref().next;
+                global::dealloc(header.as_ptr().cast(), Self::CHUNK_LAYOUT);
+            }
+        }
+    }
+}
+
+unsafe impl<const N: usize> Allocator for Slab<N> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() > N || layout.align() > BLOCK_ALIGN {
+            return Err(AllocError);
+        }
+
+        let mut state = self.state.lock();
+        if state.free.is_none() {
+            Self::grow(&mut state)?;
+        }
+
+        let block = state.free.ok_or(AllocError)?;
+        // SAFETY: Blocks on the free list hold a valid free block node.
+        state.free = unsafe { block.as_ref().next };
+        state.in_use += 1;
+
+        Ok(NonNull::slice_from_raw_parts(block.cast(), N))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, _layout: Layout) {
+        let mut state = self.state.lock();
+
+        let block = ptr.cast::<FreeBlock>();
+        // SAFETY: The caller guarantees `ptr` is a block of this slab that is
+        // no longer in use, so it can hold a free block node again.
+        unsafe { ptr::write(block.as_ptr(), FreeBlock { next: state.free }) };
+        state.free = Some(block);
+        state.in_use -= 1;
+    }
+}
+
+/// Utilization of a [`Slab`], as returned by [`Slab::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlabStats {
+    /// Size of each block in bytes.
+    pub block_size: usize,
+    /// Number of chunks taken from the global allocator.
+    pub chunks: usize,
+    /// Total number of blocks in all chunks.
+    pub capacity: usize,
+    /// Number of blocks currently handed out.
+    pub in_use: usize,
+}
+
+impl SlabStats {
+    /// Returns the number of free blocks.
+    #[inline]
+    pub fn free(&self) -> usize {
+        self.capacity - self.in_use
+    }
+}
@@ -0,0 +1,122 @@
+//! C FFI bindings for the slab allocator
+//!
+//! C cannot instantiate [`Slab`] for arbitrary block sizes, so these bindings
+//! expose slabs of [`BLOCK_SIZE`]-byte blocks only, the size of a CMIF scratch
+//! buffer. The signatures align with the declarations in `nx_slab.h`.
+
+use alloc::boxed::Box;
+use core::{
+    alloc::{Allocator, Layout},
+    ffi::c_void,
+    ptr::{self, NonNull},
+};
+
+use nx_svc::error::{KernelError, ToRawResultCode};
+
+use super::{BLOCK_ALIGN, Slab};
+
+/// Size of the blocks handed out by the C slab.
+const BLOCK_SIZE: usize = 0x100;
+
+/// Slab of [`BLOCK_SIZE`]-byte blocks, opaque to C.
+type Slab256 = Slab<BLOCK_SIZE>;
+
+/// Layout of a C slab block.
+const BLOCK_LAYOUT: Layout = match Layout::from_size_align(BLOCK_SIZE, BLOCK_ALIGN) {
+    Ok(layout) => layout,
+    Err(_) => panic!("invalid slab block layout"),
+};
+
+/// Slab utilization (C-compatible mirror of [`super::SlabStats`])
+#[repr(C)]
+struct SlabStats {
+    block_size: usize,
+    chunks: usize,
+    capacity: usize,
+    in_use: usize,
+}
+
+/// Creates an empty slab of 256-byte blocks.
+///
+/// Returns null if the slab itself cannot be allocated.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_create() -> *mut c_void {
+    Box::try_new(Slab256::new()).map_or(ptr::null_mut(), |slab| Box::into_raw(slab).cast())
+}
+
+/// Destroys a slab, returning all its chunks to the global allocator.
+///
+/// Every block of the slab must have been freed.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_destroy(slab: *mut c_void) {
+    if slab.is_null() {
+        return;
+    }
+
+    // SAFETY: The caller guarantees `slab` was returned by `slab256_create`.
+    drop(unsafe { Box::from_raw(slab.cast::<Slab256>()) });
+}
+
+/// Grows a slab until at least `blocks` blocks are free.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_reserve(slab: *mut c_void, blocks: usize) -> u32 {
+    let Some(slab) = NonNull::new(slab.cast::<Slab256>()) else {
+        return KernelError::InvalidPointer.to_rc();
+    };
+
+    // SAFETY: The caller guarantees `slab` was returned by `slab256_create`.
+    match unsafe { slab.as_ref() }.reserve(blocks) {
+        Ok(()) => 0,
+        Err(_) => KernelError::OutOfMemory.to_rc(),
+    }
+}
+
+/// Allocates a 256-byte block, aligned to 16 bytes.
+///
+/// Returns null if the slab cannot grow.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_alloc(slab: *mut c_void) -> *mut c_void {
+    let Some(slab) = NonNull::new(slab.cast::<Slab256>()) else {
+        return ptr::null_mut();
+    };
+
+    // SAFETY: The caller guarantees `slab` was returned by `slab256_create`.
+    unsafe { slab.as_ref() }
+        .allocate(BLOCK_LAYOUT)
+        .map_or(ptr::null_mut(), |block| block.as_ptr().cast())
+}
+
+/// Returns a block to its slab.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_free(slab: *mut c_void, block: *mut c_void) {
+    let (Some(slab), Some(block)) = (
+        NonNull::new(slab.cast::<Slab256>()),
+        NonNull::new(block.cast::<u8>()),
+    ) else {
+        return;
+    };
+
+    // SAFETY: The caller guarantees `block` was allocated from `slab` and is
+    // no longer used.
+    unsafe { slab.as_ref().deallocate(block, BLOCK_LAYOUT) };
+}
+
+/// Gets the current utilization of a slab.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn __nx_sys_mem__slab256_stats(slab: *mut c_void, out: *mut SlabStats) {
+    let (Some(slab), Some(out)) = (NonNull::new(slab.cast::<Slab256>()), NonNull::new(out)) else {
+        return;
+    };
+
+    // SAFETY: The caller guarantees `slab` was returned by `slab256_create`.
+    let stats = unsafe { slab.as_ref() }.stats();
+    // SAFETY: The caller guarantees `out` is valid for writes.
+    unsafe {
+        out.write(SlabStats {
+            block_size: stats.block_size,
+            chunks: stats.chunks,
+            capacity: stats.capacity,
+            in_use: stats.in_use,
+        })
+    };
+}
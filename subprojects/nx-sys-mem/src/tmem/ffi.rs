@@ -174,7 +174,7 @@ unsafe extern "C" fn __nx_sys_mem__tmem_map(t: *mut TransferMemory) -> u32 {
         }
         Err(err) => match err.kind {
             sys::MapErrorKind::VirtAddressAllocFailed => LIBNX_ERR_OUT_OF_MEMORY,
-            sys::MapErrorKind::Svc(svc_err) => svc_err.to_rc(),
+            sys::MapErrorKind::Svc { reason, .. } => reason.to_rc(),
         },
     }
 }
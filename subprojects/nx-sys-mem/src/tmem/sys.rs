@@ -164,8 +164,11 @@ pub unsafe fn map(tm: TransferMemory<Unmapped>) -> Result<TransferMemory<Mapped>
         });
     };
 
-    svc::map_transfer_memory(handle, addr, size, perm).map_err(|err| MapError {
-        kind: MapErrorKind::Svc(err),
+    svc::map_transfer_memory(handle, addr, size, perm).map_err(|reason| MapError {
+        kind: MapErrorKind::Svc {
+            addr: addr.as_ptr() as usize,
+            reason,
+        },
         tm,
     })?;
 
@@ -178,20 +181,63 @@ pub unsafe fn map(tm: TransferMemory<Unmapped>) -> Result<TransferMemory<Mapped>
     }))
 }
 
+/// Error returned by [`map`].
+///
+/// The transfer memory is handed back in `tm`, which also records the size
+/// of the attempted mapping; see [`MapError::describe`] for the likely cause.
 #[derive(Debug, thiserror::Error)]
-#[error("Failed to map transfer memory: {kind}")]
+#[error("Failed to map {:#x} bytes of transfer memory: {kind}", .tm.0.size())]
 pub struct MapError {
     #[source]
     pub kind: MapErrorKind,
     pub tm: TransferMemory<Unmapped>,
 }
 
+impl MapError {
+    /// Returns a short explanation of the most likely cause of the failure.
+    pub fn describe(&self) -> &'static str {
+        match &self.kind {
+            MapErrorKind::VirtAddressAllocFailed => {
+                "no free range of this size in the ASLR region; the address space is exhausted or fragmented"
+            }
+            MapErrorKind::Svc { reason, .. } => match reason {
+                svc::MapTransferMemoryError::InvalidHandle => {
+                    "the handle is not a transfer memory handle, or it was already closed"
+                }
+                svc::MapTransferMemoryError::InvalidAddress => "the address is not page-aligned",
+                svc::MapTransferMemoryError::InvalidSize => {
+                    "the size does not match the transfer memory object, or is not page-aligned"
+                }
+                svc::MapTransferMemoryError::InvalidCurrentMemory => {
+                    "the address range is already in use"
+                }
+                svc::MapTransferMemoryError::InvalidMemoryRegion => {
+                    "the address range lies outside the ASLR region"
+                }
+                svc::MapTransferMemoryError::InvalidPermission => {
+                    "the permission differs from the one the transfer memory was created with"
+                }
+                svc::MapTransferMemoryError::Unknown(_) => "unexpected kernel error",
+            },
+        }
+    }
+}
+
+/// Cause of a [`MapError`].
 #[derive(Debug, thiserror::Error)]
 pub enum MapErrorKind {
+    /// The VMM found no free virtual address range of the required size.
     #[error("Failed to allocate virtual address range")]
     VirtAddressAllocFailed,
-    #[error(transparent)]
-    Svc(#[from] svc::MapTransferMemoryError),
+    /// The `svcMapTransferMemory` system call failed.
+    #[error("Failed to map at {addr:#x}")]
+    Svc {
+        /// Address the mapping was attempted at.
+        addr: usize,
+        /// Error returned by the kernel, which carries its result code.
+        #[source]
+        reason: svc::MapTransferMemoryError,
+    },
 }
 
 /// Unmaps the transfer memory from the current process.
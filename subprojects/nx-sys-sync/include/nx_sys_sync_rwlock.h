@@ -79,3 +79,12 @@ bool __nx_sys_sync_rwlock_is_write_lock_held_by_current_thread(RwLock* r);
  *         while it held the write lock, and 0 if it does not.
  */
 bool __nx_sys_sync_rwlock_is_owned_by_current_thread(RwLock* r);
+
+/**
+ * @brief Attempts to upgrade a read lock held by the current thread to the write lock.
+ * @note No libnx counterpart.
+ * @param r Read/write lock object.
+ * @return 1 if the lock was upgraded, and 0 if other threads hold read locks (the read
+ *         lock is still held).
+ */
+bool __nx_sys_sync__rwlock_try_upgrade(RwLock* r);
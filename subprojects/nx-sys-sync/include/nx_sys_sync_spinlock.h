@@ -0,0 +1,38 @@
+/**
+ * @file spinlock.h
+ * @brief Spin lock for very short critical sections.
+ */
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+
+/// Spin lock datatype.
+typedef uint32_t SpinLock;
+
+/**
+ * @brief Initializes a spin lock.
+ * @param l SpinLock object.
+ * @note A spin lock can also be statically initialized by assigning 0 to it.
+ */
+void __nx_sys_sync__spinlock_init(SpinLock* l);
+
+/**
+ * @brief Locks a spin lock, spinning until it can be acquired.
+ * @param l SpinLock object.
+ * @warning The lock is not reentrant, and must only guard a few instructions.
+ */
+void __nx_sys_sync__spinlock_lock(SpinLock* l);
+
+/**
+ * @brief Attempts to lock a spin lock without waiting.
+ * @param l SpinLock object.
+ * @return 1 if the lock has been acquired successfully, and 0 on contention.
+ */
+bool __nx_sys_sync__spinlock_try_lock(SpinLock* l);
+
+/**
+ * @brief Unlocks a spin lock.
+ * @param l SpinLock object.
+ */
+void __nx_sys_sync__spinlock_unlock(SpinLock* l);
@@ -0,0 +1,55 @@
+//! # Backoff
+//!
+//! An exponential backoff that ends up yielding the core.
+//!
+//! [`nx_cpu::spin::ExponentialBackoff`] never leaves the core, as `nx-cpu` cannot issue
+//! supervisor calls. [`Backoff`] spins with it first, then falls back to yielding the core
+//! (`svcSleepThread(-1)`), for waits on another thread that may run for a while.
+
+use nx_cpu::spin::ExponentialBackoff;
+
+/// Exponential backoff for wait loops, yielding the core once spinning runs out.
+///
+/// Create a new backoff (or [`reset`](Self::reset) it) for every wait.
+#[derive(Debug, Default)]
+pub struct Backoff(ExponentialBackoff);
+
+impl Backoff {
+    /// Creates a backoff at its first step.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(ExponentialBackoff::new())
+    }
+
+    /// Resets the backoff to its first step.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    /// Spins with an increasing number of hints, without ever leaving the core.
+    ///
+    /// See [`ExponentialBackoff::spin`].
+    #[inline]
+    pub fn spin(&mut self) {
+        self.0.spin();
+    }
+
+    /// Spins like [`spin`](Self::spin), then yields the core once the spin limit is reached.
+    ///
+    /// The yield lets threads on any core run; use it when waiting on another thread to make
+    /// progress.
+    pub fn snooze(&mut self) {
+        if self.0.is_completed() {
+            nx_svc::thread::yield_with_migration();
+        } else {
+            self.0.spin();
+        }
+    }
+
+    /// Returns `true` once the backoff has moved on from spinning to yielding.
+    #[inline]
+    pub fn is_completed(&self) -> bool {
+        self.0.is_completed()
+    }
+}
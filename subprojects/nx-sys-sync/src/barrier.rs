@@ -6,7 +6,9 @@
 //!
 //! This implementation is built on top of mutex and condition variables to provide thread
 //! synchronization capabilities. It maintains an internal counter that tracks the number of
-//! threads that have reached the barrier.
+//! threads that have reached the barrier, and a generation counter that identifies the current
+//! phase. The barrier resets itself every time it trips, so the same barrier can be waited on
+//! repeatedly, e.g. once per frame.
 
 use core::cell::UnsafeCell;
 
@@ -23,11 +25,17 @@ use super::{Condvar, Mutex};
 /// any of them are allowed to proceed. When a thread calls `wait()`, it blocks until all
 /// other threads have also called `wait()`. Once the last thread calls `wait()`, all threads
 /// are unblocked and can continue execution.
+///
+/// The layout matches libnx's `Barrier`. The generation counter occupies the upper half of
+/// libnx's 64-bit `total` field, which C code never reads directly.
+#[repr(C)]
 pub struct Barrier {
-    /// Number of threads to reach the barrier
+    /// Number of threads that reached the barrier in the current generation
     count: UnsafeCell<u64>,
-    /// Number of threads to wait on
-    total: u64,
+    /// Number of threads to wait on, minus one
+    total: u32,
+    /// Generation (phase) counter, incremented each time the barrier trips
+    generation: UnsafeCell<u32>,
     /// Mutex for synchronization
     mutex: Mutex,
     /// Condition variable for thread waiting
@@ -47,11 +55,14 @@ impl Barrier {
     ///
     /// # Panics
     ///
-    /// Will not panic as long as `thread_count` is greater than 0.
+    /// Panics if `thread_count` is 0 or greater than `u32::MAX + 1`.
     pub fn new(thread_count: u64) -> Self {
+        let total = u32::try_from(thread_count - 1).expect("Barrier thread count too large");
+
         Barrier {
             count: UnsafeCell::new(0),
-            total: thread_count - 1,
+            total,
+            generation: UnsafeCell::new(0),
             mutex: Mutex::new(),
             condvar: Condvar::new(),
         }
@@ -60,24 +71,54 @@ impl Barrier {
     /// Blocks the current thread until all threads have reached this point.
     ///
     /// When the specified number of threads have called this function, all threads will be
-    /// unblocked and the barrier will be reset, ready for reuse.
-    ///
-    /// # Panics
+    /// unblocked and the barrier will be reset, ready for reuse. A thread that loops back and
+    /// waits again before the others have left counts towards the next generation, and never
+    /// releases or is released by the previous one.
     ///
-    /// Panics if the internal counter would overflow, which is extremely unlikely in
-    /// practice as it would require more threads than the maximum value of u64.
-    pub fn wait(&self) {
+    /// The last thread to arrive, which trips the barrier, is the leader: it receives a
+    /// [`BarrierWaitResult`] whose [`is_leader()`](BarrierWaitResult::is_leader) returns
+    /// `true`, and every other thread receives `false`.
+    pub fn wait(&self) -> BarrierWaitResult {
         self.mutex.lock();
 
-        let count = unsafe { &mut *self.count.get() };
-        if *count == self.total {
-            *count = 0;
-            self.condvar.wake(self.total as i32);
-        } else {
-            *count = count.checked_add(1).expect("Barrier count overflow");
-            self.condvar.wait(&self.mutex);
-        }
+        // SAFETY: The count and generation are only accessed with the mutex held.
+        let is_leader = unsafe {
+            let local_gen = *self.generation.get();
+            if *self.count.get() == self.total as u64 {
+                *self.count.get() = 0;
+                *self.generation.get() = local_gen.wrapping_add(1);
+                self.condvar.wake_all();
+                true
+            } else {
+                *self.count.get() += 1;
+
+                // Wakeups may be spurious; only a generation change releases the waiters
+                while *self.generation.get() == local_gen {
+                    self.condvar.wait(&self.mutex);
+                }
+                false
+            }
+        };
 
         self.mutex.unlock();
+
+        BarrierWaitResult(is_leader)
+    }
+}
+
+/// A `BarrierWaitResult` is returned by [`Barrier::wait()`] when all threads
+/// in the [`Barrier`] have rendezvoused.
+#[derive(Debug, Clone, Copy)]
+pub struct BarrierWaitResult(bool);
+
+impl BarrierWaitResult {
+    /// Returns `true` if this thread is the "leader thread" for the call to
+    /// [`Barrier::wait()`].
+    ///
+    /// Only one thread per generation will have `true` returned from their
+    /// result, all other threads will have `false` returned.
+    #[must_use]
+    pub fn is_leader(&self) -> bool {
+        self.0
     }
 }
@@ -15,3 +15,4 @@ mod mutex;
 mod remutex;
 mod rwlock;
 mod semaphore;
+mod spinlock;
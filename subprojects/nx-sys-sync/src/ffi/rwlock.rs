@@ -132,3 +132,22 @@ pub unsafe extern "C" fn __nx_sys_sync__rwlock_is_write_lock_held_by_current_thr
 pub unsafe extern "C" fn __nx_sys_sync__rwlock_is_owned_by_current_thread(rw: *mut RwLock) -> bool {
     unsafe { &*rw }.is_owned_by_current_thread()
 }
+
+/// Attempts to upgrade a read lock held by the current thread to the write lock.
+///
+/// No libnx counterpart.
+///
+/// # Returns
+///
+/// * `true` if the lock was upgraded; the write lock must then be released with
+///   `__nx_sys_sync__rwlock_write_unlock`
+/// * `false` if other threads hold read locks; the read lock is still held
+///
+/// # Safety
+///
+/// - `rw` must point to a valid, initialized `RwLock`
+/// - The current thread must hold a read lock
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_sync__rwlock_try_upgrade(rw: *mut RwLock) -> bool {
+    unsafe { &*rw }.try_upgrade()
+}
@@ -0,0 +1,60 @@
+//! FFI bindings for the `nx-sys-sync` crate - SpinLock
+//!
+//! libnx has no spin lock; these functions have no libnx counterpart to override.
+
+use core::mem;
+
+use crate::spinlock::SpinLock;
+
+/// Initializes the spin lock in the unlocked state.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Writes to the memory pointed to by `lock`
+/// - Requires that `lock` is valid and properly aligned
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_sync__spinlock_init(lock: *mut SpinLock) {
+    unsafe { lock.write(SpinLock::new()) }
+}
+
+/// Locks the spin lock, spinning until it can be acquired.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Requires that `lock` points to a valid SpinLock instance
+/// - Requires that `lock` is properly aligned
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_sync__spinlock_lock(lock: *mut SpinLock) {
+    mem::forget(unsafe { &*lock }.lock())
+}
+
+/// Attempts to lock the spin lock without waiting.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Requires that `lock` points to a valid SpinLock instance
+/// - Requires that `lock` is properly aligned
+///
+/// # Returns
+///
+/// Returns `true` if the lock was acquired, `false` if it was already locked.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_sync__spinlock_try_lock(lock: *mut SpinLock) -> bool {
+    unsafe { &*lock }.try_lock().map(mem::forget).is_some()
+}
+
+/// Unlocks the spin lock.
+///
+/// # Safety
+///
+/// This function is unsafe because it:
+/// - Requires that `lock` points to a valid SpinLock instance
+/// - Requires that `lock` is properly aligned
+/// - Requires that the lock is held, and was acquired through this FFI
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_sync__spinlock_unlock(lock: *mut SpinLock) {
+    unsafe { (*lock).force_unlock() }
+}
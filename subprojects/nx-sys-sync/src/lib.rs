@@ -19,6 +19,7 @@ extern crate nx_panic_handler as _; // provides #[panic_handler]
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+mod backoff;
 mod barrier;
 mod condvar;
 mod mutex;
@@ -26,9 +27,17 @@ mod once;
 mod remutex;
 mod rwlock;
 mod semaphore;
+mod spinlock;
 
 #[doc(inline)]
 pub use self::{
-    barrier::Barrier, condvar::Condvar, mutex::Mutex, once::Once, remutex::ReentrantMutex,
-    rwlock::RwLock, semaphore::Semaphore,
+    backoff::Backoff,
+    barrier::{Barrier, BarrierWaitResult},
+    condvar::Condvar,
+    mutex::Mutex,
+    once::Once,
+    remutex::ReentrantMutex,
+    rwlock::RwLock,
+    semaphore::Semaphore,
+    spinlock::{SpinGuard, SpinLock},
 };
@@ -293,6 +293,62 @@ impl RwLock {
         }
     }
 
+    /// Attempts to atomically upgrade a read lock held by the current thread to the
+    /// write lock.
+    ///
+    /// On success the read lock is consumed and the current thread holds the write lock,
+    /// which must be released with [`write_unlock`](Self::write_unlock). No other writer
+    /// can acquire the lock between the read lock being released and the write lock being
+    /// taken. On failure the read lock is still held.
+    ///
+    /// This method must only be called by a thread that currently holds a read lock.
+    ///
+    /// There is no blocking variant: two readers waiting for each other to release their
+    /// read locks in order to upgrade would deadlock. Callers that fail to upgrade should
+    /// release the read lock and take the write lock instead.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the lock was upgraded:
+    ///   - The current thread holds the only read lock
+    ///   - The current thread already holds the write lock
+    /// * `false` if other threads hold read locks
+    pub fn try_upgrade(&self) -> bool {
+        let curr_thread_handle = get_curr_thread_handle();
+
+        // If the current thread already holds the write lock, turn the read lock into
+        // a nested write lock without blocking.
+        if self.write_owner_tag == curr_thread_handle {
+            let read_lock_count = unsafe { &mut *self.read_lock_count.get() };
+            let write_lock_count = unsafe { &mut *self.write_lock_count.get() };
+            *read_lock_count -= 1;
+            *write_lock_count += 1;
+            return true;
+        }
+
+        // The mutex is only held long-term by a writer, which cannot coexist with our
+        // read lock, so this does not block for long.
+        self.mutex.lock();
+
+        // If other readers hold the lock, the upgrade is not possible
+        let read_lock_count = unsafe { &mut *self.read_lock_count.get() };
+        if *read_lock_count != 1 {
+            self.mutex.unlock();
+            return false;
+        }
+
+        // Release our read lock, and take the write lock in its place
+        *read_lock_count = 0;
+        let write_lock_count = unsafe { &mut *self.write_lock_count.get() };
+        *write_lock_count = 1;
+        self.write_owner_tag.set(curr_thread_handle);
+
+        // NOTE: The mutex is intentionally not unlocked here.
+        //       It will be unlocked by a call to read_unlock or write_unlock.
+
+        true
+    }
+
     /// Checks if the write lock is held by the current thread.
     ///
     /// # Returns
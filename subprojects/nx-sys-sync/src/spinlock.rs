@@ -0,0 +1,135 @@
+//! # Spin lock
+//!
+//! A lock that busy-waits instead of asking the kernel to suspend the waiting thread.
+//!
+//! Locking a [`Mutex`](crate::Mutex) under contention costs an `svcArbitrateLock` round trip,
+//! which dwarfs a critical section of a few instructions, such as bumping the producer index
+//! of a lock-free ring buffer. [`SpinLock`] never enters the kernel while the lock is only
+//! briefly held: waiters spin with an exponential backoff, and only yield the core
+//! (`svcSleepThread(0)`) once the backoff runs out.
+//!
+//! Spinning wastes the waiter's core for as long as the lock is held, and the kernel knows
+//! nothing about the lock, so it can neither hand it over nor apply priority inheritance. Only
+//! use a spin lock for critical sections that are a handful of instructions long and never
+//! block; use a [`Mutex`](crate::Mutex) for anything else.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use nx_cpu::spin::ExponentialBackoff;
+use static_assertions::const_assert_eq;
+
+/// Value of an unlocked spin lock.
+const UNLOCKED: u32 = 0;
+
+/// Value of a locked spin lock.
+const LOCKED: u32 = 1;
+
+/// A mutual exclusion primitive for very short critical sections.
+///
+/// The lock is not reentrant: locking it again from the thread holding it spins forever.
+/// It has no owner either, so any thread may release it through the FFI.
+// NOTE: The in-memory representation of the SpinLock must be u32 for FFI compatibility
+#[repr(C)]
+pub struct SpinLock(AtomicU32);
+
+// Ensure the in-memory size of the SpinLock is the same as u32
+const_assert_eq!(size_of::<SpinLock>(), size_of::<u32>());
+
+impl SpinLock {
+    /// Creates a new, unlocked [`SpinLock`].
+    pub const fn new() -> Self {
+        Self(AtomicU32::new(UNLOCKED))
+    }
+
+    /// Returns a raw pointer to the underlying atomic integer.
+    ///
+    /// # Safety
+    ///
+    /// This function is intended for FFI purposes and should be used with care.
+    /// The caller must ensure that the pointer is not used after the lock is dropped.
+    pub fn as_ptr(&self) -> *mut u32 {
+        self.0.as_ptr()
+    }
+
+    /// Locks the spin lock, spinning until it can be acquired.
+    ///
+    /// The lock is released when the returned guard is dropped.
+    ///
+    /// While the lock is held, waiters spin with an exponential backoff, then fall back to
+    /// yielding the core with `svcSleepThread(0)`. That yield only lets threads of the same
+    /// priority run, so the holder must never be preempted by a higher priority thread
+    /// waiting for the same lock on its core.
+    #[inline]
+    pub fn lock(&self) -> SpinGuard<'_> {
+        self.raw_lock();
+        SpinGuard { lock: self }
+    }
+
+    /// Attempts to lock the spin lock without waiting.
+    ///
+    /// Returns `None` if the lock is already held.
+    #[inline]
+    pub fn try_lock(&self) -> Option<SpinGuard<'_>> {
+        // The guard must only be built on success: dropping it releases the lock
+        self.raw_try_lock().then(|| SpinGuard { lock: self })
+    }
+
+    /// Returns `true` if the lock is currently held by any thread.
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.0.load(Ordering::Relaxed) == LOCKED
+    }
+
+    /// Releases the lock without a guard.
+    ///
+    /// # Safety
+    ///
+    /// The lock must be held, and the guard it was acquired with, if any, must have been
+    /// forgotten (e.g. with [`core::mem::forget`]).
+    #[inline]
+    pub unsafe fn force_unlock(&self) {
+        self.0.store(UNLOCKED, Ordering::Release);
+    }
+
+    /// Acquires the lock, spinning until it is available.
+    fn raw_lock(&self) {
+        let mut backoff = ExponentialBackoff::new();
+
+        while !self.raw_try_lock() {
+            // Wait on a plain load, so waiters do not fight over the cache line
+            while self.is_locked() {
+                if backoff.is_completed() {
+                    nx_svc::thread::yield_no_migration();
+                } else {
+                    backoff.spin();
+                }
+            }
+        }
+    }
+
+    /// Acquires the lock if it is available.
+    fn raw_try_lock(&self) -> bool {
+        self.0
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+impl Default for SpinLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Guard of a locked [`SpinLock`], releasing it when dropped.
+#[must_use = "if unused the SpinLock will immediately unlock"]
+pub struct SpinGuard<'a> {
+    lock: &'a SpinLock,
+}
+
+impl Drop for SpinGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.lock.0.store(UNLOCKED, Ordering::Release);
+    }
+}
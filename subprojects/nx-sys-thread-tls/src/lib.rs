@@ -145,6 +145,8 @@
 //! 2. Allocate TLS region (0x200 bytes)
 //! 3. Copy `.tdata` section (initialized TLS variables) to new TLS block
 //! 4. Zero `.tbss` section (uninitialized TLS variables)
+//!
+//!    Steps 3 and 4 are done by [`init_tls_data_segment()`], which also returns the `tls_ptr`.
 //! 5. Initialize `ThreadVars` structure using [`init_thread_vars()`]
 //! 6. Call `svcCreateThread()` with entry point, stack, and TLS
 //! 7. Call `svcStartThread()`
@@ -393,6 +395,96 @@ pub unsafe fn init_thread_vars(
     }
 }
 
+/// Returns `true` if the current thread's [`ThreadVars`] have been initialized.
+///
+/// Checks that the footer carries [`THREAD_VARS_MAGIC`] and a non-null `tls_ptr`, the two
+/// prerequisites of `__aarch64_read_tp()`. A freshly created thread has neither until
+/// [`init_thread_vars()`] runs.
+#[inline]
+pub fn is_initialized() -> bool {
+    let tv = thread_vars_ptr();
+
+    // SAFETY: `tv` points to a valid `ThreadVars` inside the current thread's TLS block. The
+    // fields are plain integers and pointers, so reading them uninitialized only yields
+    // garbage, which is what this check detects.
+    unsafe {
+        ptr::read_volatile(&raw const (*tv).magic) == THREAD_VARS_MAGIC
+            && !ptr::read_volatile(&raw const (*tv).tls_ptr).is_null()
+    }
+}
+
+/// Returns the number of bytes a spawned thread's TLS data block must span.
+///
+/// This covers the Thread Control Block (TCB) padding plus the `.tdata` and `.tbss` image, i.e.
+/// everything [`init_tls_data_segment()`] writes. Allocations should be aligned to at least
+/// the linker-provided `__tls_align`.
+#[inline]
+pub fn tls_data_segment_size() -> usize {
+    let tls_size = (&raw const __tls_end as usize) - (&raw const __tls_start as usize);
+    tls_data_start_offset() + tls_size
+}
+
+/// Initializes a spawned thread's TLS data block and returns its thread pointer.
+///
+/// Copies the initialized `.tdata` image from `__tdata_lma` into the block, right after the
+/// Thread Control Block (TCB), and zeroes the following `.tbss` region. The returned pointer
+/// is the `tls_ptr` value to pass to [`init_thread_vars()`] on the new thread.
+///
+/// # Safety
+///
+/// `tls_block` must be valid for writes of [`tls_data_segment_size()`] bytes, aligned to the
+/// linker-provided `__tls_align`, and not accessed concurrently.
+pub unsafe fn init_tls_data_segment(tls_block: *mut u8) -> *mut c_void {
+    // `__tls_start..__tls_end` spans `.tdata` followed by `.tbss`
+    let tls_size = (&raw const __tls_end as usize) - (&raw const __tls_start as usize);
+    let tdata_size = (&raw const __tdata_lma_end as usize) - (&raw const __tdata_lma as usize);
+
+    // SAFETY: The caller guarantees `tls_block` spans `tls_data_segment_size()` bytes, which
+    // is the start offset plus `tls_size`.
+    let data = unsafe { tls_block.add(tls_data_start_offset()) };
+
+    if tdata_size > 0 {
+        // SAFETY: The source is the read-only `.tdata` image and the destination lies within
+        // the caller's block; the two never overlap.
+        unsafe { ptr::copy_nonoverlapping(&raw const __tdata_lma, data, tdata_size) };
+    }
+
+    if tls_size > tdata_size {
+        // SAFETY: `.tbss` directly follows `.tdata` and ends within the caller's block.
+        unsafe { ptr::write_bytes(data.add(tdata_size), 0, tls_size - tdata_size) };
+    }
+
+    tls_block.cast()
+}
+
+/// Returns the offset from the thread pointer to the start of `.tdata`.
+///
+/// The Horizon TCB is two pointer-sized slots; the data starts after it, or at the TLS
+/// alignment if that is larger (libnx's `getTlsStartOffset()`).
+#[inline]
+fn tls_data_start_offset() -> usize {
+    let tcb_sz = 2 * size_of::<*mut c_void>();
+
+    // SAFETY: `__tls_align` is a linker-provided `usize` holding the TLS alignment.
+    let align = unsafe { __tls_align };
+
+    if align > tcb_sz { align } else { tcb_sz }
+}
+
+// SAFETY: The symbols are defined in the linker script (`switch.ld`).
+unsafe extern "C" {
+    /// Start of the main thread's `.tdata` + `.tbss` image.
+    static __tls_start: u8;
+    /// End (one-past-the-last byte) of the main thread's `.tdata` + `.tbss` image.
+    static __tls_end: u8;
+    /// Alignment requirement of the TLS data block.
+    static __tls_align: usize;
+    /// Load address of the initialized `.tdata` image.
+    static __tdata_lma: u8;
+    /// End (one-past-the-last byte) of the `.tdata` image.
+    static __tdata_lma_end: u8;
+}
+
 /// Returns a type-safe pointer to the current thread's language-specific thread object.
 ///
 /// This is a generic accessor for the `ThreadVars.thread_info_ptr` field. It returns
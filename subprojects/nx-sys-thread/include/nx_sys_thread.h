@@ -41,3 +41,43 @@ ThreadVars* __nx_sys_thread_get_thread_vars(void);
  * @return The current thread handle.
  */
 uint32_t __nx_sys_thread_get_current_thread_handle(void);
+
+/**
+ * @brief Opaque thread scope, handed to the body of __nx_sys_thread__thread_scope.
+ */
+typedef struct NxThreadScope NxThreadScope;
+
+/**
+ * @brief Thread scope body.
+ * @param scope Scope to spawn threads in, valid until the body returns.
+ * @param arg Argument passed to __nx_sys_thread__thread_scope.
+ */
+typedef void (*NxThreadScopeFn)(const NxThreadScope* scope, void* arg);
+
+/**
+ * @brief Runs a body in a new thread scope, then joins every thread spawned in it.
+ * @note Scoped threads may take pointers to the caller's stack: they are all joined before
+ *       this function returns.
+ * @param body Scope body.
+ * @param arg Argument passed to the body.
+ */
+void __nx_sys_thread__thread_scope(NxThreadScopeFn body, void* arg);
+
+/**
+ * @brief Spawns a thread in a thread scope.
+ * @warning Scoped threads have no newlib reentrancy state: the entry must not use errno or stdio.
+ * @param scope Scope handed to the running scope body.
+ * @param entry Thread entry point.
+ * @param arg Argument passed to the entry point.
+ * @return Result code.
+ */
+uint32_t __nx_sys_thread__thread_scope_spawn(const NxThreadScope* scope, void (*entry)(void*), void* arg);
+
+/**
+ * @brief Registers a callback to run when the current thread exits.
+ * @note Callbacks run most recently registered first, and may register more callbacks, which run
+ *       in the same teardown. Those of the main thread run at process exit.
+ * @param f Callback.
+ * @param arg Argument passed to the callback, which must stay valid until it runs.
+ */
+void __nx_sys_thread__at_thread_exit(void (*f)(void*), void* arg);
@@ -7,6 +7,8 @@ mod libsysbase_usleep;
 mod slots;
 mod thread_activity;
 mod thread_context;
+mod thread_exit;
 mod thread_info;
+mod thread_scope;
 mod thread_wait;
 mod tls;
@@ -0,0 +1,35 @@
+//! FFI bindings for the thread exit callbacks.
+
+use core::ffi::c_void;
+
+use crate::thread_impl as sys;
+
+/// Registers `f(arg)` to run when the current thread exits.
+///
+/// Callbacks run in reverse registration order, see [`sys::at_thread_exit`].
+///
+/// # Safety
+///
+/// `f` must be a valid function pointer, and `arg` must stay valid until the
+/// callback runs.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_thread__at_thread_exit(
+    f: unsafe extern "C" fn(arg: *mut c_void),
+    arg: *mut c_void,
+) {
+    let arg = CallbackArg(arg);
+
+    sys::at_thread_exit(move || {
+        // SAFETY: The caller guarantees `f` and `arg` are valid until the callback runs.
+        unsafe { f(arg.into_inner()) }
+    });
+}
+
+/// Argument of an exit callback.
+struct CallbackArg(*mut c_void);
+
+impl CallbackArg {
+    fn into_inner(self) -> *mut c_void {
+        self.0
+    }
+}
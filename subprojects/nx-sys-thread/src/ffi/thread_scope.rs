@@ -0,0 +1,78 @@
+//! FFI bindings for the scoped thread API.
+//!
+//! C has no closures, so a scope is opened with a body callback: the body
+//! receives an opaque [`NxThreadScope`] to spawn threads in, and every thread
+//! spawned in it is joined before [`__nx_sys_thread__thread_scope`] returns.
+//! Scoped threads may therefore take pointers to the caller's stack.
+
+use core::ffi::c_void;
+
+use nx_svc::error::ToRawResultCode;
+
+use crate::thread_impl as sys;
+
+/// Opaque scope handed to the body of [`__nx_sys_thread__thread_scope`].
+#[repr(C)]
+pub struct NxThreadScope {
+    _priv: [u8; 0],
+}
+
+/// Runs `body` in a new thread scope, then joins every thread spawned in it.
+///
+/// # Safety
+///
+/// `body` must be a valid function pointer. The scope pointer it receives is
+/// only valid until it returns, and must only be used from the calling thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_thread__thread_scope(
+    body: unsafe extern "C" fn(scope: *const NxThreadScope, arg: *mut c_void),
+    arg: *mut c_void,
+) {
+    sys::scope(|scope| {
+        let scope = (scope as *const sys::Scope<'_, '_>).cast::<NxThreadScope>();
+
+        // SAFETY: The caller guarantees `body` is valid; the scope outlives the call.
+        unsafe { body(scope, arg) }
+    });
+}
+
+/// Spawns a thread running `entry(arg)` in a thread scope.
+///
+/// The thread is joined when the scope ends.
+///
+/// # Safety
+///
+/// - `scope` must be the pointer handed to the running scope body, on the thread running it.
+/// - `entry` must be a valid function pointer, and `arg` must stay valid for the thread until
+///   the scope ends. Scoped threads have no newlib reentrancy state, so `entry` must not rely
+///   on it (e.g., `errno` or stdio).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn __nx_sys_thread__thread_scope_spawn(
+    scope: *const NxThreadScope,
+    entry: unsafe extern "C" fn(arg: *mut c_void),
+    arg: *mut c_void,
+) -> u32 {
+    // SAFETY: The caller guarantees `scope` comes from the running scope body.
+    let scope = unsafe { &*scope.cast::<sys::Scope<'_, '_>>() };
+    let arg = ThreadArg(arg);
+
+    scope
+        .spawn(move || {
+            // SAFETY: The caller guarantees `entry` and `arg` are valid for the thread.
+            unsafe { entry(arg.into_inner()) }
+        })
+        .map_or_else(|err| err.to_rc(), |_| 0)
+}
+
+/// Thread argument handed over to a scoped thread.
+struct ThreadArg(*mut c_void);
+
+// SAFETY: The caller of `__nx_sys_thread__thread_scope_spawn` hands the
+// argument over to the spawned thread.
+unsafe impl Send for ThreadArg {}
+
+impl ThreadArg {
+    fn into_inner(self) -> *mut c_void {
+        self.0
+    }
+}
@@ -1,4 +1,5 @@
 #![no_std]
+#![feature(thread_local)]
 
 extern crate alloc;
 extern crate nx_alloc; // Provides #[global_allocator]
@@ -8,6 +9,7 @@ extern crate nx_panic_handler; // provides #[panic_handler]
 pub mod ffi;
 
 mod thread_impl;
+pub mod tls;
 pub mod tls_block;
 
 pub use nx_sys_thread_tls as tls_region;
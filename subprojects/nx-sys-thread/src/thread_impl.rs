@@ -6,6 +6,7 @@ mod activity;
 mod context;
 mod exit;
 mod handle;
+mod scope;
 mod sleep;
 mod stackmem;
 mod wait;
@@ -14,6 +15,7 @@ pub use activity::*;
 pub use context::*;
 pub use exit::*;
 pub use handle::*;
+pub use scope::*;
 pub use sleep::*;
 pub use stackmem::*;
 pub use wait::*;
@@ -23,7 +23,7 @@ use super::handle::Thread;
 /// [`super::activity::pause`]) otherwise the kernel will refuse the request
 /// with an error.
 pub fn dump_context(thread: &Thread) -> Result<Context, DumpContextError> {
-    svc::get_context3(thread.handle)
+    svc::get_context(thread.handle)
         .map(Into::into)
         .map_err(Into::into)
 }
@@ -136,17 +136,27 @@ pub enum DumpContextError {
     #[error("Invalid handle")]
     InvalidHandle,
 
+    /// Target is the calling thread.
+    #[error("Busy")]
+    Busy,
+
+    /// Target thread is not paused.
+    #[error("Invalid state")]
+    InvalidState,
+
     /// Any unforeseen kernel error. Contains the original [`nx_svc::result::Error`]
     /// so callers can inspect the raw result code.
     #[error("Unknown error: {0}")]
     Unknown(nx_svc::result::Error),
 }
 
-impl From<svc::GetContext3Error> for DumpContextError {
-    fn from(value: svc::GetContext3Error) -> Self {
+impl From<svc::GetContextError> for DumpContextError {
+    fn from(value: svc::GetContextError) -> Self {
         match value {
-            svc::GetContext3Error::InvalidHandle => DumpContextError::InvalidHandle,
-            svc::GetContext3Error::Unknown(err) => DumpContextError::Unknown(err),
+            svc::GetContextError::InvalidHandle => DumpContextError::InvalidHandle,
+            svc::GetContextError::Busy => DumpContextError::Busy,
+            svc::GetContextError::InvalidState => DumpContextError::InvalidState,
+            svc::GetContextError::Unknown(err) => DumpContextError::Unknown(err),
         }
     }
 }
@@ -155,7 +165,9 @@ impl From<svc::GetContext3Error> for DumpContextError {
 impl nx_svc::error::ToRawResultCode for DumpContextError {
     fn to_rc(self) -> nx_svc::error::ResultCode {
         match self {
-            Self::InvalidHandle => svc::GetContext3Error::InvalidHandle.to_rc(),
+            Self::InvalidHandle => svc::GetContextError::InvalidHandle.to_rc(),
+            Self::Busy => svc::GetContextError::Busy.to_rc(),
+            Self::InvalidState => svc::GetContextError::InvalidState.to_rc(),
             Self::Unknown(err) => err.to_rc(),
         }
     }
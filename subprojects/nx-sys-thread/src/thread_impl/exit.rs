@@ -1,33 +1,128 @@
 //! Thread exit implementation
 //!
-//! This module provides the thread exit functionality.
+//! This module provides the thread exit functionality, and the per-thread exit
+//! callbacks registered with [`at_thread_exit`].
+//!
+//! ## Teardown order
+//!
+//! When a thread spawned by this crate exits, [`exit`] runs, in order:
+//! 1. The thread's exit callbacks, most recently registered first
+//! 2. The TLS slot destructors (when slots support is reimplemented)
+//!
+//! Exit callbacks therefore run while the thread's TLS slots and thread-local
+//! variables are still valid, and may use them.
+//!
+//! Threads created with libnx's `threadCreate` and started with `threadStart`
+//! exit through libnx's `threadExit` instead. Its last step is the C
+//! `svcExitThread`, whose [exit hook](nx_svc::thread::set_exit_hook) runs the
+//! callbacks, after libnx's own TLS slot destructors but while thread-local
+//! variables are still valid. This needs the `nx-svc` FFI overrides.
+//!
+//! The main thread is torn down by neither: its exit callbacks run when the
+//! process exits, from a handler registered with the C runtime's `atexit`.
+//!
+//! Both handlers are registered the first time a callback is registered.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::RefCell,
+    ffi::c_int,
+    mem,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use nx_svc::thread as svc;
 
 use super::handle::Thread;
 
+crate::tls! {
+    /// Exit callbacks of the current thread, in registration order.
+    static EXIT_CALLBACKS: RefCell<Vec<Box<dyn FnOnce()>>> = RefCell::new(Vec::new());
+}
+
+/// Whether the process exit handler running the main thread's callbacks was
+/// registered with the C runtime.
+static PROCESS_EXIT_HANDLER_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Registers `f` to run when the current thread exits.
+///
+/// Callbacks run in reverse registration order (LIFO), before the thread's TLS
+/// slot destructors, see [Teardown order](self#teardown-order). A callback may
+/// register further callbacks; they run in the same teardown, before the
+/// remaining earlier ones.
+///
+/// Callbacks registered on the main thread run when the process exits. Those
+/// registered on a thread created with libnx's `threadCreate` run when it
+/// exits through `threadExit`, see [Teardown order](self#teardown-order).
+pub fn at_thread_exit(f: impl FnOnce() + 'static) {
+    // Threads created by libnx exit through the C `svcExitThread`
+    svc::set_exit_hook(run_thread_exit_callbacks);
+    register_process_exit_handler();
+
+    EXIT_CALLBACKS.with(|callbacks| callbacks.borrow_mut().push(Box::new(f)));
+}
+
+/// Runs, then drops, the current thread's exit callbacks, most recently
+/// registered first.
+fn run_thread_exit_callbacks() {
+    // The borrow is released before each call, so callbacks can register more
+    while let Some(callback) = EXIT_CALLBACKS.with(|callbacks| callbacks.borrow_mut().pop()) {
+        callback();
+    }
+
+    // Thread-local variables are never dropped, so free the list's buffer
+    drop(EXIT_CALLBACKS.with(|callbacks| mem::take(&mut *callbacks.borrow_mut())));
+}
+
+/// Registers the handler running the main thread's exit callbacks at process
+/// exit, if not registered yet.
+fn register_process_exit_handler() {
+    unsafe extern "C" {
+        // This is a newlib/libc function
+        fn atexit(func: extern "C" fn()) -> c_int;
+    }
+
+    extern "C" fn process_exit_handler() {
+        // Runs on the thread calling `exit()`, the main thread when `main` returns
+        run_thread_exit_callbacks();
+    }
+
+    if PROCESS_EXIT_HANDLER_REGISTERED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    // SAFETY: `process_exit_handler` is a valid handler for the whole process
+    // lifetime.
+    if unsafe { atexit(process_exit_handler) } != 0 {
+        // Out of handler slots: retry on the next registration
+        PROCESS_EXIT_HANDLER_REGISTERED.store(false, Ordering::Release);
+    }
+}
+
 /// Exits the current thread.
 ///
 /// This function performs cleanup operations and terminates the thread:
+/// - Runs the thread's exit callbacks, see [`at_thread_exit`]
 /// - Runs TLS slot destructors (when slots support is reimplemented)
-/// - Removes the thread from the global registry
 /// - Clears pointer fields to catch use-after-free bugs
 /// - Terminates the thread via svcExitThread (never returns)
 ///
+/// There is no thread registry to remove the thread from: threads spawned by
+/// this crate are never registered, and those created with libnx's
+/// `threadCreate` stay in libnx's own thread list, which its `threadExit`
+/// maintains.
+///
 /// # Safety
 /// This function must only be called by the thread that is exiting.
 /// The thread parameter must be a valid pointer to the current thread's info structure.
 pub unsafe fn exit(_thread: &mut Thread) -> ! {
+    // Exit callbacks run first, while TLS slots and thread-locals are still valid
+    run_thread_exit_callbacks();
+
     // TODO: Reimplement TLS slots destructors
     // SAFETY: Called on the current thread.
     // unsafe { slots::run_destructors() };
 
-    // TODO: Reimplement thread registry functionality
-    // Remove thread from the global registry
-    // SAFETY: `thread` was previously inserted during creation; removing it
-    // now is valid and ensures the registry is kept consistent.
-    // unsafe { registry::remove(thread) };
-
     // TODO: Reimplement TLS slots cleanup
     // Clear pointer fields to catch use-after-free bugs in debug builds.
     // thread.tls_slots = None;
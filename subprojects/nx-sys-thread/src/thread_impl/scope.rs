@@ -0,0 +1,342 @@
+//! Scoped threads
+//!
+//! [`scope`] spawns threads that may borrow non-`'static` data from the
+//! enclosing stack frame, the counterpart of `std::thread::scope`. Every thread
+//! spawned in a scope is joined before [`scope`] returns, so the borrowed data
+//! is guaranteed to outlive it.
+//!
+//! # Panics
+//!
+//! The runtime is built with `panic = "abort"`, so a panic in a scoped thread
+//! aborts the whole process. Panics are never propagated to the spawning
+//! thread, and [`ScopedJoinHandle::join`] never observes one.
+//!
+//! # Limitations
+//!
+//! Scoped threads carry no newlib reentrancy state, so they must not call into
+//! C code that relies on it (e.g., `errno` or stdio).
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cell::{RefCell, UnsafeCell},
+    ffi::c_void,
+    marker::PhantomData,
+    mem, ptr,
+};
+
+use nx_svc::{
+    raw::INVALID_HANDLE,
+    thread::{self as svc, CreateThreadError, Handle},
+};
+use nx_sys_mem::{alignment::PAGE_SIZE, stack::MapError as StackMemMapError};
+
+use super::{
+    activity::{self, ThreadStartError},
+    exit,
+    handle::Thread,
+    stackmem::{PageAlignedBufError, PageAlignedBuffer, ThreadStackMem},
+    wait::{self, WaitForExitError},
+};
+use crate::{tls_block, tls_region};
+
+/// Default stack size of a scoped thread (128 KiB).
+pub const SCOPED_THREAD_STACK_SIZE: usize = 0x20000;
+
+/// Priority of scoped threads, matching libnx's default thread priority.
+const SCOPED_THREAD_PRIORITY: i32 = 0x2C;
+
+/// Processor ID selecting the process' default core.
+const SCOPED_THREAD_CPUID: i32 = -2;
+
+/// Creates a scope for spawning threads that borrow local data.
+///
+/// The closure receives a [`Scope`] to spawn threads with. Once the closure
+/// returns, every thread spawned in the scope that has not been joined yet is
+/// joined automatically, and then the closure's result is returned.
+///
+/// Because of that join, spawned threads may borrow data from the enclosing
+/// stack frame, such as the two halves of a local array split with
+/// `split_at_mut`.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        threads: RefCell::new(Vec::new()),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+
+    let result = f(&scope);
+    scope.join_all();
+
+    result
+}
+
+/// A scope to spawn scoped threads in.
+///
+/// See [`scope`] for details. The scope is owned by the spawning thread;
+/// scoped threads cannot spawn further threads in it.
+pub struct Scope<'scope, 'env: 'scope> {
+    threads: RefCell<Vec<ScopedThread>>,
+    /// Invariance over `'scope`, so the scope cannot be shrunk.
+    scope: PhantomData<&'scope mut &'scope ()>,
+    /// Invariance over `'env`, so borrowed data cannot be shrunk.
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope> Scope<'scope, '_> {
+    /// Spawns a thread with the default stack size
+    /// ([`SCOPED_THREAD_STACK_SIZE`]).
+    ///
+    /// The thread may borrow anything that outlives the scope. It is joined
+    /// automatically at the end of the scope unless joined earlier through the
+    /// returned handle.
+    #[inline]
+    pub fn spawn<F, T>(&'scope self, f: F) -> Result<ScopedJoinHandle<'scope, T>, SpawnError>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        self.spawn_with_stack_size(SCOPED_THREAD_STACK_SIZE, f)
+    }
+
+    /// Spawns a thread with a stack of at least `stack_size` bytes.
+    ///
+    /// The stack size is rounded up to a multiple of the page size.
+    pub fn spawn_with_stack_size<F, T>(
+        &'scope self,
+        stack_size: usize,
+        f: F,
+    ) -> Result<ScopedJoinHandle<'scope, T>, SpawnError>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let packet = Arc::new(Packet {
+            result: UnsafeCell::new(None),
+        });
+
+        let their_packet = Arc::clone(&packet);
+        let main: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            let result = f();
+            // SAFETY: Only this thread writes the result, and it is only read
+            // after this thread has exited.
+            unsafe { *their_packet.result.get() = Some(result) };
+        });
+
+        // SAFETY: The scope joins the thread before `'scope` ends, so the
+        // closure never outlives the data it borrows.
+        let main: Box<dyn FnOnce() + Send + 'static> = unsafe { mem::transmute(main) };
+
+        let thread = ScopedThread::spawn(stack_size, main)?;
+        let handle = thread.thread.handle;
+        self.threads.borrow_mut().push(thread);
+
+        Ok(ScopedJoinHandle {
+            handle,
+            packet,
+            _scope: PhantomData,
+        })
+    }
+
+    /// Joins every thread spawned in the scope and releases its resources.
+    fn join_all(&self) {
+        let threads = mem::take(&mut *self.threads.borrow_mut());
+        for thread in threads {
+            thread.join_and_release();
+        }
+    }
+}
+
+/// An owned permission to join a scoped thread.
+///
+/// Dropping the handle does not detach the thread; it is still joined at the
+/// end of its scope.
+pub struct ScopedJoinHandle<'scope, T> {
+    handle: Handle,
+    packet: Arc<Packet<T>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<T> ScopedJoinHandle<'_, T> {
+    /// Waits for the thread to finish and returns its result.
+    pub fn join(self) -> Result<T, WaitForExitError> {
+        wait::wait_handle_exit(&self.handle)?;
+
+        // SAFETY: The thread has exited, so the result is no longer written.
+        let result = unsafe { (*self.packet.result.get()).take() };
+
+        // A thread that panicked aborts the process, so an exited thread
+        // always left its result behind.
+        Ok(result.expect("scoped thread exited without a result"))
+    }
+}
+
+/// Error returned by [`Scope::spawn`] and [`Scope::spawn_with_stack_size`].
+#[derive(Debug, thiserror::Error)]
+pub enum SpawnError {
+    /// Failed to allocate the stack memory.
+    #[error("Failed to allocate stack memory")]
+    StackAlloc(#[source] PageAlignedBufError),
+    /// Failed to map the stack memory.
+    #[error("Failed to map stack memory")]
+    StackMap(#[source] StackMemMapError),
+    /// Failed to create the kernel thread.
+    #[error("Failed to create thread")]
+    Create(#[source] CreateThreadError),
+    /// Failed to start the kernel thread.
+    #[error("Failed to start thread")]
+    Start(#[source] ThreadStartError),
+}
+
+#[cfg(feature = "ffi")]
+impl nx_svc::error::ToRawResultCode for SpawnError {
+    fn to_rc(self) -> nx_svc::error::ResultCode {
+        use nx_svc::error::KernelError;
+
+        match self {
+            Self::StackAlloc(_) | Self::StackMap(StackMemMapError::VirtAddrAllocFailed) => {
+                KernelError::OutOfMemory.to_rc()
+            }
+            Self::StackMap(StackMemMapError::Svc(err)) => err.to_rc(),
+            Self::Create(err) => err.to_rc(),
+            Self::Start(err) => err.to_rc(),
+        }
+    }
+}
+
+/// Storage for a scoped thread's result.
+struct Packet<T> {
+    result: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: The result is written once by the scoped thread and only read by the
+// joiner after the thread has exited.
+unsafe impl<T: Send> Sync for Packet<T> {}
+
+/// Values handed over to a scoped thread on start.
+///
+/// Written by the spawning thread before the thread is started, and left
+/// untouched until it has exited.
+struct StartArgs {
+    handle: Handle,
+    thread: *mut Thread,
+    tls_tp: *mut c_void,
+    main: Option<Box<dyn FnOnce() + Send>>,
+}
+
+/// Resources of a running scoped thread.
+struct ScopedThread {
+    thread: Box<Thread>,
+    /// Kept alive until the thread exits.
+    _start: Box<StartArgs>,
+}
+
+impl ScopedThread {
+    /// Allocates the stack and TLS of a new thread, then creates and starts it.
+    fn spawn(stack_size: usize, main: Box<dyn FnOnce() + Send>) -> Result<Self, SpawnError> {
+        // Like libnx, the TLS block lives in the same mapping, above the stack
+        let stack_size = stack_size.max(1).next_multiple_of(PAGE_SIZE);
+        let tls_offset = stack_size.next_multiple_of(tls_block::align().max(1));
+        let total_size =
+            (tls_offset + tls_region::tls_data_segment_size()).next_multiple_of(PAGE_SIZE);
+
+        let buffer = PageAlignedBuffer::alloc(total_size).map_err(SpawnError::StackAlloc)?;
+        let stack_mem = ThreadStackMem::map(buffer).map_err(SpawnError::StackMap)?;
+
+        let mirror = stack_mem.mirror_ptr().as_ptr().cast::<u8>();
+        // SAFETY: Both offsets lie within the mapped region.
+        let (stack_top, tls_ptr) = unsafe { (mirror.add(stack_size), mirror.add(tls_offset)) };
+        // SAFETY: The TLS block spans `tls_data_segment_size()` bytes, is
+        // aligned to the TLS alignment, and is not used by anyone else yet.
+        let tls_tp = unsafe { tls_region::init_tls_data_segment(tls_ptr) };
+
+        let mut start = Box::new(StartArgs {
+            // SAFETY: Replaced by the real handle before the thread starts.
+            handle: unsafe { Handle::from_raw(INVALID_HANDLE) },
+            thread: ptr::null_mut(),
+            tls_tp,
+            main: Some(main),
+        });
+
+        // SAFETY: `thread_entry` has the expected signature, `start` outlives
+        // the thread, and `stack_top` is page-aligned and stays mapped until
+        // the thread has exited.
+        let handle = match unsafe {
+            svc::create(
+                thread_entry as *mut c_void,
+                (&raw mut *start).cast(),
+                stack_top.cast(),
+                SCOPED_THREAD_PRIORITY,
+                SCOPED_THREAD_CPUID,
+            )
+        } {
+            Ok(handle) => handle,
+            Err(err) => {
+                let _ = stack_mem.unmap();
+                return Err(SpawnError::Create(err));
+            }
+        };
+
+        let mut thread = Box::new(Thread { handle, stack_mem });
+        start.handle = handle;
+        start.thread = &raw mut *thread;
+
+        if let Err(err) = activity::start(&thread) {
+            let Thread { handle, stack_mem } = *thread;
+            let _ = svc::close_handle(handle);
+            let _ = stack_mem.unmap();
+            return Err(SpawnError::Start(err));
+        }
+
+        Ok(Self {
+            thread,
+            _start: start,
+        })
+    }
+
+    /// Waits for the thread to exit and releases its resources.
+    fn join_and_release(self) {
+        if wait::wait_thread_exit(&self.thread).is_err() {
+            // The thread may still be running on its stack, so keep every
+            // resource alive rather than freeing memory in use.
+            mem::forget(self);
+            return;
+        }
+
+        let Thread { handle, stack_mem } = *self.thread;
+        let _ = svc::close_handle(handle);
+        let _ = stack_mem.unmap();
+    }
+}
+
+/// Entry point of every scoped thread.
+///
+/// # Safety
+///
+/// `arg` must point to the thread's [`StartArgs`].
+unsafe extern "C" fn thread_entry(arg: *mut c_void) -> ! {
+    let start = arg.cast::<StartArgs>();
+
+    // SAFETY: The spawning thread filled in the start args before starting
+    // this thread, and does not access them until it has exited.
+    let (handle, thread, tls_tp, main) = unsafe {
+        (
+            (*start).handle,
+            (*start).thread,
+            (*start).tls_tp,
+            (*start).main.take(),
+        )
+    };
+
+    // SAFETY: Called once, on this thread, before any code reads its TLS.
+    unsafe { tls_region::init_thread_vars(handle, thread.cast(), ptr::null_mut(), tls_tp) };
+
+    if let Some(main) = main {
+        main();
+    }
+
+    // SAFETY: Called on the exiting thread with its own `Thread`.
+    unsafe { exit::exit(&mut *thread) }
+}
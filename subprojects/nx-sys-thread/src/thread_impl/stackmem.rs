@@ -5,7 +5,7 @@ use nx_sys_mem::{
     buf::{Buf, Buffer, BufferRef},
     stack::{
         self as stack_mem, MapError as StackMemMapError, MappedStackMemory,
-        MappedStackMemory as StackMem,
+        MappedStackMemory as StackMem, UnmapError as StackMemUnmapError,
     },
 };
 
@@ -38,6 +38,13 @@ where
     pub fn size(&self) -> usize {
         self.0.size()
     }
+
+    /// Unmaps the thread stack memory, returning the backing buffer
+    ///
+    /// The thread using the stack must have exited.
+    pub fn unmap(self) -> Result<B, StackMemUnmapError> {
+        unsafe { stack_mem::unmap(self.0) }
+    }
 }
 
 impl<'a> ThreadStackMem<BufferRef<'a>> {
@@ -0,0 +1,96 @@
+//! Thread-local variables.
+//!
+//! `#[thread_local]` statics are addressed through `__aarch64_read_tp()`, which loads the
+//! thread pointer from `ThreadVars.tls_ptr` in the thread's TLS region. Until that field is
+//! set, every access silently reads and writes through a garbage pointer. The [`tls!`]
+//! macro declares thread-local variables behind a [`LocalKey`] whose accessor checks, in
+//! debug builds, that the TLS region was initialized first. The value is then reached
+//! through [`LocalKey::with`], typically wrapped in a `Cell` or `RefCell` to update it.
+//!
+//! ## Initialization order
+//!
+//! Thread-local variables must not be accessed before `init_thread_vars()`
+//! ([`tls_region::init_thread_vars`](crate::tls_region::init_thread_vars)) has run on the
+//! current thread. The runtime does this before `main` for the main thread, and spawned
+//! threads do it before running their entry point, so this only concerns code that runs
+//! earlier: runtime startup, and allocator or synchronization code used during it.
+
+use crate::tls_region;
+
+/// Declares thread-local variables accessed through a [`LocalKey`].
+///
+/// Each `static NAME: T = init;` item becomes a `LocalKey<T>`; every thread sees its own
+/// copy of the value, initialized to `init`, which must be a constant expression.
+///
+/// The values are `#[thread_local]` statics, so the crate using the macro must enable
+/// `#![feature(thread_local)]`.
+#[macro_export]
+macro_rules! tls {
+    () => {};
+    (
+        $(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $init:expr;
+        $($rest:tt)*
+    ) => {
+        $(#[$attr])*
+        $vis static $name: $crate::tls::LocalKey<$ty> = {
+            fn __get() -> *const $ty {
+                #[thread_local]
+                static VALUE: $ty = $init;
+                &raw const VALUE
+            }
+            // SAFETY: `__get` returns the current thread's copy of a `#[thread_local]` static.
+            unsafe { $crate::tls::LocalKey::new(__get) }
+        };
+
+        $crate::tls!($($rest)*);
+    };
+}
+
+/// Key to a thread-local variable declared with [`tls!`].
+pub struct LocalKey<T: 'static> {
+    get: fn() -> *const T,
+}
+
+impl<T: 'static> LocalKey<T> {
+    /// Creates a key from the accessor of a `#[thread_local]` static.
+    ///
+    /// # Safety
+    ///
+    /// `get` must return a pointer to the current thread's copy of a `#[thread_local]`
+    /// static. Use [`tls!`] instead of calling this directly.
+    #[doc(hidden)]
+    pub const unsafe fn new(get: fn() -> *const T) -> Self {
+        Self { get }
+    }
+
+    /// Calls `f` with a reference to the current thread's value.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the current thread's TLS region is not initialized yet,
+    /// see [`assert_tls_initialized`].
+    #[track_caller]
+    pub fn with<R>(&'static self, f: impl FnOnce(&T) -> R) -> R {
+        if cfg!(debug_assertions) {
+            assert_tls_initialized();
+        }
+
+        // SAFETY: The pointer refers to the current thread's copy, which lives as long as the
+        // thread; the reference does not escape `f`.
+        f(unsafe { &*(self.get)() })
+    }
+}
+
+/// Panics if the current thread's TLS region is not initialized.
+///
+/// Checks the prerequisites of `__aarch64_read_tp()`: the `ThreadVars` magic and the thread
+/// pointer it loads. Call this early in code that may run before thread setup, to turn
+/// silent memory corruption into an immediate, explicit failure.
+#[track_caller]
+pub fn assert_tls_initialized() {
+    assert!(
+        tls_region::is_initialized(),
+        "thread-local storage accessed before the thread's TLS was initialized \
+         (ThreadVars magic or tls_ptr missing; init_thread_vars() has not run on this thread)"
+    );
+}
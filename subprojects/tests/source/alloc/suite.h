@@ -0,0 +1,64 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that shrinking an allocation splits it in place.
+ *
+ * This test verifies that realloc to a smaller size:
+ * 1. Returns the same pointer, with the data preserved
+ * 2. Returns the tail of the block to the heap
+ */
+test_rc_t test_0001_realloc_shrink_splits_in_place(void);
+
+/**
+ * @brief Test that growing an allocation merges it with the free block after it.
+ *
+ * This test verifies that realloc to a larger size:
+ * 1. Returns the same pointer when the next block has been freed
+ * 2. Preserves the data
+ */
+test_rc_t test_0002_realloc_grow_merges_next_free_block(void);
+
+/**
+ * @brief Test that growing an allocation moves it when the next block is in use.
+ *
+ * This test verifies that realloc to a larger size:
+ * 1. Returns a new pointer when the next block is still allocated
+ * 2. Preserves the data
+ * 3. Leaves the next block untouched
+ */
+test_rc_t test_0003_realloc_grow_moves_past_used_block(void);
+
+/**
+ * @brief Benchmark `Vec::push`-style growth through realloc.
+ *
+ * Grows a buffer by doubling its capacity, as `Vec::push` does, and reports how
+ * many reallocations moved the buffer and how long the growth took, next to a
+ * malloc + memcpy + free baseline that always moves.
+ */
+test_rc_t test_0004_realloc_vec_growth_benchmark(void);
+
+/**
+ * Test suite for the heap allocator.
+ */
+static void alloc_suite(void) {
+    TEST_SUITE("alloc");
+
+    TEST_CASE(
+        "Test 0001: realloc_shrink_splits_in_place",
+        test_0001_realloc_shrink_splits_in_place
+    )
+    TEST_CASE(
+        "Test 0002: realloc_grow_merges_next_free_block",
+        test_0002_realloc_grow_merges_next_free_block
+    )
+    TEST_CASE(
+        "Test 0003: realloc_grow_moves_past_used_block",
+        test_0003_realloc_grow_moves_past_used_block
+    )
+    TEST_CASE(
+        "Test 0004: realloc_vec_growth_benchmark",
+        test_0004_realloc_vec_growth_benchmark
+    )
+}
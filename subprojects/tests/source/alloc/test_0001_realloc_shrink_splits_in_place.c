@@ -0,0 +1,82 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+#include <switch.h>
+
+#include "nx_alloc.h"
+
+#include "../harness.h"
+
+#define OLD_SIZE 1024
+#define NEW_SIZE 256
+#define PATTERN 0xA5
+
+/**
+ * @brief Test that shrinking an allocation splits it in place.
+ *
+ * A guard block is allocated right after the block to shrink, so the freed
+ * tail cannot merge into the free space at the top of the heap.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_realloc_shrink_splits_in_place(void) {
+    Result rc = 0;
+
+    //* Given
+    uint8_t* block = malloc(OLD_SIZE);
+    uint8_t* guard = malloc(64);
+    if (block == NULL || guard == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    memset(block, PATTERN, OLD_SIZE);
+
+    NxAllocHeapStats before = {0};
+    __nx_alloc__heap_stats(&before);
+
+    //* When
+    uint8_t* const original = block;
+    uint8_t* shrunk = realloc(block, NEW_SIZE);
+    if (shrunk != NULL) {
+        block = shrunk;
+    }
+
+    NxAllocHeapStats after = {0};
+    __nx_alloc__heap_stats(&after);
+
+    //* Then
+    if (shrunk == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The block must not move
+    if (shrunk != original) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    for (size_t i = 0; i < NEW_SIZE; i++) {
+        if (shrunk[i] != PATTERN) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+    // The tail must go back to the heap
+    if (before.used - after.used != OLD_SIZE - NEW_SIZE) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (after.used + after.free != after.size) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    free(guard);
+    free(block);
+    return rc;
+}
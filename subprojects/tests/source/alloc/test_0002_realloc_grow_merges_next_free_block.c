@@ -0,0 +1,73 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+#include <switch.h>
+
+#include "../harness.h"
+
+#define BLOCK_SIZE 256
+#define NEW_SIZE 512
+#define PATTERN 0x5A
+
+/**
+ * @brief Test that growing an allocation merges it with the free block after it.
+ *
+ * Three blocks are allocated back to back, and the middle one is freed. The
+ * first block then grows into the freed one; the last block keeps the freed
+ * space from merging into the free space at the top of the heap.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED or
+ *         TEST_SKIPPED (the blocks were not allocated back to back) otherwise.
+ */
+test_rc_t test_0002_realloc_grow_merges_next_free_block(void) {
+    Result rc = 0;
+
+    //* Given
+    uint8_t* block = malloc(BLOCK_SIZE);
+    uint8_t* next = malloc(BLOCK_SIZE);
+    uint8_t* guard = malloc(BLOCK_SIZE);
+    if (block == NULL || next == NULL || guard == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The allocation metadata sits between the blocks, so allow some slack
+    if (next < block + BLOCK_SIZE || next > block + 2 * BLOCK_SIZE) {
+        rc = TEST_SKIPPED;
+        goto test_cleanup;
+    }
+
+    memset(block, PATTERN, BLOCK_SIZE);
+
+    free(next);
+    next = NULL;
+
+    //* When
+    uint8_t* grown = realloc(block, NEW_SIZE);
+
+    //* Then
+    if (grown == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The block must not move
+    if (grown != block) {
+        block = grown;
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    for (size_t i = 0; i < BLOCK_SIZE; i++) {
+        if (grown[i] != PATTERN) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    free(guard);
+    free(next);
+    free(block);
+    return rc;
+}
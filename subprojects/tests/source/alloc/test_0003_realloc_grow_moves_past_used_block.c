@@ -0,0 +1,69 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+#include <switch.h>
+
+#include "../harness.h"
+
+#define BLOCK_SIZE 256
+#define NEW_SIZE 4096
+#define PATTERN 0x3C
+#define NEXT_PATTERN 0xC3
+
+/**
+ * @brief Test that growing an allocation moves it when the next block is in use.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED or
+ *         TEST_SKIPPED (the blocks were not allocated back to back) otherwise.
+ */
+test_rc_t test_0003_realloc_grow_moves_past_used_block(void) {
+    Result rc = 0;
+
+    //* Given
+    uint8_t* block = malloc(BLOCK_SIZE);
+    uint8_t* next = malloc(BLOCK_SIZE);
+    if (block == NULL || next == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The allocation metadata sits between the blocks, so allow some slack
+    if (next < block + BLOCK_SIZE || next > block + 2 * BLOCK_SIZE) {
+        rc = TEST_SKIPPED;
+        goto test_cleanup;
+    }
+
+    memset(block, PATTERN, BLOCK_SIZE);
+    memset(next, NEXT_PATTERN, BLOCK_SIZE);
+
+    //* When
+    uint8_t* const original = block;
+    uint8_t* grown = realloc(block, NEW_SIZE);
+    if (grown != NULL) {
+        block = grown;
+    }
+
+    //* Then
+    if (grown == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The used neighbour is in the way, so the block must move
+    if (grown == original) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    for (size_t i = 0; i < BLOCK_SIZE; i++) {
+        if (grown[i] != PATTERN || next[i] != NEXT_PATTERN) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    free(next);
+    free(block);
+    return rc;
+}
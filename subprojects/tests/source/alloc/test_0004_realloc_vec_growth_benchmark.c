@@ -0,0 +1,142 @@
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <switch.h>
+
+#include "../harness.h"
+
+#define NUM_ELEMS (1 << 16)
+#define INITIAL_CAPACITY 4
+
+/**
+ * Result of a growth run.
+ */
+typedef struct {
+    uint64_t ticks;
+    uint32_t moves;
+    uint32_t grows;
+    int ok;
+} GrowthRun;
+
+/**
+ * Pushes NUM_ELEMS elements, doubling the capacity with realloc when full.
+ */
+static GrowthRun grow_with_realloc(void) {
+    GrowthRun run = {0};
+
+    size_t cap = INITIAL_CAPACITY;
+    uint32_t* buf = malloc(cap * sizeof(uint32_t));
+    if (buf == NULL) {
+        return run;
+    }
+
+    const uint64_t start = armGetSystemTick();
+    for (uint32_t len = 0; len < NUM_ELEMS; len++) {
+        if (len == cap) {
+            cap *= 2;
+            uint32_t* grown = realloc(buf, cap * sizeof(uint32_t));
+            if (grown == NULL) {
+                free(buf);
+                return run;
+            }
+
+            run.grows++;
+            if (grown != buf) {
+                run.moves++;
+            }
+            buf = grown;
+        }
+        buf[len] = len;
+    }
+    run.ticks = armGetSystemTick() - start;
+
+    run.ok = 1;
+    for (uint32_t i = 0; i < NUM_ELEMS; i++) {
+        if (buf[i] != i) {
+            run.ok = 0;
+            break;
+        }
+    }
+
+    free(buf);
+    return run;
+}
+
+/**
+ * Pushes NUM_ELEMS elements, doubling the capacity with malloc + memcpy + free when full.
+ */
+static GrowthRun grow_with_copy(void) {
+    GrowthRun run = {0};
+
+    size_t cap = INITIAL_CAPACITY;
+    uint32_t* buf = malloc(cap * sizeof(uint32_t));
+    if (buf == NULL) {
+        return run;
+    }
+
+    const uint64_t start = armGetSystemTick();
+    for (uint32_t len = 0; len < NUM_ELEMS; len++) {
+        if (len == cap) {
+            uint32_t* grown = malloc(cap * 2 * sizeof(uint32_t));
+            if (grown == NULL) {
+                free(buf);
+                return run;
+            }
+
+            memcpy(grown, buf, cap * sizeof(uint32_t));
+            free(buf);
+            cap *= 2;
+
+            run.grows++;
+            run.moves++;
+            buf = grown;
+        }
+        buf[len] = len;
+    }
+    run.ticks = armGetSystemTick() - start;
+
+    run.ok = 1;
+    for (uint32_t i = 0; i < NUM_ELEMS; i++) {
+        if (buf[i] != i) {
+            run.ok = 0;
+            break;
+        }
+    }
+
+    free(buf);
+    return run;
+}
+
+/**
+ * @brief Benchmark `Vec::push`-style growth through realloc.
+ *
+ * Only the data is asserted on; the timings and move counts are reported.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_realloc_vec_growth_benchmark(void) {
+    Result rc = 0;
+
+    //* Given
+    // Warm up the heap, so neither run pays for its initialization
+    free(malloc(NUM_ELEMS * sizeof(uint32_t)));
+
+    //* When
+    const GrowthRun copy = grow_with_copy();
+    const GrowthRun in_place = grow_with_realloc();
+
+    //* Then
+    if (!copy.ok || !in_place.ok) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    printf("\n  realloc:     %u/%u grows moved, %llu ns\n",
+        in_place.moves, in_place.grows, (unsigned long long)armTicksToNs(in_place.ticks));
+    printf("  malloc+copy: %u/%u grows moved, %llu ns\n  ",
+        copy.moves, copy.grows, (unsigned long long)armTicksToNs(copy.ticks));
+
+test_cleanup:
+    return rc;
+}
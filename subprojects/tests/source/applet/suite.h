@@ -0,0 +1,25 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that an empty applet message queue is not reported as an error.
+ *
+ * This test verifies that:
+ * 1. Every pending message is received successfully
+ * 2. Once the queue is drained, receiving succeeds without writing a message,
+ *    rather than failing with the NoMessage result (0x680)
+ */
+test_rc_t test_0001_receive_message_empty_queue_is_not_an_error(void);
+
+/**
+ * Test suite for the applet service helpers.
+ */
+static void applet_suite(void) {
+    TEST_SUITE("applet");
+
+    TEST_CASE(
+        "Test 0001: receive_message_empty_queue_is_not_an_error",
+        test_0001_receive_message_empty_queue_is_not_an_error
+    )
+}
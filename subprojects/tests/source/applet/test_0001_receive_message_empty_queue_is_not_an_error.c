@@ -0,0 +1,59 @@
+#include <stdbool.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "../harness.h"
+
+/// Receives a message from the applet message queue (nx-rt, not aliased to appletReceiveMessage).
+uint32_t __nx_rt__applet_receive_message(uint32_t* msg);
+
+/// Message value no applet message uses, left in place when the queue is empty
+#define NO_MESSAGE_SENTINEL 0xFFFFFFFF
+
+/// Upper bound on the messages pending at startup
+#define MAX_PENDING_MESSAGES 64
+
+/**
+ * @brief Test that an empty applet message queue is not reported as an error.
+ *
+ * The queue is drained one message at a time. The NoMessage result the
+ * service returns once it is empty must be reported as success, with the
+ * message left untouched.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_receive_message_empty_queue_is_not_an_error(void) {
+    Result rc = 0;
+
+    //* Given
+    bool drained = false;
+
+    //* When
+    for (int i = 0; i < MAX_PENDING_MESSAGES; i++) {
+        uint32_t msg = NO_MESSAGE_SENTINEL;
+        rc = __nx_rt__applet_receive_message(&msg);
+        if (R_FAILED(rc)) {
+            break;
+        }
+
+        if (msg == NO_MESSAGE_SENTINEL) {
+            drained = true;
+            break;
+        }
+    }
+
+    //* Then
+    // Verify neither a pending message nor the empty queue failed
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // Verify the queue was drained and reported as empty
+    if (!drained) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,37 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that ConfigEntry parsing stops at the EndOfList entry.
+ *
+ * This test verifies that:
+ * 1. The entries up to and including EndOfList are counted
+ * 2. The entries past EndOfList are not read
+ * 3. The array is not reported as truncated
+ */
+test_rc_t test_0001_config_entries_stop_at_end_of_list(void);
+
+/**
+ * @brief Test that an unterminated ConfigEntry array is truncated.
+ *
+ * This test verifies that an array with no EndOfList entry is parsed up to the
+ * entry bound and no further, and is reported as truncated.
+ */
+test_rc_t test_0002_config_entries_truncate_unterminated_array(void);
+
+/**
+ * Test suite for the loader environment parsing.
+ */
+static void env_suite(void) {
+    TEST_SUITE("env");
+
+    TEST_CASE(
+        "Test 0001: config_entries_stop_at_end_of_list",
+        test_0001_config_entries_stop_at_end_of_list
+    )
+    TEST_CASE(
+        "Test 0002: config_entries_truncate_unterminated_array",
+        test_0002_config_entries_truncate_unterminated_array
+    )
+}
@@ -0,0 +1,51 @@
+#include <stdbool.h>
+#include <switch.h>
+
+#include "nx_rt_env.h"
+
+#include "../harness.h"
+
+/// Number of entries in the array, including the ones past EndOfList
+#define ENTRY_COUNT 4
+
+/// Index of the EndOfList entry
+#define END_OF_LIST_INDEX 2
+
+/**
+ * @brief Test that ConfigEntry parsing stops at the EndOfList entry.
+ *
+ * Only the entries before EndOfList hold a valid key; the ones after it hold
+ * a key no loader emits, so they would show up in the count if read.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_config_entries_stop_at_end_of_list(void) {
+    Result rc = 0;
+
+    //* Given
+    ConfigEntry entries[ENTRY_COUNT] = {0};
+    for (int i = 0; i < ENTRY_COUNT; i++) {
+        entries[i].Key = i < END_OF_LIST_INDEX ? EntryType_NextLoadPath : 0xFFFFFFFF;
+    }
+    entries[END_OF_LIST_INDEX].Key = EntryType_EndOfList;
+
+    //* When
+    bool truncated = true;
+    const size_t count = __nx_rt__env_count_config_entries(entries, &truncated);
+
+    //* Then
+    // Verify the count ends at EndOfList, which is itself counted
+    if (count != END_OF_LIST_INDEX + 1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify a terminated array is not reported as truncated
+    if (truncated) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,45 @@
+#include <stdbool.h>
+#include <switch.h>
+
+#include "nx_rt_env.h"
+
+#include "../harness.h"
+
+/**
+ * @brief Test that an unterminated ConfigEntry array is truncated.
+ *
+ * The array is one entry longer than the bound, and that last entry is an
+ * EndOfList: reaching it would mean the bound was not enforced.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_config_entries_truncate_unterminated_array(void) {
+    Result rc = 0;
+
+    //* Given
+    ConfigEntry entries[NX_RT_ENV_MAX_CONFIG_ENTRIES + 1] = {0};
+    for (int i = 0; i < NX_RT_ENV_MAX_CONFIG_ENTRIES; i++) {
+        entries[i].Key = EntryType_NextLoadPath;
+    }
+    entries[NX_RT_ENV_MAX_CONFIG_ENTRIES].Key = EntryType_EndOfList;
+
+    //* When
+    bool truncated = false;
+    const size_t count = __nx_rt__env_count_config_entries(entries, &truncated);
+
+    //* Then
+    // Verify parsing stops at the bound, before the trailing EndOfList
+    if (count != NX_RT_ENV_MAX_CONFIG_ENTRIES) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the missing EndOfList is reported
+    if (!truncated) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,31 @@
+#pragma once
+
+#include <stdbool.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_rt_hid.h"
+
+/// Largest raw stick value on each axis.
+#define STICK_MAX 0x7FFF
+
+/// Default Gamepad deadzones.
+#define DEFAULT_INNER_DEADZONE 0.1f
+#define DEFAULT_OUTER_DEADZONE 0.1f
+
+/**
+ * @brief Returns true if both axes are within 1e-4 of the expected position.
+ */
+static inline bool stick_close(float x, float y, float expected_x, float expected_y) {
+    const float dx = x - expected_x;
+    const float dy = y - expected_y;
+    return dx > -1e-4f && dx < 1e-4f && dy > -1e-4f && dy < 1e-4f;
+}
+
+/**
+ * @brief Applies the default Gamepad deadzones to a raw stick position.
+ */
+static inline uint32_t stick_apply_default(int32_t raw_x, int32_t raw_y, float* x, float* y) {
+    const HidAnalogStickState stick = {.x = raw_x, .y = raw_y};
+    return __nx_rt__hid_stick_apply_deadzones(DEFAULT_INNER_DEADZONE, DEFAULT_OUTER_DEADZONE, stick, x, y);
+}
@@ -0,0 +1,69 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that a centered or resting stick reads as centered.
+ *
+ * This test verifies that:
+ * 1. The raw center reads as exactly (0, 0)
+ * 2. Noise of ~5% off center on both axes reads as (0, 0)
+ */
+test_rc_t test_0001_stick_center_reads_as_centered(void);
+
+/**
+ * @brief Test that the raw extremes read as fully pushed.
+ *
+ * This test verifies that:
+ * 1. The raw extremes of each axis read as -1.0 or 1.0 on that axis
+ * 2. A worn stick reaching 92% of the range still reads as fully pushed
+ */
+test_rc_t test_0002_stick_raw_extremes_read_as_fully_pushed(void);
+
+/**
+ * @brief Test that diagonals are clamped to the unit circle.
+ */
+test_rc_t test_0003_stick_diagonal_is_clamped_radially(void);
+
+/**
+ * @brief Test that the range between the deadzones is rescaled.
+ *
+ * This test verifies that:
+ * 1. A stick halfway between the deadzones reads as 0.5
+ * 2. A diagonal below the inner deadzone on each axis, but outside it
+ *    radially, is not centered
+ */
+test_rc_t test_0004_stick_live_range_is_rescaled(void);
+
+/**
+ * @brief Test that without deadzones the position is only normalized.
+ */
+test_rc_t test_0005_stick_no_deadzones_only_normalize(void);
+
+/**
+ * Test suite for the gamepad stick mapping.
+ */
+static void hid_stick_suite(void) {
+    TEST_SUITE("hid::stick");
+
+    TEST_CASE(
+        "Test 0001: stick_center_reads_as_centered",
+        test_0001_stick_center_reads_as_centered
+    )
+    TEST_CASE(
+        "Test 0002: stick_raw_extremes_read_as_fully_pushed",
+        test_0002_stick_raw_extremes_read_as_fully_pushed
+    )
+    TEST_CASE(
+        "Test 0003: stick_diagonal_is_clamped_radially",
+        test_0003_stick_diagonal_is_clamped_radially
+    )
+    TEST_CASE(
+        "Test 0004: stick_live_range_is_rescaled",
+        test_0004_stick_live_range_is_rescaled
+    )
+    TEST_CASE(
+        "Test 0005: stick_no_deadzones_only_normalize",
+        test_0005_stick_no_deadzones_only_normalize
+    )
+}
@@ -0,0 +1,42 @@
+#include <switch.h>
+
+#include "../harness.h"
+#include "stick.h"
+
+/**
+ * @brief Test that a centered or resting stick reads as centered.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_stick_center_reads_as_centered(void) {
+    Result rc = 0;
+
+    //* Given
+    float center_x = -1.0f, center_y = -1.0f;
+    float noise_x = -1.0f, noise_y = -1.0f;
+
+    //* When
+    rc = stick_apply_default(0, 0, &center_x, &center_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    rc = stick_apply_default(1200, -1200, &noise_x, &noise_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (center_x != 0.0f || center_y != 0.0f) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (noise_x != 0.0f || noise_y != 0.0f) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,45 @@
+#include <stddef.h>
+#include <switch.h>
+
+#include "../harness.h"
+#include "stick.h"
+
+/**
+ * @brief Test that the raw extremes read as fully pushed.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_stick_raw_extremes_read_as_fully_pushed(void) {
+    Result rc = 0;
+
+    //* Given
+    const struct {
+        int32_t raw_x, raw_y;
+        float x, y;
+    } cases[] = {
+        {STICK_MAX, 0, 1.0f, 0.0f},
+        {-STICK_MAX, 0, -1.0f, 0.0f},
+        {0, STICK_MAX, 0.0f, 1.0f},
+        {0, -STICK_MAX, 0.0f, -1.0f},
+        // A worn stick only reaching 92% of the range
+        {STICK_MAX * 92 / 100, 0, 1.0f, 0.0f},
+    };
+
+    for (size_t i = 0; i < sizeof(cases) / sizeof(cases[0]); i++) {
+        //* When
+        float x = 0.0f, y = 0.0f;
+        rc = stick_apply_default(cases[i].raw_x, cases[i].raw_y, &x, &y);
+        if (R_FAILED(rc)) {
+            goto test_cleanup;
+        }
+
+        //* Then
+        if (!stick_close(x, y, cases[i].x, cases[i].y)) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    return rc;
+}
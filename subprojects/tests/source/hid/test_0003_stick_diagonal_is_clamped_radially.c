@@ -0,0 +1,45 @@
+#include <switch.h>
+
+#include "../harness.h"
+#include "stick.h"
+
+/// 1 / sqrt(2)
+#define DIAG 0.70710678f
+
+/**
+ * @brief Test that diagonals are clamped to the unit circle.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_stick_diagonal_is_clamped_radially(void) {
+    Result rc = 0;
+
+    //* Given
+    float up_right_x = 0.0f, up_right_y = 0.0f;
+    float up_left_x = 0.0f, up_left_y = 0.0f;
+
+    //* When
+    rc = stick_apply_default(STICK_MAX, STICK_MAX, &up_right_x, &up_right_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    rc = stick_apply_default(-STICK_MAX, STICK_MAX, &up_left_x, &up_left_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (!stick_close(up_right_x, up_right_y, DIAG, DIAG)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (!stick_close(up_left_x, up_left_y, -DIAG, DIAG)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
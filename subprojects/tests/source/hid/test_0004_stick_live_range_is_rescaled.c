@@ -0,0 +1,43 @@
+#include <switch.h>
+
+#include "../harness.h"
+#include "stick.h"
+
+/**
+ * @brief Test that the range between the deadzones is rescaled.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_stick_live_range_is_rescaled(void) {
+    Result rc = 0;
+
+    //* Given
+    float half_x = 0.0f, half_y = 0.0f;
+    float diag_x = 0.0f, diag_y = 0.0f;
+
+    //* When
+    rc = stick_apply_default(STICK_MAX / 2, 0, &half_x, &half_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // 8% on each axis: ~11.3% from the center
+    rc = stick_apply_default(STICK_MAX * 8 / 100, STICK_MAX * 8 / 100, &diag_x, &diag_y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (!stick_close(half_x, half_y, 0.5f, 0.0f)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (diag_x <= 0.0f || diag_y <= 0.0f || !stick_close(diag_x, diag_y, diag_y, diag_x)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,32 @@
+#include <switch.h>
+
+#include "../harness.h"
+#include "stick.h"
+
+/**
+ * @brief Test that without deadzones the position is only normalized.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0005_stick_no_deadzones_only_normalize(void) {
+    Result rc = 0;
+
+    //* Given
+    const HidAnalogStickState stick = {.x = STICK_MAX / 4, .y = -STICK_MAX / 2};
+    float x = 0.0f, y = 0.0f;
+
+    //* When
+    rc = __nx_rt__hid_stick_apply_deadzones(0.0f, 0.0f, stick, &x, &y);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (!stick_close(x, y, 0.25f, -0.5f)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -3,15 +3,37 @@
 #include <switch.h>
 
 #include "harness.h"
+#include "alloc/suite.h"
+#include "applet/suite.h"
+#include "env/suite.h"
+#include "hid/suite.h"
+#include "mem/suite.h"
 #include "rand/suite.h"
+#include "sf/suite.h"
 #include "sync/suite.h"
+#include "thread/suite.h"
+#include "time/suite.h"
+#include "vi/suite.h"
 
 /**
  * Test suites
  */
 static TestSuiteFn test_suites[] = {
+    // alloc
+    alloc_suite,
+    // applet
+    applet_suite,
+    // env
+    env_suite,
+    // hid
+    hid_stick_suite,
+    // mem
+    mem_slab_suite,
+    mem_bump_suite,
     // random
     rand_suite,
+    // sf
+    sf_suite,
     // sync
     sync_mutex_suite,
     sync_remutex_suite,
@@ -19,7 +41,15 @@ static TestSuiteFn test_suites[] = {
     sync_barrier_suite,
     sync_rwlock_suite,
     sync_semaphore_suite,
+    sync_spinlock_suite,
     sync_oneshot_suite,
+    // thread
+    thread_scope_suite,
+    thread_exit_suite,
+    // time
+    time_suite,
+    // vi
+    vi_bq_suite,
 };
 
 int main()
@@ -0,0 +1,84 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that a slab recycles freed blocks.
+ *
+ * This test verifies that:
+ * 1. Blocks are distinct and 16-byte aligned
+ * 2. A freed block is handed out again by the next allocation
+ * 3. The slab statistics track the blocks in use
+ */
+test_rc_t test_0001_slab_recycles_freed_blocks(void);
+
+/**
+ * @brief Test that a slab grows one chunk at a time.
+ *
+ * This test verifies that:
+ * 1. A new chunk is only allocated once every block of the previous ones is in use
+ * 2. Freeing every block keeps the chunks for reuse
+ */
+test_rc_t test_0002_slab_grows_one_chunk_at_a_time(void);
+
+/**
+ * @brief Benchmark the slab against malloc/free.
+ *
+ * Times a tight allocate/free loop, and a batch of allocations followed by a
+ * batch of frees, on a slab and on the global allocator, and reports both.
+ */
+test_rc_t test_0003_slab_vs_malloc_benchmark(void);
+
+/**
+ * @brief Test that a bump allocator fails once its arena is exhausted.
+ *
+ * This test verifies that:
+ * 1. Allocations are handed out until the arena is full, then fail
+ * 2. A reset releases the whole arena and allocation starts over
+ */
+test_rc_t test_0004_bump_allocates_until_exhausted(void);
+
+/**
+ * @brief Test that a bump allocator aligns addresses, not arena offsets.
+ *
+ * This test verifies that, on an arena starting at an odd address:
+ * 1. An aligned allocation lands on an aligned address
+ * 2. The alignment padding is counted as used
+ */
+test_rc_t test_0005_bump_aligns_absolute_addresses(void);
+
+/**
+ * Test suite for the slab allocator.
+ */
+static void mem_slab_suite(void) {
+    TEST_SUITE("mem::slab");
+
+    TEST_CASE(
+        "Test 0001: slab_recycles_freed_blocks",
+        test_0001_slab_recycles_freed_blocks
+    )
+    TEST_CASE(
+        "Test 0002: slab_grows_one_chunk_at_a_time",
+        test_0002_slab_grows_one_chunk_at_a_time
+    )
+    TEST_CASE(
+        "Test 0003: slab_vs_malloc_benchmark",
+        test_0003_slab_vs_malloc_benchmark
+    )
+}
+
+/**
+ * Test suite for the bump allocator.
+ */
+static void mem_bump_suite(void) {
+    TEST_SUITE("mem::bump");
+
+    TEST_CASE(
+        "Test 0004: bump_allocates_until_exhausted",
+        test_0004_bump_allocates_until_exhausted
+    )
+    TEST_CASE(
+        "Test 0005: bump_aligns_absolute_addresses",
+        test_0005_bump_aligns_absolute_addresses
+    )
+}
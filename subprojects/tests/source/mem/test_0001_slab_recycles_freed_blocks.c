@@ -0,0 +1,71 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_slab.h"
+
+#include "../harness.h"
+
+/**
+ * @brief Test that a slab recycles freed blocks.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_slab_recycles_freed_blocks(void) {
+    Result rc = 0;
+
+    //* Given
+    NxSlab256* slab = __nx_sys_mem__slab256_create();
+    if (slab == NULL) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    void* a = __nx_sys_mem__slab256_alloc(slab);
+    void* b = __nx_sys_mem__slab256_alloc(slab);
+    void* c = NULL;
+    if (a == NULL || b == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    NxSlabStats before = {0};
+    __nx_sys_mem__slab256_stats(slab, &before);
+
+    //* When
+    __nx_sys_mem__slab256_free(slab, a);
+    void* const freed = a;
+    a = NULL;
+
+    c = __nx_sys_mem__slab256_alloc(slab);
+
+    NxSlabStats after = {0};
+    __nx_sys_mem__slab256_stats(slab, &after);
+
+    //* Then
+    if (freed == b || ((uintptr_t)freed & 0xF) != 0 || ((uintptr_t)b & 0xF) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The freed block must be handed out again
+    if (c != freed) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (before.block_size != 0x100 || before.in_use != 2 || before.chunks != 1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (after.in_use != 2 || after.chunks != 1 || after.capacity != before.capacity) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    __nx_sys_mem__slab256_free(slab, c);
+    __nx_sys_mem__slab256_free(slab, b);
+    __nx_sys_mem__slab256_free(slab, a);
+    __nx_sys_mem__slab256_destroy(slab);
+    return rc;
+}
@@ -0,0 +1,96 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <switch.h>
+
+#include "nx_slab.h"
+
+#include "../harness.h"
+
+/**
+ * @brief Test that a slab grows one chunk at a time.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_slab_grows_one_chunk_at_a_time(void) {
+    Result rc = 0;
+    void** blocks = NULL;
+    size_t allocated = 0;
+
+    //* Given
+    NxSlab256* slab = __nx_sys_mem__slab256_create();
+    if (slab == NULL) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    // Reserving a single block allocates exactly one chunk
+    rc = __nx_sys_mem__slab256_reserve(slab, 1);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    NxSlabStats reserved = {0};
+    __nx_sys_mem__slab256_stats(slab, &reserved);
+
+    const size_t per_chunk = reserved.capacity;
+    blocks = calloc(per_chunk + 1, sizeof(void*));
+    if (blocks == NULL || reserved.chunks != 1 || per_chunk == 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* When
+    // Fill the first chunk
+    for (; allocated < per_chunk; allocated++) {
+        blocks[allocated] = __nx_sys_mem__slab256_alloc(slab);
+        if (blocks[allocated] == NULL) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+    NxSlabStats full = {0};
+    __nx_sys_mem__slab256_stats(slab, &full);
+
+    // One more block needs a second chunk
+    blocks[allocated] = __nx_sys_mem__slab256_alloc(slab);
+    if (blocks[allocated] == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+    allocated++;
+
+    NxSlabStats grown = {0};
+    __nx_sys_mem__slab256_stats(slab, &grown);
+
+    for (; allocated > 0; allocated--) {
+        __nx_sys_mem__slab256_free(slab, blocks[allocated - 1]);
+    }
+
+    NxSlabStats freed = {0};
+    __nx_sys_mem__slab256_stats(slab, &freed);
+
+    //* Then
+    if (full.chunks != 1 || full.in_use != per_chunk) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (grown.chunks != 2 || grown.capacity != 2 * per_chunk || grown.in_use != per_chunk + 1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The chunks are kept until the slab is destroyed
+    if (freed.chunks != 2 || freed.in_use != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    for (; allocated > 0; allocated--) {
+        __nx_sys_mem__slab256_free(slab, blocks[allocated - 1]);
+    }
+    free(blocks);
+    __nx_sys_mem__slab256_destroy(slab);
+    return rc;
+}
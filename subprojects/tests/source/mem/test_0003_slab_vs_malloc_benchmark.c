@@ -0,0 +1,111 @@
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <switch.h>
+
+#include "nx_slab.h"
+
+#include "../harness.h"
+
+#define BLOCK_SIZE 0x100
+#define LOOP_ITERATIONS 10000
+#define BATCH_SIZE 64
+#define BATCH_ROUNDS 200
+
+static void* g_batch[BATCH_SIZE];
+
+/**
+ * @brief Benchmark the slab against malloc/free.
+ *
+ * Only allocation failures are asserted on; the timings are reported.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_slab_vs_malloc_benchmark(void) {
+    Result rc = 0;
+
+    //* Given
+    NxSlab256* slab = __nx_sys_mem__slab256_create();
+    if (slab == NULL) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    // Keep chunk allocations out of the timed loops
+    rc = __nx_sys_mem__slab256_reserve(slab, BATCH_SIZE);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    // Tight allocate/free loop
+    uint64_t start = armGetSystemTick();
+    for (int i = 0; i < LOOP_ITERATIONS; i++) {
+        void* block = __nx_sys_mem__slab256_alloc(slab);
+        if (block == NULL) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+        __nx_sys_mem__slab256_free(slab, block);
+    }
+    const uint64_t slab_loop = armGetSystemTick() - start;
+
+    start = armGetSystemTick();
+    for (int i = 0; i < LOOP_ITERATIONS; i++) {
+        void* block = malloc(BLOCK_SIZE);
+        if (block == NULL) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+        free(block);
+    }
+    const uint64_t malloc_loop = armGetSystemTick() - start;
+
+    // Batches of allocations, then frees
+    int failed = 0;
+    start = armGetSystemTick();
+    for (int round = 0; round < BATCH_ROUNDS; round++) {
+        for (int i = 0; i < BATCH_SIZE; i++) {
+            g_batch[i] = __nx_sys_mem__slab256_alloc(slab);
+            failed += g_batch[i] == NULL;
+        }
+        for (int i = 0; i < BATCH_SIZE; i++) {
+            __nx_sys_mem__slab256_free(slab, g_batch[i]);
+        }
+    }
+    const uint64_t slab_batch = armGetSystemTick() - start;
+
+    start = armGetSystemTick();
+    for (int round = 0; round < BATCH_ROUNDS; round++) {
+        for (int i = 0; i < BATCH_SIZE; i++) {
+            g_batch[i] = malloc(BLOCK_SIZE);
+            failed += g_batch[i] == NULL;
+        }
+        for (int i = 0; i < BATCH_SIZE; i++) {
+            free(g_batch[i]);
+        }
+    }
+    const uint64_t malloc_batch = armGetSystemTick() - start;
+
+    //* Then
+    NxSlabStats stats = {0};
+    __nx_sys_mem__slab256_stats(slab, &stats);
+
+    // Every block must be back, and the reservation must have been enough
+    if (failed != 0 || stats.in_use != 0 || stats.capacity < BATCH_SIZE) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    printf("\n  alloc/free x%d: slab %llu ns, malloc %llu ns\n",
+        LOOP_ITERATIONS,
+        (unsigned long long)armTicksToNs(slab_loop),
+        (unsigned long long)armTicksToNs(malloc_loop));
+    printf("  batch x%d of %d: slab %llu ns, malloc %llu ns\n  ",
+        BATCH_ROUNDS, BATCH_SIZE,
+        (unsigned long long)armTicksToNs(slab_batch),
+        (unsigned long long)armTicksToNs(malloc_batch));
+
+test_cleanup:
+    __nx_sys_mem__slab256_destroy(slab);
+    return rc;
+}
@@ -0,0 +1,66 @@
+#include <stdalign.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_bump.h"
+
+#include "../harness.h"
+
+/// Size of the backing arena in bytes
+#define ARENA_SIZE 64
+
+/// Size of each allocation in bytes
+#define BLOCK_SIZE 16
+
+/// Alignment of each allocation in bytes
+#define BLOCK_ALIGN 8
+
+static alignas(BLOCK_ALIGN) u8 g_arena[ARENA_SIZE];
+
+/**
+ * @brief Test that a bump allocator fails once its arena is exhausted.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_bump_allocates_until_exhausted(void) {
+    Result rc = 0;
+
+    //* Given
+    NxBump* bump = __nx_sys_mem__bump_create(g_arena, sizeof(g_arena));
+    if (bump == NULL) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    //* When
+    size_t count = 0;
+    while (__nx_sys_mem__bump_alloc(bump, BLOCK_SIZE, BLOCK_ALIGN) != NULL) {
+        count++;
+        if (count > ARENA_SIZE / BLOCK_SIZE) {
+            break;
+        }
+    }
+
+    const void* const exhausted = __nx_sys_mem__bump_alloc(bump, BLOCK_SIZE, BLOCK_ALIGN);
+    const size_t used_before_reset = __nx_sys_mem__bump_used(bump);
+
+    __nx_sys_mem__bump_reset(bump);
+    const size_t used_after_reset = __nx_sys_mem__bump_used(bump);
+    const void* const after_reset = __nx_sys_mem__bump_alloc(bump, BLOCK_SIZE, BLOCK_ALIGN);
+
+    //* Then
+    // Verify every block fit in the arena and the arena is filled
+    if (count != ARENA_SIZE / BLOCK_SIZE || exhausted != NULL || used_before_reset != ARENA_SIZE) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify a reset hands out the arena again from the start
+    if (used_after_reset != 0 || after_reset != g_arena) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    __nx_sys_mem__bump_destroy(bump);
+    return rc;
+}
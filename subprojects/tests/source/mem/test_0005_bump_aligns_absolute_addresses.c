@@ -0,0 +1,57 @@
+#include <stdalign.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_bump.h"
+
+#include "../harness.h"
+
+/// Size of the backing arena in bytes
+#define ARENA_SIZE 64
+
+/// Alignment requested from the allocator
+#define BLOCK_ALIGN 16
+
+static alignas(BLOCK_ALIGN) u8 g_arena[ARENA_SIZE];
+
+/**
+ * @brief Test that a bump allocator aligns addresses, not arena offsets.
+ *
+ * The arena starts one byte past a 16-byte boundary, so an aligned offset
+ * would yield a misaligned address.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0005_bump_aligns_absolute_addresses(void) {
+    Result rc = 0;
+
+    //* Given
+    u8* const base = g_arena + 1;
+    NxBump* bump = __nx_sys_mem__bump_create(base, ARENA_SIZE - 1);
+    if (bump == NULL) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    //* When
+    u8* const first = __nx_sys_mem__bump_alloc(bump, 1, 1);
+    u8* const aligned = __nx_sys_mem__bump_alloc(bump, 8, BLOCK_ALIGN);
+    const size_t used = __nx_sys_mem__bump_used(bump);
+
+    //* Then
+    // Verify the unaligned allocation starts the arena
+    if (first != base) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the aligned block sits on the next 16-byte boundary, padding included in the usage
+    if (aligned != g_arena + BLOCK_ALIGN || ((uintptr_t)aligned % BLOCK_ALIGN) != 0 ||
+        used != (size_t)(aligned + 8 - base)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    __nx_sys_mem__bump_destroy(bump);
+    return rc;
+}
@@ -0,0 +1,65 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that a cloned service session works independently of the original.
+ *
+ * This test verifies that __nx_sf__service_clone:
+ * 1. Opens a new session, with its own handle, to the same server object
+ * 2. Answers requests on the cloned session
+ * 3. Leaves the original session usable after the clone is closed
+ */
+test_rc_t test_0001_service_clone_works_independently(void);
+
+/**
+ * @brief Test that a Type C output pointer request has the expected layout.
+ *
+ * This test verifies that __nx_sf__cmif_make_out_pointer_request:
+ * 1. Writes a Request HIPC header with one receive list entry
+ * 2. Places the CMIF header at the first 16-byte aligned data word
+ * 3. Places the receive list entry, with the buffer address and size, after the data words
+ */
+test_rc_t test_0002_out_pointer_request_layout(void);
+
+/**
+ * @brief Test that a Type C output pointer reports the bytes the server wrote.
+ *
+ * This test verifies that __nx_sf__service_dispatch_out_pointer:
+ * 1. Sends setsys GetFirmwareVersion with a Type C output buffer
+ * 2. Recovers the written length from the response send statics
+ * 3. Receives the same firmware version as libnx
+ */
+test_rc_t test_0003_out_pointer_dispatch_reports_written_len(void);
+
+/**
+ * @brief Test that a Type C output pointer larger than 64 KB is rejected.
+ *
+ * This test verifies that __nx_sf__service_dispatch_out_pointer returns
+ * LibnxError_BadInput, without sending, for a buffer over the 16-bit size limit.
+ */
+test_rc_t test_0004_out_pointer_rejects_oversized_buffer(void);
+
+/**
+ * Test suite for sf (service framework).
+ */
+static void sf_suite(void) {
+    TEST_SUITE("sf");
+
+    TEST_CASE(
+        "Test 0001: service_clone_works_independently",
+        test_0001_service_clone_works_independently
+    )
+    TEST_CASE(
+        "Test 0002: out_pointer_request_layout",
+        test_0002_out_pointer_request_layout
+    )
+    TEST_CASE(
+        "Test 0003: out_pointer_dispatch_reports_written_len",
+        test_0003_out_pointer_dispatch_reports_written_len
+    )
+    TEST_CASE(
+        "Test 0004: out_pointer_rejects_oversized_buffer",
+        test_0004_out_pointer_rejects_oversized_buffer
+    )
+}
@@ -0,0 +1,61 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_sf_service.h"
+
+#include "../harness.h"
+
+/// ISystemSettingsServer::GetLockScreenFlag
+#define SETSYS_CMD_GET_LOCK_SCREEN_FLAG 7
+
+/**
+ * @brief Test that a cloned service session works independently of the original.
+ *
+ * The clone is used, then closed, before the original session is used again.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_service_clone_works_independently(void) {
+    Result rc = 0;
+
+    //* Given
+    rc = setsysInitialize();
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+
+    Service* setsys = setsysGetServiceSession();
+
+    //* When
+    Service clone = {0};
+    rc = __nx_sf__service_clone(setsys, &clone);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    const Handle clone_session = clone.session;
+
+    u8 clone_flag = 0;
+    const Result clone_rc = serviceDispatchOut(&clone, SETSYS_CMD_GET_LOCK_SCREEN_FLAG, clone_flag);
+    __nx_sf__service_close(&clone);
+
+    u8 flag = 0;
+    const Result orig_rc = serviceDispatchOut(setsys, SETSYS_CMD_GET_LOCK_SCREEN_FLAG, flag);
+
+    //* Then
+    // Verify the clone got a session of its own
+    if (clone_session == INVALID_HANDLE || clone_session == setsys->session) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify both sessions answered, with the same server state
+    if (R_FAILED(clone_rc) || R_FAILED(orig_rc) || clone_flag != flag) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    setsysExit();
+    return rc;
+}
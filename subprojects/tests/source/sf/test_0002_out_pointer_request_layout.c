@@ -0,0 +1,67 @@
+#include <stdalign.h>
+#include <stdint.h>
+#include <string.h>
+#include <switch.h>
+
+#include "nx_sf_service.h"
+
+#include "../harness.h"
+
+/// ISystemSettingsServer::GetFirmwareVersion
+#define SETSYS_CMD_GET_FIRMWARE_VERSION 3
+
+/// CMIF input header magic ("SFCI")
+#define CMIF_IN_HEADER_MAGIC 0x49434653
+
+static u32 read_u32(const u8* buf, size_t offset) {
+    u32 value;
+    memcpy(&value, buf + offset, sizeof(value));
+    return value;
+}
+
+/**
+ * @brief Test that a Type C output pointer request has the expected layout.
+ *
+ * The request is built in a local buffer, then its HIPC header, CMIF header
+ * and receive list entry are checked word by word.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_out_pointer_request_layout(void) {
+    Result rc = 0;
+
+    //* Given
+    alignas(16) u8 msg[0x200] = {0};
+    u8 out[0x100] = {0};
+    const uintptr_t out_addr = (uintptr_t)out;
+
+    //* When
+    rc = __nx_sf__cmif_make_out_pointer_request(msg, SETSYS_CMD_GET_FIRMWARE_VERSION, out, sizeof(out));
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    // Verify the HIPC header: type 4 (Request), 8 data words, one receive list entry
+    if (read_u32(msg, 0x00) != 0x00000004 || read_u32(msg, 0x04) != (8 | (3 << 10))) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the CMIF header at the first 16-byte aligned data word
+    if (read_u32(msg, 0x10) != CMIF_IN_HEADER_MAGIC || read_u32(msg, 0x14) != 0 ||
+        read_u32(msg, 0x18) != SETSYS_CMD_GET_FIRMWARE_VERSION) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the receive list entry follows the 8 data words (0x08 + 0x20)
+    const u32 expected_hi = (u32)((out_addr >> 32) & 0xFFFF) | ((u32)sizeof(out) << 16);
+    if (read_u32(msg, 0x28) != (u32)out_addr || read_u32(msg, 0x2C) != expected_hi) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,62 @@
+#include <stdint.h>
+#include <string.h>
+#include <switch.h>
+
+#include "nx_sf_service.h"
+
+#include "../harness.h"
+
+/// ISystemSettingsServer::GetFirmwareVersion
+#define SETSYS_CMD_GET_FIRMWARE_VERSION 3
+
+/**
+ * @brief Test that a Type C output pointer reports the bytes the server wrote.
+ *
+ * GetFirmwareVersion is sent with the Rust request builder. The reply must
+ * fill the whole SetSysFirmwareVersion and agree with setsysGetFirmwareVersion.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_out_pointer_dispatch_reports_written_len(void) {
+    Result rc = 0;
+
+    //* Given
+    rc = setsysInitialize();
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+
+    SetSysFirmwareVersion expected = {0};
+    rc = setsysGetFirmwareVersion(&expected);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    SetSysFirmwareVersion fw = {0};
+    size_t written = 0;
+    rc = __nx_sf__service_dispatch_out_pointer(
+        setsysGetServiceSession(), SETSYS_CMD_GET_FIRMWARE_VERSION, &fw, sizeof(fw), &written
+    );
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    // Verify the server filled the whole buffer
+    if (written != sizeof(fw)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the contents match libnx (revision fields differ between cmd 3 and 4)
+    if (fw.major != expected.major || fw.minor != expected.minor || fw.micro != expected.micro ||
+        strncmp(fw.display_version, expected.display_version, sizeof(fw.display_version)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    setsysExit();
+    return rc;
+}
@@ -0,0 +1,54 @@
+#include <stdint.h>
+#include <stdlib.h>
+#include <switch.h>
+
+#include "nx_sf_service.h"
+
+#include "../harness.h"
+
+/// ISystemSettingsServer::GetFirmwareVersion
+#define SETSYS_CMD_GET_FIRMWARE_VERSION 3
+
+/// One byte over the 16-bit Type C size field
+#define OVERSIZED_LEN 0x10000
+
+/**
+ * @brief Test that a Type C output pointer larger than 64 KB is rejected.
+ *
+ * The request must fail with LibnxError_BadInput before anything is sent.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_out_pointer_rejects_oversized_buffer(void) {
+    Result rc = 0;
+
+    //* Given
+    rc = setsysInitialize();
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+
+    u8* out = malloc(OVERSIZED_LEN);
+    if (out == NULL) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* When
+    size_t written = 0xDEAD;
+    const Result send_rc = __nx_sf__service_dispatch_out_pointer(
+        setsysGetServiceSession(), SETSYS_CMD_GET_FIRMWARE_VERSION, out, OVERSIZED_LEN, &written
+    );
+
+    //* Then
+    // Verify the buffer was rejected without touching the output length
+    if (send_rc != MAKERESULT(Module_Libnx, LibnxError_BadInput) || written != 0xDEAD) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    free(out);
+    setsysExit();
+    return rc;
+}
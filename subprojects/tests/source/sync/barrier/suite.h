@@ -7,6 +7,12 @@
  */
 test_rc_t test_0001_barrier_sync_multiple_threads(void);
 
+/**
+ * This test reuses a barrier across many phases, checking that no thread
+ * runs ahead of the others.
+ */
+test_rc_t test_0002_barrier_reuse_many_phases(void);
+
 
 /**
  * Test suite for sync/barrier.
@@ -18,4 +24,9 @@ static void sync_barrier_suite(void) {
         "Test 0001: barrier_sync_multiple_threads",
         test_0001_barrier_sync_multiple_threads
     )
+
+    TEST_CASE(
+        "Test 0002: barrier_reuse_many_phases",
+        test_0002_barrier_reuse_many_phases
+    )
 }
@@ -0,0 +1,111 @@
+#include <stdatomic.h>
+#include <stdint.h>
+
+#include <switch.h>
+
+#include "../../harness.h"
+
+//<editor-fold desc="Test 0002: Barrier reuse many phases">
+
+#define NUM_THREADS 4
+#define NUM_PHASES 1000
+
+static Barrier g_barrier;
+static _Atomic uint64_t g_phase[NUM_THREADS];
+static _Atomic uint64_t g_violations = 0;
+
+/**
+ * Thread function for Test #0002
+ */
+static void thread_func(void* arg)
+{
+    uint64_t num = (uint64_t) arg;
+
+    for (uint64_t i=0; i<NUM_PHASES; i++)
+    {
+        // Announce the phase this thread is in
+        atomic_store(&g_phase[num], i);
+
+        // Wait for all threads to reach the barrier
+        barrierWait(&g_barrier);
+
+        // Every thread must have reached this phase, and none may be more than
+        // one phase ahead (i.e. past the next barrier)
+        for (uint64_t j=0; j<NUM_THREADS; j++) {
+            const uint64_t phase = atomic_load(&g_phase[j]);
+            if (phase < i || phase > i + 1) {
+                atomic_fetch_add(&g_violations, 1);
+            }
+        }
+    }
+}
+
+test_rc_t test_0002_barrier_reuse_many_phases(void)
+{
+    Result rc = 0;
+
+    //* Given
+    // Initialize the test global barrier
+    barrierInit(&g_barrier, NUM_THREADS);
+
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        atomic_store(&g_phase[i], 0);
+    }
+    atomic_store(&g_violations, 0);
+
+    //* When
+    // Create the threads
+    static Thread thread[NUM_THREADS];
+
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        rc = threadCreate(&thread[i], thread_func, (void*)i, NULL, 0x10000, 0x2C, -2);
+        if (R_FAILED(rc)) {
+            goto test_cleanup;
+        }
+    }
+
+    // Start the threads
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        rc = threadStart(&thread[i]);
+        if (R_FAILED(rc)) {
+            goto test_cleanup;
+        }
+    }
+
+    // Wait for all threads to run through every phase
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        threadWaitForExit(&thread[i]);
+    }
+
+    //* Then
+    // Assert no thread ever ran ahead of, or fell behind, the others
+    if (atomic_load(&g_violations) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Assert every thread completed the last phase
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        if (atomic_load(&g_phase[i]) != NUM_PHASES - 1) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+    // Assert the barrier count has been reset after the last phase
+    if (g_barrier.count != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* Cleanup
+test_cleanup:
+    for (uint64_t i=0; i<NUM_THREADS; i++) {
+        threadWaitForExit(&thread[i]);
+        threadClose(&thread[i]);
+    }
+
+    return rc;
+}
+
+//</editor-fold>
@@ -63,6 +63,8 @@ test_rc_t test_0005_rwlock_reader_writer_priority(void);
  * - Non-blocking Operations: Verifies try operations don't block when lock is held
  * - Contention Handling: Tests behavior when locks are unavailable
  * - Success Cases: Verifies try operations succeed when locks are available
+ * - Upgrades: Verifies a sole reader can upgrade to the write lock, and that the
+ *   upgrade fails, keeping the read lock, while another thread holds a read lock
  */
 test_rc_t test_0006_rwlock_try_operations(void);
 
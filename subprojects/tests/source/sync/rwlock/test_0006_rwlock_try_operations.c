@@ -1,8 +1,209 @@
 #include <stdint.h>
 #include <stdbool.h>
+
 #include <switch.h>
+
 #include "../../harness.h"
 
+// Declared in nx_sys_sync_rwlock.h, which redefines the libnx RwLock type
+bool __nx_sys_sync__rwlock_try_upgrade(RwLock* r);
+
+static RwLock g_rwlock;
+static bool g_try_read_result = false;
+static bool g_try_write_result = false;
+static Semaphore g_holder_locked;
+static Semaphore g_holder_release;
+
+/**
+ * Thread function for Test #0006
+ *
+ * Attempts to acquire the read lock and then the write lock without blocking,
+ * releasing each one if it was acquired, and records the results.
+ */
+static void try_thread_func(void *arg) {
+    g_try_read_result = rwlockTryReadLock(&g_rwlock);
+    if (g_try_read_result) {
+        rwlockReadUnlock(&g_rwlock);
+    }
+
+    g_try_write_result = rwlockTryWriteLock(&g_rwlock);
+    if (g_try_write_result) {
+        rwlockWriteUnlock(&g_rwlock);
+    }
+}
+
+/**
+ * Runs the try thread to completion and stores its results.
+ */
+static Result run_try_thread(bool *try_read, bool *try_write) {
+    Thread thread;
+    Result rc = threadCreate(&thread, try_thread_func, NULL, NULL, 0x10000, 0x2C, -2);
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+
+    rc = threadStart(&thread);
+    if (R_SUCCEEDED(rc)) {
+        threadWaitForExit(&thread);
+        *try_read = g_try_read_result;
+        *try_write = g_try_write_result;
+    }
+
+    threadClose(&thread);
+    return rc;
+}
+
+/**
+ * Thread function for Test #0006
+ *
+ * Holds a read lock until the main thread releases it.
+ */
+static void holder_thread_func(void *arg) {
+    rwlockReadLock(&g_rwlock);
+    semaphoreSignal(&g_holder_locked);
+
+    semaphoreWait(&g_holder_release);
+    rwlockReadUnlock(&g_rwlock);
+}
+
+/**
+ * Test non-blocking try operations for read and write locks, and read lock upgrades.
+ */
 test_rc_t test_0006_rwlock_try_operations(void) {
-    return 0; // Stub
-} 
+    Result rc = 0;
+
+    //* Given
+    // Initialize the test global rwlock
+    rwlockInit(&g_rwlock);
+
+    bool unlocked_read, unlocked_write;
+    bool read_locked_read, read_locked_write;
+    bool write_locked_read, write_locked_write;
+    bool released_read, released_write;
+    bool sole_upgraded, upgraded_read, upgraded_write, upgraded_held;
+    bool shared_upgraded, shared_read, shared_write;
+
+    //* When
+    // T0: Try both locks from another thread while the lock is free
+    rc = run_try_thread(&unlocked_read, &unlocked_write);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // T1: Try both locks from another thread while the main thread holds a read lock
+    rwlockReadLock(&g_rwlock);
+    rc = run_try_thread(&read_locked_read, &read_locked_write);
+    rwlockReadUnlock(&g_rwlock);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // T2: Try both locks from another thread while the main thread holds the write lock
+    rwlockWriteLock(&g_rwlock);
+    rc = run_try_thread(&write_locked_read, &write_locked_write);
+    rwlockWriteUnlock(&g_rwlock);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // T3: Try both locks from another thread after the write lock has been released
+    rc = run_try_thread(&released_read, &released_write);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // T4: Upgrade the main thread's read lock while no other thread holds one
+    rwlockReadLock(&g_rwlock);
+    sole_upgraded = __nx_sys_sync__rwlock_try_upgrade(&g_rwlock);
+    upgraded_held = rwlockIsWriteLockHeldByCurrentThread(&g_rwlock);
+    rc = run_try_thread(&upgraded_read, &upgraded_write);
+    if (sole_upgraded) {
+        rwlockWriteUnlock(&g_rwlock);
+    } else {
+        rwlockReadUnlock(&g_rwlock);
+    }
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    // T5: Try to upgrade the main thread's read lock while another thread holds one
+    semaphoreInit(&g_holder_locked, 0);
+    semaphoreInit(&g_holder_release, 0);
+
+    Thread holder;
+    rc = threadCreate(&holder, holder_thread_func, NULL, NULL, 0x10000, 0x2C, -2);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    rc = threadStart(&holder);
+    if (R_FAILED(rc)) {
+        threadClose(&holder);
+        goto test_cleanup;
+    }
+    semaphoreWait(&g_holder_locked);
+
+    rwlockReadLock(&g_rwlock);
+    shared_upgraded = __nx_sys_sync__rwlock_try_upgrade(&g_rwlock);
+    rc = run_try_thread(&shared_read, &shared_write);
+    if (shared_upgraded) {
+        rwlockWriteUnlock(&g_rwlock);
+    } else {
+        rwlockReadUnlock(&g_rwlock);
+    }
+
+    semaphoreSignal(&g_holder_release);
+    threadWaitForExit(&holder);
+    threadClose(&holder);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    // - T0
+    // Assert that both try operations succeed on a free lock
+    if (!unlocked_read || !unlocked_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // - T1
+    // Assert that readers can share the lock, but a writer cannot acquire it
+    if (!read_locked_read || read_locked_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // - T2
+    // Assert that neither readers nor writers can acquire a write-locked lock
+    if (write_locked_read || write_locked_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // - T3
+    // Assert that both try operations succeed again once the lock is released
+    if (!released_read || !released_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // - T4
+    // Assert that the sole reader is upgraded to an exclusive write lock
+    if (!sole_upgraded || !upgraded_held || upgraded_read || upgraded_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // - T5
+    // Assert that the upgrade fails while another reader holds the lock, and the read lock
+    // is still held and shared
+    if (shared_upgraded || !shared_read || shared_write) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* Clean-up
+test_cleanup:
+    return rc;
+}
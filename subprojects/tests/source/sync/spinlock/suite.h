@@ -0,0 +1,34 @@
+#pragma once
+
+#include "../../harness.h"
+
+/**
+ * Test spin lock lock, try-lock and unlock in a single thread.
+ */
+test_rc_t test_0001_spinlock_lock_unlock_single_thread(void);
+
+/**
+ * This test creates threads on different cores that all increment a shared counter
+ * under the spin lock, as fast as they can.
+ *
+ * This test covers:
+ * - Mutual Exclusion: No two threads are ever inside the critical section at once
+ * - No Lost Updates: The counter ends up at the total number of increments
+ */
+test_rc_t test_0002_spinlock_contention_stress(void);
+
+/**
+ * Test suite for sync/spinlock.
+ */
+static void sync_spinlock_suite(void) {
+    TEST_SUITE("sync/spinlock");
+
+    TEST_CASE(
+        "Test 0001: spinlock_lock_unlock_single_thread",
+        test_0001_spinlock_lock_unlock_single_thread
+    )
+    TEST_CASE(
+        "Test 0002: spinlock_contention_stress",
+        test_0002_spinlock_contention_stress
+    )
+}
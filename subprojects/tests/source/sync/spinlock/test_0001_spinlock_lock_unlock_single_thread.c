@@ -0,0 +1,58 @@
+#include <stdint.h>
+#include <stdbool.h>
+
+#include <switch.h>
+
+#include "nx_sys_sync_spinlock.h"
+
+#include "../../harness.h"
+
+//<editor-fold desc="Test 0001: SpinLock lock unlock single thread">
+
+static SpinLock g_lock;
+
+/**
+ * Test spin lock lock, try-lock and unlock in a single thread.
+ */
+test_rc_t test_0001_spinlock_lock_unlock_single_thread(void) {
+    //* Given
+    __nx_sys_sync__spinlock_init(&g_lock);
+    const uint32_t lock_tag_t0 = g_lock;
+
+    //* When
+    __nx_sys_sync__spinlock_lock(&g_lock);
+    const uint32_t lock_tag_t1 = g_lock;
+    const bool try_lock_t1 = __nx_sys_sync__spinlock_try_lock(&g_lock);
+
+    __nx_sys_sync__spinlock_unlock(&g_lock);
+    const uint32_t lock_tag_t2 = g_lock;
+
+    const bool try_lock_t3 = __nx_sys_sync__spinlock_try_lock(&g_lock);
+    const uint32_t lock_tag_t3 = g_lock;
+    __nx_sys_sync__spinlock_unlock(&g_lock);
+
+    //* Then
+    // Assert that the lock starts unlocked
+    if (lock_tag_t0 != 0) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    // Assert that the lock is locked, and cannot be locked again
+    if (lock_tag_t1 == 0 || try_lock_t1) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    // Assert that the lock is unlocked
+    if (lock_tag_t2 != 0) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    // Assert that try-lock acquires the unlocked lock
+    if (!try_lock_t3 || lock_tag_t3 == 0) {
+        return TEST_ASSERTION_FAILED;
+    }
+
+    return TEST_SUCCESS;
+}
+
+//</editor-fold>
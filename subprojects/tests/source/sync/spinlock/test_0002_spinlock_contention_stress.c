@@ -0,0 +1,108 @@
+#include <stdint.h>
+#include <stdbool.h>
+
+#include <switch.h>
+
+#include "nx_sys_sync_spinlock.h"
+
+#include "../../harness.h"
+
+//<editor-fold desc="Test 0002: SpinLock contention stress">
+
+// One thread per application core
+#define NUM_THREADS 3
+#define ITERATIONS 100000
+
+static SpinLock g_lock;
+static volatile uint64_t g_counter = 0;
+static volatile bool g_inside = false;
+static volatile uint32_t g_overlaps = 0;
+
+/**
+ * Thread function for Test #0002
+ */
+static void thread_func(void *arg) {
+    (void) arg;
+
+    for (int i = 0; i < ITERATIONS; i++) {
+        __nx_sys_sync__spinlock_lock(&g_lock);
+
+        // Another thread inside the critical section means the lock failed
+        if (g_inside) {
+            g_overlaps++;
+        }
+        g_inside = true;
+
+        g_counter++;
+
+        g_inside = false;
+        __nx_sys_sync__spinlock_unlock(&g_lock);
+    }
+}
+
+/**
+ * This test creates threads on different cores that all increment a shared counter
+ * under the spin lock, as fast as they can.
+ */
+test_rc_t test_0002_spinlock_contention_stress(void) {
+    Result rc = 0;
+
+    //* Given
+    __nx_sys_sync__spinlock_init(&g_lock);
+    g_counter = 0;
+    g_inside = false;
+    g_overlaps = 0;
+
+    // Create one thread per core, so the threads contend in parallel
+    Thread threads[NUM_THREADS];
+    int created = 0;
+    for (int i = 0; i < NUM_THREADS; i++) {
+        rc = threadCreate(&threads[i], thread_func, NULL, NULL, 0x10000, 0x2C, i);
+        if (R_FAILED(rc)) {
+            goto test_cleanup;
+        }
+        created++;
+    }
+
+    //* When
+    for (int i = 0; i < NUM_THREADS; i++) {
+        rc = threadStart(&threads[i]);
+        if (R_FAILED(rc)) {
+            goto test_cleanup;
+        }
+    }
+
+    for (int i = 0; i < NUM_THREADS; i++) {
+        threadWaitForExit(&threads[i]);
+    }
+
+    //* Then
+    // Assert that no two threads were ever inside the critical section together
+    if (g_overlaps != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Assert that no increment was lost
+    if (g_counter != (uint64_t) NUM_THREADS * ITERATIONS) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Assert that the lock was left unlocked
+    if (g_lock != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* Clean-up
+test_cleanup:
+    for (int i = 0; i < created; i++) {
+        threadWaitForExit(&threads[i]);
+        threadClose(&threads[i]);
+    }
+
+    return rc;
+}
+
+//</editor-fold>
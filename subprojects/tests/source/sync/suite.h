@@ -6,4 +6,5 @@
 #include "barrier/suite.h"
 #include "rwlock/suite.h"
 #include "semaphore/suite.h"
+#include "spinlock/suite.h"
 #include "oneshot/suite.h"
@@ -0,0 +1,54 @@
+#pragma once
+
+#include <stddef.h>
+#include <stdint.h>
+
+#include "nx_sys_thread.h"
+
+/// Order the callbacks registered by register_exit_records() run in.
+#define EXIT_ORDER_LIFO 3241
+
+/**
+ * Exit callback argument: appends its digit to the decimal number in order,
+ * then registers then, if set.
+ */
+typedef struct ExitRecord {
+    uint32_t* order;
+    uint32_t digit;
+    struct ExitRecord* then;
+} ExitRecord;
+
+/**
+ * Exit callbacks of a thread, registered by register_exit_records().
+ */
+typedef struct {
+    uint32_t order;
+    ExitRecord records[4];
+} ExitCtx;
+
+/**
+ * Exit callback: appends its digit, then registers the next record.
+ */
+static void record_exit(void* arg) {
+    ExitRecord* record = (ExitRecord*)arg;
+
+    *record->order = *record->order * 10 + record->digit;
+    if (record->then != NULL) {
+        __nx_sys_thread__at_thread_exit(record_exit, record->then);
+    }
+}
+
+/**
+ * Registers 1, then 2, then 3. 2 registers 4 during teardown, which runs
+ * before 1: the expected order is EXIT_ORDER_LIFO.
+ */
+static void register_exit_records(ExitCtx* ctx) {
+    for (uint32_t i = 0; i < 4; i++) {
+        ctx->records[i] = (ExitRecord){.order = &ctx->order, .digit = i + 1, .then = NULL};
+    }
+    ctx->records[1].then = &ctx->records[3];
+
+    for (size_t i = 0; i < 3; i++) {
+        __nx_sys_thread__at_thread_exit(record_exit, &ctx->records[i]);
+    }
+}
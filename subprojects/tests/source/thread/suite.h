@@ -0,0 +1,72 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that scoped threads can borrow data from the caller's stack.
+ *
+ * This test verifies that:
+ * 1. Two scoped threads can read disjoint halves of a stack-allocated array
+ * 2. Each thread's result is visible to the caller once the scope returns
+ */
+test_rc_t test_0001_thread_scope_borrows_local_array(void);
+
+/**
+ * @brief Test that a thread scope joins every thread spawned in it.
+ *
+ * This test verifies that:
+ * 1. Threads that are never joined explicitly are joined when the scope ends
+ * 2. The scope does not return before the slowest thread has finished
+ */
+test_rc_t test_0002_thread_scope_joins_unjoined_threads(void);
+
+/**
+ * @brief Test that exit callbacks run in LIFO order on a plain thread.
+ *
+ * This test verifies that, on a thread created with threadCreate and started
+ * with threadStart:
+ * 1. The exit callbacks run when the thread returns, most recent first
+ * 2. A callback registered during teardown runs before the earlier ones
+ */
+test_rc_t test_0003_at_thread_exit_lifo_on_plain_thread(void);
+
+/**
+ * @brief Test that exit callbacks run in LIFO order on a scoped thread.
+ *
+ * This test verifies that:
+ * 1. The exit callbacks run when the thread returns, most recent first
+ * 2. A callback registered during teardown runs before the earlier ones
+ */
+test_rc_t test_0004_at_thread_exit_lifo_on_scoped_thread(void);
+
+/**
+ * Test suite for scoped threads.
+ */
+static void thread_scope_suite(void) {
+    TEST_SUITE("thread::scope");
+
+    TEST_CASE(
+        "Test 0001: thread_scope_borrows_local_array",
+        test_0001_thread_scope_borrows_local_array
+    )
+    TEST_CASE(
+        "Test 0002: thread_scope_joins_unjoined_threads",
+        test_0002_thread_scope_joins_unjoined_threads
+    )
+}
+
+/**
+ * Test suite for the thread exit callbacks.
+ */
+static void thread_exit_suite(void) {
+    TEST_SUITE("thread::exit");
+
+    TEST_CASE(
+        "Test 0003: at_thread_exit_lifo_on_plain_thread",
+        test_0003_at_thread_exit_lifo_on_plain_thread
+    )
+    TEST_CASE(
+        "Test 0004: at_thread_exit_lifo_on_scoped_thread",
+        test_0004_at_thread_exit_lifo_on_scoped_thread
+    )
+}
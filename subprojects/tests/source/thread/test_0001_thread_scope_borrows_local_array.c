@@ -0,0 +1,96 @@
+#include <stddef.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_sys_thread.h"
+
+#include "../harness.h"
+
+#define ARRAY_LEN 8
+
+/**
+ * A half of the borrowed array, and the sum computed by its thread.
+ */
+typedef struct {
+    const uint32_t* data;
+    size_t len;
+    uint32_t sum;
+} Half;
+
+/**
+ * Scope body argument.
+ */
+typedef struct {
+    Half halves[2];
+    uint32_t spawn_rc[2];
+} ScopeCtx;
+
+/**
+ * Scoped thread entry: sums its half of the array.
+ */
+static void sum_half(void* arg) {
+    Half* half = (Half*)arg;
+
+    uint32_t sum = 0;
+    for (size_t i = 0; i < half->len; i++) {
+        sum += half->data[i];
+    }
+
+    half->sum = sum;
+}
+
+/**
+ * Scope body: spawns one thread per half.
+ */
+static void scope_body(const NxThreadScope* scope, void* arg) {
+    ScopeCtx* ctx = (ScopeCtx*)arg;
+
+    for (size_t i = 0; i < 2; i++) {
+        ctx->spawn_rc[i] = __nx_sys_thread__thread_scope_spawn(scope, sum_half, &ctx->halves[i]);
+    }
+}
+
+/**
+ * @brief Test that scoped threads can borrow data from the caller's stack.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_thread_scope_borrows_local_array(void) {
+    Result rc = 0;
+
+    //* Given
+    // The array lives on this function's stack
+    const uint32_t data[ARRAY_LEN] = {1, 2, 3, 4, 5, 6, 7, 8};
+
+    ScopeCtx ctx = {
+        .halves = {
+            { .data = &data[0], .len = ARRAY_LEN / 2, .sum = 0 },
+            { .data = &data[ARRAY_LEN / 2], .len = ARRAY_LEN / 2, .sum = 0 },
+        },
+        .spawn_rc = {0},
+    };
+
+    //* When
+    __nx_sys_thread__thread_scope(scope_body, &ctx);
+
+    //* Then
+    if (ctx.spawn_rc[0] != 0 || ctx.spawn_rc[1] != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // 1 + 2 + 3 + 4
+    if (ctx.halves[0].sum != 10) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // 5 + 6 + 7 + 8
+    if (ctx.halves[1].sum != 26) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
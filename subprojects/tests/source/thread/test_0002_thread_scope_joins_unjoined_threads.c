@@ -0,0 +1,90 @@
+#include <stddef.h>
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_sys_thread.h"
+
+#include "../harness.h"
+
+#define NUM_THREADS 4
+#define THREAD_DELAY_MS 20
+
+/**
+ * @brief Sleeps the current thread for the given number of milliseconds.
+ * @param ms The number of milliseconds to sleep.
+ */
+static inline void threadSleepMs(int64_t ms) {
+    svcSleepThread(ms * 1000000);
+}
+
+/**
+ * A scoped thread's delay and counter.
+ */
+typedef struct {
+    int64_t delay_ms;
+    uint32_t counter;
+} Slot;
+
+/**
+ * Scope body argument.
+ */
+typedef struct {
+    Slot slots[NUM_THREADS];
+    uint32_t spawn_rc[NUM_THREADS];
+} ScopeCtx;
+
+/**
+ * Scoped thread entry: sleeps, then bumps its own counter.
+ */
+static void sleep_then_increment(void* arg) {
+    Slot* slot = (Slot*)arg;
+
+    threadSleepMs(slot->delay_ms);
+    slot->counter++;
+}
+
+/**
+ * Scope body: spawns the threads and returns without joining them.
+ */
+static void scope_body(const NxThreadScope* scope, void* arg) {
+    ScopeCtx* ctx = (ScopeCtx*)arg;
+
+    for (size_t i = 0; i < NUM_THREADS; i++) {
+        ctx->spawn_rc[i] = __nx_sys_thread__thread_scope_spawn(scope, sleep_then_increment, &ctx->slots[i]);
+    }
+}
+
+/**
+ * @brief Test that a thread scope joins every thread spawned in it.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_thread_scope_joins_unjoined_threads(void) {
+    Result rc = 0;
+
+    //* Given
+    ScopeCtx ctx = {0};
+    for (size_t i = 0; i < NUM_THREADS; i++) {
+        // The last spawned thread is the slowest one
+        ctx.slots[i].delay_ms = (int64_t)(i + 1) * THREAD_DELAY_MS;
+    }
+
+    //* When
+    __nx_sys_thread__thread_scope(scope_body, &ctx);
+
+    //* Then
+    for (size_t i = 0; i < NUM_THREADS; i++) {
+        if (ctx.spawn_rc[i] != 0) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+
+        if (ctx.slots[i].counter != 1) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,55 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_sys_thread.h"
+
+#include "../harness.h"
+#include "exit_order.h"
+
+#define STACK_SIZE 0x10000
+
+/**
+ * Thread entry: registers the exit callbacks, then returns.
+ */
+static void register_then_return(void* arg) {
+    register_exit_records((ExitCtx*)arg);
+}
+
+/**
+ * @brief Test that exit callbacks run in LIFO order on a plain thread.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_at_thread_exit_lifo_on_plain_thread(void) {
+    Result rc = 0;
+    Thread thread = {0};
+
+    //* Given
+    ExitCtx ctx = {0};
+
+    rc = threadCreate(&thread, register_then_return, &ctx, NULL, STACK_SIZE, 0x2C, -2);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    rc = threadStart(&thread);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    rc = threadWaitForExit(&thread);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (ctx.order != EXIT_ORDER_LIFO) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    threadClose(&thread);
+    return rc;
+}
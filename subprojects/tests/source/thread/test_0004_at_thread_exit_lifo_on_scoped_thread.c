@@ -0,0 +1,59 @@
+#include <switch.h>
+
+#include "nx_sys_thread.h"
+
+#include "../harness.h"
+#include "exit_order.h"
+
+/**
+ * Scoped thread entry: registers the exit callbacks, then returns.
+ */
+static void register_then_return(void* arg) {
+    register_exit_records((ExitCtx*)arg);
+}
+
+/**
+ * Scope body argument.
+ */
+typedef struct {
+    ExitCtx exit;
+    uint32_t spawn_rc;
+} ScopeCtx;
+
+/**
+ * Scope body: spawns the thread registering the exit callbacks.
+ */
+static void scope_body(const NxThreadScope* scope, void* arg) {
+    ScopeCtx* ctx = (ScopeCtx*)arg;
+
+    ctx->spawn_rc = __nx_sys_thread__thread_scope_spawn(scope, register_then_return, &ctx->exit);
+}
+
+/**
+ * @brief Test that exit callbacks run in LIFO order on a scoped thread.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_at_thread_exit_lifo_on_scoped_thread(void) {
+    Result rc = 0;
+
+    //* Given
+    ScopeCtx ctx = {0};
+
+    //* When
+    __nx_sys_thread__thread_scope(scope_body, &ctx);
+
+    //* Then
+    if (ctx.spawn_rc != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (ctx.exit.order != EXIT_ORDER_LIFO) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
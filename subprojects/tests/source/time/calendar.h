@@ -0,0 +1,19 @@
+#pragma once
+
+#include <stdint.h>
+#include <switch.h>
+
+/**
+ * @brief Builds a calendar time at 12:30:15 on the given date.
+ */
+static inline TimeCalendarTime calendar_time(uint16_t year, uint8_t month, uint8_t day) {
+    return (TimeCalendarTime){
+        .year = year,
+        .month = month,
+        .day = day,
+        .hour = 12,
+        .minute = 30,
+        .second = 15,
+        .pad = 0,
+    };
+}
@@ -0,0 +1,15 @@
+#pragma once
+
+#include <stdint.h>
+
+/// System tick frequency, in Hz.
+#define SYSTEM_TICK_FREQ 19200000ULL
+
+/**
+ * @brief Returns the number of whole periods of @p period_ns in @p ticks system ticks.
+ *
+ * Computed without rounding the elapsed time to whole nanoseconds.
+ */
+static inline uint64_t periods_in_ticks(uint64_t ticks, uint64_t period_ns) {
+    return (uint64_t)(((unsigned __int128)ticks * 1000000000ULL) / ((unsigned __int128)period_ns * SYSTEM_TICK_FREQ));
+}
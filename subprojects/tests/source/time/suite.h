@@ -0,0 +1,110 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that struct tm conversions use the POSIX field offsets.
+ *
+ * This test verifies that:
+ * 1. tm_year counts years since 1900 and tm_mon is 0-based
+ * 2. Converting a struct tm back gives the original calendar time
+ */
+test_rc_t test_0001_tm_uses_posix_offsets(void);
+
+/**
+ * @brief Test that the day of the year accounts for leap years.
+ *
+ * This test verifies that tm_yday is computed with the Gregorian rules,
+ * including centuries that are not leap years (1900) and those that are (2000).
+ */
+test_rc_t test_0002_tm_day_of_year_in_leap_years(void);
+
+/**
+ * @brief Test that the day of the week is computed from the date.
+ */
+test_rc_t test_0003_tm_day_of_week(void);
+
+/**
+ * @brief Test that out-of-range struct tm fields are rejected.
+ *
+ * This test verifies that a month past December and a year before year 0 are
+ * reported as such, without writing the calendar time.
+ */
+test_rc_t test_0004_tm_rejects_out_of_range_fields(void);
+
+/**
+ * @brief Test that a periodic timer does not drift over many polls.
+ *
+ * This test verifies that, with a period that is not a whole number of ticks
+ * (60 Hz), one simulated hour of polls every 7 ms reports exactly as many
+ * periods as fit in the elapsed time.
+ */
+test_rc_t test_0005_periodic_timer_no_drift_over_many_polls(void);
+
+/**
+ * @brief Test that a periodic timer follows the system tick counter.
+ *
+ * This test verifies that, polled with the real system tick while the thread
+ * sleeps between polls, the timer reports as many periods as elapsed.
+ */
+test_rc_t test_0006_periodic_timer_tracks_system_tick(void);
+
+/**
+ * @brief Test that a long gap between polls is capped and dropped.
+ *
+ * This test verifies that:
+ * 1. A single poll reports at most max_steps periods
+ * 2. The periods past the cap are not reported by later polls
+ */
+test_rc_t test_0007_periodic_timer_long_gap_is_capped_and_dropped(void);
+
+/**
+ * @brief Test that the clocks still read and advance with shared memory disabled.
+ *
+ * This test verifies that, after __nx_rt__time_disable_shared_memory:
+ * 1. __nx_rt__time_has_shared_memory reports false
+ * 2. The user system clock and the steady clock read through IPC agree with
+ *    the earlier shared memory reads
+ * 3. Both clocks advance across a sleep, and the steady clock source ID is kept
+ */
+test_rc_t test_0008_clocks_advance_without_shared_memory(void);
+
+/**
+ * Test suite for the time service helpers.
+ */
+static void time_suite(void) {
+    TEST_SUITE("time");
+
+    TEST_CASE(
+        "Test 0001: tm_uses_posix_offsets",
+        test_0001_tm_uses_posix_offsets
+    )
+    TEST_CASE(
+        "Test 0002: tm_day_of_year_in_leap_years",
+        test_0002_tm_day_of_year_in_leap_years
+    )
+    TEST_CASE(
+        "Test 0003: tm_day_of_week",
+        test_0003_tm_day_of_week
+    )
+    TEST_CASE(
+        "Test 0004: tm_rejects_out_of_range_fields",
+        test_0004_tm_rejects_out_of_range_fields
+    )
+    TEST_CASE(
+        "Test 0005: periodic_timer_no_drift_over_many_polls",
+        test_0005_periodic_timer_no_drift_over_many_polls
+    )
+    TEST_CASE(
+        "Test 0006: periodic_timer_tracks_system_tick",
+        test_0006_periodic_timer_tracks_system_tick
+    )
+    TEST_CASE(
+        "Test 0007: periodic_timer_long_gap_is_capped_and_dropped",
+        test_0007_periodic_timer_long_gap_is_capped_and_dropped
+    )
+    TEST_CASE(
+        "Test 0008: clocks_advance_without_shared_memory",
+        test_0008_clocks_advance_without_shared_memory
+    )
+}
@@ -0,0 +1,57 @@
+#include <string.h>
+#include <time.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "calendar.h"
+
+/**
+ * @brief Test that struct tm conversions use the POSIX field offsets.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_tm_uses_posix_offsets(void) {
+    Result rc = 0;
+
+    //* Given
+    const TimeCalendarTime first = calendar_time(1900, 1, 1);
+    const TimeCalendarTime last = calendar_time(2024, 12, 31);
+
+    //* When
+    struct tm first_tm = {0};
+    struct tm last_tm = {0};
+    TimeCalendarTime round_trip = {0};
+
+    if (__nx_rt__time_calendar_time_to_tm(&first, &first_tm) != 0
+        || __nx_rt__time_calendar_time_to_tm(&last, &last_tm) != 0
+        || __nx_rt__time_tm_to_calendar_time(&last_tm, &round_trip) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (first_tm.tm_year != 0 || first_tm.tm_mon != 0 || first_tm.tm_mday != 1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (last_tm.tm_year != 124 || last_tm.tm_mon != 11) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (last_tm.tm_hour != 12 || last_tm.tm_min != 30 || last_tm.tm_sec != 15 || last_tm.tm_isdst != -1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (memcmp(&round_trip, &last, sizeof(TimeCalendarTime)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,60 @@
+#include <stddef.h>
+#include <time.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "calendar.h"
+
+/**
+ * A date and its expected 0-based day of the year.
+ */
+typedef struct {
+    uint16_t year;
+    uint8_t month;
+    uint8_t day;
+    int yday;
+} YdayCase;
+
+static const YdayCase g_cases[] = {
+    // Leap year
+    { 2024, 3, 1, 60 },
+    { 2024, 12, 31, 365 },
+    // Common year
+    { 2023, 3, 1, 59 },
+    // Century, not a leap year
+    { 1900, 3, 1, 59 },
+    // Century divisible by 400, a leap year
+    { 2000, 3, 1, 60 },
+};
+
+/**
+ * @brief Test that the day of the year accounts for leap years.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_tm_day_of_year_in_leap_years(void) {
+    Result rc = 0;
+
+    for (size_t i = 0; i < sizeof(g_cases) / sizeof(g_cases[0]); i++) {
+        //* Given
+        const TimeCalendarTime time = calendar_time(g_cases[i].year, g_cases[i].month, g_cases[i].day);
+
+        //* When
+        struct tm tm = {0};
+        if (__nx_rt__time_calendar_time_to_tm(&time, &tm) != 0) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+
+        //* Then
+        if (tm.tm_yday != g_cases[i].yday) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    return rc;
+}
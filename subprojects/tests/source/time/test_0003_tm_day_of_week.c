@@ -0,0 +1,57 @@
+#include <stddef.h>
+#include <time.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "calendar.h"
+
+/**
+ * A date and its expected day of the week (0 = Sunday).
+ */
+typedef struct {
+    uint16_t year;
+    uint8_t month;
+    uint8_t day;
+    int wday;
+} WdayCase;
+
+static const WdayCase g_cases[] = {
+    // Thursday
+    { 1970, 1, 1, 4 },
+    // Thursday, leap day
+    { 2024, 2, 29, 4 },
+    // Saturday
+    { 2000, 1, 1, 6 },
+};
+
+/**
+ * @brief Test that the day of the week is computed from the date.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_tm_day_of_week(void) {
+    Result rc = 0;
+
+    for (size_t i = 0; i < sizeof(g_cases) / sizeof(g_cases[0]); i++) {
+        //* Given
+        const TimeCalendarTime time = calendar_time(g_cases[i].year, g_cases[i].month, g_cases[i].day);
+
+        //* When
+        struct tm tm = {0};
+        if (__nx_rt__time_calendar_time_to_tm(&time, &tm) != 0) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+
+        //* Then
+        if (tm.tm_wday != g_cases[i].wday) {
+            rc = TEST_ASSERTION_FAILED;
+            goto test_cleanup;
+        }
+    }
+
+test_cleanup:
+    return rc;
+}
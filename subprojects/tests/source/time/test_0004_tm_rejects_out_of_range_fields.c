@@ -0,0 +1,51 @@
+#include <string.h>
+#include <time.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "calendar.h"
+
+/**
+ * @brief Test that out-of-range struct tm fields are rejected.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_tm_rejects_out_of_range_fields(void) {
+    Result rc = 0;
+
+    //* Given
+    const TimeCalendarTime time = calendar_time(2024, 1, 1);
+    struct tm tm = {0};
+    if (__nx_rt__time_calendar_time_to_tm(&time, &tm) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    struct tm month_12 = tm;
+    month_12.tm_mon = 12;
+
+    struct tm before_year_0 = tm;
+    before_year_0.tm_year = -1901;
+
+    //* When
+    TimeCalendarTime untouched = {0};
+    const uint32_t month_rc = __nx_rt__time_tm_to_calendar_time(&month_12, &untouched);
+    const uint32_t year_rc = __nx_rt__time_tm_to_calendar_time(&before_year_0, &untouched);
+
+    //* Then
+    if (month_rc != NxRtTmField_Month || year_rc != NxRtTmField_Year) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    const TimeCalendarTime zero = {0};
+    if (memcmp(&untouched, &zero, sizeof(TimeCalendarTime)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
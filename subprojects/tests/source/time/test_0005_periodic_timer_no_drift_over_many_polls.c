@@ -0,0 +1,50 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "periodic.h"
+
+/// 60 Hz: not a whole number of ticks
+#define PERIOD_NS 16666667ULL
+#define POLL_MS 7ULL
+#define DURATION_MS (60ULL * 60ULL * 1000ULL)
+
+/**
+ * @brief Test that a periodic timer does not drift over many polls.
+ *
+ * The ticks are simulated, starting from the current system tick.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0005_periodic_timer_no_drift_over_many_polls(void) {
+    Result rc = 0;
+
+    //* Given
+    const uint64_t start = armGetSystemTick();
+
+    NxRtPeriodicTimer timer;
+    rc = __nx_rt__time_periodic_timer_init(&timer, PERIOD_NS, start, 8);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    const uint64_t poll_ticks = SYSTEM_TICK_FREQ * POLL_MS / 1000;
+    uint64_t now = start;
+    uint64_t total = 0;
+    for (uint64_t i = 0; i < DURATION_MS / POLL_MS; i++) {
+        now += poll_ticks;
+        total += __nx_rt__time_periodic_timer_poll(&timer, now);
+    }
+
+    //* Then
+    if (total != periods_in_ticks(now - start, PERIOD_NS)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,62 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "periodic.h"
+
+#define PERIOD_NS 1000000ULL
+#define POLL_MS 7
+#define NUM_POLLS 50
+
+/**
+ * @brief Sleeps the current thread for the given number of milliseconds.
+ * @param ms The number of milliseconds to sleep.
+ */
+static inline void threadSleepMs(int64_t ms) {
+    svcSleepThread(ms * 1000000);
+}
+
+/**
+ * @brief Test that a periodic timer follows the system tick counter.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0006_periodic_timer_tracks_system_tick(void) {
+    Result rc = 0;
+
+    //* Given
+    const uint64_t start = armGetSystemTick();
+
+    // A cap high enough to never drop periods, even if the thread oversleeps
+    NxRtPeriodicTimer timer;
+    rc = __nx_rt__time_periodic_timer_init(&timer, PERIOD_NS, start, UINT32_MAX);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    uint64_t now = start;
+    uint64_t total = 0;
+    for (int i = 0; i < NUM_POLLS; i++) {
+        threadSleepMs(POLL_MS);
+        now = armGetSystemTick();
+        total += __nx_rt__time_periodic_timer_poll(&timer, now);
+    }
+
+    //* Then
+    if (total != periods_in_ticks(now - start, PERIOD_NS)) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The thread slept for at least that long
+    if (total < NUM_POLLS * POLL_MS) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
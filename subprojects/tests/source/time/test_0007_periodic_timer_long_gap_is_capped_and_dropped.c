@@ -0,0 +1,49 @@
+#include <stdint.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+#include "periodic.h"
+
+#define PERIOD_NS 1000000ULL
+#define MAX_STEPS 4
+
+/**
+ * @brief Test that a long gap between polls is capped and dropped.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0007_periodic_timer_long_gap_is_capped_and_dropped(void) {
+    Result rc = 0;
+
+    //* Given
+    NxRtPeriodicTimer timer;
+    rc = __nx_rt__time_periodic_timer_init(&timer, PERIOD_NS, 0, MAX_STEPS);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* When
+    // One hour asleep
+    const uint64_t now = SYSTEM_TICK_FREQ * 3600;
+    const uint32_t after_gap = __nx_rt__time_periodic_timer_poll(&timer, now);
+    const uint32_t same_tick = __nx_rt__time_periodic_timer_poll(&timer, now);
+    // One period (19200 ticks) later
+    const uint32_t next_period = __nx_rt__time_periodic_timer_poll(&timer, now + 19200);
+
+    //* Then
+    if (after_gap != MAX_STEPS) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // The dropped periods are not replayed afterwards
+    if (same_tick != 0 || next_period != 1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,112 @@
+#include <stdint.h>
+#include <string.h>
+#include <switch.h>
+
+#include "nx_rt_time.h"
+
+#include "../harness.h"
+
+/// Time to sleep between the two IPC reads
+#define SLEEP_MS 1500
+
+/// Allowed gap, in seconds, between two reads of the same instant (second granularity)
+#define READ_TOLERANCE_S 1
+
+/**
+ * @brief Sleeps the current thread for the given number of milliseconds.
+ * @param ms The number of milliseconds to sleep.
+ */
+static inline void threadSleepMs(int64_t ms) {
+    svcSleepThread(ms * 1000000);
+}
+
+/**
+ * @brief Test that the clocks still read and advance with shared memory disabled.
+ *
+ * The user system clock and the steady clock are read once through shared
+ * memory (when the firmware maps it), then twice through IPC, 1.5 s apart.
+ * The Time service is reinitialized afterwards to map shared memory again.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0008_clocks_advance_without_shared_memory(void) {
+    Result rc = 0;
+
+    //* Given
+    u64 shmem_time = 0;
+    TimeSteadyClockTimePoint shmem_point = {0};
+    rc = timeGetCurrentTime(TimeType_UserSystemClock, &shmem_time);
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+    rc = __nx_rt__time_get_steady_clock_time_point(&shmem_point);
+    if (R_FAILED(rc)) {
+        return rc;
+    }
+
+    //* When
+    rc = __nx_rt__time_disable_shared_memory();
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+    const bool has_shmem = __nx_rt__time_has_shared_memory();
+
+    u64 ipc_time_before = 0;
+    TimeSteadyClockTimePoint ipc_point_before = {0};
+    rc = timeGetCurrentTime(TimeType_UserSystemClock, &ipc_time_before);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+    rc = __nx_rt__time_get_steady_clock_time_point(&ipc_point_before);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    threadSleepMs(SLEEP_MS);
+
+    u64 ipc_time_after = 0;
+    TimeSteadyClockTimePoint ipc_point_after = {0};
+    rc = timeGetCurrentTime(TimeType_UserSystemClock, &ipc_time_after);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+    rc = __nx_rt__time_get_steady_clock_time_point(&ipc_point_after);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    // Verify reads no longer go through shared memory
+    if (has_shmem) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify the IPC reads agree with the earlier shared memory reads
+    if (ipc_time_before < shmem_time || ipc_time_before - shmem_time > READ_TOLERANCE_S ||
+        ipc_point_before.time_point < shmem_point.time_point ||
+        ipc_point_before.time_point - shmem_point.time_point > READ_TOLERANCE_S ||
+        memcmp(&ipc_point_before.source_id, &shmem_point.source_id, sizeof(Uuid)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    // Verify both clocks advanced by the sleep, give or take a second
+    const u64 time_elapsed = ipc_time_after - ipc_time_before;
+    const s64 steady_elapsed = ipc_point_after.time_point - ipc_point_before.time_point;
+    if (ipc_time_after < ipc_time_before || time_elapsed < 1 || time_elapsed > 2 ||
+        steady_elapsed < 1 || steady_elapsed > 2 ||
+        memcmp(&ipc_point_after.source_id, &ipc_point_before.source_id, sizeof(Uuid)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    // Reconnect so later tests read through shared memory again
+    timeExit();
+    const Result init_rc = timeInitialize();
+    if (R_SUCCEEDED(rc) && R_FAILED(init_rc)) {
+        rc = init_rc;
+    }
+    return rc;
+}
@@ -0,0 +1,13 @@
+#pragma once
+
+#include <stddef.h>
+#include <switch.h>
+
+/// Interface descriptor of IGraphicBufferProducer requests.
+#define BQ_INTERFACE_DESCRIPTOR "android.gui.IGraphicBufferProducer"
+
+/// Flattened size of a BqQueueBufferInput, without the struct's tail padding.
+#define BQ_QUEUE_BUFFER_INPUT_SIZE (offsetof(BqQueueBufferInput, fence) + sizeof(NvMultiFence))
+
+/// Dequeue status bit: the buffer in the slot must be requested again.
+#define BQ_BUFFER_NEEDS_REALLOCATION 0x1
@@ -0,0 +1,58 @@
+#pragma once
+
+#include "../harness.h"
+
+/**
+ * @brief Test that the DEQUEUE_BUFFER request matches the libnx encoding.
+ *
+ * This test verifies that the request payload is byte-for-byte the one
+ * libnx's parcel writer builds for bqDequeueBuffer.
+ */
+test_rc_t test_0001_bq_dequeue_buffer_request_matches_libnx(void);
+
+/**
+ * @brief Test that the QUEUE_BUFFER request matches the libnx encoding.
+ *
+ * This test verifies that the request payload, including the flattened
+ * queue input and its fence, is byte-for-byte the one libnx's parcel writer
+ * builds for bqQueueBuffer.
+ */
+test_rc_t test_0002_bq_queue_buffer_request_matches_libnx(void);
+
+/**
+ * @brief Test that a DEQUEUE_BUFFER reply with a fence is parsed.
+ *
+ * This test verifies that:
+ * 1. The slot and every fence of the reply are read back
+ * 2. The reallocation bit of the status is reported
+ */
+test_rc_t test_0003_bq_dequeue_buffer_reply_with_fence(void);
+
+/**
+ * @brief Test that a DEQUEUE_BUFFER reply with an error status is rejected.
+ */
+test_rc_t test_0004_bq_dequeue_buffer_reply_error_status(void);
+
+/**
+ * Test suite for the IGraphicBufferProducer parcels.
+ */
+static void vi_bq_suite(void) {
+    TEST_SUITE("vi::bq");
+
+    TEST_CASE(
+        "Test 0001: bq_dequeue_buffer_request_matches_libnx",
+        test_0001_bq_dequeue_buffer_request_matches_libnx
+    )
+    TEST_CASE(
+        "Test 0002: bq_queue_buffer_request_matches_libnx",
+        test_0002_bq_queue_buffer_request_matches_libnx
+    )
+    TEST_CASE(
+        "Test 0003: bq_dequeue_buffer_reply_with_fence",
+        test_0003_bq_dequeue_buffer_reply_with_fence
+    )
+    TEST_CASE(
+        "Test 0004: bq_dequeue_buffer_reply_error_status",
+        test_0004_bq_dequeue_buffer_reply_error_status
+    )
+}
@@ -0,0 +1,51 @@
+#include <string.h>
+#include <switch.h>
+
+#include "nx_rt_vi.h"
+
+#include "../harness.h"
+#include "bq.h"
+
+/**
+ * @brief Test that the DEQUEUE_BUFFER request matches the libnx encoding.
+ *
+ * The expected payload is written with the libnx parcel functions, in the
+ * order bqDequeueBuffer writes them.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0001_bq_dequeue_buffer_request_matches_libnx(void) {
+    Result rc = 0;
+
+    //* Given
+    Parcel expected;
+    parcelCreate(&expected);
+    parcelWriteInterfaceToken(&expected, BQ_INTERFACE_DESCRIPTOR);
+    parcelWriteInt32(&expected, 0);
+    parcelWriteUInt32(&expected, 1280);
+    parcelWriteUInt32(&expected, 720);
+    parcelWriteInt32(&expected, PIXEL_FORMAT_RGBA_8888);
+    parcelWriteUInt32(&expected, 0xB00);
+
+    //* When
+    u8 payload[0x400];
+    size_t size = 0;
+    rc = __nx_rt__vi_bq_dequeue_buffer_request(false, 1280, 720, PIXEL_FORMAT_RGBA_8888, 0xB00, payload, sizeof(payload), &size);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (size != expected.payload_size) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (memcmp(payload, expected.payload, size) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,61 @@
+#include <string.h>
+#include <switch.h>
+
+#include "nx_rt_vi.h"
+
+#include "../harness.h"
+#include "bq.h"
+
+/**
+ * @brief Test that the QUEUE_BUFFER request matches the libnx encoding.
+ *
+ * The expected payload is written with the libnx parcel functions, in the
+ * order bqQueueBuffer writes them.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0002_bq_queue_buffer_request_matches_libnx(void) {
+    Result rc = 0;
+
+    //* Given
+    BqQueueBufferInput input;
+    memset(&input, 0, sizeof(input));
+    input.timestamp = 0x1122334455667788LL;
+    input.isAutoTimestamp = 1;
+    input.crop = (BqRect){.left = 0, .top = 0, .right = 1280, .bottom = 720};
+    input.scalingMode = 1;
+    input.transform = 4;
+    input.stickyTransform = 0;
+    input.swapInterval = 1;
+    input.fence.num_fences = 2;
+    input.fence.fences[0] = (NvFence){.id = 7, .value = 42};
+    input.fence.fences[1] = (NvFence){.id = 8, .value = 43};
+
+    Parcel expected;
+    parcelCreate(&expected);
+    parcelWriteInterfaceToken(&expected, BQ_INTERFACE_DESCRIPTOR);
+    parcelWriteInt32(&expected, 2);
+    parcelWriteFlattenedObject(&expected, &input, BQ_QUEUE_BUFFER_INPUT_SIZE);
+
+    //* When
+    u8 payload[0x400];
+    size_t size = 0;
+    rc = __nx_rt__vi_bq_queue_buffer_request(2, &input, payload, sizeof(payload), &size);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (size != expected.payload_size) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (memcmp(payload, expected.payload, size) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
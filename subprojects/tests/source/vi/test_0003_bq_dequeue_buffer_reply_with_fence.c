@@ -0,0 +1,57 @@
+#include <string.h>
+#include <switch.h>
+
+#include "nx_rt_vi.h"
+
+#include "../harness.h"
+#include "bq.h"
+
+/**
+ * @brief Test that a DEQUEUE_BUFFER reply with a fence is parsed.
+ *
+ * The reply is written with the libnx parcel functions, in the order
+ * bqDequeueBuffer reads them.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0003_bq_dequeue_buffer_reply_with_fence(void) {
+    Result rc = 0;
+
+    //* Given
+    NvMultiFence fence;
+    memset(&fence, 0, sizeof(fence));
+    fence.num_fences = 2;
+    fence.fences[0] = (NvFence){.id = 1, .value = 10};
+    fence.fences[1] = (NvFence){.id = 2, .value = 20};
+
+    Parcel reply;
+    parcelCreate(&reply);
+    parcelWriteInt32(&reply, 2);
+    parcelWriteInt32(&reply, 1);
+    parcelWriteFlattenedObject(&reply, &fence, sizeof(fence));
+    parcelWriteInt32(&reply, BQ_BUFFER_NEEDS_REALLOCATION);
+
+    //* When
+    s32 slot = -1;
+    NvMultiFence parsed;
+    memset(&parsed, 0xFF, sizeof(parsed));
+    bool needs_reallocation = false;
+    rc = __nx_rt__vi_bq_parse_dequeue_buffer_reply(reply.payload, reply.payload_size, &slot, &parsed, &needs_reallocation);
+    if (R_FAILED(rc)) {
+        goto test_cleanup;
+    }
+
+    //* Then
+    if (slot != 2 || !needs_reallocation) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+    if (memcmp(&parsed, &fence, sizeof(fence)) != 0) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}
@@ -0,0 +1,40 @@
+#include <switch.h>
+
+#include "nx_rt_vi.h"
+
+#include "../harness.h"
+#include "bq.h"
+
+/// -EWOULDBLOCK: no free slot in an asynchronous dequeue
+#define STATUS_WOULD_BLOCK (-11)
+
+/**
+ * @brief Test that a DEQUEUE_BUFFER reply with an error status is rejected.
+ *
+ * @return TEST_SUCCESS if the test passes, TEST_ASSERTION_FAILED otherwise.
+ */
+test_rc_t test_0004_bq_dequeue_buffer_reply_error_status(void) {
+    Result rc = 0;
+
+    //* Given
+    Parcel reply;
+    parcelCreate(&reply);
+    parcelWriteInt32(&reply, 0);
+    parcelWriteInt32(&reply, 0);
+    parcelWriteInt32(&reply, STATUS_WOULD_BLOCK);
+
+    //* When
+    s32 slot = -1;
+    NvMultiFence fence;
+    bool needs_reallocation = false;
+    const Result parse_rc = __nx_rt__vi_bq_parse_dequeue_buffer_reply(reply.payload, reply.payload_size, &slot, &fence, &needs_reallocation);
+
+    //* Then
+    if (R_SUCCEEDED(parse_rc) || slot != -1) {
+        rc = TEST_ASSERTION_FAILED;
+        goto test_cleanup;
+    }
+
+test_cleanup:
+    return rc;
+}